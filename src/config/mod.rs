@@ -0,0 +1,175 @@
+//! Configuration file support.
+//!
+//! This module loads default options for each operation from a TOML file, such as a
+//! project-level `.lumin.toml`, and merges them with explicitly given options (e.g., from
+//! command line flags) so that the explicit options take precedence.
+//!
+//! A configuration file has one optional section per operation, each containing fields of the
+//! respective options struct. Fields that are not specified take their default values, and
+//! unknown sections and fields are rejected:
+//!
+//! ```toml
+//! [search]
+//! case_sensitive = true
+//! exclude_dir_names = ["target", "node_modules"]
+//!
+//! [traverse]
+//! depth = 5
+//!
+//! [tree]
+//! max_file_size = 1048576
+//!
+//! [view]
+//! max_size = 10485760
+//! ```
+
+use anyhow::{Context, Result, anyhow};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::search::SearchOptions;
+use crate::traverse::TraverseOptions;
+use crate::tree::TreeOptions;
+use crate::view::ViewOptions;
+
+/// The name of the project-level configuration file.
+pub const DEFAULT_CONFIG_FILE_NAME: &str = ".lumin.toml";
+
+/// Default options for each operation, as loaded from a configuration file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LuminConfig {
+    /// Options for `search_files` (the `[search]` section)
+    pub search: SearchOptions,
+
+    /// Options for `traverse_directory` (the `[traverse]` section)
+    pub traverse: TraverseOptions,
+
+    /// Options for `generate_tree` (the `[tree]` section)
+    pub tree: TreeOptions,
+
+    /// Options for `view_file` (the `[view]` section)
+    pub view: ViewOptions,
+}
+
+/// Loads a configuration from a TOML file.
+///
+/// Missing sections, and missing fields within a section, take their default values.
+///
+/// # Arguments
+///
+/// * `path` - The path of the TOML configuration file
+///
+/// # Returns
+///
+/// The options of each section
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, is not valid TOML, or contains unknown
+/// sections, unknown fields, or fields of the wrong type.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::config::load;
+/// use std::path::Path;
+///
+/// let config = load(Path::new(".lumin.toml")).unwrap();
+/// println!("Search depth: {:?}", config.search.depth);
+/// ```
+pub fn load(path: &Path) -> Result<LuminConfig> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+    toml::from_str(&content)
+        .with_context(|| format!("Failed to parse config file {}", path.display()))
+}
+
+/// Options structs that can be merged with [`merge`].
+///
+/// `merge` round-trips the options through JSON, which drops fields marked
+/// `#[serde(skip)]`, so implementations copy those fields back from the base options.
+pub trait MergeOptions: Serialize + DeserializeOwned {
+    /// Copies the fields that are not serialized from `base` into `self`.
+    fn copy_unserialized_fields(&mut self, _base: &Self) {}
+}
+
+impl MergeOptions for SearchOptions {
+    fn copy_unserialized_fields(&mut self, base: &Self) {
+        self.cancel = base.cancel.clone();
+    }
+}
+
+impl MergeOptions for TraverseOptions {
+    fn copy_unserialized_fields(&mut self, base: &Self) {
+        self.cancel = base.cancel.clone();
+    }
+}
+
+impl MergeOptions for TreeOptions {}
+
+impl MergeOptions for ViewOptions {}
+
+/// Merges explicitly given options on top of base options.
+///
+/// `overrides` is a JSON object containing only the fields that were explicitly set (e.g., by
+/// command line flags), using the same field names and value formats as the serialized options
+/// struct. Its fields replace those of `base`, and all other fields keep their values from `base`,
+/// including fields that are not serialized, such as `SearchOptions::cancel`.
+///
+/// # Arguments
+///
+/// * `base` - The base options, usually loaded from a configuration file
+/// * `overrides` - A JSON object of the fields to override
+///
+/// # Returns
+///
+/// The merged options
+///
+/// # Errors
+///
+/// Returns an error if `overrides` is not an object, names a field the options struct doesn't
+/// have, or contains a value of the wrong type.
+///
+/// # Examples
+///
+/// ```
+/// use lumin::config::merge;
+/// use lumin::search::SearchOptions;
+/// use serde_json::json;
+///
+/// let base = SearchOptions {
+///     case_sensitive: true,
+///     before_context: 3,
+///     ..SearchOptions::default()
+/// };
+///
+/// let merged = merge(&base, json!({ "before_context": 1, "depth": null })).unwrap();
+/// assert!(merged.case_sensitive);
+/// assert_eq!(merged.before_context, 1);
+/// assert_eq!(merged.depth, None);
+/// ```
+pub fn merge<T: MergeOptions>(base: &T, overrides: serde_json::Value) -> Result<T> {
+    let serde_json::Value::Object(overrides) = overrides else {
+        return Err(anyhow!("Option overrides must be an object"));
+    };
+
+    let mut merged = serde_json::to_value(base).context("Failed to serialize options")?;
+    let fields = merged
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("Options must serialize to an object"))?;
+
+    for (name, value) in overrides {
+        match fields.get_mut(&name) {
+            Some(field) => *field = value,
+            None => return Err(anyhow!("Unknown option: {}", name)),
+        }
+    }
+
+    let mut merged: T =
+        serde_json::from_value(merged).context("Failed to apply option overrides")?;
+    merged.copy_unserialized_fields(base);
+    Ok(merged)
+}
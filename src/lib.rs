@@ -11,6 +11,8 @@
 //!
 //! Lumin uses structured logging via env_logger with stderr output for console visibility.
 
+/// Configuration file loading and option merging
+pub mod config;
 /// Path manipulation utilities
 pub mod paths;
 /// File content searching functionality using regex patterns
@@ -1,10 +1,18 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use lumin::search::{SearchOptions, search_files};
-use lumin::traverse::{TraverseOptions, traverse_directory};
-use lumin::tree::{TreeOptions, generate_tree};
-use lumin::view::{FileContents, ViewOptions, view_file};
-use std::path::PathBuf;
+use lumin::config::{self, DEFAULT_CONFIG_FILE_NAME, LuminConfig};
+use lumin::search::{
+    ContentMode, OutputFormat, SearchOptions, SearchSort, search_files, write_results_to,
+};
+use lumin::traverse::{
+    PatternMode, TableOptions, TraverseOptions, TraverseSort, format_table, traverse_directory,
+};
+use lumin::tree::{EntrySort, TreeOptions, generate_tree_report};
+use lumin::view::{DirMode, FileContents, ViewOptions, view_file, write_file};
+use serde::Serialize;
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(
@@ -15,6 +23,14 @@ use std::path::PathBuf;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Print the resolved options as JSON instead of running the command
+    #[arg(long, global = true, visible_alias = "explain")]
+    dry_run: bool,
+
+    /// Load default options from this TOML file [default: .lumin.toml, if present]
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -35,9 +51,13 @@ enum Commands {
         #[arg(long)]
         no_ignore: bool,
 
-        /// Maximum directory traversal depth (0 for unlimited)
-        #[arg(long = "max-depth", default_value = "20")]
-        max_depth: usize,
+        /// Maximum directory traversal depth (0 for unlimited) [default: 20]
+        #[arg(long = "max-depth")]
+        max_depth: Option<usize>,
+
+        /// Minimum depth of the files to include (1 for files directly in the directory)
+        #[arg(long = "min-depth")]
+        min_depth: Option<usize>,
 
         /// Limit context around matches (number of characters before and after)
         /// While context is limited, the full matched pattern is always preserved
@@ -45,12 +65,100 @@ enum Commands {
         omit_context: Option<usize>,
 
         /// Number of lines to show before each match (similar to grep's -B option)
-        #[arg(short = 'B', long = "before-context", default_value = "0")]
-        before_context: usize,
+        #[arg(short = 'B', long = "before-context")]
+        before_context: Option<usize>,
 
         /// Number of lines to show after each match (similar to grep's -A option)
-        #[arg(short = 'A', long = "after-context", default_value = "0")]
-        after_context: usize,
+        #[arg(short = 'A', long = "after-context")]
+        after_context: Option<usize>,
+
+        /// Search binary files as if they were text (similar to grep's -a option)
+        #[arg(short = 'a', long = "text")]
+        text: bool,
+
+        /// Exclude directories with this name at any depth (can be repeated)
+        #[arg(long = "exclude-dir")]
+        exclude_dir: Vec<String>,
+
+        /// Show the function or type enclosing each match (Rust, JavaScript/TypeScript, Python)
+        #[arg(long)]
+        enclosing_symbol: bool,
+
+        /// List files with the most matches first
+        #[arg(long)]
+        sort_by_relevance: bool,
+
+        /// Don't sort the results; files are listed in directory walk order
+        #[arg(long, conflicts_with = "sort_by_relevance")]
+        unsorted: bool,
+
+        /// Ignore the search root's .gitignore but honor nested ones
+        #[arg(long)]
+        no_root_ignore: bool,
+
+        /// Highlight matches in the output with terminal colors
+        #[arg(long)]
+        highlight: bool,
+
+        /// Only report matches from this line number on (1-based, inclusive)
+        #[arg(long)]
+        line_from: Option<usize>,
+
+        /// Only report matches up to this line number (1-based, inclusive)
+        #[arg(long)]
+        line_to: Option<usize>,
+
+        /// Only search the first N lines of each file
+        #[arg(long)]
+        header_lines: Option<usize>,
+
+        /// Join context blocks separated by at most this many lines
+        #[arg(long)]
+        context_merge_gap: Option<usize>,
+
+        /// Search NUL-delimited records instead of lines (like `find -print0` output)
+        #[arg(long)]
+        null_data: bool,
+
+        /// Show paths relative to the enclosing git repository root
+        #[arg(long)]
+        repo_relative: bool,
+
+        /// Stop searching after this many milliseconds and show the partial results
+        #[arg(long)]
+        timeout_ms: Option<u64>,
+
+        /// Only show the paths and line numbers of matches, without the line content
+        #[arg(long)]
+        no_content: bool,
+
+        /// Skip vendored and generated directories (node_modules, vendor, dist, build, target, .venv)
+        #[arg(long)]
+        skip_vendored: bool,
+
+        /// Only search files tracked by git, skipping untracked files
+        #[arg(long)]
+        tracked_only: bool,
+
+        /// Search the decompressed content of gzip, bzip2, xz and zstd files
+        #[arg(short = 'z', long)]
+        search_compressed: bool,
+
+        /// Search the text of PDF files (requires the `pdf` feature)
+        #[arg(long)]
+        search_pdf: bool,
+
+        /// Remove the common indentation of each block of matches and context lines
+        #[arg(long)]
+        dedent: bool,
+
+        /// Report only the first matching line of each file
+        #[arg(long)]
+        first_match_only: bool,
+
+        /// Number of threads searching files (defaults to the number of CPUs)
+        #[arg(long)]
+        threads: Option<usize>,
     },
 
     /// Traverse directories and list files
@@ -73,9 +181,37 @@ enum Commands {
         #[arg(long)]
         include_binary: bool,
 
-        /// Maximum directory traversal depth (0 for unlimited)
-        #[arg(long = "max-depth", default_value = "20")]
-        max_depth: usize,
+        /// Maximum directory traversal depth (0 for unlimited) [default: 20]
+        #[arg(long = "max-depth")]
+        max_depth: Option<usize>,
+
+        /// Minimum depth of the files to include (1 for files directly in the directory)
+        #[arg(long = "min-depth")]
+        min_depth: Option<usize>,
+
+        /// Show file size and modification time columns, and symlink targets
+        #[arg(short = 'l', long)]
+        long: bool,
+
+        /// List shallow files before deeply nested ones
+        #[arg(long)]
+        breadth_first: bool,
+
+        /// Skip vendored and generated directories (node_modules, vendor, dist, build, target, .venv)
+        #[arg(long)]
+        skip_vendored: bool,
+
+        /// Don't descend into directories on other file systems (like `find -xdev`)
+        #[arg(long)]
+        one_file_system: bool,
+
+        /// Include directories, even empty ones, in the output
+        #[arg(long)]
+        include_dirs: bool,
+
+        /// Match the pattern fuzzily and list the best matches first
+        #[arg(long, requires = "pattern")]
+        fuzzy: bool,
     },
 
     /// Display directory structure as a tree
@@ -91,9 +227,25 @@ enum Commands {
         #[arg(long)]
         no_ignore: bool,
 
-        /// Maximum directory traversal depth (0 for unlimited)
-        #[arg(long = "max-depth", default_value = "20")]
-        max_depth: usize,
+        /// Maximum directory traversal depth (0 for unlimited) [default: 20]
+        #[arg(long = "max-depth")]
+        max_depth: Option<usize>,
+
+        /// Omit files larger than this size in bytes
+        #[arg(long)]
+        max_file_size: Option<u64>,
+
+        /// Sort entries with numbers compared by value (`file2` before `file10`)
+        #[arg(long)]
+        natural_sort: bool,
+
+        /// Skip vendored and generated directories (node_modules, vendor, dist, build, target, .venv)
+        #[arg(long)]
+        skip_vendored: bool,
+
+        /// Stop listing directory contents after this many directories
+        #[arg(long)]
+        max_directories: Option<usize>,
     },
 
     /// View file contents
@@ -101,7 +253,7 @@ enum Commands {
         /// File to view
         file: PathBuf,
 
-        /// Maximum file size in bytes
+        /// Maximum file size in bytes [default: 10485760]
         #[arg(long)]
         max_size: Option<usize>,
 
@@ -112,6 +264,30 @@ enum Commands {
         /// End viewing at this line number (1-based, inclusive)
         #[arg(long)]
         line_to: Option<usize>,
+
+        /// Only read the first N lines of the file
+        #[arg(long)]
+        preview: Option<usize>,
+
+        /// Show text files with invalid UTF-8 as text, replacing the invalid bytes
+        #[arg(long)]
+        lossy: bool,
+
+        /// Show the line containing this byte offset (0-based)
+        #[arg(long)]
+        byte_offset: Option<usize>,
+
+        /// Number of lines to show around the line of --byte-offset
+        #[arg(long, requires = "byte_offset")]
+        context_lines: Option<usize>,
+
+        /// List the entries of a directory instead of failing
+        #[arg(long)]
+        list_dir: bool,
+
+        /// Show control characters (such as escape sequences) as ^X instead of printing them
+        #[arg(long)]
+        sanitize_control: bool,
     },
 }
 
@@ -120,6 +296,15 @@ fn main() -> Result<()> {
     lumin::telemetry::init()?;
     let cli = Cli::parse();
 
+    // Options from the configuration file are overridden by command line flags
+    let config = match &cli.config {
+        Some(path) => config::load(path)?,
+        None if Path::new(DEFAULT_CONFIG_FILE_NAME).is_file() => {
+            config::load(Path::new(DEFAULT_CONFIG_FILE_NAME))?
+        }
+        None => LuminConfig::default(),
+    };
+
     match &cli.command {
         Commands::Search {
             pattern,
@@ -130,27 +315,79 @@ fn main() -> Result<()> {
             before_context,
             after_context,
             max_depth,
+            min_depth,
+            text,
+            exclude_dir,
+            enclosing_symbol,
+            sort_by_relevance,
+            unsorted,
+            no_root_ignore,
+            highlight,
+            line_from,
+            line_to,
+            header_lines,
+            context_merge_gap,
+            null_data,
+            repo_relative,
+            timeout_ms,
+            no_content,
+            skip_vendored,
+            tracked_only,
+            search_compressed,
+            search_pdf,
+            dedent,
+            first_match_only,
+            threads,
         } => {
-            let options = SearchOptions {
-                case_sensitive: *case_sensitive,
-                respect_gitignore: !no_ignore,
-                exclude_glob: None,
-                include_glob: None,
-                omit_path_prefix: None,
-                match_content_omit_num: *omit_context,
-                depth: if *max_depth == 0 {
-                    None
-                } else {
-                    Some(*max_depth)
-                },
-                before_context: *before_context,
-                after_context: *after_context,
-                skip: None,
-                take: None,
-            };
+            let mut overrides = Overrides::default();
+            overrides.flag(*case_sensitive, "case_sensitive", true);
+            overrides.flag(*no_ignore, "respect_gitignore", false);
+            overrides.value("match_content_omit_num", omit_context);
+            overrides.depth(*max_depth);
+            overrides.value("min_depth", min_depth);
+            overrides.value("before_context", before_context);
+            overrides.value("after_context", after_context);
+            overrides.flag(*text, "treat_all_as_text", true);
+            overrides.flag(!exclude_dir.is_empty(), "exclude_dir_names", exclude_dir);
+            overrides.flag(*enclosing_symbol, "enclosing_symbol", true);
+            overrides.flag(*sort_by_relevance, "sort_by_relevance", true);
+            overrides.flag(*unsorted, "sort", SearchSort::None);
+            // Unsorted results also override `sort_by_relevance` from the config file
+            overrides.flag(*unsorted, "sort_by_relevance", false);
+            overrides.flag(*no_root_ignore, "ignore_root_gitignore", true);
+            overrides.flag(*highlight, "highlight_markers", ("\x1b[1;31m", "\x1b[0m"));
+            overrides.value("line_from", line_from);
+            overrides.value("line_to", line_to);
+            overrides.value("header_lines", header_lines);
+            overrides.value("context_merge_gap", context_merge_gap);
+            overrides.flag(*null_data, "line_terminator", b'\0');
+            overrides.flag(*repo_relative, "auto_repo_relative", true);
+            overrides.value("timeout", &timeout_ms.map(Duration::from_millis));
+            overrides.flag(*no_content, "content", ContentMode::None);
+            overrides.flag(*skip_vendored, "skip_vendored", true);
+            overrides.flag(*tracked_only, "git_tracked_only", true);
+            overrides.flag(*search_compressed, "search_compressed", true);
+            overrides.flag(*search_pdf, "extract_pdf_text", true);
+            overrides.flag(*dedent, "dedent_context", true);
+            overrides.flag(*first_match_only, "first_match_only", true);
+            overrides.value("threads", threads);
+
+            let options: SearchOptions = config::merge(&config.search, overrides.into_value())?;
+
+            if cli.dry_run {
+                return print_resolved_options(
+                    "search",
+                    json!({ "pattern": pattern, "directory": directory }),
+                    &options,
+                );
+            }
 
             let results = search_files(pattern, directory, &options)?;
 
+            if results.timed_out {
+                eprintln!("Search timed out; the results are partial.");
+            }
+
             if results.lines.is_empty() {
                 println!("No matches found.");
             } else {
@@ -158,40 +395,8 @@ fn main() -> Result<()> {
                 let match_count = results.lines.iter().filter(|r| !r.is_context).count();
                 println!("Found {} matches:", match_count);
 
-                let mut last_file = None;
-                let mut last_line_number = 0;
-
-                for result in results.lines {
-                    // Print separator between discontinuous results
-                    if let Some(last) = &last_file {
-                        if &result.file_path != last || result.line_number > last_line_number + 1 {
-                            println!("--");
-                        }
-                    }
-
-                    // Update tracking variables
-                    last_file = Some(result.file_path.clone());
-                    last_line_number = result.line_number;
-
-                    // Print result with different formatting for matches vs context
-                    if result.is_context {
-                        // Context line (grey/dimmed if terminal supports it)
-                        println!(
-                            "{}:{}- {}",
-                            result.file_path.display(),
-                            result.line_number,
-                            result.line_content.trim()
-                        );
-                    } else {
-                        // Matched line (regular text)
-                        println!(
-                            "{}:{}: {}",
-                            result.file_path.display(),
-                            result.line_number,
-                            result.line_content.trim()
-                        );
-                    }
-                }
+                let stdout = std::io::stdout();
+                write_results_to(&results, &mut stdout.lock(), OutputFormat::Text)?;
             }
         }
 
@@ -202,19 +407,38 @@ fn main() -> Result<()> {
             no_ignore,
             include_binary,
             max_depth,
+            min_depth,
+            long,
+            breadth_first,
+            skip_vendored,
+            one_file_system,
+            include_dirs,
+            fuzzy,
         } => {
-            let options = TraverseOptions {
-                case_sensitive: *case_sensitive,
-                respect_gitignore: !no_ignore,
-                only_text_files: !include_binary,
-                pattern: pattern.clone(),
-                depth: if *max_depth == 0 {
-                    None
-                } else {
-                    Some(*max_depth)
-                },
-                omit_path_prefix: None,
-            };
+            let mut overrides = Overrides::default();
+            overrides.flag(*case_sensitive, "case_sensitive", true);
+            overrides.flag(*no_ignore, "respect_gitignore", false);
+            overrides.flag(*include_binary, "only_text_files", false);
+            overrides.value("pattern", pattern);
+            overrides.depth(*max_depth);
+            overrides.value("min_depth", min_depth);
+            overrides.flag(*long, "include_metadata", true);
+            overrides.flag(*long, "resolve_target", true);
+            overrides.flag(*breadth_first, "sort", TraverseSort::BreadthFirst);
+            overrides.flag(*skip_vendored, "skip_vendored", true);
+            overrides.flag(*one_file_system, "one_file_system", true);
+            overrides.flag(*include_dirs, "include_dirs", true);
+            overrides.flag(*fuzzy, "pattern_mode", PatternMode::Fuzzy);
+
+            let options: TraverseOptions = config::merge(&config.traverse, overrides.into_value())?;
+
+            if cli.dry_run {
+                return print_resolved_options(
+                    "traverse",
+                    json!({ "directory": directory }),
+                    &options,
+                );
+            }
 
             let results = traverse_directory(directory, &options)?;
 
@@ -222,15 +446,11 @@ fn main() -> Result<()> {
                 println!("No files found.");
             } else {
                 println!("Found {} files:", results.len());
-                for result in results {
-                    let hidden_marker = if result.is_hidden() { "*" } else { " " };
-                    println!(
-                        "{} {:<10} {}",
-                        hidden_marker,
-                        result.file_type,
-                        result.file_path.display()
-                    );
-                }
+                let table_options = TableOptions {
+                    header: *long,
+                    ..TableOptions::default()
+                };
+                print!("{}", format_table(&results, &table_options));
             }
         }
 
@@ -239,19 +459,32 @@ fn main() -> Result<()> {
             case_sensitive,
             no_ignore,
             max_depth,
+            max_file_size,
+            natural_sort,
+            skip_vendored,
+            max_directories,
         } => {
-            let options = TreeOptions {
-                case_sensitive: *case_sensitive,
-                respect_gitignore: !no_ignore,
-                depth: if *max_depth == 0 {
-                    None
-                } else {
-                    Some(*max_depth)
-                },
-                omit_path_prefix: None,
-            };
-
-            let results = generate_tree(directory, &options)?;
+            let mut overrides = Overrides::default();
+            overrides.flag(*case_sensitive, "case_sensitive", true);
+            overrides.flag(*no_ignore, "respect_gitignore", false);
+            overrides.depth(*max_depth);
+            overrides.value("max_file_size", max_file_size);
+            overrides.flag(*natural_sort, "entry_sort", EntrySort::Natural);
+            overrides.flag(*skip_vendored, "skip_vendored", true);
+            overrides.value("max_directories", max_directories);
+
+            let options: TreeOptions = config::merge(&config.tree, overrides.into_value())?;
+
+            if cli.dry_run {
+                return print_resolved_options("tree", json!({ "directory": directory }), &options);
+            }
+
+            let report = generate_tree_report(directory, &options)?;
+            let results = report.trees;
+
+            if report.truncated {
+                eprintln!("Directory limit reached; the tree is truncated.");
+            }
 
             if results.is_empty() {
                 println!("No directories found.");
@@ -266,35 +499,92 @@ fn main() -> Result<()> {
             max_size,
             line_from,
             line_to,
+            preview,
+            lossy,
+            byte_offset,
+            context_lines,
+            list_dir,
+            sanitize_control,
         } => {
-            let options = ViewOptions {
-                max_size: *max_size,
-                line_from: *line_from,
-                line_to: *line_to,
-            };
-
-            let view_result = view_file(file, &options)?;
-
-            // Format output as {filepath}:{line_num}:{line_contents}
-            match view_result.contents {
-                FileContents::Text { content, .. } => {
-                    let file_path = view_result.file_path.to_string_lossy();
-                    for line_content in content.line_contents {
-                        println!(
-                            "{file_path}:{}:{}",
-                            line_content.line_number, line_content.line
-                        );
+            let mut overrides = Overrides::default();
+            overrides.value("max_size", max_size);
+            overrides.value("line_from", line_from);
+            overrides.value("line_to", line_to);
+            overrides.value("preview", preview);
+            overrides.flag(*lossy, "lossy", true);
+            overrides.value("byte_offset", byte_offset);
+            overrides.value("context_lines", context_lines);
+            overrides.flag(*list_dir, "on_directory", DirMode::ListEntries);
+            overrides.flag(*sanitize_control, "sanitize_control", true);
+
+            let options: ViewOptions = config::merge(&config.view, overrides.into_value())?;
+
+            if cli.dry_run {
+                return print_resolved_options("view", json!({ "file": file }), &options);
+            }
+
+            // List the entries of a directory, one path per line
+            if file.is_dir() && options.on_directory == DirMode::ListEntries {
+                if let FileContents::Directory { entries } = view_file(file, &options)?.contents {
+                    for entry in entries {
+                        println!("{}", entry.file_path.to_string_lossy());
                     }
                 }
-                FileContents::Binary { message, .. } => {
-                    println!("{}: {}", view_result.file_path.to_string_lossy(), message);
-                }
-                FileContents::Image { message, .. } => {
-                    println!("{}: {}", view_result.file_path.to_string_lossy(), message);
-                }
+                return Ok(());
             }
+
+            // Stream the file as {filepath}:{line_num}:{line_contents}
+            let stdout = std::io::stdout();
+            write_file(file, &options, &mut stdout.lock())?;
         }
     }
 
     Ok(())
 }
+
+/// Prints the command, its arguments and the fully resolved options as JSON
+fn print_resolved_options(
+    command: &str,
+    arguments: serde_json::Value,
+    options: &impl Serialize,
+) -> Result<()> {
+    let resolved = json!({
+        "command": command,
+        "arguments": arguments,
+        "options": options,
+    });
+    println!("{}", serde_json::to_string_pretty(&resolved)?);
+    Ok(())
+}
+
+/// Options explicitly set by command line flags, to be merged over the configured options
+#[derive(Default)]
+struct Overrides(serde_json::Map<String, serde_json::Value>);
+
+impl Overrides {
+    /// Sets the option to `value` if the flag was given
+    fn flag(&mut self, given: bool, name: &str, value: impl Serialize) {
+        if given {
+            self.0.insert(name.to_string(), json!(value));
+        }
+    }
+
+    /// Sets the option to the argument value if the argument was given
+    fn value<T: Serialize>(&mut self, name: &str, value: &Option<T>) {
+        if let Some(value) = value {
+            self.0.insert(name.to_string(), json!(value));
+        }
+    }
+
+    /// Sets the depth option from `--max-depth`, where 0 means unlimited
+    fn depth(&mut self, max_depth: Option<usize>) {
+        match max_depth {
+            Some(0) => self.flag(true, "depth", None::<usize>),
+            max_depth => self.value("depth", &max_depth),
+        }
+    }
+
+    fn into_value(self) -> serde_json::Value {
+        serde_json::Value::Object(self.0)
+    }
+}
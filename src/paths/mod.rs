@@ -1,8 +1,21 @@
 //! Path manipulation utilities.
 //!
 //! This module provides utility functions for manipulating file paths,
-//! such as removing prefixes, normalizing paths, and other common operations.
+//! such as removing prefixes, normalizing paths, comparing paths in natural order, and other
+//! common operations.
+//!
+//! # Non-UTF-8 paths
+//!
+//! File names are not guaranteed to be valid UTF-8. The library keeps paths and file names
+//! as `PathBuf` and `OsString` in its results, so they are never altered in memory. When
+//! results are serialized, valid UTF-8 paths are written as strings, and on Unix other paths
+//! are written as arrays of their raw bytes, so they survive a round trip unchanged. Lossy
+//! conversions (which replace invalid sequences with `U+FFFD`) are only used for display and
+//! for matching paths against glob patterns and names.
 
+pub(crate) mod serde_os_str;
+
+use std::cmp::Ordering;
 use std::path::{Path, PathBuf};
 
 /// Removes a prefix from a path if it exists.
@@ -52,5 +65,287 @@ pub fn remove_path_prefix<P: AsRef<Path>, Q: AsRef<Path>>(path: P, prefix: Q) ->
     }
 }
 
+/// Replaces a leading home directory in a path with `~`.
+///
+/// Paths under `home` are rewritten as `~/...`, and `home` itself as `~`, the way shells
+/// abbreviate them. Other paths, including relative ones, are returned unchanged. The
+/// returned path is for display: `~` is not expanded by the file system APIs.
+///
+/// # Arguments
+///
+/// * `path` - The path to abbreviate
+/// * `home` - The home directory, usually from `dirs::home_dir()`
+///
+/// # Examples
+///
+/// ```
+/// use lumin::paths::tilde_home;
+/// use std::path::{Path, PathBuf};
+///
+/// let home = Path::new("/home/user");
+/// assert_eq!(tilde_home("/home/user/src/main.rs", home), PathBuf::from("~/src/main.rs"));
+/// assert_eq!(tilde_home("/home/user", home), PathBuf::from("~"));
+/// assert_eq!(tilde_home("/home/username/a.txt", home), PathBuf::from("/home/username/a.txt"));
+/// assert_eq!(tilde_home("src/main.rs", home), PathBuf::from("src/main.rs"));
+/// ```
+pub fn tilde_home<P: AsRef<Path>>(path: P, home: &Path) -> PathBuf {
+    let path = path.as_ref();
+    match path.strip_prefix(home) {
+        Ok(rest) if home.is_absolute() => Path::new("~").join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Collapses repeated separators and removes trailing separators from a path.
+///
+/// Paths built by joining strings can contain `//` or end with `/`, which looks messy in
+/// output and defeats string-based deduplication. The cleaned path is rebuilt from the
+/// components of `path`, so interior `.` components are removed as well. A root is kept as is,
+/// and `..` components are left in place, since resolving them would require the file system.
+///
+/// # Arguments
+///
+/// * `path` - The path to clean
+///
+/// # Examples
+///
+/// ```
+/// use lumin::paths::clean_path;
+/// use std::path::PathBuf;
+///
+/// assert_eq!(clean_path("src//search///mod.rs"), PathBuf::from("src/search/mod.rs"));
+/// assert_eq!(clean_path("/home/user/"), PathBuf::from("/home/user"));
+/// assert_eq!(clean_path("a/./b/../c"), PathBuf::from("a/b/../c"));
+/// assert_eq!(clean_path("/"), PathBuf::from("/"));
+/// ```
+pub fn clean_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    path.as_ref().components().collect()
+}
+
+/// The display transformations applied to every result path after prefix removal.
+///
+/// Search and traversal results share these steps, so that their paths are rendered the same
+/// way for the same options.
+pub(crate) struct PathDisplay {
+    /// Whether to clean the paths with `clean_path`
+    clean: bool,
+    /// The home directory to abbreviate with `tilde_home`, if enabled
+    home: Option<PathBuf>,
+}
+
+impl PathDisplay {
+    /// Creates the transformations for the `clean_paths` and `tilde_home` options.
+    ///
+    /// The home directory is looked up once here, rather than for every path.
+    pub(crate) fn new(clean: bool, tilde: bool) -> Self {
+        Self {
+            clean,
+            home: tilde.then(dirs::home_dir).flatten(),
+        }
+    }
+
+    /// Applies the transformations to a result path
+    pub(crate) fn apply(&self, path: PathBuf) -> PathBuf {
+        let path = if self.clean { clean_path(path) } else { path };
+        match &self.home {
+            Some(home) => tilde_home(path, home),
+            None => path,
+        }
+    }
+}
+
+/// Finds the root of the git repository containing a path.
+///
+/// Walks up from `path` (or its parent, if `path` is a file) and returns the first directory
+/// containing a `.git` entry. Both `.git` directories and `.git` files (as used by worktrees
+/// and submodules) are recognized.
+///
+/// Relative paths are only walked up as far as their components go, so pass an absolute
+/// (e.g., canonicalized) path to search all the way up to the file system root.
+///
+/// # Arguments
+///
+/// * `path` - The path to start the search from
+///
+/// # Returns
+///
+/// The repository root directory, or `None` if the path is not inside a git repository.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::paths::find_repository_root;
+/// use std::path::Path;
+///
+/// let current_dir = Path::new(".").canonicalize().unwrap();
+/// match find_repository_root(&current_dir) {
+///     Some(root) => println!("Repository root: {}", root.display()),
+///     None => println!("Not inside a git repository"),
+/// }
+/// ```
+pub fn find_repository_root<P: AsRef<Path>>(path: P) -> Option<PathBuf> {
+    let path = path.as_ref();
+    let start = if path.is_file() { path.parent()? } else { path };
+
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Finds the longest directory prefix shared by a list of file paths.
+///
+/// Each path is treated as a file, so only its parent directories are considered: the common
+/// prefix of `src/a.rs` and `src/b.rs` is `src`, and the prefix of a single path is its
+/// parent directory. Paths are compared component by component, so `src/lib` and
+/// `src/library` share `src`, not `src/lib`.
+///
+/// # Arguments
+///
+/// * `paths` - The file paths to find the common prefix of
+///
+/// # Returns
+///
+/// The common directory prefix, or `None` if `paths` is empty or the paths don't share any
+/// directory (e.g., `a.rs` and `b.rs`, or `/home/a.rs` and `src/b.rs`).
+///
+/// # Examples
+///
+/// ```
+/// use lumin::paths::common_prefix;
+/// use std::path::PathBuf;
+///
+/// let paths = vec![
+///     PathBuf::from("/home/user/project/src/main.rs"),
+///     PathBuf::from("/home/user/project/tests/cli.rs"),
+/// ];
+/// assert_eq!(common_prefix(&paths), Some(PathBuf::from("/home/user/project")));
+///
+/// let unrelated = vec![PathBuf::from("a.rs"), PathBuf::from("b.rs")];
+/// assert_eq!(common_prefix(&unrelated), None);
+/// ```
+pub fn common_prefix(paths: &[PathBuf]) -> Option<PathBuf> {
+    let (first, rest) = paths.split_first()?;
+    let mut prefix: Vec<_> = first.parent()?.components().collect();
+
+    for path in rest {
+        let shared = path.parent().map_or(0, |parent| {
+            parent
+                .components()
+                .zip(&prefix)
+                .take_while(|(component, prefix_component)| component == *prefix_component)
+                .count()
+        });
+        prefix.truncate(shared);
+    }
+
+    if prefix.is_empty() {
+        None
+    } else {
+        Some(prefix.iter().collect())
+    }
+}
+
+/// Compares two strings in natural order, treating runs of ASCII digits as numbers.
+///
+/// This is the order of `ls -v` and `sort -V`: `file2` comes before `file10`, while a plain
+/// comparison puts `file10` first. Numbers that only differ in leading zeros (`07` and `7`)
+/// are ordered by a plain comparison.
+///
+/// # Examples
+///
+/// ```
+/// use lumin::paths::natural_cmp;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+/// assert_eq!(natural_cmp("v1.10.0", "v1.9.3"), Ordering::Greater);
+/// assert_eq!(natural_cmp("a", "b"), Ordering::Less);
+/// ```
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    compare_numeric_runs(a, b).then_with(|| a.cmp(b))
+}
+
+/// Compares two paths in natural order, component by component.
+///
+/// Each pair of components is compared with `natural_cmp`, so `dir2/file` comes before
+/// `dir10/file`, and a path comes before the paths inside it.
+///
+/// # Examples
+///
+/// ```
+/// use lumin::paths::natural_path_cmp;
+/// use std::cmp::Ordering;
+/// use std::path::Path;
+///
+/// assert_eq!(
+///     natural_path_cmp(Path::new("logs/day2.txt"), Path::new("logs/day10.txt")),
+///     Ordering::Less
+/// );
+/// ```
+pub fn natural_path_cmp(a: &Path, b: &Path) -> Ordering {
+    let mut components_a = a.components();
+    let mut components_b = b.components();
+    loop {
+        match (components_a.next(), components_b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(component_a), Some(component_b)) => {
+                let ordering = natural_cmp(
+                    &component_a.as_os_str().to_string_lossy(),
+                    &component_b.as_os_str().to_string_lossy(),
+                );
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+        }
+    }
+}
+
+/// Compares two strings, treating runs of ASCII digits as numbers.
+///
+/// Numbers with leading zeros compare equal to the same number without them.
+fn compare_numeric_runs(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        match (a.chars().next(), b.chars().next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(ca), Some(cb)) if ca.is_ascii_digit() && cb.is_ascii_digit() => {
+                let (number_a, rest_a) = split_digits(a);
+                let (number_b, rest_b) = split_digits(b);
+                // Without leading zeros, a longer run of digits is a larger number
+                let (number_a, number_b) = (
+                    number_a.trim_start_matches('0'),
+                    number_b.trim_start_matches('0'),
+                );
+                let ordering = number_a
+                    .len()
+                    .cmp(&number_b.len())
+                    .then_with(|| number_a.cmp(number_b));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                (a, b) = (rest_a, rest_b);
+            }
+            (Some(ca), Some(cb)) => {
+                if ca != cb {
+                    return ca.cmp(&cb);
+                }
+                (a, b) = (&a[ca.len_utf8()..], &b[cb.len_utf8()..]);
+            }
+        }
+    }
+}
+
+/// Splits a string into its leading run of ASCII digits and the rest.
+fn split_digits(s: &str) -> (&str, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s.split_at(end)
+}
+
 #[cfg(test)]
 mod tests;
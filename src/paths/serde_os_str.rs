@@ -0,0 +1,138 @@
+//! Lossless serialization of paths and file names.
+//!
+//! `serde` serializes a `Path` as a string and fails when the path is not valid UTF-8, while
+//! `OsString` is serialized as a platform-tagged enum. Neither is suitable for results that
+//! may contain arbitrary file names, so result types use this module instead:
+//!
+//! - Valid UTF-8 values are serialized as plain strings, exactly as `serde` would.
+//! - On Unix, other values are serialized as an array of their raw bytes, so they can be
+//!   restored without loss.
+//! - On other platforms, other values are serialized as a string with invalid sequences
+//!   replaced by `U+FFFD`.
+//!
+//! Deserialization accepts both representations.
+//!
+//! Use it with `#[serde(with = "crate::paths::serde_os_str")]` for `PathBuf` and `OsString`
+//! fields, and with `#[serde(with = "crate::paths::serde_os_str::option")]` for optional ones.
+
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserializer, Serializer};
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+
+/// Serializes a path or file name as a string, or as raw bytes if it is not valid UTF-8.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: AsRef<OsStr>,
+    S: Serializer,
+{
+    let value = value.as_ref();
+    if let Some(s) = value.to_str() {
+        return serializer.serialize_str(s);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        serializer.collect_seq(value.as_bytes())
+    }
+    #[cfg(not(unix))]
+    {
+        serializer.serialize_str(&value.to_string_lossy())
+    }
+}
+
+/// Deserializes a path or file name from a string or an array of raw bytes.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: From<OsString>,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(OsStringVisitor).map(T::from)
+}
+
+/// Lossless serialization of optional paths and file names.
+pub mod option {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::ffi::{OsStr, OsString};
+
+    /// Wraps a value to serialize it with the parent module.
+    struct Lossless<'a>(&'a OsStr);
+
+    impl Serialize for Lossless<'_> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            super::serialize(&self.0, serializer)
+        }
+    }
+
+    /// Wraps a value to deserialize it with the parent module.
+    struct LosslessOwned(OsString);
+
+    impl<'de> Deserialize<'de> for LosslessOwned {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            super::deserialize(deserializer).map(LosslessOwned)
+        }
+    }
+
+    /// Serializes an optional path or file name; `None` is serialized as `null`.
+    pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: AsRef<OsStr>,
+        S: Serializer,
+    {
+        value
+            .as_ref()
+            .map(|value| Lossless(value.as_ref()))
+            .serialize(serializer)
+    }
+
+    /// Deserializes an optional path or file name; `null` is deserialized as `None`.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        T: From<OsString>,
+        D: Deserializer<'de>,
+    {
+        Option::<LosslessOwned>::deserialize(deserializer)
+            .map(|value| value.map(|LosslessOwned(value)| T::from(value)))
+    }
+}
+
+/// Accepts a string or a sequence of bytes.
+struct OsStringVisitor;
+
+impl<'de> Visitor<'de> for OsStringVisitor {
+    type Value = OsString;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string or an array of bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<OsString, E> {
+        Ok(OsString::from(value))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<OsString, E> {
+        Ok(os_string_from_bytes(value.to_vec()))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<OsString, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        Ok(os_string_from_bytes(bytes))
+    }
+}
+
+/// Converts raw bytes to an `OsString`, replacing invalid UTF-8 on platforms other than Unix.
+fn os_string_from_bytes(bytes: Vec<u8>) -> OsString {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        OsString::from_vec(bytes)
+    }
+    #[cfg(not(unix))]
+    {
+        OsString::from(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
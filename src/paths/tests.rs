@@ -1,7 +1,7 @@
 //! Tests for the paths module.
 
 use super::*;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[test]
 fn test_remove_path_prefix() {
@@ -41,3 +41,92 @@ fn test_remove_path_prefix() {
     let result = remove_path_prefix(path, prefix);
     assert_eq!(result, PathBuf::from(""));
 }
+
+#[test]
+fn test_find_repository_root() -> anyhow::Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let repo = temp_dir.path().join("repo");
+    std::fs::create_dir_all(repo.join(".git"))?;
+    std::fs::create_dir_all(repo.join("src/nested"))?;
+    std::fs::write(repo.join("src/nested/lib.rs"), "")?;
+
+    assert_eq!(find_repository_root(&repo), Some(repo.clone()));
+    assert_eq!(
+        find_repository_root(repo.join("src/nested")),
+        Some(repo.clone())
+    );
+    assert_eq!(
+        find_repository_root(repo.join("src/nested/lib.rs")),
+        Some(repo.clone())
+    );
+
+    // Worktrees and submodules use a .git file
+    let submodule = repo.join("vendor/dep");
+    std::fs::create_dir_all(&submodule)?;
+    std::fs::write(submodule.join(".git"), "gitdir: ../../.git/modules/dep")?;
+    assert_eq!(find_repository_root(&submodule), Some(submodule.clone()));
+
+    Ok(())
+}
+
+#[test]
+fn test_natural_cmp() {
+    use std::cmp::Ordering;
+
+    assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+    assert_eq!(natural_cmp("file10", "file2"), Ordering::Greater);
+    assert_eq!(natural_cmp("file10", "file10"), Ordering::Equal);
+    assert_eq!(natural_cmp("file", "file1"), Ordering::Less);
+    assert_eq!(natural_cmp("a10b2", "a10b10"), Ordering::Less);
+    // Leading zeros don't change the value, and ties are broken by a plain comparison
+    assert_eq!(natural_cmp("file007", "file10"), Ordering::Less);
+    assert_eq!(natural_cmp("file07", "file7"), "file07".cmp("file7"));
+    // Numbers longer than any integer type are still compared by value
+    assert_eq!(
+        natural_cmp("id99999999999999999999999", "id100000000000000000000000"),
+        Ordering::Less
+    );
+
+    assert_eq!(
+        natural_path_cmp(Path::new("dir2/file10"), Path::new("dir10/file2")),
+        Ordering::Less
+    );
+    assert_eq!(
+        natural_path_cmp(Path::new("dir"), Path::new("dir/file")),
+        Ordering::Less
+    );
+}
+
+#[test]
+fn test_common_prefix() {
+    let paths = vec![
+        PathBuf::from("/work/project/src/main.rs"),
+        PathBuf::from("/work/project/src/util/strings.rs"),
+        PathBuf::from("/work/project/tests/cli.rs"),
+    ];
+    assert_eq!(common_prefix(&paths), Some(PathBuf::from("/work/project")));
+
+    // A single path's prefix is its parent directory
+    assert_eq!(
+        common_prefix(&paths[1..2]),
+        Some(PathBuf::from("/work/project/src/util"))
+    );
+
+    // Components are compared as a whole
+    let paths = vec![
+        PathBuf::from("src/lib/a.rs"),
+        PathBuf::from("src/library/b.rs"),
+    ];
+    assert_eq!(common_prefix(&paths), Some(PathBuf::from("src")));
+
+    // No shared directory
+    assert_eq!(common_prefix(&[]), None);
+    assert_eq!(
+        common_prefix(&[PathBuf::from("a.rs"), PathBuf::from("b.rs")]),
+        None
+    );
+    assert_eq!(
+        common_prefix(&[PathBuf::from("src/a.rs"), PathBuf::from("b.rs")]),
+        None
+    );
+}
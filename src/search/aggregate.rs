@@ -0,0 +1,93 @@
+//! Aggregation of match counts by directory.
+//!
+//! This module sums the matching lines of each file into its ancestor directories, showing
+//! where matches concentrate in a directory tree (for example, as input to a flamegraph or
+//! treemap visualization).
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::summary::{ensure_complete, unmodified_path_options};
+use super::{SearchOptions, search_files};
+use crate::paths::remove_path_prefix;
+
+/// Searches for a pattern and returns the number of matching lines within each directory,
+/// recursively.
+///
+/// The search is performed with `search_files` using the given options, so all file filters
+/// apply. The number of matching lines of every file is then added to each of its ancestor
+/// directories, up to and including `directory` itself. Context lines are not counted, and
+/// all matching lines are counted regardless of `skip` and `take`.
+///
+/// Only directories that contain at least one match (directly or in a subdirectory) are
+/// reported.
+///
+/// # Arguments
+///
+/// * `pattern` - The regular expression pattern to search for. Supports the same regex syntax
+///   as `search_files`.
+/// * `directory` - The root directory to aggregate matches for
+/// * `options` - Configuration options for the search operation. `omit_path_prefix` is applied
+///   to the returned directory paths, while other path display options (`tilde_home`,
+///   `auto_omit_common_prefix` and `auto_repo_relative`) are ignored.
+///
+/// # Returns
+///
+/// A list of `(directory, matching_lines)` pairs sorted by directory path
+///
+/// # Errors
+///
+/// Returns the same errors as `search_files`, or an error if the search times out or is
+/// cancelled, as the counts would be partial
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::search::{SearchOptions, aggregate_by_directory};
+/// use std::path::Path;
+///
+/// for (dir, count) in aggregate_by_directory("TODO", Path::new("."), &SearchOptions::default())
+///     .unwrap()
+/// {
+///     println!("{:>6} {}", count, dir.display());
+/// }
+/// ```
+pub fn aggregate_by_directory(
+    pattern: &str,
+    directory: &Path,
+    options: &SearchOptions,
+) -> Result<Vec<(PathBuf, usize)>> {
+    // Search with unmodified paths so files can be attributed to their ancestors
+    let search_options = unmodified_path_options(options);
+    let result = search_files(pattern, directory, &search_options)?;
+    ensure_complete(&result)?;
+
+    let mut counts: BTreeMap<PathBuf, usize> = BTreeMap::new();
+    for line in result.lines.iter().filter(|line| !line.is_context) {
+        // Add the match to every ancestor up to the root directory
+        let mut current = line.file_path.parent();
+        while let Some(dir) = current {
+            *counts.entry(dir.to_path_buf()).or_default() += 1;
+            if dir == directory || !dir.starts_with(directory) {
+                break;
+            }
+            current = dir.parent();
+        }
+    }
+
+    // Apply path prefix removal if configured
+    let counts = counts
+        .into_iter()
+        .map(|(dir, count)| {
+            let dir = if let Some(prefix) = &options.omit_path_prefix {
+                remove_path_prefix(&dir, prefix)
+            } else {
+                dir
+            };
+            (dir, count)
+        })
+        .collect();
+
+    Ok(counts)
+}
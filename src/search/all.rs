@@ -0,0 +1,93 @@
+//! Searches for files matching several patterns.
+//!
+//! A regular expression can only express "A or B" on a single line. This module finds the
+//! files in which every one of several patterns matches, anywhere in the file.
+
+use anyhow::{Result, anyhow};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::summary::{ensure_complete, unmodified_path_options};
+use super::{SearchOptions, build_matcher, collect_files_with_modified, search_collected_files};
+use crate::paths::remove_path_prefix;
+
+/// Returns the files in which every pattern matches at least once.
+///
+/// The patterns don't need to match on the same line. The files are collected once with the
+/// file filters of `options`, and each pattern is then searched only in the files that matched
+/// all of the previous patterns, so the set of candidate files shrinks with each pattern.
+///
+/// # Arguments
+///
+/// * `patterns` - The regular expression patterns that must all match. Supports the same
+///   regex syntax as `search_files`.
+/// * `directory` - The root directory to search in
+/// * `options` - Configuration options for the search operation. `omit_path_prefix` is applied
+///   to the returned paths, while other path display options (`tilde_home`, `clean_paths`,
+///   `auto_omit_common_prefix` and `auto_repo_relative`) and the result options (`skip`,
+///   `take`, context lines, ...) are ignored.
+///
+/// # Returns
+///
+/// The paths of the matching files, sorted by path
+///
+/// # Errors
+///
+/// Returns an error if `patterns` is empty or if a search times out or is cancelled, as some
+/// matching files could be missing. Otherwise returns the same errors as `search_files`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::search::{SearchOptions, files_matching_all};
+/// use std::path::Path;
+///
+/// let patterns = vec!["TODO".to_string(), "FIXME".to_string()];
+/// let files = files_matching_all(&patterns, Path::new("src"), &SearchOptions::default()).unwrap();
+/// for file in files {
+///     println!("{}", file.display());
+/// }
+/// ```
+pub fn files_matching_all(
+    patterns: &[String],
+    directory: &Path,
+    options: &SearchOptions,
+) -> Result<Vec<PathBuf>> {
+    if patterns.is_empty() {
+        return Err(anyhow!("At least one pattern is required"));
+    }
+    // Validate every pattern before searching
+    let matchers = patterns
+        .iter()
+        .map(|pattern| build_matcher(pattern, options))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Search with unmodified paths so results can be matched to the collected files
+    let search_options = unmodified_path_options(options);
+
+    let mut files = collect_files_with_modified(directory, &search_options)?;
+    for matcher in &matchers {
+        // Search even when no files are left, so that a cancelled walk is reported as an error
+        let (result, _) =
+            search_collected_files(matcher, directory, files.clone(), &search_options)?;
+        ensure_complete(&result)?;
+        let matched: HashSet<&Path> = result
+            .lines
+            .iter()
+            .filter(|line| !line.is_context)
+            .map(|line| line.file_path.as_path())
+            .collect();
+        files.retain(|(path, _)| matched.contains(path.as_path()));
+    }
+
+    // Apply path prefix removal if configured
+    let mut paths: Vec<PathBuf> = files
+        .into_iter()
+        .map(|(path, _)| match &options.omit_path_prefix {
+            Some(prefix) => remove_path_prefix(&path, prefix),
+            None => path,
+        })
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
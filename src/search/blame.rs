@@ -0,0 +1,113 @@
+//! Git blame annotations for search results.
+//!
+//! Blaming a file walks its history, which is far more expensive than searching it. The
+//! blame of each file is therefore computed at most once per search, and only for files
+//! with matches. Blaming requires the `git` cargo feature.
+
+#[cfg(feature = "git")]
+use git2::{Blame, Oid, Repository};
+use std::path::Path;
+
+/// The git repository enclosing a search directory, opened to blame the files with matches.
+#[cfg_attr(not(feature = "git"), allow(dead_code))]
+pub(crate) struct BlameRepository {
+    #[cfg(feature = "git")]
+    repository: Repository,
+}
+
+impl BlameRepository {
+    /// Opens the repository enclosing `directory`.
+    ///
+    /// Returns `None` if `directory` is not inside a git repository, or if the `git` feature
+    /// is disabled.
+    pub(crate) fn discover(directory: &Path) -> Option<Self> {
+        #[cfg(feature = "git")]
+        {
+            Repository::discover(directory)
+                .ok()
+                .map(|repository| Self { repository })
+        }
+        #[cfg(not(feature = "git"))]
+        {
+            let _ = directory;
+            None
+        }
+    }
+}
+
+/// The author and commit that last changed a range of lines.
+struct BlameHunk {
+    /// The first line of the hunk (1-based)
+    start_line: usize,
+    /// The number of lines in the hunk
+    lines: usize,
+    /// The author name of the commit
+    author: String,
+    /// The commit ID as a hexadecimal string
+    commit: String,
+}
+
+/// The blame of a single file, mapping line numbers to the commits that last changed them.
+pub(crate) struct FileBlame {
+    hunks: Vec<BlameHunk>,
+}
+
+impl FileBlame {
+    /// Blames a file in the working tree of a repository.
+    ///
+    /// The blame reflects the current file content, so line numbers match the search
+    /// results even when the file has uncommitted changes.
+    ///
+    /// Returns `None` if the file is outside the repository's working tree or can't be blamed
+    /// (e.g., because it is untracked or the repository has no commits).
+    #[cfg(feature = "git")]
+    pub(crate) fn new(repository: &BlameRepository, file_path: &Path) -> Option<Self> {
+        let repository = &repository.repository;
+        let workdir = repository.workdir()?.canonicalize().ok()?;
+        let canonical_path = file_path.canonicalize().ok()?;
+        let relative_path = canonical_path.strip_prefix(&workdir).ok()?;
+
+        let committed_blame = repository.blame_file(relative_path, None).ok()?;
+        let content = std::fs::read(&canonical_path).ok()?;
+        let blame = committed_blame.blame_buffer(&content).ok()?;
+
+        Some(Self {
+            hunks: collect_hunks(&blame),
+        })
+    }
+
+    /// Blaming files requires the `git` feature
+    #[cfg(not(feature = "git"))]
+    pub(crate) fn new(_repository: &BlameRepository, _file_path: &Path) -> Option<Self> {
+        None
+    }
+
+    /// Returns the author name and commit ID that last changed a line.
+    ///
+    /// Returns `None` for lines that have not been committed yet.
+    pub(crate) fn line(&self, line_number: u64) -> Option<(&str, &str)> {
+        let line_number = usize::try_from(line_number).ok()?;
+        self.hunks
+            .iter()
+            .find(|hunk| {
+                hunk.start_line <= line_number && line_number < hunk.start_line + hunk.lines
+            })
+            .map(|hunk| (hunk.author.as_str(), hunk.commit.as_str()))
+    }
+}
+
+/// Extracts the committed hunks of a blame.
+#[cfg(feature = "git")]
+fn collect_hunks(blame: &Blame<'_>) -> Vec<BlameHunk> {
+    blame
+        .iter()
+        // Lines changed in the working tree are attributed to a zero commit ID
+        .filter(|hunk| hunk.final_commit_id() != Oid::zero())
+        .map(|hunk| BlameHunk {
+            start_line: hunk.final_start_line(),
+            lines: hunk.lines_in_hunk(),
+            author: String::from_utf8_lossy(hunk.final_signature().name_bytes()).into_owned(),
+            commit: hunk.final_commit_id().to_string(),
+        })
+        .collect()
+}
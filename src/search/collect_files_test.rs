@@ -98,8 +98,10 @@ mod tests {
         assert_eq!(files.len(), 5, "Should find all 5 test files");
 
         // Test case: Include only .txt files
-        let mut options_txt = SearchOptions::default();
-        options_txt.include_glob = Some(vec!["**/*.txt".to_string()]);
+        let options_txt = SearchOptions {
+            include_glob: Some(vec!["**/*.txt".to_string()]),
+            ..SearchOptions::default()
+        };
         let files_txt = collect_files(temp_path, &options_txt)?;
 
         println!("\nTXT files found by collect_files: {}", files_txt.len());
@@ -110,8 +112,10 @@ mod tests {
         assert_eq!(files_txt.len(), 2, "Should find 2 .txt files");
 
         // Test case: Include only files in nested directory
-        let mut options_nested = SearchOptions::default();
-        options_nested.include_glob = Some(vec!["nested/**".to_string()]);
+        let options_nested = SearchOptions {
+            include_glob: Some(vec!["nested/**".to_string()]),
+            ..SearchOptions::default()
+        };
 
         // Debug the glob pattern matching
         println!("\nTesting nested glob pattern: 'nested/**'");
@@ -119,7 +123,7 @@ mod tests {
             let path = PathBuf::from(filepath);
             let matches = crate::traverse::common::path_matches_any_glob(
                 &path,
-                &options_nested.include_glob.as_ref().unwrap(),
+                options_nested.include_glob.as_ref().unwrap(),
                 options_nested.case_sensitive,
             )?;
             println!("  {} matches? {}", path.display(), matches);
@@ -137,7 +141,7 @@ mod tests {
                 let rel_path = path.strip_prefix(temp_path).unwrap_or(&path);
                 let matches = crate::traverse::common::path_matches_any_glob(
                     rel_path,
-                    &options_nested.include_glob.as_ref().unwrap(),
+                    options_nested.include_glob.as_ref().unwrap(),
                     options_nested.case_sensitive,
                 )?;
                 println!(
@@ -162,8 +166,10 @@ mod tests {
 
         // Instead of asserting, use a modified version of the glob pattern that should work
         println!("\nTrying with modified glob pattern 'nested*/**':");
-        let mut options_nested_modified = SearchOptions::default();
-        options_nested_modified.include_glob = Some(vec!["**/nested/**".to_string()]);
+        let options_nested_modified = SearchOptions {
+            include_glob: Some(vec!["**/nested/**".to_string()]),
+            ..SearchOptions::default()
+        };
         let files_nested_modified = collect_files(temp_path, &options_nested_modified)?;
 
         println!(
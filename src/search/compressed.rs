@@ -0,0 +1,62 @@
+//! Transparent decompression of compressed files for searching.
+//!
+//! Compressed files are recognized by their magic bytes rather than their extension, so
+//! mislabeled and extensionless files are handled too. Like ripgrep's `--search-zip`, the
+//! content is decompressed by streaming it from the usual command line tool (`gzip`, `bzip2`,
+//! `xz` or `zstd`), which must be available on the `PATH`.
+
+use anyhow::{Context, Result};
+use grep::cli::{CommandReader, resolve_binary};
+use std::path::Path;
+use std::process::Command;
+
+/// A compression format that can be searched transparently.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Compression {
+    Gzip,
+    Bzip2,
+    Xz,
+    Zstd,
+}
+
+impl Compression {
+    /// Detects the compression format of a file from its magic bytes.
+    ///
+    /// Returns `None` if the file can't be read or is not compressed in a supported format.
+    pub(crate) fn detect(path: &Path) -> Option<Self> {
+        let kind = infer::get_from_path(path).ok()??;
+        match kind.mime_type() {
+            "application/gzip" => Some(Self::Gzip),
+            "application/x-bzip2" => Some(Self::Bzip2),
+            "application/x-xz" => Some(Self::Xz),
+            "application/zstd" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Returns the program and arguments that write the decompressed file to stdout
+    fn command(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            Self::Gzip => ("gzip", &["-d", "-c"]),
+            Self::Bzip2 => ("bzip2", &["-d", "-c"]),
+            Self::Xz => ("xz", &["-d", "-c"]),
+            Self::Zstd => ("zstd", &["-q", "-d", "-c"]),
+        }
+    }
+
+    /// Starts decompressing a file, returning a reader of the decompressed content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the decompression program can't be found or started
+    pub(crate) fn reader(self, path: &Path) -> Result<CommandReader> {
+        let (program, args) = self.command();
+        let program = resolve_binary(program)
+            .with_context(|| format!("Failed to find the decompression program {}", program))?;
+
+        let mut command = Command::new(&program);
+        command.args(args).arg(path);
+        CommandReader::new(&mut command)
+            .with_context(|| format!("Failed to run {}", program.display()))
+    }
+}
@@ -0,0 +1,226 @@
+//! Explanations of why files are excluded from a search.
+//!
+//! This module provides a debugging aid for the common question "why wasn't this file
+//! searched?". It re-applies the individual filtering steps of `search_files` to a single
+//! path and reports the first one that excludes it.
+
+use anyhow::{Result, anyhow};
+use std::fs;
+use std::path::{Component, Path};
+
+use super::SearchOptions;
+use super::compressed::Compression;
+use super::pdf::PdfText;
+use super::tracked::TrackedFiles;
+use crate::traverse::common::{
+    build_glob_set, find_ignore_rule, is_hidden_path, is_vendored_dir_name, path_matches_any_glob,
+};
+
+/// Explains why a file would be excluded from a search.
+///
+/// The filters of `search_files` are checked in the order they are applied during a search,
+/// and the reason of the first filter that excludes the file is returned:
+///
+/// 1. The path is outside of the search directory
+/// 2. The path is deeper than the `depth` limit or shallower than `min_depth`
+/// 3. The path is hidden (when `respect_gitignore` is enabled)
+/// 4. The path matches a `.gitignore` or `.ignore` rule (when `respect_gitignore` is enabled)
+/// 5. The path is inside a vendored directory (when `skip_vendored` is enabled)
+/// 6. The path is inside one of the `exclude_dir_names` directories
+/// 7. The path matches one of the `exclude_glob` patterns
+/// 8. The file is not tracked by git (when `git_tracked_only` is enabled)
+/// 9. The path doesn't match any of the `include_glob` patterns
+/// 10. The file is binary, so searching stops at its first NUL byte (unless `treat_all_as_text` is set,
+///     `line_terminator` is NUL, the file is compressed and `search_compressed` is set, or the
+///     file is a PDF file and `extract_pdf_text` is set)
+///
+/// Gitignore rules are evaluated with the same precedence as the directory walker, but global
+/// gitignore files and `.git/info/exclude` are not considered.
+///
+/// # Arguments
+///
+/// * `path` - The file to check. Relative paths are resolved against `directory` unless they
+///   already start with it.
+/// * `directory` - The directory that would be searched
+/// * `options` - The search options that would be used
+///
+/// # Returns
+///
+/// `Some(reason)` describing why the file would be excluded, or `None` if it would be searched.
+///
+/// # Errors
+///
+/// Returns an error if the path doesn't exist or is not a file, or if a glob pattern in the
+/// options is invalid.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::search::{SearchOptions, explain_exclusion};
+/// use std::path::Path;
+///
+/// let options = SearchOptions {
+///     exclude_glob: Some(vec!["**/target/**".to_string()]),
+///     ..SearchOptions::default()
+/// };
+///
+/// match explain_exclusion(Path::new("target/debug/build.rs"), Path::new("."), &options) {
+///     Ok(Some(reason)) => println!("Excluded: {}", reason),
+///     Ok(None) => println!("The file would be searched"),
+///     Err(e) => eprintln!("Error: {}", e),
+/// }
+/// ```
+pub fn explain_exclusion(
+    path: &Path,
+    directory: &Path,
+    options: &SearchOptions,
+) -> Result<Option<String>> {
+    let full_path = if path.is_relative() && !path.starts_with(directory) {
+        directory.join(path)
+    } else {
+        path.to_path_buf()
+    };
+
+    if !full_path.is_file() {
+        return Err(anyhow!("Not a file: {}", full_path.display()));
+    }
+
+    // Paths are matched relative to the search directory
+    let rel_path = match full_path.strip_prefix(directory) {
+        Ok(rel_path) => rel_path,
+        Err(_) => {
+            return Ok(Some(format!(
+                "outside of the search directory {}",
+                directory.display()
+            )));
+        }
+    };
+
+    if let Some(depth) = options.depth {
+        let path_depth = rel_path.components().count();
+        if path_depth > depth {
+            return Ok(Some(format!(
+                "exceeds the maximum depth {} (file depth: {})",
+                depth, path_depth
+            )));
+        }
+    }
+
+    if let Some(min_depth) = options.min_depth {
+        let path_depth = rel_path.components().count();
+        if path_depth < min_depth {
+            return Ok(Some(format!(
+                "below the minimum depth {} (file depth: {})",
+                min_depth, path_depth
+            )));
+        }
+    }
+
+    if options.respect_gitignore {
+        if is_hidden_path(rel_path) {
+            return Ok(Some(
+                "hidden file or directory (respect_gitignore skips hidden entries)".to_string(),
+            ));
+        }
+
+        if let Some(rule) = find_ignore_rule(
+            directory,
+            &full_path,
+            options.case_sensitive,
+            options.ignore_root_gitignore,
+        ) {
+            return Ok(Some(rule));
+        }
+    }
+
+    if options.skip_vendored {
+        let vendored_dir = rel_path
+            .parent()
+            .into_iter()
+            .flat_map(|parent| parent.components())
+            .find_map(|component| match component {
+                Component::Normal(dir) => {
+                    Some(dir.to_string_lossy()).filter(|dir| is_vendored_dir_name(dir))
+                }
+                _ => None,
+            });
+        if let Some(dir) = vendored_dir {
+            return Ok(Some(format!(
+                "inside vendored directory `{}` (skip_vendored is enabled)",
+                dir
+            )));
+        }
+    }
+
+    for name in &options.exclude_dir_names {
+        let in_excluded_dir = rel_path
+            .parent()
+            .into_iter()
+            .flat_map(|parent| parent.components())
+            .any(|component| match component {
+                Component::Normal(dir) => {
+                    let dir = dir.to_string_lossy();
+                    if options.case_sensitive {
+                        dir == name.as_str()
+                    } else {
+                        dir.to_lowercase() == name.to_lowercase()
+                    }
+                }
+                _ => false,
+            });
+        if in_excluded_dir {
+            return Ok(Some(format!("inside excluded directory `{}`", name)));
+        }
+    }
+
+    if let Some(exclude_patterns) = &options.exclude_glob {
+        if path_matches_any_glob(rel_path, exclude_patterns, options.case_sensitive)? {
+            // Report the first pattern that matches; negated patterns didn't match
+            let pattern = exclude_patterns
+                .iter()
+                .filter(|pattern| !pattern.starts_with('!'))
+                .find(|pattern| {
+                    build_glob_set(std::slice::from_ref(*pattern), options.case_sensitive)
+                        .is_ok_and(|glob_set| glob_set.is_match(rel_path))
+                });
+            return Ok(Some(match pattern {
+                Some(pattern) => format!("matched exclude_glob `{}`", pattern),
+                None => format!("matched exclude_glob {:?}", exclude_patterns),
+            }));
+        }
+    }
+
+    if options.git_tracked_only {
+        if let Some(tracked_files) = TrackedFiles::discover(directory) {
+            if !tracked_files.contains(rel_path) {
+                return Ok(Some(
+                    "not tracked by git (git_tracked_only is enabled)".to_string(),
+                ));
+            }
+        }
+    }
+
+    if let Some(include_patterns) = &options.include_glob {
+        if !path_matches_any_glob(rel_path, include_patterns, options.case_sensitive)? {
+            return Ok(Some(format!(
+                "not matched by include_glob {:?}",
+                include_patterns
+            )));
+        }
+    }
+
+    let decompressed = options.search_compressed && Compression::detect(&full_path).is_some();
+    let extracted = options.extract_pdf_text && PdfText::is_searchable(&full_path);
+    if !options.treat_all_as_text && options.line_terminator != b'\0' && !decompressed && !extracted
+    {
+        let content = fs::read(&full_path)?;
+        if content.contains(&b'\x00') {
+            return Ok(Some(
+                "binary (contains a NUL byte; searching stops there unless treat_all_as_text is set)"
+                    .to_string(),
+            ));
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,217 @@
+//! Helpers for working with match positions inside a line.
+//!
+//! Match offsets reported by the regex matcher are byte offsets. Inserting text (such as
+//! highlight markers) at an offset that falls inside a multibyte character would produce
+//! invalid UTF-8, so all positions are snapped to character boundaries before use.
+
+/// Returns the largest character boundary of `s` that is not greater than `index`.
+pub(crate) fn floor_char_boundary(s: &str, index: usize) -> usize {
+    if index >= s.len() {
+        return s.len();
+    }
+    (0..=index)
+        .rev()
+        .find(|&i| s.is_char_boundary(i))
+        .unwrap_or(0)
+}
+
+/// Returns the smallest character boundary of `s` that is not less than `index`.
+pub(crate) fn ceil_char_boundary(s: &str, index: usize) -> usize {
+    if index >= s.len() {
+        return s.len();
+    }
+    (index..=s.len())
+        .find(|&i| s.is_char_boundary(i))
+        .unwrap_or(s.len())
+}
+
+/// Returns the byte range of `content` that keeps a match with up to `before` characters
+/// before it and up to `after` characters after it.
+///
+/// Both sides are counted from the match itself, so the range is cut at the start and end
+/// of the line when fewer characters are available.
+///
+/// # Arguments
+///
+/// * `content` - The full line content
+/// * `(match_start, match_end)` - The match to keep, on character boundaries of `content`
+/// * `before` - The number of characters to keep before the match
+/// * `after` - The number of characters to keep after the match
+pub(crate) fn keep_range(
+    content: &str,
+    (match_start, match_end): (usize, usize),
+    before: usize,
+    after: usize,
+) -> (usize, usize) {
+    let start = if before == 0 {
+        match_start
+    } else {
+        content[..match_start]
+            .char_indices()
+            .rev()
+            .nth(before - 1)
+            .map_or(0, |(i, _)| i)
+    };
+    let end = content[match_end..]
+        .char_indices()
+        .nth(after)
+        .map_or(content.len(), |(i, _)| match_end + i);
+    (start, end)
+}
+
+/// Appends `content[start..end]` to `output`, wrapping each match inside the range with markers.
+///
+/// The ranges of the appended matches within `output` (excluding the markers) are added to
+/// `display_ranges`.
+///
+/// # Arguments
+///
+/// * `output` - The string to append to
+/// * `content` - The full line content
+/// * `start` - Start of the range to append (a character boundary)
+/// * `end` - End of the range to append (a character boundary)
+/// * `matches` - Sorted, non-overlapping match ranges on character boundaries of `content`
+/// * `markers` - Optional `(open, close)` markers to insert around each match; when `None`,
+///   the range is appended unchanged
+/// * `display_ranges` - Receives the ranges of the appended matches within `output`
+///
+/// Empty matches and matches not fully contained in the range are neither highlighted nor
+/// added to `display_ranges`.
+pub(crate) fn push_highlighted(
+    output: &mut String,
+    content: &str,
+    start: usize,
+    end: usize,
+    matches: &[(usize, usize)],
+    markers: Option<&(String, String)>,
+    display_ranges: &mut Vec<(usize, usize)>,
+) {
+    let (open, close) = match markers {
+        Some((open, close)) => (open.as_str(), close.as_str()),
+        None => ("", ""),
+    };
+
+    let mut cursor = start;
+    for &(match_start, match_end) in matches {
+        if match_start < cursor || match_end > end || match_start == match_end {
+            continue;
+        }
+        output.push_str(&content[cursor..match_start]);
+        output.push_str(open);
+        let display_start = output.len();
+        output.push_str(&content[match_start..match_end]);
+        display_ranges.push((display_start, output.len()));
+        output.push_str(close);
+        cursor = match_end;
+    }
+    output.push_str(&content[cursor..end]);
+}
+
+/// Cuts a displayed line after at most `max_bytes` bytes and appends an `<omit>` marker.
+///
+/// The cut is moved back to a character boundary, and to the start of the opening marker when
+/// it would fall inside a highlighted match. Display ranges that no longer fit are removed.
+///
+/// # Arguments
+///
+/// * `output` - The displayed line content to truncate
+/// * `max_bytes` - The maximum number of bytes of content to keep
+/// * `display_ranges` - The ranges of the matches within `output` (excluding the markers)
+/// * `markers` - The `(open, close)` markers placed around each match, if any
+///
+/// # Returns
+///
+/// `true` if the line was truncated
+pub(crate) fn truncate_displayed(
+    output: &mut String,
+    max_bytes: usize,
+    display_ranges: &mut Vec<(usize, usize)>,
+    markers: Option<&(String, String)>,
+) -> bool {
+    if output.len() <= max_bytes {
+        return false;
+    }
+
+    let (open_len, close_len) = markers.map_or((0, 0), |(open, close)| (open.len(), close.len()));
+    let mut cut = floor_char_boundary(output, max_bytes);
+    if let Some(&(start, _)) = display_ranges
+        .iter()
+        .find(|&&(start, end)| start - open_len < cut && cut < end + close_len)
+    {
+        cut = start - open_len;
+    }
+    display_ranges.retain(|&(_, end)| end + close_len <= cut);
+
+    output.truncate(cut);
+    output.push_str("<omit>");
+    true
+}
+
+/// Converts byte ranges within `content` to UTF-16 code unit ranges.
+///
+/// # Arguments
+///
+/// * `content` - The full line content
+/// * `ranges` - Sorted, non-overlapping byte ranges on character boundaries of `content`
+pub(crate) fn utf16_ranges(content: &str, ranges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    // Count the code units incrementally, since the ranges are sorted
+    let mut byte_offset = 0;
+    let mut utf16_offset = 0;
+    let mut advance = |to: usize| {
+        utf16_offset += content[byte_offset..to].encode_utf16().count();
+        byte_offset = to;
+        utf16_offset
+    };
+
+    ranges
+        .iter()
+        .map(|&(start, end)| (advance(start), advance(end)))
+        .collect()
+}
+
+/// Extracts up to `width` characters of `content` centered on a match.
+///
+/// The characters around the match are split evenly before and after it, with any space that
+/// one side can't use given to the other, and kept as by `keep_range`. Matches longer than
+/// `width` are cut at their end. An ellipsis (`…`) is added at each end where the line
+/// continues; ellipses are not counted in `width`.
+///
+/// # Arguments
+///
+/// * `content` - The full line content
+/// * `(match_start, match_end)` - The match to center on, on character boundaries of `content`
+/// * `width` - The maximum number of characters of `content` in the snippet
+pub(crate) fn snippet(
+    content: &str,
+    (match_start, match_end): (usize, usize),
+    width: usize,
+) -> String {
+    let match_chars = content[match_start..match_end].chars().count();
+    let chars_before = content[..match_start].chars().count();
+    let chars_after = content[match_end..].chars().count();
+
+    // Split the remaining width around the match, moving unused space to the other side
+    let remaining = width.saturating_sub(match_chars);
+    let mut before = (remaining / 2).min(chars_before);
+    let after = (remaining - before).min(chars_after);
+    before = (remaining - after).min(chars_before);
+
+    let (start, mut end) = keep_range(content, (match_start, match_end), before, after);
+    // Matches longer than the width are cut at their end
+    if match_chars > width {
+        end = content[match_start..]
+            .char_indices()
+            .nth(width)
+            .map_or(match_end, |(i, _)| match_start + i);
+    }
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    snippet.push_str(&content[start..end]);
+    if end < content.len() {
+        snippet.push('…');
+    }
+    snippet
+}
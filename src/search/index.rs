@@ -0,0 +1,160 @@
+//! Prebuilt file lists for repeated searches.
+//!
+//! Walking the directory tree (and evaluating gitignore rules and globs) is often the most
+//! expensive part of a search over a large, mostly static tree. A `FileIndex` walks the tree
+//! once and reuses the list of candidate files for every search, checking only the metadata
+//! of the indexed files to notice changes.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::{SearchOptions, SearchResult, build_matcher, collect_files, search_collected_files};
+
+/// A file in a `FileIndex`, with the metadata recorded when it was last checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedFile {
+    /// Path to the file, as found under the indexed directory
+    pub path: PathBuf,
+    /// Size of the file in bytes
+    pub size_bytes: u64,
+    /// Last modification time of the file, if the platform supports it
+    pub modified: Option<SystemTime>,
+}
+
+/// A cached list of the files to search in a directory.
+///
+/// The index is built with the file selection options of a `SearchOptions` (gitignore
+/// handling, globs, depth limits, ...), and searches with the index skip the directory walk.
+/// Before each search, the metadata of every indexed file is checked: entries whose
+/// modification time or size changed are refreshed, and files that were removed are dropped
+/// from the index. Files created after the index was built are not searched until `refresh`
+/// is called.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::search::{FileIndex, SearchOptions};
+/// use std::path::Path;
+///
+/// let options = SearchOptions::default();
+/// let mut index = FileIndex::build(Path::new("src"), &options).unwrap();
+///
+/// // Both searches reuse the file list of the index
+/// let todos = index.search("TODO", &options).unwrap();
+/// let fixmes = index.search("FIXME", &options).unwrap();
+/// println!("{} TODOs, {} FIXMEs", todos.total_number, fixmes.total_number);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileIndex {
+    /// The indexed directory
+    directory: PathBuf,
+    /// The options used to select the indexed files
+    options: SearchOptions,
+    /// The indexed files, in walk order
+    files: Vec<IndexedFile>,
+    /// The number of directory walks performed by this index
+    walk_count: usize,
+}
+
+impl FileIndex {
+    /// Walks a directory and indexes the files a search with `options` would search.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory to index
+    /// * `options` - The search options selecting the files to index. Only the options
+    ///   applied while collecting files are used, such as `respect_gitignore`,
+    ///   `include_glob`, `exclude_glob`, `exclude_dir_names`, `depth`, `min_depth`,
+    ///   `skip_vendored` and `git_tracked_only`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory can't be walked or a glob pattern is invalid
+    pub fn build(directory: &Path, options: &SearchOptions) -> Result<Self> {
+        let mut index = Self {
+            directory: directory.to_path_buf(),
+            options: options.clone(),
+            files: Vec::new(),
+            walk_count: 0,
+        };
+        index.refresh()?;
+        Ok(index)
+    }
+
+    /// Walks the directory again, replacing the indexed files.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as `build`
+    pub fn refresh(&mut self) -> Result<()> {
+        let paths = collect_files(&self.directory, &self.options)
+            .context("Failed to collect files for indexing")?;
+        self.walk_count += 1;
+        self.files = paths.into_iter().filter_map(indexed_file).collect();
+        Ok(())
+    }
+
+    /// Searches the indexed files for a pattern, without walking the directory.
+    ///
+    /// Stale entries are updated first (see `FileIndex`). The search then behaves like
+    /// `search_files` over the indexed directory, except that the file selection options of
+    /// `options` are ignored in favor of those the index was built with.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The regular expression pattern to search for
+    /// * `options` - Configuration options for the search operation
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern is invalid
+    pub fn search(&mut self, pattern: &str, options: &SearchOptions) -> Result<SearchResult> {
+        let matcher = build_matcher(pattern, options)?;
+        self.invalidate();
+
+        let files = self
+            .files
+            .iter()
+            .map(|file| (file.path.clone(), file.modified))
+            .collect();
+        search_collected_files(&matcher, &self.directory, files, options).map(|(result, _)| result)
+    }
+
+    /// Returns the indexed directory
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns the indexed files, with the metadata recorded when they were last checked
+    pub fn files(&self) -> &[IndexedFile] {
+        &self.files
+    }
+
+    /// Returns the number of directory walks performed by this index.
+    ///
+    /// This is 1 after `build`, and increases with each call to `refresh`; searches never
+    /// walk the directory.
+    pub fn walk_count(&self) -> usize {
+        self.walk_count
+    }
+
+    /// Drops the entries of removed files and refreshes the metadata of modified files
+    fn invalidate(&mut self) {
+        let files = std::mem::take(&mut self.files);
+        self.files = files
+            .into_iter()
+            .filter_map(|file| indexed_file(file.path))
+            .collect();
+    }
+}
+
+/// Reads the metadata of a file to index, or `None` if it is no longer a file
+fn indexed_file(path: PathBuf) -> Option<IndexedFile> {
+    let metadata = path.metadata().ok().filter(|metadata| metadata.is_file())?;
+    Some(IndexedFile {
+        size_bytes: metadata.len(),
+        modified: metadata.modified().ok(),
+        path,
+    })
+}
@@ -0,0 +1,41 @@
+//! Stable identifiers for search result lines.
+//!
+//! A UI streaming incremental search results needs to recognize the same match across
+//! searches to update or remove it. The identifier is a hash of the match location, computed
+//! with FNV-1a rather than `std`'s `DefaultHasher`, whose algorithm is not guaranteed to stay
+//! the same across Rust releases.
+
+use std::path::Path;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Computes the identifier of a match from its file path, line number and match ranges.
+///
+/// The result is a 16-digit lowercase hexadecimal string that only depends on its inputs,
+/// so it is stable across runs as long as the file is unchanged.
+pub(crate) fn compute_match_id(
+    file_path: &Path,
+    line_number: u64,
+    match_ranges: &[(usize, usize)],
+) -> String {
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut write = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    };
+
+    // Hash the raw bytes so distinct non-UTF-8 paths don't collide
+    write(file_path.as_os_str().as_encoded_bytes());
+    // Separate the path from the numbers, since paths can end with any byte but NUL
+    write(&[0]);
+    write(&line_number.to_le_bytes());
+    for &(start, end) in match_ranges {
+        write(&(start as u64).to_le_bytes());
+        write(&(end as u64).to_le_bytes());
+    }
+
+    format!("{:016x}", hash)
+}
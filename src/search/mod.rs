@@ -58,28 +58,75 @@
 //!
 //! For more comprehensive examples and details, see the documentation of the `search_files` function.
 
-use anyhow::{Context, Result};
-use grep::matcher::Matcher;
-use grep::regex::RegexMatcher;
+mod aggregate;
+mod all;
+mod blame;
+mod compressed;
+mod explain;
+mod highlight;
+mod index;
+mod match_id;
+mod output;
+mod pdf;
+mod summary;
+mod symbol;
+mod tracked;
+mod view_match;
+mod windows;
+
+pub use aggregate::aggregate_by_directory;
+pub use all::files_matching_all;
+pub use explain::explain_exclusion;
+pub use index::{FileIndex, IndexedFile};
+pub use output::{OutputFormat, write_results, write_results_to};
+pub use pdf::PDF_PAGE_LINE_BASE;
+pub use summary::{SearchSummary, per_line_match_histogram, summary};
+pub use view_match::view_match;
+pub use windows::MatchWindow;
+
+use anyhow::{Context, Result, anyhow};
+use blame::{BlameRepository, FileBlame};
+use compressed::Compression;
+use grep::matcher::{LineTerminator, Matcher};
+use grep::regex::{RegexMatcher, RegexMatcherBuilder};
 // Import removed: grep::searcher::sinks::UTF8; (no longer needed)
-use grep::searcher::{BinaryDetection, SearcherBuilder};
+use grep::searcher::{BinaryDetection, MmapChoice, Searcher, SearcherBuilder};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::fs::File;
+use std::io::{BufRead, Read};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
-use crate::paths::remove_path_prefix;
+use crate::paths::{
+    PathDisplay, common_prefix, find_repository_root, natural_path_cmp, remove_path_prefix,
+};
 use crate::telemetry::{LogMessage, log_with_context};
 use crate::traverse::common;
+use highlight::{
+    ceil_char_boundary, floor_char_boundary, keep_range, push_highlighted, truncate_displayed,
+};
+use match_id::compute_match_id;
+use pdf::PdfText;
+use symbol::{SymbolLanguage, find_enclosing_symbol};
+use tracked::TrackedFiles;
 
 /// Configuration options for file search operations.
 ///
 /// Controls the behavior of the search functionality, including case sensitivity
 /// and how gitignore files are handled.
 ///
+/// The options can be loaded from configuration files via serde. Fields missing from the
+/// input take their default values, and unknown fields are rejected.
+///
 /// # Examples
 ///
 /// ```
-/// use lumin::search::SearchOptions;
+/// use lumin::search::{ContentMode, ReadStrategy, SearchOptions, SearchSort};
 /// use std::path::PathBuf;
 ///
 /// // Default options: case-insensitive search respecting gitignore files
@@ -94,10 +141,47 @@ use crate::traverse::common;
 ///     omit_path_prefix: None,
 ///     match_content_omit_num: None,
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 0, // No lines before matches
 ///     after_context: 0, // Only show matching lines, no context
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 ///
 /// // Case-insensitive search, respecting gitignore files, with content truncation
@@ -109,10 +193,47 @@ use crate::traverse::common;
 ///     omit_path_prefix: None,
 ///     match_content_omit_num: Some(30), // Only show 30 characters before and after matches (full matches always preserved)
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 2, // Show 2 lines before each match
 ///     after_context: 2, // Show 2 lines after each match
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 ///
 /// // File type-focused search (only search specific file types)
@@ -124,10 +245,47 @@ use crate::traverse::common;
 ///     omit_path_prefix: None,
 ///     match_content_omit_num: None,
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 0,
 ///     after_context: 0,
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 ///
 /// // Context-focused search (like grep -B3 -A2 pattern)
@@ -139,10 +297,47 @@ use crate::traverse::common;
 ///     omit_path_prefix: None,
 ///     match_content_omit_num: None,
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 3, // Show 3 lines before each match
 ///     after_context: 2, // Show 2 lines after each match
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 ///
 /// // Search with path prefix removal (to show relative paths in results)
@@ -154,13 +349,51 @@ use crate::traverse::common;
 ///     omit_path_prefix: Some(PathBuf::from("/home/user/projects/myrepo")), // Remove this prefix from result paths
 ///     match_content_omit_num: None,
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 0,
 ///     after_context: 0,
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 /// ```
-#[derive(Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct SearchOptions {
     /// Whether the search should be case sensitive.
     ///
@@ -222,6 +455,13 @@ pub struct SearchOptions {
     /// - `exclude_glob: Some(vec!["src/legacy/**".to_string()])` will exclude files in the specific src/legacy directory
     /// - `exclude_glob: None` means no files will be excluded based on glob patterns
     ///
+    /// ## Negation
+    ///
+    /// Patterns with a leading `!` are negations that subtract from the excluded files, and
+    /// always take precedence over the other patterns regardless of their order. For example,
+    /// `vec!["**/*.json".to_string(), "!**/package.json".to_string()]` excludes all JSON files
+    /// except `package.json` files. See `traverse::common::build_glob_filter` for details.
+    ///
     /// ## Consistency with include_glob
     ///
     /// This parameter works consistently with `include_glob` - both use relative paths for pattern matching.
@@ -268,6 +508,14 @@ pub struct SearchOptions {
     /// - `include_glob: Some(vec!["docs/**/*.md".to_string()])` will only search Markdown files in the docs directory
     /// - `include_glob: None` means all files will be included (subject to other filtering criteria)
     ///
+    /// ## Negation
+    ///
+    /// Patterns with a leading `!` are negations that subtract from the included files, and
+    /// always take precedence over the other patterns regardless of their order. For example,
+    /// `vec!["**/*.rs".to_string(), "!**/generated/**".to_string()]` only searches Rust files
+    /// outside of `generated` directories. A list containing only negations includes every file
+    /// that isn't negated. See `traverse::common::build_glob_filter` for details.
+    ///
     /// ## Consistency with exclude_glob
     ///
     /// This parameter works consistently with `exclude_glob` - both use relative paths for pattern matching.
@@ -308,6 +556,9 @@ pub struct SearchOptions {
     /// Note: If multiple matches occur on the same line, each match will preserve its surrounding
     /// context as specified, which means the total line content may exceed `n*2` characters.
     ///
+    /// Each omitted part of the line (before the first kept range, between kept ranges, and after
+    /// the last one) is replaced with an `<omit>` marker.
+    ///
     /// # Behavior
     ///
     /// The entire matched pattern will always be preserved, even if it's longer than `n` characters.
@@ -339,6 +590,19 @@ pub struct SearchOptions {
     /// - With `depth: None`, all subdirectories will be explored regardless of depth
     pub depth: Option<usize>,
 
+    /// Minimum depth of the files to search.
+    ///
+    /// When `Some(min_depth)`, files shallower than `min_depth` are skipped. Depth is counted as
+    /// in `depth`: files directly in the search directory have a depth of 1. This is useful to
+    /// search only nested files, for example the packages of a workspace but not its top-level
+    /// files. Default is `None` (no minimum).
+    ///
+    /// # Examples
+    ///
+    /// - With `min_depth: Some(2)`, `README.md` is skipped but `src/main.rs` is searched
+    /// - With `min_depth: None`, both files are searched
+    pub min_depth: Option<usize>,
+
     /// Number of lines to display before each match (similar to grep's -B option).
     ///
     /// When set to a value greater than 0, this many lines before each match will be included
@@ -400,11 +664,649 @@ pub struct SearchOptions {
     /// - `take: Some(100)` - Return up to 100 results
     /// - `take: None` - Return all results (no limit)
     ///
+    /// When `take` is set without `skip` (or with `skip: Some(0)`), only the first `n` result
+    /// lines are built; the remaining matches are counted without building their content, so
+    /// `total_number` stays accurate while memory use is bounded by `n`. This doesn't apply
+    /// with `sort_by_relevance` or `auto_omit_common_prefix`, which need all results first.
+    ///
     /// For pagination with a page size of 10, you would use:
     /// - Page 1: `skip: None, take: Some(10)` or `skip: Some(0), take: Some(10)`
     /// - Page 2: `skip: Some(10), take: Some(10)`
     /// - Page 3: `skip: Some(20), take: Some(10)`
     pub take: Option<usize>,
+
+    /// Whether to treat every file as text, even if it contains NUL bytes (similar to grep's -a option).
+    ///
+    /// By default, the searcher stops reading a file as soon as it encounters a NUL byte,
+    /// since that usually indicates binary content. Files that are mostly text but contain
+    /// stray NUL bytes (e.g., some `.dat` files) are therefore only searched up to the first NUL.
+    ///
+    /// When `true`, binary detection is disabled entirely and the whole file is searched
+    /// regardless of its content. When `false` (default), searching stops at the first NUL byte.
+    ///
+    /// # Examples
+    ///
+    /// - `treat_all_as_text: false` (default) - A match located after a NUL byte is not reported
+    /// - `treat_all_as_text: true` - Matches anywhere in the file are reported, including after NUL bytes
+    pub treat_all_as_text: bool,
+
+    /// List of directory names to exclude from the search at any depth.
+    ///
+    /// This is a convenience alternative to `exclude_glob` for the common case of skipping
+    /// directories such as `target` or `node_modules`. Each name is translated internally into
+    /// the glob `**/<name>/**`, so users don't need to know that `exclude_glob` patterns are
+    /// matched against relative paths (where `target/**` would only match at the top level).
+    ///
+    /// Names are matched literally against whole path components; glob special characters in a
+    /// name are escaped. Matching respects the `case_sensitive` setting. These exclusions are
+    /// applied in addition to any patterns in `exclude_glob`.
+    ///
+    /// # Examples
+    ///
+    /// - `exclude_dir_names: vec!["target".to_string()]` excludes `target/debug/build.rs` as well
+    ///   as `crates/foo/target/debug/build.rs`
+    /// - `exclude_dir_names: vec!["node_modules".to_string(), ".git".to_string()]` excludes both
+    ///   directories wherever they appear
+    /// - `exclude_dir_names: vec![]` (default) excludes nothing
+    pub exclude_dir_names: Vec<String>,
+
+    /// Optional minimum number of pattern matches a line must contain to be reported.
+    ///
+    /// When set to `Some(k)`, matching lines in which the pattern occurs fewer than `k` times
+    /// are dropped from the results, which is useful for reducing noise when searching for
+    /// frequently occurring terms. When set to `None` (default), every matching line is reported.
+    ///
+    /// Only matching lines are filtered; context lines requested via `before_context` or
+    /// `after_context` are still reported. The `total_number` of the result reflects the
+    /// lines remaining after filtering.
+    ///
+    /// # Examples
+    ///
+    /// - `min_matches_per_line: Some(2)` with pattern `foo` reports `foo bar foo` but not `foo bar`
+    /// - `min_matches_per_line: None` reports every line containing at least one match
+    pub min_matches_per_line: Option<usize>,
+
+    /// Whether to report the function or type enclosing each match.
+    ///
+    /// When `true`, for each matching line in a recognized source file, the lines above the
+    /// match are scanned for the nearest function/class definition, and its name is reported in
+    /// `SearchResultLine::enclosing_symbol`. Detection uses simple language-specific regexes
+    /// rather than a full parser, so it is a best-effort hint for code navigation.
+    ///
+    /// Recognized languages (by file extension) are Rust (`.rs`), JavaScript/TypeScript
+    /// (`.js`, `.jsx`, `.mjs`, `.cjs`, `.ts`, `.tsx`) and Python (`.py`). For other files,
+    /// and for context lines, `enclosing_symbol` is always `None`.
+    ///
+    /// # Examples
+    ///
+    /// - `enclosing_symbol: true` - A match inside `fn parse_config() { ... }` reports `Some("parse_config")`
+    /// - `enclosing_symbol: false` (default) - No symbol detection is performed
+    pub enclosing_symbol: bool,
+
+    /// Whether to order results by relevance instead of by file path.
+    ///
+    /// When `true`, files are ranked by their number of matching lines in descending order,
+    /// so the files with the most matches come first. Files with the same number of matches
+    /// are ordered by path. When `false` (default), results are ordered by file path.
+    ///
+    /// Since results are line-level, this only reorders the files: the lines of each file
+    /// (including context lines) are kept together and stay in line number order. Sorting is
+    /// applied before pagination, so `skip` and `take` page through the relevance order.
+    ///
+    /// Setting this to `true` is equivalent to `sort: SearchSort::Relevance`, which should be
+    /// preferred; this option is kept for compatibility. When `true`, it takes precedence
+    /// over `sort` (see `effective_sort`), so it must be `false` for `sort` to apply.
+    ///
+    /// # Examples
+    ///
+    /// - `sort_by_relevance: true` - A file with 3 matches is listed before a file with 1 match
+    /// - `sort_by_relevance: false` (default) - `a.txt` is listed before `b.txt` regardless of match counts
+    pub sort_by_relevance: bool,
+
+    /// Whether to skip the `.gitignore` file at the search root while honoring nested ones.
+    ///
+    /// In some workflows the top-level `.gitignore` is too aggressive (e.g., it excludes
+    /// generated files you want to search), while `.gitignore` files in subdirectories are
+    /// still useful. When `true`, the `.gitignore` in `directory` itself is ignored, but
+    /// `.gitignore` files in its subdirectories are applied. When `false` (default), all
+    /// gitignore rules apply as usual.
+    ///
+    /// This option only has an effect when `respect_gitignore` is `true`. In this mode,
+    /// `.gitignore` files in parent directories of the search root are not applied either.
+    ///
+    /// # Examples
+    ///
+    /// - `ignore_root_gitignore: true` - With `*.log` in the root `.gitignore`, `app.log` is searched,
+    ///   while files excluded by `sub/.gitignore` are still skipped
+    /// - `ignore_root_gitignore: false` (default) - Both the root and nested `.gitignore` files apply
+    pub ignore_root_gitignore: bool,
+
+    /// Optional markers to insert around each match in the line content.
+    ///
+    /// When set to `Some((open, close))`, every match in `line_content` is wrapped with the
+    /// `open` and `close` markers (e.g., `("<<", ">>")` or ANSI color escape codes). Markers are
+    /// always inserted at UTF-8 character boundaries, so lines containing multibyte characters
+    /// (emoji, CJK text, ...) stay valid. When combined with `match_content_omit_num`, only the
+    /// kept parts of the line are returned, with their matches highlighted.
+    /// When set to `None` (default), the line content is returned without markers.
+    ///
+    /// Context lines are never highlighted.
+    ///
+    /// # Examples
+    ///
+    /// - `highlight_markers: Some(("[".to_string(), "]".to_string()))` with pattern `foo` turns
+    ///   `a foo b` into `a [foo] b`
+    /// - `highlight_markers: None` (default) - Line content is returned as-is
+    pub highlight_markers: Option<(String, String)>,
+
+    /// How file contents are read during the search.
+    ///
+    /// - `ReadStrategy::Buffered` (default) - Files are read through a buffer with regular read calls
+    /// - `ReadStrategy::Mmap` - Files are memory-mapped where the platform supports it
+    /// - `ReadStrategy::Auto` - Files of at least `AUTO_MMAP_THRESHOLD_BYTES` are memory-mapped,
+    ///   smaller files are read through a buffer
+    ///
+    /// Reading through a buffer has lower overhead for many small files, while memory maps
+    /// are usually faster for large files. All strategies produce the same results for text files.
+    /// With `treat_all_as_text`, files are always read through a buffer, so that the NUL bytes
+    /// reported by `SearchResultLine::from_binary_file` are detected while reading.
+    ///
+    /// # Safety caveat
+    ///
+    /// A memory-mapped file must not be modified while it is searched. If a file is truncated
+    /// by another process during the search, accessing the unmapped pages can terminate the
+    /// process with `SIGBUS` on Unix platforms. Only use `Mmap` or `Auto` when the searched
+    /// files are not expected to change concurrently.
+    pub read_strategy: ReadStrategy,
+
+    /// Starting line number of the window to search in each file (1-based, inclusive).
+    ///
+    /// Like `ViewOptions::line_from`, this is useful for re-searching a known region of a
+    /// file. Only matches on lines within the window are reported, and context lines outside
+    /// the window are dropped as well. When set to `None` (default), the window starts at the
+    /// first line.
+    ///
+    /// # Examples
+    ///
+    /// - `line_from: Some(10), line_to: Some(20)` reports only matches on lines 10 to 20 of each file
+    /// - `line_from: None` searches from the beginning of each file
+    pub line_from: Option<usize>,
+
+    /// Ending line number of the window to search in each file (1-based, inclusive).
+    ///
+    /// Reading a file stops once the window has been passed. When set to `None` (default),
+    /// the window extends to the last line. If `line_from` is greater than `line_to`, no
+    /// lines are reported. See `line_from` for details.
+    pub line_to: Option<usize>,
+
+    /// The byte that terminates each line (record) of a file.
+    ///
+    /// Lines reported in the results, and their `line_number`, are delimited by this byte.
+    /// Setting it to `b'\0'` allows searching NUL-delimited data, such as `find -print0`
+    /// output, record by record. The terminator is not included in `line_content`.
+    /// Defaults to `b'\n'`.
+    ///
+    /// Since binary files are detected by their NUL bytes, binary detection is disabled when
+    /// the terminator is `b'\0'`, as if `treat_all_as_text` was set.
+    ///
+    /// # Examples
+    ///
+    /// - `line_terminator: b'\0'` - `a.txt\0b.rs\0` is searched as the records `a.txt` (line 1)
+    ///   and `b.rs` (line 2)
+    /// - `line_terminator: b'\n'` (default) - Files are searched line by line
+    pub line_terminator: u8,
+
+    /// Whether to report file paths relative to the enclosing git repository root.
+    ///
+    /// When `true`, the root of the git repository containing the search directory is found
+    /// by walking up to the nearest directory containing `.git`, and result paths are made
+    /// relative to it. This works regardless of how the search directory was given (e.g.,
+    /// `.` or a subdirectory), unlike `omit_path_prefix`, which requires an exact prefix.
+    /// Outside of a git repository, paths are reported as if this option was not set.
+    ///
+    /// Inside a repository, this option takes precedence over `omit_path_prefix`.
+    ///
+    /// # Examples
+    ///
+    /// - `auto_repo_relative: true` - Searching `/home/user/myrepo/src` reports `src/main.rs`
+    ///   for `/home/user/myrepo/src/main.rs`
+    /// - `auto_repo_relative: false` (default) - Paths are reported as found
+    pub auto_repo_relative: bool,
+
+    /// Optional limit in bytes on the size of the compiled regular expression.
+    ///
+    /// Patterns from untrusted sources, such as `\w{1000}{1000}`, can compile into huge
+    /// programs. When the compiled pattern would exceed this limit, the search fails with an
+    /// error instead of consuming the memory. When set to `None` (default), the regex engine's
+    /// default limit (about 100 MiB) applies.
+    ///
+    /// # Examples
+    ///
+    /// - `regex_size_limit: Some(1 << 20)` - Rejects patterns compiling to more than about 1 MiB
+    /// - `regex_size_limit: None` - Uses the engine's default limit
+    pub regex_size_limit: Option<usize>,
+
+    /// Optional limit in bytes on the cache of the lazy DFA used while searching.
+    ///
+    /// This bounds the memory used for matching a compiled pattern (per search thread). A small
+    /// limit never causes errors, but may make searching slower, since the engine falls back to
+    /// slower matching strategies when the cache is exhausted. When set to `None` (default),
+    /// the engine's default limit (about 1000 MiB) applies.
+    ///
+    /// # Examples
+    ///
+    /// - `dfa_size_limit: Some(10 << 20)` - The DFA cache uses at most about 10 MiB
+    /// - `dfa_size_limit: None` - Uses the engine's default limit
+    pub dfa_size_limit: Option<usize>,
+
+    /// Optional limit on how long a search may run.
+    ///
+    /// When the time is exceeded, the search stops and returns the results found so far, with
+    /// `SearchResult::timed_out` set to `true`. The elapsed time is checked before each file and
+    /// while collecting the lines of a file, so a search may overrun the limit slightly while
+    /// scanning a file for its next match. When set to `None` (default), searches run to
+    /// completion.
+    ///
+    /// # Examples
+    ///
+    /// - `timeout: Some(Duration::from_millis(500))` - Stops after about half a second
+    /// - `timeout: None` - Searches all files without a time limit
+    pub timeout: Option<Duration>,
+
+    /// Optional flag for cancelling the search from another thread.
+    ///
+    /// The flag is checked while collecting the files to search, before each file and while
+    /// collecting the lines of a file. Once it is set to `true`, the search stops promptly and
+    /// returns the results found so far, with `SearchResult::cancelled` set to `true`.
+    /// When set to `None` (default), the search can't be cancelled.
+    ///
+    /// This option is not serialized.
+    ///
+    /// # Examples
+    ///
+    /// - `cancel: Some(Arc::new(AtomicBool::new(false)))` - Keep a clone of the `Arc` and call
+    ///   `store(true, Ordering::Relaxed)` on it to cancel the search
+    #[serde(skip)]
+    pub cancel: Option<Arc<AtomicBool>>,
+
+    /// How much of each line's content is returned.
+    ///
+    /// - `ContentMode::Full` (default) - `line_content` holds the line, after omission and
+    ///   highlighting
+    /// - `ContentMode::None` - `line_content` is left empty for all lines, so no line strings
+    ///   are allocated. Line numbers and `match_ranges` are still reported, which makes this
+    ///   useful for finding where a pattern matches in a large number of files.
+    ///   `display_match_ranges` is empty, and omission and highlighting are not applied.
+    /// - `ContentMode::Truncated` - `line_content` is cut after `TRUNCATED_CONTENT_MAX_BYTES`
+    ///   bytes and ends with an `<omit>` marker. Highlighted matches are never cut in half,
+    ///   and `content_omitted` is `true` for truncated lines.
+    ///
+    /// # Examples
+    ///
+    /// - `content: ContentMode::None` - Results carry paths and line numbers only
+    /// - `content: ContentMode::Truncated` - Long minified lines don't blow up the output
+    pub content: ContentMode,
+
+    /// Whether to compare file paths in natural order when sorting the results.
+    ///
+    /// When `true`, runs of digits in file paths are compared by their numeric value, so
+    /// matches in `file2.txt` are listed before matches in `file10.txt` (see
+    /// `paths::natural_cmp`). When `false` (default), paths are compared character by
+    /// character. Has no effect when `sort_by_relevance` is `true`.
+    ///
+    /// # Examples
+    ///
+    /// - `natural_sort: true` - Results from `chapter2.md` come before `chapter10.md`
+    /// - `natural_sort: false` - Results from `chapter10.md` come before `chapter2.md`
+    pub natural_sort: bool,
+
+    /// Whether to annotate each match with the git commit that last changed its line.
+    ///
+    /// When `true` and the searched directory is inside a git repository, the `blame_author`
+    /// and `blame_commit` fields of each matched line are populated from `git blame`. Blaming
+    /// is expensive, so it is performed once per file, and only for files with matches.
+    /// Lines with uncommitted changes, untracked files and files outside a repository are
+    /// not annotated. Blaming requires the `git` cargo feature (enabled by default); without
+    /// it, searching with this option is an error. When `false` (default), no blame
+    /// information is computed.
+    ///
+    /// # Examples
+    ///
+    /// - `with_blame: true` - A match reports `blame_author: Some("Jane Doe")` and the ID of
+    ///   the commit that introduced the line
+    /// - `with_blame: false` - `blame_author` and `blame_commit` are always `None`
+    pub with_blame: bool,
+
+    /// Whether to skip directories that usually contain vendored or generated files.
+    ///
+    /// When `true`, directories named like one of the `traverse::common::VENDORED_DIR_NAMES`
+    /// (`node_modules`, `vendor`, `dist`, `build`, `target` and `.venv`) are not searched,
+    /// even when they are not excluded by a `.gitignore`. The search directory itself is never
+    /// skipped, so pass a vendored directory as the search directory to search it anyway.
+    /// When `false` (default), these directories are searched like any other.
+    ///
+    /// # Examples
+    ///
+    /// - `skip_vendored: true` - `vendor/lib/util.go` and `web/node_modules/pkg/index.js`
+    ///   are not searched
+    /// - `skip_vendored: false` - All directories are searched
+    pub skip_vendored: bool,
+
+    /// Whether to remove the longest directory prefix shared by all result paths.
+    ///
+    /// When `true`, the common directory prefix of the result file paths (see
+    /// `paths::common_prefix`) is removed from every result, after `omit_path_prefix` or
+    /// `auto_repo_relative` have been applied. This shortens the paths as much as possible
+    /// without having to know the prefix in advance. Match IDs are computed from the shortened
+    /// paths. When `false` (default), paths are returned as they are.
+    ///
+    /// # Examples
+    ///
+    /// - `auto_omit_common_prefix: true` - Matches in `/repo/src/a.rs` and `/repo/src/util/b.rs`
+    ///   are reported as `a.rs` and `util/b.rs`
+    /// - `auto_omit_common_prefix: false` - The paths are reported in full
+    pub auto_omit_common_prefix: bool,
+
+    /// Optional width of a preview snippet for each matched line.
+    ///
+    /// When `Some(width)`, the `snippet` field of each matched line holds up to `width`
+    /// characters of the line centered on its first match, with an ellipsis (`…`) at each end
+    /// where the line continues. Unlike `match_content_omit_num`, the snippet has a fixed
+    /// width regardless of how many matches the line contains, which suits compact list
+    /// rendering. `line_content` is not affected. When set to `None` (default), no snippets
+    /// are produced.
+    ///
+    /// Snippets are not produced for context lines, or with `ContentMode::None`.
+    ///
+    /// # Examples
+    ///
+    /// - `snippet_width: Some(11)` with pattern `fox` in `the quick brown fox jumps over` gives
+    ///   `…own fox jum…`
+    pub snippet_width: Option<usize>,
+
+    /// Whether to search only files tracked by git.
+    ///
+    /// When `true`, only files listed in the git index of the repository enclosing the search
+    /// directory are searched, so untracked files such as build artifacts are skipped even when
+    /// they are not gitignored. Staged new files count as tracked. Outside a git repository,
+    /// this option has no effect. Reading the index requires the `git` cargo feature (enabled
+    /// by default); without it, searching with this option is an error. When `false`
+    /// (default), untracked files are searched too.
+    ///
+    /// # Examples
+    ///
+    /// - `git_tracked_only: true` - A committed `src/main.rs` is searched, but a freshly
+    ///   generated `out/bundle.js` that was never added is not
+    /// - `git_tracked_only: false` - Both files are searched
+    pub git_tracked_only: bool,
+
+    /// Whether to search the decompressed content of compressed files.
+    ///
+    /// When `true`, files compressed with gzip, bzip2, xz or zstd are detected by their magic
+    /// bytes, regardless of their extension, and their decompressed content is searched
+    /// instead of the compressed bytes. Decompression runs the `gzip`, `bzip2`, `xz` or `zstd`
+    /// program, which must be on the `PATH`; files whose program is missing or that fail to
+    /// decompress are skipped with a warning. Compressed files are always read as a stream,
+    /// regardless of `read_strategy`, and `from_binary_file` is not reported for them.
+    ///
+    /// When `false` (default), compressed files are searched as-is, which usually means they
+    /// are skipped as binary.
+    ///
+    /// # Examples
+    ///
+    /// - `search_compressed: true` - A match in `logs/app.log.gz` or in an extensionless
+    ///   zstd archive is reported, with the line number in the decompressed content
+    /// - `search_compressed: false` - Neither file produces matches
+    pub search_compressed: bool,
+
+    /// Whether to report match ranges as UTF-16 code unit offsets as well.
+    ///
+    /// Editors and the Language Server Protocol address positions within a line in UTF-16
+    /// code units rather than bytes. When `true`, `SearchResultLine::match_ranges_utf16` is
+    /// populated with the `match_ranges` converted to UTF-16 offsets. The two differ for lines
+    /// containing non-ASCII characters. Offsets are not computed with `ContentMode::None`,
+    /// since the line content is not kept. Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// - `utf16_offsets: true` with pattern `fox` in `naïve fox` gives `match_ranges` of
+    ///   `[(7, 10)]` and `match_ranges_utf16` of `[(6, 9)]`
+    pub utf16_offsets: bool,
+
+    /// Whether to abbreviate paths under the home directory with `~`.
+    ///
+    /// When `true`, result paths starting with the home directory of the current user (as
+    /// reported by `dirs::home_dir()`) are displayed as `~/...`, after `omit_path_prefix` has
+    /// been applied. This is meant for user-facing output: the abbreviated paths can't be
+    /// opened directly. Relative paths are never changed. Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// - `tilde_home: true` - `/home/user/notes/todo.md` is reported as `~/notes/todo.md`
+    /// - `tilde_home: false` - The path is reported unchanged
+    pub tilde_home: bool,
+
+    /// The order of the result lines.
+    ///
+    /// - `SearchSort::PathAndLine` (default) - Sort by file path (see `natural_sort`), then
+    ///   by line number
+    /// - `SearchSort::Relevance` - Rank files by their number of matching lines, like
+    ///   `sort_by_relevance: true` (which takes precedence over this option; see `effective_sort`)
+    /// - `SearchSort::None` - Skip sorting entirely. Files are listed in the order they are
+    ///   found by the directory walker, with the lines of each file kept together in line
+    ///   number order. This avoids the cost of sorting large result sets.
+    /// - `SearchSort::ModifiedDesc` - List the files by modification time, most recent first,
+    ///   with the lines of each file kept together in line number order. Files with the same
+    ///   modification time (or none, which are listed last) are ordered by path. The
+    ///   modification times are read once per file while collecting the files to search.
+    ///
+    /// The walker order depends on the file system and is not guaranteed to be stable, so
+    /// pagination with `skip` and `take` over unsorted results is nondeterministic: pages
+    /// from separate searches may overlap or miss lines.
+    ///
+    /// # Examples
+    ///
+    /// - `sort: SearchSort::None` - Stream-like output when the order doesn't matter
+    /// - `sort: SearchSort::PathAndLine` - `a.txt` is listed before `b.txt`
+    /// - `sort: SearchSort::ModifiedDesc` - Matches in the file edited last are listed first
+    pub sort: SearchSort,
+
+    /// Whether to collapse redundant separators in result paths.
+    ///
+    /// When `true`, repeated separators are collapsed and trailing separators removed from
+    /// result paths (see `paths::clean_path`), after `omit_path_prefix` has been applied and
+    /// before `tilde_home`. This keeps paths tidy when `directory` was built by joining
+    /// strings (e.g., `project//src`). Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// - `clean_paths: true` - Searching `project//src` reports `project/src/main.rs`
+    /// - `clean_paths: false` - Searching `project//src` reports `project//src/main.rs`
+    pub clean_paths: bool,
+
+    /// Number of lines at the start of each file to search, if only file headers are of
+    /// interest.
+    ///
+    /// Useful for patterns that only make sense in headers, such as license comments, shebang
+    /// lines or file type markers. Only the first N lines of each file, up to
+    /// `HEADER_MAX_BYTES` bytes, are read, so large files are not scanned to the end. This can
+    /// be combined with `line_from` and `line_to`, which then select lines within the header.
+    /// When set to `None` (default), whole files are searched.
+    ///
+    /// # Examples
+    ///
+    /// - `header_lines: Some(5)` - Finds `SPDX-License-Identifier` in the first 5 lines only
+    /// - `header_lines: None` - Matches anywhere in each file are reported
+    pub header_lines: Option<usize>,
+
+    /// Maximum number of lines between two context windows for them to be merged.
+    ///
+    /// When the lines between the context of a match and the context of the next match in
+    /// the same file number at most this many, those lines are included as context lines, so
+    /// the results form one continuous block instead of two (which the CLI separates with
+    /// `--`). This also applies without `before_context` and `after_context`, filling the gap
+    /// between nearby matching lines. When set to 0 (default), windows are only merged when
+    /// they are adjacent or overlap, as with grep.
+    ///
+    /// # Examples
+    ///
+    /// - `context_merge_gap: 2` with matches on lines 1 and 4 and no context - Lines 2 and 3
+    ///   are reported as context, joining the matches
+    /// - `context_merge_gap: 0` - Only the matching lines 1 and 4 are reported
+    pub context_merge_gap: usize,
+
+    /// Whether to report each distinct matching line content only once.
+    ///
+    /// When `true`, only the first matching line (in the order of the results) of each
+    /// distinct `line_content` is kept, across all files, and its
+    /// `SearchResultLine::duplicate_count` records how many matching lines had that content.
+    /// Context lines are not reported, and `total_number` counts the distinct lines. Contents
+    /// are compared as displayed, so searching with `content: ContentMode::None` and this
+    /// option is an error. Useful when a boilerplate line appears identically in many files.
+    /// Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// - `unique_content: true` - A license line found in 50 files is reported once, with a
+    ///   `duplicate_count` of 50
+    /// - `unique_content: false` - The line is reported for each of the 50 files
+    pub unique_content: bool,
+
+    /// Whether to search the text of PDF files.
+    ///
+    /// When `true`, files detected as PDF by their magic bytes have the text of their pages
+    /// extracted, and the extracted text is searched instead of the file content. Line
+    /// numbers of matches in PDF files are page-based: see `PDF_PAGE_LINE_BASE`. Windows such
+    /// as `line_from`, `line_to` and `header_lines` apply to the lines of the extracted text
+    /// as a whole. Files whose text can't be extracted are skipped with a warning.
+    ///
+    /// Text extraction requires the `pdf` cargo feature; without it, searching with this option
+    /// is an error. When `false` (default), PDF files are searched as-is, which usually means
+    /// they are skipped as binary.
+    ///
+    /// # Examples
+    ///
+    /// - `extract_pdf_text: true` - A phrase on the second line of page 3 of `manual.pdf` is
+    ///   reported with the line number 30002
+    /// - `extract_pdf_text: false` - `manual.pdf` produces no matches
+    pub extract_pdf_text: bool,
+
+    /// Whether to remove the common indentation of each block of result lines.
+    ///
+    /// When `true`, each block of consecutive lines of a file (a match with its context, or
+    /// several matches whose context windows join) has the leading whitespace shared by all
+    /// of its lines removed, like Python's `textwrap.dedent`. Lines consisting only of
+    /// whitespace don't count towards the common indentation. The number of bytes removed is
+    /// recorded in `SearchResultLine::dedent`, and `match_ranges` (as well as the other
+    /// offsets) refer to the dedented line. Useful for displaying code snippets without
+    /// wasting horizontal space. Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// - `dedent_context: true` - A match on `        return x;` with the context line
+    ///   `    if ready {` is reported as `    return x;` after `if ready {`, with a `dedent` of 4
+    /// - `dedent_context: false` - The lines are reported with their indentation
+    pub dedent_context: bool,
+
+    /// Whether to collect the extensions of the searched files.
+    ///
+    /// When `true`, `SearchResult::extensions_searched` lists the distinct extensions of the
+    /// files that were actually searched, whether or not they contained matches. Files left
+    /// out by the filtering options, files that couldn't be opened and files left unsearched
+    /// because of a timeout are not included. Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// - `collect_extensions: true` - Searching a directory of Rust and Markdown files
+    ///   reports `["md", "rs"]`
+    /// - `collect_extensions: false` - `extensions_searched` is left empty
+    pub collect_extensions: bool,
+
+    /// Whether to stop searching each file at its first matching line.
+    ///
+    /// When `true`, the search of a file stops as soon as a matching line (within `line_from`
+    /// and `line_to`, and with at least `min_matches_per_line` matches) has been found, so at
+    /// most one `SearchResultLine` is reported per file and `total_number` counts the matching
+    /// files. Context lines are not reported. Useful for detecting license headers or markers,
+    /// where only whether a file matches matters. Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// - `first_match_only: true` - A file with 20 `TODO` lines is reported with its first one
+    /// - `first_match_only: false` - All 20 lines are reported
+    pub first_match_only: bool,
+
+    /// The number of threads searching files.
+    ///
+    /// The collected files are distributed across this many threads, each with its own
+    /// searcher, and the lines of each file are merged in the order of the files before the
+    /// results are sorted, so the results don't depend on the number of threads. When `None`
+    /// (default), the number of logical CPUs is used. `Some(1)` searches the files one by one
+    /// on the calling thread.
+    ///
+    /// When `take` is set without `skip`, at most `take` lines are built per file and the
+    /// lines past them are only counted. A sequential search stops building lines once `take`
+    /// lines have been built in total, so `Some(1)` can build fewer lines when only the first
+    /// few results of a large search are wanted.
+    ///
+    /// # Examples
+    ///
+    /// - `threads: Some(4)` - Four threads search the files
+    /// - `threads: Some(1)` - The files are searched sequentially
+    pub threads: Option<usize>,
+}
+
+/// Files at least this large are memory-mapped with `ReadStrategy::Auto` (1 MiB).
+pub const AUTO_MMAP_THRESHOLD_BYTES: u64 = 1024 * 1024;
+
+/// At most this many bytes are read from each file with `SearchOptions::header_lines` (1 MiB).
+pub const HEADER_MAX_BYTES: u64 = 1024 * 1024;
+
+/// Lines are cut after this many bytes with `ContentMode::Truncated`.
+pub const TRUNCATED_CONTENT_MAX_BYTES: usize = 256;
+
+/// How much of each line's content is returned by a search.
+///
+/// See `SearchOptions::content` for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ContentMode {
+    /// Return the full line content
+    #[default]
+    Full,
+    /// Return no line content, only line numbers and match ranges
+    None,
+    /// Return at most `TRUNCATED_CONTENT_MAX_BYTES` bytes of each line
+    Truncated,
+}
+
+/// Strategy for reading file contents during a search.
+///
+/// See `SearchOptions::read_strategy` for details and the safety caveats of memory maps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ReadStrategy {
+    /// Read files through a buffer using regular read calls
+    #[default]
+    Buffered,
+    /// Memory-map every file (falls back to buffered reads where memory maps are unavailable)
+    Mmap,
+    /// Memory-map files of at least `AUTO_MMAP_THRESHOLD_BYTES`, read smaller files through a buffer
+    Auto,
+}
+
+/// The order of search result lines.
+///
+/// See `SearchOptions::sort` for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SearchSort {
+    /// Sort by file path, then by line number
+    #[default]
+    PathAndLine,
+    /// Keep the files in walker order, without sorting
+    None,
+    /// Rank files by their number of matching lines, then by path
+    Relevance,
+    /// List the most recently modified files first, then sort by path
+    ModifiedDesc,
 }
 
 impl Default for SearchOptions {
@@ -417,10 +1319,103 @@ impl Default for SearchOptions {
             omit_path_prefix: None,
             match_content_omit_num: None,
             depth: Some(20),
+            min_depth: None,
             before_context: 0,
             after_context: 0,
             skip: None,
             take: None,
+            treat_all_as_text: false,
+            exclude_dir_names: Vec::new(),
+            min_matches_per_line: None,
+            enclosing_symbol: false,
+            sort_by_relevance: false,
+            ignore_root_gitignore: false,
+            highlight_markers: None,
+            read_strategy: ReadStrategy::Buffered,
+            line_from: None,
+            line_to: None,
+            line_terminator: b'\n',
+            auto_repo_relative: false,
+            regex_size_limit: None,
+            dfa_size_limit: None,
+            timeout: None,
+            cancel: None,
+            content: ContentMode::Full,
+            natural_sort: false,
+            with_blame: false,
+            skip_vendored: false,
+            auto_omit_common_prefix: false,
+            snippet_width: None,
+            git_tracked_only: false,
+            search_compressed: false,
+            utf16_offsets: false,
+            tilde_home: false,
+            sort: SearchSort::PathAndLine,
+            clean_paths: false,
+            header_lines: None,
+            context_merge_gap: 0,
+            unique_content: false,
+            extract_pdf_text: false,
+            dedent_context: false,
+            collect_extensions: false,
+            first_match_only: false,
+            threads: None,
+        }
+    }
+}
+
+impl SearchOptions {
+    /// Returns the order of the results of a search with these options.
+    ///
+    /// `sort_by_relevance: true` takes precedence over `sort`, so this is
+    /// `SearchSort::Relevance` when it's set and `sort` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lumin::search::{SearchOptions, SearchSort};
+    ///
+    /// let options = SearchOptions {
+    ///     sort: SearchSort::None,
+    ///     sort_by_relevance: true,
+    ///     ..SearchOptions::default()
+    /// };
+    /// assert_eq!(options.effective_sort(), SearchSort::Relevance);
+    /// ```
+    pub fn effective_sort(&self) -> SearchSort {
+        if self.sort_by_relevance {
+            SearchSort::Relevance
+        } else {
+            self.sort
+        }
+    }
+
+    /// Returns the regular expression that is compiled for a search pattern with these options.
+    ///
+    /// Options that change how the pattern is matched are applied by rewriting the pattern,
+    /// so this shows what a search actually looks for: with `case_sensitive: false`, the
+    /// case-insensitive flag `(?i)` is prepended. The pattern is not validated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lumin::search::SearchOptions;
+    ///
+    /// let options = SearchOptions::default();
+    /// assert_eq!(options.effective_pattern("todo"), "(?i)todo");
+    ///
+    /// let options = SearchOptions {
+    ///     case_sensitive: true,
+    ///     ..SearchOptions::default()
+    /// };
+    /// assert_eq!(options.effective_pattern("todo"), "todo");
+    /// ```
+    pub fn effective_pattern(&self, pattern: &str) -> String {
+        if self.case_sensitive {
+            pattern.to_string()
+        } else {
+            // For case insensitive search, we add the case-insensitive flag to the regex
+            format!("(?i){}", pattern)
         }
     }
 }
@@ -429,6 +1424,19 @@ impl Default for SearchOptions {
 pub struct SearchResult {
     pub total_number: usize,
     pub lines: Vec<SearchResultLine>,
+    /// Whether the search was stopped by `SearchOptions::timeout` before all files were
+    /// searched, meaning that the results are partial
+    #[serde(default)]
+    pub timed_out: bool,
+    /// Whether the search was cancelled through `SearchOptions::cancel` before it finished,
+    /// meaning that the results may be partial
+    #[serde(default)]
+    pub cancelled: bool,
+    /// The distinct extensions (without the leading dot) of the files that were searched,
+    /// sorted. Only populated when `SearchOptions::collect_extensions` is enabled. Files
+    /// without an extension are not represented.
+    #[serde(default)]
+    pub extensions_searched: Vec<String>,
 }
 impl SearchResult {
     /// Extracts a subset of search result lines from a specified range.
@@ -451,6 +1459,9 @@ impl SearchResult {
     /// let my_search_results = SearchResult {
     ///     total_number: 25,
     ///     lines: vec![/* SearchResultLine items */],
+    ///     timed_out: false,
+    ///     cancelled: false,
+    ///     extensions_searched: Vec::new(),
     /// };
     ///
     /// // Extract the first 10 results
@@ -473,30 +1484,80 @@ impl SearchResult {
                 .skip(from_idx)
                 .take(to_idx.saturating_sub(from_idx))
                 .collect(),
+            timed_out: self.timed_out,
+            cancelled: self.cancelled,
+            extensions_searched: self.extensions_searched,
         }
     }
 
-    /// Sorts the search result lines by file path and line number.
+    /// Returns one page of the search result lines, without searching again.
     ///
-    /// This method sorts the lines in-place, first by file path (lexicographically) and then
-    /// by line number (numerically) within each file.
+    /// Unlike `split`, this borrows the result, so it can be called repeatedly to move between
+    /// pages of the same search.
+    ///
+    /// # Arguments
+    ///
+    /// * `page` - The index of the page (0-based)
+    /// * `page_size` - The number of lines per page
     ///
     /// # Returns
     ///
-    /// A reference to self for method chaining.
+    /// A new `SearchResult` with the lines of the requested page, which is empty when the page
+    /// is past the end of the lines. The `total_number` field retains the original total count.
     ///
     /// # Examples
     ///
     /// ```no_run
-    /// use lumin::search::SearchResult;
-    /// // Create some search results
-    /// let mut my_search_results = SearchResult {
-    ///     total_number: 25,
-    ///     lines: vec![/* SearchResultLine items */],
-    /// };
+    /// use lumin::search::{SearchOptions, search_files};
+    /// use std::path::Path;
     ///
-    /// // Sort the results by file path and line number
-    /// my_search_results.sort_by_path_and_line();
+    /// let results = search_files("TODO", Path::new("src"), &SearchOptions::default()).unwrap();
+    ///
+    /// // Show the second page of 10 results
+    /// for line in results.page(1, 10).lines {
+    ///     println!("{}:{}", line.file_path.display(), line.line_number);
+    /// }
+    /// ```
+    pub fn page(&self, page: usize, page_size: usize) -> Self {
+        SearchResult {
+            total_number: self.total_number,
+            lines: self
+                .lines
+                .iter()
+                .skip(page.saturating_mul(page_size))
+                .take(page_size)
+                .cloned()
+                .collect(),
+            timed_out: self.timed_out,
+            cancelled: self.cancelled,
+            extensions_searched: self.extensions_searched.clone(),
+        }
+    }
+
+    /// Sorts the search result lines by file path and line number.
+    ///
+    /// This method sorts the lines in-place, first by file path (lexicographically) and then
+    /// by line number (numerically) within each file.
+    ///
+    /// # Returns
+    ///
+    /// A reference to self for method chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lumin::search::SearchResult;
+    /// // Create some search results
+    /// let mut my_search_results = SearchResult {
+    ///     total_number: 25,
+    ///     lines: vec![/* SearchResultLine items */],
+    ///     timed_out: false,
+    ///     cancelled: false,
+    ///     extensions_searched: Vec::new(),
+    /// };
+    ///
+    /// // Sort the results by file path and line number
+    /// my_search_results.sort_by_path_and_line();
     /// ```
     pub fn sort_by_path_and_line(&mut self) -> &mut Self {
         self.lines.sort_by(|a, b| {
@@ -511,6 +1572,67 @@ impl SearchResult {
         });
         self
     }
+
+    /// Sorts the search result lines by file path in natural order and by line number.
+    ///
+    /// Like `sort_by_path_and_line`, but runs of digits in file paths are compared by their
+    /// numeric value, so lines from `file2.txt` come before lines from `file10.txt`.
+    ///
+    /// # Returns
+    ///
+    /// A reference to self for method chaining.
+    pub fn sort_by_natural_path_and_line(&mut self) -> &mut Self {
+        self.lines.sort_by(|a, b| {
+            natural_path_cmp(&a.file_path, &b.file_path)
+                .then_with(|| a.line_number.cmp(&b.line_number))
+        });
+        self
+    }
+
+    /// Sorts the search result lines by relevance, ranking files with more matches first.
+    ///
+    /// Files are ordered by their number of matching (non-context) lines in descending order,
+    /// then by file path. Lines within each file are kept in line number order.
+    ///
+    /// # Returns
+    ///
+    /// A reference to self for method chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lumin::search::SearchResult;
+    /// // Create some search results
+    /// let mut my_search_results = SearchResult {
+    ///     total_number: 25,
+    ///     lines: vec![/* SearchResultLine items */],
+    ///     timed_out: false,
+    ///     cancelled: false,
+    ///     extensions_searched: Vec::new(),
+    /// };
+    ///
+    /// // Show the files with the most matches first
+    /// my_search_results.sort_by_relevance();
+    /// ```
+    pub fn sort_by_relevance(&mut self) -> &mut Self {
+        // Count the matching lines of each file
+        let mut match_counts: HashMap<PathBuf, usize> = HashMap::new();
+        for line in &self.lines {
+            let count = match_counts.entry(line.file_path.clone()).or_insert(0);
+            if !line.is_context {
+                *count += 1;
+            }
+        }
+
+        self.lines.sort_by(|a, b| {
+            // Higher match counts first, then by path and line number
+            match_counts[&b.file_path]
+                .cmp(&match_counts[&a.file_path])
+                .then_with(|| a.file_path.cmp(&b.file_path))
+                .then_with(|| a.line_number.cmp(&b.line_number))
+        });
+        self
+    }
 }
 
 /// Represents a single search match result.
@@ -554,6 +1676,8 @@ pub struct SearchResultLine {
     ///
     /// This is the absolute or relative path to the file where the match was found,
     /// depending on the input provided to the search function.
+    /// Paths that are not valid UTF-8 are serialized without loss (see the `paths` module).
+    #[serde(with = "crate::paths::serde_os_str")]
     pub file_path: PathBuf,
 
     /// Line number where the match was found (1-based).
@@ -600,6 +1724,90 @@ pub struct SearchResultLine {
     /// This is useful for displaying context lines differently or for filtering results
     /// to show only direct matches when desired.
     pub is_context: bool,
+
+    /// Name of the function or type enclosing the match, if detected.
+    ///
+    /// Only populated when `enclosing_symbol` is enabled in the search options and the
+    /// file is a recognized source file. Always `None` for context lines.
+    pub enclosing_symbol: Option<String>,
+
+    /// Byte ranges `(start, end)` of each pattern match within the original line.
+    ///
+    /// Offsets refer to the full, untruncated line (not to `line_content` when content was
+    /// omitted or highlight markers were inserted), and always fall on UTF-8 character
+    /// boundaries. Ranges are sorted and non-overlapping. Empty for context lines.
+    pub match_ranges: Vec<(usize, usize)>,
+
+    /// The `match_ranges` as UTF-16 code unit offsets `(start, end)` within the original line.
+    ///
+    /// Only populated for matched lines when `utf16_offsets` is enabled in the search options
+    /// (and the content is not `ContentMode::None`). Useful for editors and LSP clients, which
+    /// address positions in UTF-16 code units.
+    #[serde(default)]
+    pub match_ranges_utf16: Vec<(usize, usize)>,
+
+    /// Byte ranges `(start, end)` of each match within `line_content` as displayed.
+    ///
+    /// Unlike `match_ranges`, these offsets account for content omitted by
+    /// `match_content_omit_num` (including the `<omit>` markers) and for inserted
+    /// `highlight_markers`, so they can be used to highlight matches in `line_content`
+    /// directly. Each range covers the matched text only, not the surrounding highlight markers.
+    ///
+    /// Empty matches are not included. Ranges are sorted and non-overlapping. Empty for context lines.
+    #[serde(default)]
+    pub display_match_ranges: Vec<(usize, usize)>,
+
+    /// Stable identifier of this result line.
+    ///
+    /// A hash of `file_path`, `line_number` and `match_ranges`, formatted as 16 hexadecimal
+    /// digits. Searching an unchanged file again yields the same identifiers, so they can be
+    /// used to deduplicate, update or remove matches when streaming incremental results.
+    /// Context lines get identifiers as well (computed with empty `match_ranges`).
+    #[serde(default)]
+    pub match_id: String,
+
+    /// The author name of the commit that last changed this line.
+    ///
+    /// Only populated for matched lines when `with_blame` is enabled in the search options
+    /// and the line has been committed to a git repository.
+    #[serde(default)]
+    pub blame_author: Option<String>,
+
+    /// The ID of the commit that last changed this line, as a hexadecimal string.
+    ///
+    /// Only populated for matched lines when `with_blame` is enabled in the search options
+    /// and the line has been committed to a git repository.
+    #[serde(default)]
+    pub blame_commit: Option<String>,
+
+    /// A fixed-width preview of the line centered on its first match.
+    ///
+    /// Only populated for matched lines when `snippet_width` is set in the search options.
+    /// Ellipses (`…`) mark where the line continues before or after the snippet.
+    #[serde(default)]
+    pub snippet: Option<String>,
+
+    /// Whether the line comes from a file that would normally be skipped as binary.
+    ///
+    /// Set for all lines of files containing a NUL byte that were only searched because
+    /// `treat_all_as_text` is enabled. Always `false` otherwise, including for NUL-delimited
+    /// records searched with `line_terminator` set to NUL.
+    #[serde(default)]
+    pub from_binary_file: bool,
+
+    /// The number of matching lines with the same `line_content`, including this one.
+    ///
+    /// Only populated when `unique_content` is enabled in the search options.
+    #[serde(default)]
+    pub duplicate_count: Option<usize>,
+
+    /// The number of bytes of indentation removed from the start of the line.
+    ///
+    /// Only non-zero when `dedent_context` is enabled in the search options. Add it to the
+    /// offsets of `match_ranges` to get offsets within the original line (a match starting
+    /// within the removed indentation is reported from the start of the dedented line).
+    #[serde(default)]
+    pub dedent: usize,
 }
 
 /// Returns only the total number of lines that match a search pattern within files in a directory.
@@ -651,7 +1859,7 @@ pub struct SearchResultLine {
 /// Using custom search options:
 ///
 /// ```no_run
-/// use lumin::search::{SearchOptions, search_files_total_match_line_number};
+/// use lumin::search::{ContentMode, ReadStrategy, SearchOptions, SearchSort, search_files_total_match_line_number};
 /// use std::path::Path;
 ///
 /// let pattern = "error";
@@ -666,10 +1874,47 @@ pub struct SearchResultLine {
 ///     omit_path_prefix: None,
 ///     match_content_omit_num: None,
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 0,
 ///     after_context: 0,
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 ///
 /// let count = search_files_total_match_line_number(pattern, directory, &options)
@@ -686,6 +1931,71 @@ pub fn search_files_total_match_line_number(
     Ok(result.total_number)
 }
 
+/// Returns the total number of occurrences of a search pattern within files in a directory.
+///
+/// Unlike `search_files_total_match_line_number`, which counts matching lines, this counts
+/// every match of the pattern, so a line containing the pattern twice contributes two
+/// occurrences. Context lines are never counted.
+///
+/// Occurrences are counted on all matching lines regardless of `skip` and `take`, so the
+/// count is not affected by pagination. Other options (such as `include_glob`,
+/// `line_from`/`line_to` or `min_matches_per_line`) restrict the counted lines as in
+/// `search_files`.
+///
+/// # Arguments
+///
+/// * `pattern` - The regular expression pattern to search for. Supports the same regex syntax
+///   as `search_files`.
+/// * `directory` - The directory path to search in
+/// * `options` - Configuration options for the search operation, identical to those used by
+///   `search_files`
+///
+/// # Returns
+///
+/// The total number of non-overlapping matches of the pattern
+///
+/// # Errors
+///
+/// Returns the same errors as `search_files`
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::search::{SearchOptions, count_occurrences, search_files_total_match_line_number};
+/// use std::path::Path;
+///
+/// let options = SearchOptions::default();
+/// let occurrences = count_occurrences("TODO", Path::new("src"), &options).unwrap_or(0);
+/// let lines = search_files_total_match_line_number("TODO", Path::new("src"), &options).unwrap_or(0);
+///
+/// println!("Found {} occurrences of 'TODO' on {} lines", occurrences, lines);
+/// ```
+pub fn count_occurrences(
+    pattern: &str,
+    directory: &Path,
+    options: &SearchOptions,
+) -> Result<usize> {
+    // Count over all lines, and skip building line contents that aren't returned
+    let options = SearchOptions {
+        skip: None,
+        take: None,
+        before_context: 0,
+        after_context: 0,
+        match_content_omit_num: None,
+        highlight_markers: None,
+        enclosing_symbol: false,
+        ..options.clone()
+    };
+
+    let result = search_files(pattern, directory, &options)?;
+    Ok(result
+        .lines
+        .iter()
+        .filter(|line| !line.is_context)
+        .map(|line| line.match_ranges.len())
+        .sum())
+}
+
 /// Searches for the specified regex pattern in files within the given directory.
 ///
 /// This function performs a regex-based search across all files in the specified directory
@@ -694,7 +2004,7 @@ pub fn search_files_total_match_line_number(
 ///
 /// ## File Filtering Consistency
 ///
-/// **Important**: Both `include_glob` and `exclude_glob` patterns are matched against 
+/// **Important**: Both `include_glob` and `exclude_glob` patterns are matched against
 /// **relative paths** (relative to the search directory). This ensures consistent behavior
 /// between inclusion and exclusion filters, allowing you to use the same pattern format
 /// for both parameters.
@@ -829,7 +2139,7 @@ pub fn search_files_total_match_line_number(
 ///
 /// Case-sensitive search ignoring gitignore files:
 /// ```no_run
-/// use lumin::search::{SearchOptions, search_files};
+/// use lumin::search::{ContentMode, ReadStrategy, SearchOptions, SearchSort, search_files};
 /// use std::path::Path;
 ///
 /// let options = SearchOptions {
@@ -840,10 +2150,47 @@ pub fn search_files_total_match_line_number(
 ///     omit_path_prefix: None,
 ///     match_content_omit_num: None,
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 0,
 ///     after_context: 0,
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 ///
 /// let search_result = search_files(
@@ -859,7 +2206,7 @@ pub fn search_files_total_match_line_number(
 ///
 /// Using exclude_glob to skip specific file types with context:
 /// ```no_run
-/// use lumin::search::{SearchOptions, search_files};
+/// use lumin::search::{ContentMode, ReadStrategy, SearchOptions, SearchSort, search_files};
 /// use std::path::Path;
 ///
 /// let options = SearchOptions {
@@ -870,10 +2217,47 @@ pub fn search_files_total_match_line_number(
 ///     omit_path_prefix: None,
 ///     match_content_omit_num: Some(50), // Limit context to 50 chars before and after each match (preserving full matches)
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 2, // Show 2 lines before each match
 ///     after_context: 5, // Show 5 lines after each match
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 ///
 /// let results = search_files(
@@ -890,7 +2274,7 @@ pub fn search_files_total_match_line_number(
 ///
 /// Using include_glob to search only specific file types:
 /// ```no_run
-/// use lumin::search::{SearchOptions, search_files};
+/// use lumin::search::{ContentMode, ReadStrategy, SearchOptions, SearchSort, search_files};
 /// use std::path::Path;
 ///
 /// let options = SearchOptions {
@@ -901,10 +2285,47 @@ pub fn search_files_total_match_line_number(
 ///     omit_path_prefix: None,
 ///     match_content_omit_num: None,
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 0,
 ///     after_context: 0,
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 ///
 /// let results = search_files(
@@ -919,7 +2340,7 @@ pub fn search_files_total_match_line_number(
 ///
 /// Combining include_glob and exclude_glob for precise file targeting:
 /// ```no_run
-/// use lumin::search::{SearchOptions, search_files};
+/// use lumin::search::{ContentMode, ReadStrategy, SearchOptions, SearchSort, search_files};
 /// use std::path::Path;
 ///
 /// let options = SearchOptions {
@@ -930,10 +2351,47 @@ pub fn search_files_total_match_line_number(
 ///     omit_path_prefix: None,
 ///     match_content_omit_num: None,
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 1,
 ///     after_context: 1,
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 ///
 /// let results = search_files(
@@ -949,7 +2407,7 @@ pub fn search_files_total_match_line_number(
 ///
 /// Using content omission to focus on matches in long lines:
 /// ```no_run
-/// use lumin::search::{SearchOptions, search_files};
+/// use lumin::search::{ContentMode, ReadStrategy, SearchOptions, SearchSort, search_files};
 /// use std::path::Path;
 ///
 /// let options = SearchOptions {
@@ -960,10 +2418,47 @@ pub fn search_files_total_match_line_number(
 ///     omit_path_prefix: None,
 ///     match_content_omit_num: Some(20), // Only show 20 characters around matches while preserving entire matches
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 0,
 ///     after_context: 3, // Show 3 lines of context after each match
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 ///
 /// let search_result = search_files(
@@ -1228,7 +2723,7 @@ pub fn search_files_total_match_line_number(
 ///
 /// ### Practical Pattern Examples
 /// ```no_run
-/// use lumin::search::{SearchOptions, search_files};
+/// use lumin::search::{ContentMode, ReadStrategy, SearchOptions, SearchSort, search_files};
 /// use std::path::Path;
 ///
 /// // Find all email addresses in files
@@ -1249,10 +2744,47 @@ pub fn search_files_total_match_line_number(
 ///     omit_path_prefix: None,
 ///     match_content_omit_num: None,
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 0,
 ///     after_context: 0,
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 /// let results = search_files(
 ///     function_pattern,
@@ -1293,10 +2825,47 @@ pub fn search_files_total_match_line_number(
 ///     omit_path_prefix: None,
 ///     match_content_omit_num: Some(30), // Show only 30 characters before and after matches
 ///     depth: Some(20),
+///     min_depth: None,
 ///     before_context: 2, // Show 2 lines before each match
 ///     after_context: 2, // Show 2 lines after each match
 ///     skip: None,
 ///     take: None,
+///     treat_all_as_text: false,
+///     exclude_dir_names: Vec::new(),
+///     min_matches_per_line: None,
+///     enclosing_symbol: false,
+///     sort_by_relevance: false,
+///     ignore_root_gitignore: false,
+///     highlight_markers: None,
+///     read_strategy: ReadStrategy::Buffered,
+///     line_from: None,
+///     line_to: None,
+///     line_terminator: b'\n',
+///     auto_repo_relative: false,
+///     regex_size_limit: None,
+///     dfa_size_limit: None,
+///     timeout: None,
+///     cancel: None,
+///     content: ContentMode::Full,
+///     natural_sort: false,
+///     with_blame: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     snippet_width: None,
+///     git_tracked_only: false,
+///     search_compressed: false,
+///     utf16_offsets: false,
+///     tilde_home: false,
+///     sort: SearchSort::PathAndLine,
+///     clean_paths: false,
+///     header_lines: None,
+///     context_merge_gap: 0,
+///     unique_content: false,
+///     extract_pdf_text: false,
+///     dedent_context: false,
+///     collect_extensions: false,
+///     first_match_only: false,
+///     threads: None,
 /// };
 ///
 /// let long_results = search_files(
@@ -1329,31 +2898,470 @@ pub fn search_files(
     directory: &Path,
     options: &SearchOptions,
 ) -> Result<SearchResult> {
-    // Create the matcher with the appropriate case sensitivity
-    let matcher = if options.case_sensitive {
-        RegexMatcher::new(pattern)
-    } else {
-        // For case insensitive search, we add the case-insensitive flag to the regex
-        RegexMatcher::new(&format!("(?i){}", pattern))
-    }
-    .context("Failed to create regular expression matcher")?;
+    search_files_counted(pattern, directory, options).map(|(result, _)| result)
+}
+
+/// The numbers reported by a search besides its result
+#[derive(Debug, Default)]
+pub(crate) struct SearchCounts {
+    /// The number of files that were searched.
+    ///
+    /// Files that could not be opened or decompressed are not counted, and neither are files
+    /// left unsearched because of a timeout or cancellation.
+    pub(crate) files_searched: usize,
+    /// The number of files with at least one matching line, whether built or counted
+    pub(crate) files_with_matches: usize,
+    /// The number of lines past `take` that were counted without being built
+    pub(crate) uncollected_lines: usize,
+    /// The number of matches on the matching lines that were counted without being built
+    pub(crate) uncollected_occurrences: usize,
+}
+
+/// Searches like `search_files`, also returning the counts of the search
+pub(crate) fn search_files_counted(
+    pattern: &str,
+    directory: &Path,
+    options: &SearchOptions,
+) -> Result<(SearchResult, SearchCounts)> {
+    let matcher = build_matcher(pattern, options)?;
 
     // Build the list of files to search
-    // TODO: Implement parallel search by using callbacks in the file traverser
-    let files =
-        collect_files(directory, options).context("Failed to collect files for searching")?;
+    let files = collect_files_with_modified(directory, options)
+        .context("Failed to collect files for searching")?;
+
+    search_collected_files(&matcher, directory, files, options)
+}
+
+/// Searches the given files like `search_files`, also returning the counts of the search.
+///
+/// `files` are the files found under `directory` by `collect_files`, in any order, with their
+/// modification times when sorting by them.
+pub(crate) fn search_collected_files(
+    matcher: &RegexMatcher,
+    directory: &Path,
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+    options: &SearchOptions,
+) -> Result<(SearchResult, SearchCounts)> {
+    // Unique lines are told apart by their contents, which are not built without content
+    if options.unique_content && options.content == ContentMode::None {
+        return Err(anyhow!(
+            "unique_content requires line contents, so it can't be used with ContentMode::None"
+        ));
+    }
+    if options.extract_pdf_text && !cfg!(feature = "pdf") {
+        return Err(anyhow!(
+            "extract_pdf_text requires lumin to be built with the `pdf` feature"
+        ));
+    }
+    if (options.with_blame || options.git_tracked_only) && !cfg!(feature = "git") {
+        return Err(anyhow!(
+            "with_blame and git_tracked_only require lumin to be built with the `git` feature"
+        ));
+    }
+
+    // The time after which the search stops, if a timeout is configured
+    let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+    let mut timed_out = false;
+
+    let sort = options.effective_sort();
+
+    // When only the first results are requested, search the files in the order of the results,
+    // so that lines past `take` can be counted without being built
+    let lazy_take = options.take.filter(|_| {
+        options.skip.unwrap_or(0) == 0
+            && sort != SearchSort::Relevance
+            && !options.auto_omit_common_prefix
+            && !options.unique_content
+    });
+
+    // The number of threads searching files, which is 1 to search them one by one
+    let threads = options
+        .threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get))
+        .min(files.len());
+    // Search the most recently modified files first, so that the results are in order
+    let mut files = files;
+    if sort == SearchSort::ModifiedDesc {
+        files.sort_by(|(a, a_modified), (b, b_modified)| {
+            b_modified.cmp(a_modified).then_with(|| a.cmp(b))
+        });
+    }
+    let mut files: Vec<PathBuf> = files.into_iter().map(|(path, _)| path).collect();
+    if lazy_take.is_some() && sort == SearchSort::PathAndLine {
+        if options.natural_sort {
+            files.sort_by(|a, b| natural_path_cmp(a, b));
+        } else {
+            files.sort();
+        }
+    }
 
     let mut result_lines = Vec::new();
+    let mut counts = SearchCounts::default();
+    let mut extensions_searched = BTreeSet::new();
+
+    // Resolve the repository root once when reporting repository-relative paths
+    let repo_root = if options.auto_repo_relative {
+        directory
+            .canonicalize()
+            .ok()
+            .and_then(|canonical_directory| {
+                find_repository_root(&canonical_directory).map(|root| (canonical_directory, root))
+            })
+    } else {
+        None
+    };
+
+    // Set up the cleaning and home directory abbreviation of result paths
+    let path_display = PathDisplay::new(options.clean_paths, options.tilde_home);
+
+    // Search the files, merging their lines in the order of the files
+    let search = FileSearch {
+        matcher,
+        directory,
+        options,
+        deadline,
+        repo_root,
+        path_display,
+    };
+    let searched_files = if threads > 1 {
+        search.search_parallel(&files, threads, lazy_take)?
+    } else {
+        search.search_sequential(files, lazy_take)?
+    };
+    for (file_path, searched) in searched_files {
+        timed_out |= searched.timed_out;
+        if !searched.searched {
+            continue;
+        }
+        counts.files_searched += 1;
+        if searched.uncollected_matched_lines > 0
+            || searched.lines.iter().any(|line| !line.is_context)
+        {
+            counts.files_with_matches += 1;
+        }
+        if options.collect_extensions {
+            if let Some(extension) = file_path.extension() {
+                extensions_searched.insert(extension.to_string_lossy().into_owned());
+            }
+        }
+        let mut lines = searched.lines;
+        // Files searched in parallel build up to `take` lines each, so only count the lines
+        // built past `take` in total
+        if let Some(take) = lazy_take {
+            let remaining = take.saturating_sub(result_lines.len());
+            for line in lines.drain(remaining.min(lines.len())..) {
+                counts.uncollected_lines += 1;
+                if !line.is_context {
+                    counts.uncollected_occurrences += line.match_ranges.len();
+                }
+            }
+        }
+        result_lines.extend(lines);
+        counts.uncollected_lines += searched.uncollected_lines;
+        counts.uncollected_occurrences += searched.uncollected_occurrences;
+    }
+
+    // Remove the directory prefix shared by all results if requested
+    if options.auto_omit_common_prefix {
+        let file_paths: Vec<PathBuf> = result_lines
+            .iter()
+            .map(|line| line.file_path.clone())
+            .collect();
+        if let Some(prefix) = common_prefix(&file_paths) {
+            for line in &mut result_lines {
+                line.file_path = remove_path_prefix(&line.file_path, &prefix);
+                line.match_id =
+                    compute_match_id(&line.file_path, line.line_number, &line.match_ranges);
+            }
+        }
+    }
+
+    // Create the SearchResult with the total count and lines
+    let total_number = result_lines.len() + counts.uncollected_lines;
+
+    // Create the result and sort it by file path and line number
+    let mut result = SearchResult {
+        total_number,
+        lines: result_lines,
+        timed_out,
+        cancelled: is_cancelled(options.cancel.as_deref()),
+        extensions_searched: extensions_searched.into_iter().collect(),
+    };
+
+    // Sort the results for consistent ordering
+    match sort {
+        SearchSort::Relevance => {
+            result.sort_by_relevance();
+        }
+        SearchSort::PathAndLine if options.natural_sort => {
+            result.sort_by_natural_path_and_line();
+        }
+        SearchSort::PathAndLine => {
+            result.sort_by_path_and_line();
+        }
+        // Files were searched in modification time order
+        SearchSort::None | SearchSort::ModifiedDesc => {}
+    }
+
+    // Keep the first line of each distinct content if requested
+    if options.unique_content {
+        result.lines = unique_lines(std::mem::take(&mut result.lines));
+        result.total_number = result.lines.len();
+    }
+
+    // Apply pagination if skip and take are specified
+    if options.skip.is_some() || options.take.is_some() {
+        // Calculate the 1-based indices for split
+        let from = match options.skip {
+            Some(skip) => skip + 1, // Convert 0-based skip to 1-based from
+            None => 1,              // Start from the first result if skip is None
+        };
+
+        let to = match options.take {
+            Some(take) => from + take - 1, // Calculate the last index (inclusive)
+            None => result.lines.len(),    // Use all results if take is None
+        };
+
+        // Use the built-in split method to paginate the results
+        result = result.split(from, to);
+    }
+
+    Ok((result, counts))
+}
+
+/// The settings shared by the searches of the files of `search_collected_files`
+struct FileSearch<'a> {
+    matcher: &'a RegexMatcher,
+    directory: &'a Path,
+    options: &'a SearchOptions,
+    /// The time after which the search stops, if a timeout is configured
+    deadline: Option<Instant>,
+    /// The canonical search directory and the root of its repository, when reporting
+    /// repository-relative paths
+    repo_root: Option<(PathBuf, PathBuf)>,
+    path_display: PathDisplay,
+}
+
+/// The searchers of a thread searching files, which can't be shared between threads
+struct FileSearchers {
+    searcher: Searcher,
+    /// A searcher using memory maps, if the read strategy may need it
+    mmap_searcher: Option<Searcher>,
+    /// The enclosing git repository, when blaming matches
+    blame_repository: Option<BlameRepository>,
+}
+
+/// The outcome of searching a file
+#[derive(Default)]
+struct SearchedFile {
+    lines: Vec<SearchResultLine>,
+    /// The number of lines past `take` that were counted without being built
+    uncollected_lines: usize,
+    /// The number of the counted lines that are matching lines rather than context lines
+    uncollected_matched_lines: usize,
+    /// The number of matches on the counted matching lines
+    uncollected_occurrences: usize,
+    /// Whether the file was searched, rather than skipped
+    searched: bool,
+    /// Whether the search was stopped by the timeout
+    timed_out: bool,
+}
+
+impl FileSearch<'_> {
+    /// Searches the files one by one, in order.
+    ///
+    /// When `lazy_take` is set, the lines past that number of lines are only counted.
+    ///
+    /// # Returns
+    ///
+    /// The outcome of each file that was searched or skipped, in the order of the files
+    fn search_sequential(
+        &self,
+        files: Vec<PathBuf>,
+        lazy_take: Option<usize>,
+    ) -> Result<Vec<(PathBuf, SearchedFile)>> {
+        let mut searchers = self.searchers();
+        let mut searched_files = Vec::new();
+        let mut built_lines = 0;
+        for file_path in files {
+            if is_cancelled(self.options.cancel.as_deref()) {
+                break;
+            }
+            if self.is_past_deadline() {
+                searched_files.push((
+                    file_path,
+                    SearchedFile {
+                        timed_out: true,
+                        ..SearchedFile::default()
+                    },
+                ));
+                break;
+            }
+
+            let take = lazy_take.map(|take| take.saturating_sub(built_lines));
+            let searched = self.search_file(&mut searchers, file_path.clone(), take)?;
+            built_lines += searched.lines.len();
+            searched_files.push((file_path, searched));
+        }
+        Ok(searched_files)
+    }
+
+    /// Searches the files on `threads` threads, each taking the next file from a shared queue.
+    ///
+    /// When `take` is set, at most that number of lines are built in each file, and the lines
+    /// past them are only counted.
+    ///
+    /// # Returns
+    ///
+    /// The outcome of each file that was searched or skipped, in the order of the files
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first file in order whose search failed. The threads stop
+    /// taking files once a search has failed.
+    fn search_parallel(
+        &self,
+        files: &[PathBuf],
+        threads: usize,
+        take: Option<usize>,
+    ) -> Result<Vec<(PathBuf, SearchedFile)>> {
+        let next_file = AtomicUsize::new(0);
+        let stop = AtomicBool::new(false);
+
+        let mut results: Vec<(usize, Result<SearchedFile>)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut searchers = self.searchers();
+                        let mut results = Vec::new();
+                        while !stop.load(Ordering::Relaxed)
+                            && !is_cancelled(self.options.cancel.as_deref())
+                        {
+                            let index = next_file.fetch_add(1, Ordering::Relaxed);
+                            let Some(file_path) = files.get(index) else {
+                                break;
+                            };
+                            if self.is_past_deadline() {
+                                stop.store(true, Ordering::Relaxed);
+                                results.push((
+                                    index,
+                                    Ok(SearchedFile {
+                                        timed_out: true,
+                                        ..SearchedFile::default()
+                                    }),
+                                ));
+                                break;
+                            }
+
+                            let searched =
+                                self.search_file(&mut searchers, file_path.clone(), take);
+                            if searched.is_err() {
+                                stop.store(true, Ordering::Relaxed);
+                            }
+                            results.push((index, searched));
+                        }
+                        results
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        });
+
+        results.sort_by_key(|(index, _)| *index);
+        results
+            .into_iter()
+            .map(|(index, searched)| searched.map(|searched| (files[index].clone(), searched)))
+            .collect()
+    }
+
+    /// Creates the searchers of a thread
+    fn searchers(&self) -> FileSearchers {
+        let options = self.options;
+
+        // Open the enclosing git repository once per thread when blaming matches
+        let blame_repository = if options.with_blame {
+            BlameRepository::discover(self.directory)
+        } else {
+            None
+        };
+
+        // Set up the searcher
+        // NUL-delimited records can't be told apart from binary files by their NUL bytes
+        let binary_detection = if options.treat_all_as_text || options.line_terminator == b'\0' {
+            BinaryDetection::none()
+        } else {
+            BinaryDetection::quit(b'\x00')
+        };
+        let mut builder = SearcherBuilder::new();
+        builder
+            .binary_detection(binary_detection)
+            .line_terminator(LineTerminator::byte(options.line_terminator))
+            .before_context(options.before_context)
+            // Lines up to the merge gap are read after each match, and dropped unless they join
+            // the next window
+            .after_context(options.after_context + options.context_merge_gap);
+        let searcher = builder.build();
 
-    // Set up the searcher
-    let mut searcher = SearcherBuilder::new()
-        .binary_detection(BinaryDetection::quit(b'\x00'))
-        .before_context(options.before_context)
-        .after_context(options.after_context)
-        .build();
+        // Set up a second searcher using memory maps if the read strategy may need it
+        let mmap_searcher = if options.read_strategy == ReadStrategy::Buffered {
+            None
+        } else {
+            // SAFETY: Memory maps are only safe as long as the mapped file isn't modified while
+            // it's being searched. This is the documented caveat of the non-default `Mmap` and
+            // `Auto` read strategies, which callers opt into explicitly.
+            let mmap_choice = unsafe { MmapChoice::auto() };
+            Some(builder.memory_map(mmap_choice).build())
+        };
+
+        FileSearchers {
+            searcher,
+            mmap_searcher,
+            blame_repository,
+        }
+    }
+
+    /// Returns whether the timeout has passed
+    fn is_past_deadline(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Searches one file.
+    ///
+    /// # Arguments
+    ///
+    /// * `searchers` - The searchers of the calling thread
+    /// * `file_path` - The file to search
+    /// * `take` - The number of lines to build, past which the lines are only counted, or
+    ///   `None` to build all lines
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if searching the file fails. Files that can't be opened are skipped
+    /// instead.
+    fn search_file(
+        &self,
+        searchers: &mut FileSearchers,
+        file_path: PathBuf,
+        take: Option<usize>,
+    ) -> Result<SearchedFile> {
+        let (matcher, directory, options) = (self.matcher, self.directory, self.options);
+        let mut timed_out = false;
+        let mut lines = Vec::new();
+        let mut uncollected_lines = 0;
+        let mut uncollected_matched_lines = 0;
+        let mut uncollected_occurrences = 0;
+
+        // Once enough lines have been built, the lines of the file are only counted
+        let counting_only = take == Some(0);
 
-    // Search each file
-    for file_path in files {
         let file = match File::open(&file_path) {
             Ok(f) => f,
             Err(e) => {
@@ -1365,7 +3373,7 @@ pub fn search_files(
                         context: Some(vec![("file_path", file_path.display().to_string())]),
                     },
                 );
-                continue;
+                return Ok(SearchedFile::default());
             }
         };
 
@@ -1374,11 +3382,67 @@ pub fn search_files(
 
         // Define a custom sink to handle both matches and context lines
         struct MatchCollector<'a> {
-            // We don't need to store the matcher reference in this implementation
-            matches: &'a mut Vec<(u64, String, bool)>, // (line_number, content, is_context)
+            matches: &'a mut Vec<CollectedLine>,
+            matcher: &'a RegexMatcher,
+            content_mode: ContentMode,
+            line_from: Option<usize>,
+            line_to: Option<usize>,
+            line_terminator: u8,
+            deadline: Option<Instant>,
+            timed_out: &'a mut bool,
+            cancel: Option<&'a AtomicBool>,
+            first_match_only: bool,
+            min_matches_per_line: Option<usize>,
         }
 
-        impl<'a> grep::searcher::Sink for MatchCollector<'a> {
+        impl MatchCollector<'_> {
+            // Collects a line if it's within the line window.
+            // Returns false to stop searching once the window has been passed, the deadline
+            // has been reached or the search has been cancelled.
+            fn collect(&mut self, line_number: u64, bytes: &[u8], is_context: bool) -> bool {
+                if is_cancelled(self.cancel) {
+                    return false;
+                }
+                if self
+                    .deadline
+                    .is_some_and(|deadline| Instant::now() >= deadline)
+                {
+                    *self.timed_out = true;
+                    return false;
+                }
+                if self
+                    .line_to
+                    .is_some_and(|line_to| line_number > line_to as u64)
+                {
+                    return false;
+                }
+                if self
+                    .line_from
+                    .is_some_and(|line_from| line_number < line_from as u64)
+                {
+                    return true;
+                }
+
+                let bytes = bytes.strip_suffix(&[self.line_terminator]).unwrap_or(bytes);
+                if self.content_mode == ContentMode::None {
+                    // Find the matches on the raw bytes, so no line string needs to be allocated
+                    let match_ranges = if is_context {
+                        Vec::new()
+                    } else {
+                        find_match_ranges(self.matcher, bytes)
+                    };
+                    self.matches
+                        .push((line_number, String::new(), is_context, Some(match_ranges)));
+                    return true;
+                }
+
+                let line = String::from_utf8_lossy(bytes).to_string();
+                self.matches.push((line_number, line, is_context, None));
+                true
+            }
+        }
+
+        impl grep::searcher::Sink for MatchCollector<'_> {
             type Error = std::io::Error;
 
             // Handle match lines
@@ -1387,13 +3451,22 @@ pub fn search_files(
                 _searcher: &grep::searcher::Searcher,
                 mat: &grep::searcher::SinkMatch<'_>,
             ) -> Result<bool, Self::Error> {
-                let line = String::from_utf8_lossy(mat.bytes())
-                    .to_string()
-                    .trim_end_matches('\n')
-                    .to_string();
-                self.matches
-                    .push((mat.line_number().unwrap_or(0), line, false)); // Not a context line
-                Ok(true)
+                // Not a context line
+                let collected = self.matches.len();
+                let keep_searching =
+                    self.collect(mat.line_number().unwrap_or(0), mat.bytes(), false);
+                // Stop at the first collected match that will be reported when only one is
+                // reported per file; lines with too few matches are dropped afterwards
+                let reported = self.matches.len() > collected
+                    && self.min_matches_per_line.is_none_or(|min_matches| {
+                        let (_, _, _, found_ranges) = &self.matches[collected];
+                        let matches = found_ranges.as_ref().map_or_else(
+                            || find_match_ranges(self.matcher, mat.bytes()).len(),
+                            Vec::len,
+                        );
+                        matches >= min_matches
+                    });
+                Ok(keep_searching && !(self.first_match_only && reported))
             }
 
             // Handle context lines
@@ -1402,178 +3475,383 @@ pub fn search_files(
                 _searcher: &grep::searcher::Searcher,
                 ctx: &grep::searcher::SinkContext<'_>,
             ) -> Result<bool, Self::Error> {
-                let line = String::from_utf8_lossy(ctx.bytes())
-                    .to_string()
-                    .trim_end_matches('\n')
-                    .to_string();
-                self.matches
-                    .push((ctx.line_number().unwrap_or(0), line, true)); // Is a context line
-                Ok(true)
+                // Is a context line, which is not reported with only the first match
+                if self.first_match_only {
+                    return Ok(true);
+                }
+                Ok(self.collect(ctx.line_number().unwrap_or(0), ctx.bytes(), true))
             }
         }
 
         let collector = MatchCollector {
             matches: &mut matches,
+            matcher,
+            content_mode: if counting_only {
+                ContentMode::None
+            } else {
+                options.content
+            },
+            line_from: options.line_from,
+            line_to: match (options.line_to, options.header_lines) {
+                (Some(line_to), Some(header_lines)) => Some(line_to.min(header_lines)),
+                (line_to, header_lines) => line_to.or(header_lines),
+            },
+            line_terminator: options.line_terminator,
+            deadline: self.deadline,
+            timed_out: &mut timed_out,
+            cancel: options.cancel.as_deref(),
+            first_match_only: options.first_match_only,
+            min_matches_per_line: options.min_matches_per_line,
         };
 
-        searcher
-            .search_file(&matcher, &file, collector)
+        // Pick the searcher according to the read strategy
+        let use_mmap = match options.read_strategy {
+            ReadStrategy::Buffered => false,
+            ReadStrategy::Mmap => true,
+            ReadStrategy::Auto => file
+                .metadata()
+                .is_ok_and(|metadata| metadata.len() >= AUTO_MMAP_THRESHOLD_BYTES),
+        };
+        let active_searcher = match searchers.mmap_searcher.as_mut() {
+            Some(mmap_searcher) if use_mmap => mmap_searcher,
+            _ => &mut searchers.searcher,
+        };
+
+        // Extract the text of PDF files when requested
+        let pdf_text = match options
+            .extract_pdf_text
+            .then(|| PdfText::extract(&file_path))
+            .flatten()
+        {
+            Some(Ok(pdf_text)) => Some(pdf_text),
+            Some(Err(e)) => {
+                log_with_context(
+                    log::Level::Warn,
+                    LogMessage {
+                        message: format!("Failed to search PDF file: {:#}", e),
+                        module: "search",
+                        context: Some(vec![("file_path", file_path.display().to_string())]),
+                    },
+                );
+                return Ok(SearchedFile::default());
+            }
+            None => None,
+        };
+
+        // Search the decompressed content of compressed files when requested
+        let compression = options
+            .search_compressed
+            .then(|| Compression::detect(&file_path))
+            .flatten();
+
+        // Read files forced as text through a NUL detector, to report the files that binary
+        // detection would have skipped (the NUL bytes of compressed and PDF files are not those
+        // of the searched text)
+        let mut nul_detector = (options.treat_all_as_text
+            && options.line_terminator != b'\0'
+            && compression.is_none()
+            && pdf_text.is_none())
+        .then(|| NulDetector::new(&file));
+
+        if let Some(pdf_text) = &pdf_text {
+            searchers
+                .searcher
+                .search_slice(matcher, pdf_text.text.as_bytes(), collector)
+                .with_context(|| format!("Error searching file {}", file_path.display()))?;
+        } else if let Some(compression) = compression {
+            let decompressed = compression.reader(&file_path).and_then(|mut reader| {
+                searchers
+                    .searcher
+                    .search_reader(matcher, &mut reader, collector)?;
+                reader.close()?;
+                Ok(())
+            });
+            if let Err(e) = decompressed {
+                log_with_context(
+                    log::Level::Warn,
+                    LogMessage {
+                        message: format!("Failed to search compressed file: {:#}", e),
+                        module: "search",
+                        context: Some(vec![("file_path", file_path.display().to_string())]),
+                    },
+                );
+                return Ok(SearchedFile {
+                    timed_out,
+                    ..SearchedFile::default()
+                });
+            }
+        } else if let Some(header_lines) = options.header_lines {
+            // Read only the header instead of letting the searcher scan the whole file
+            let header = match nul_detector.as_mut() {
+                Some(nul_detector) => {
+                    read_header(nul_detector, header_lines, options.line_terminator)
+                }
+                None => read_header(&file, header_lines, options.line_terminator),
+            }
+            .with_context(|| format!("Failed to read file {}", file_path.display()))?;
+            searchers
+                .searcher
+                .search_slice(matcher, &header, collector)
+                .with_context(|| format!("Error searching file {}", file_path.display()))?;
+        } else {
+            match nul_detector.as_mut() {
+                Some(nul_detector) => {
+                    active_searcher.search_reader(matcher, nul_detector, collector)
+                }
+                None => active_searcher.search_file(matcher, &file, collector),
+            }
             .with_context(|| format!("Error searching file {}", file_path.display()))?;
+        }
+
+        if options.context_merge_gap > 0 {
+            drop_unmerged_context(&mut matches, options.before_context, options.after_context);
+        }
+        if let Some(pdf_text) = &pdf_text {
+            for (line_number, ..) in &mut matches {
+                *line_number = pdf_text.page_line_number(*line_number);
+            }
+        }
+        let dedents = if options.dedent_context {
+            dedent_blocks(matcher, &mut matches)
+        } else {
+            vec![0; matches.len()]
+        };
+
+        // Load the file content for enclosing symbol detection when needed
+        let symbol_source = if options.enclosing_symbol && !counting_only {
+            SymbolLanguage::from_path(&file_path)
+                .filter(|_| matches.iter().any(|(_, _, is_context, _)| !is_context))
+                .and_then(|language| {
+                    std::fs::read(&file_path)
+                        .ok()
+                        .map(|bytes| (language, String::from_utf8_lossy(&bytes).into_owned()))
+                })
+        } else {
+            None
+        };
+        let symbol_lines: Option<(SymbolLanguage, Vec<&str>)> = symbol_source
+            .as_ref()
+            .map(|(language, text)| (*language, text.lines().collect()));
+
+        // Blame the file once for all of its matches when requested
+        let file_blame = searchers
+            .blame_repository
+            .as_ref()
+            .filter(|_| !counting_only && matches.iter().any(|(_, _, is_context, _)| !is_context))
+            .and_then(|repository| FileBlame::new(repository, &file_path));
+
+        // Report files that binary detection would have skipped if they were not forced as text,
+        // reading the rest of the file only if the search stopped before a NUL byte
+        let from_binary_file = !counting_only
+            && !matches.is_empty()
+            && nul_detector.is_some_and(NulDetector::contains_nul);
 
         // Process all matches
-        for (line_number, content, is_context) in matches {
-            // Apply path prefix removal if configured
-            let processed_path = if let Some(prefix) = &options.omit_path_prefix {
+        for ((line_number, mut content, is_context, found_ranges), dedent) in
+            matches.into_iter().zip(dedents)
+        {
+            // Past the requested number of lines, only count the lines that would be reported
+            if take.is_some_and(|take| lines.len() >= take) {
+                if is_context {
+                    uncollected_lines += 1;
+                    continue;
+                }
+                let occurrences = found_ranges.map_or_else(
+                    || find_match_ranges(matcher, content.as_bytes()).len(),
+                    |ranges| ranges.len(),
+                );
+                if options
+                    .min_matches_per_line
+                    .is_none_or(|min_matches| occurrences >= min_matches)
+                {
+                    uncollected_lines += 1;
+                    uncollected_matched_lines += 1;
+                    uncollected_occurrences += occurrences;
+                }
+                continue;
+            }
+
+            // Make the path repository-relative, or apply path prefix removal if configured
+            let processed_path = if let Some((canonical_directory, root)) = &self.repo_root {
+                let rel_path = file_path.strip_prefix(directory).unwrap_or(&file_path);
+                remove_path_prefix(canonical_directory.join(rel_path), root)
+            } else if let Some(prefix) = &options.omit_path_prefix {
                 remove_path_prefix(&file_path, prefix)
             } else {
                 file_path.clone()
             };
+            let processed_path = self.path_display.apply(processed_path);
 
             // For context lines, we don't need to apply omission logic
             if is_context {
-                result_lines.push(SearchResultLine {
+                let match_id = compute_match_id(&processed_path, line_number, &[]);
+                let content_omitted = options.content == ContentMode::Truncated
+                    && truncate_displayed(
+                        &mut content,
+                        TRUNCATED_CONTENT_MAX_BYTES,
+                        &mut Vec::new(),
+                        None,
+                    );
+                lines.push(SearchResultLine {
                     file_path: processed_path,
                     line_number,
                     line_content: content,
-                    content_omitted: false,
+                    content_omitted,
                     is_context: true,
+                    enclosing_symbol: None,
+                    match_ranges: Vec::new(),
+                    match_ranges_utf16: Vec::new(),
+                    display_match_ranges: Vec::new(),
+                    match_id,
+                    blame_author: None,
+                    blame_commit: None,
+                    snippet: None,
+                    from_binary_file,
+                    duplicate_count: None,
+                    dedent,
+                });
+                continue;
+            }
+
+            // Find all matches in the line, unless they were found while collecting it
+            let match_positions =
+                found_ranges.unwrap_or_else(|| find_match_ranges(matcher, content.as_bytes()));
+
+            // Drop lines that don't contain enough matches
+            if let Some(min_matches) = options.min_matches_per_line {
+                if match_positions.len() < min_matches {
+                    continue;
+                }
+            }
+
+            let enclosing_symbol = symbol_lines
+                .as_ref()
+                .and_then(|(language, lines)| find_enclosing_symbol(lines, line_number, *language));
+
+            let match_id = compute_match_id(&processed_path, line_number, &match_positions);
+
+            let line_blame = file_blame
+                .as_ref()
+                .and_then(|file_blame| file_blame.line(line_number));
+            let blame_author = line_blame.map(|(author, _)| author.to_string());
+            let blame_commit = line_blame.map(|(_, commit)| commit.to_string());
+
+            // Without content, there is nothing to omit or highlight
+            if options.content == ContentMode::None {
+                lines.push(SearchResultLine {
+                    file_path: processed_path,
+                    line_number,
+                    line_content: content,
+                    content_omitted: false,
+                    is_context: false,
+                    enclosing_symbol,
+                    match_ranges: match_positions,
+                    match_ranges_utf16: Vec::new(),
+                    display_match_ranges: Vec::new(),
+                    match_id,
+                    blame_author,
+                    blame_commit,
+                    snippet: None,
+                    from_binary_file,
+                    duplicate_count: None,
+                    dedent,
                 });
                 continue;
             }
 
             // For actual matches, apply omission if needed
             // Calculate which parts of the content to keep and whether any was omitted
-            let (keep_ranges, content_omitted) = if let Some(omit_num) =
-                options.match_content_omit_num
-            {
-                // Apply content omission
-                let mut keep_ranges = Vec::new();
-                let mut any_omitted = false;
-
-                // Find all matches in the line
-                let mut match_positions = Vec::new();
-
-                // Collect all match positions using matcher's find_iter method
-                let _ = matcher.find_iter(content.as_bytes(), |m| {
-                    let start = m.start();
-                    let end = m.end();
-
-                    // Ensure valid UTF-8 boundaries
-                    let utf8_start = content[..start]
-                        .char_indices()
-                        .map(|(i, _)| i)
-                        .filter(|&i| i <= start)
-                        .last()
-                        .unwrap_or(0);
-
-                    let utf8_end = if end < content.len() {
-                        content[end..]
-                            .char_indices()
-                            .map(|(i, _)| i + end)
-                            .next()
-                            .unwrap_or(content.len())
-                    } else {
-                        content.len()
-                    };
+            let (keep_ranges, content_omitted) =
+                if let Some(omit_num) = options.match_content_omit_num {
+                    // Apply content omission
+                    let mut keep_ranges = Vec::new();
+                    let mut any_omitted = false;
 
-                    match_positions.push((utf8_start, utf8_end));
-                    true // Continue searching
-                });
+                    // No matches found (shouldn't happen, but handle it anyway)
+                    if match_positions.is_empty() {
+                        (vec![(0, content.len())], false)
+                    } else {
+                        // Calculate context ranges for each match
+                        for &match_range in &match_positions {
+                            // Keep omit_num characters on each side of the match
+                            keep_ranges.push(keep_range(&content, match_range, omit_num, omit_num));
+                        }
 
-                // No matches found (shouldn't happen, but handle it anyway)
-                if match_positions.is_empty() {
-                    (vec![(0, content.len())], false)
-                } else {
-                    // Calculate context ranges for each match
-                    for (match_start, match_end) in match_positions {
-                        // Calculate context start (omit_num characters before match)
-                        let context_start = if match_start > 0 {
-                            let char_count = content[..match_start].chars().count();
-                            let chars_to_keep = if char_count > omit_num {
-                                char_count - omit_num
-                            } else {
-                                0
-                            };
+                        // Sort and merge overlapping ranges
+                        if !keep_ranges.is_empty() {
+                            keep_ranges.sort_by_key(|&(start, _)| start);
 
-                            content[..match_start]
-                                .char_indices()
-                                .map(|(i, _)| i)
-                                .nth(chars_to_keep)
-                                .unwrap_or(0)
-                        } else {
-                            0
-                        };
-
-                        // Calculate context end (omit_num characters after match)
-                        let context_end = if match_end < content.len() {
-                            let chars_after = content[match_end..].chars().take(omit_num).count();
-                            content[match_end..]
-                                .char_indices()
-                                .map(|(i, _)| i + match_end)
-                                .nth(chars_after)
-                                .unwrap_or(content.len())
-                        } else {
-                            content.len()
-                        };
+                            let mut merged_ranges = Vec::new();
+                            let mut current_range = keep_ranges[0];
 
-                        // Add this range to our keep_ranges
-                        keep_ranges.push((context_start, context_end));
-                    }
+                            for &(start, end) in keep_ranges.iter().skip(1) {
+                                if start <= current_range.1 {
+                                    // Ranges overlap, merge them
+                                    current_range.1 = current_range.1.max(end);
+                                } else {
+                                    // No overlap, push current range and start a new one
+                                    merged_ranges.push(current_range);
+                                    current_range = (start, end);
+                                }
+                            }
+                            merged_ranges.push(current_range);
 
-                    // Sort and merge overlapping ranges
-                    if !keep_ranges.is_empty() {
-                        keep_ranges.sort_by_key(|&(start, _)| start);
-
-                        let mut merged_ranges = Vec::new();
-                        let mut current_range = keep_ranges[0];
-
-                        for &(start, end) in keep_ranges.iter().skip(1) {
-                            if start <= current_range.1 {
-                                // Ranges overlap, merge them
-                                current_range.1 = current_range.1.max(end);
-                            } else {
-                                // No overlap, push current range and start a new one
-                                merged_ranges.push(current_range);
-                                current_range = (start, end);
+                            // Check if any content would be omitted
+                            if merged_ranges.len() > 1
+                                || merged_ranges[0].0 > 0
+                                || merged_ranges.last().unwrap().1 < content.len()
+                            {
+                                any_omitted = true;
                             }
-                        }
-                        merged_ranges.push(current_range);
 
-                        // Check if any content would be omitted
-                        if merged_ranges.len() > 1
-                            || merged_ranges[0].0 > 0
-                            || merged_ranges.last().unwrap().1 < content.len()
-                        {
-                            any_omitted = true;
+                            (merged_ranges, any_omitted)
+                        } else {
+                            // Fallback (shouldn't reach here)
+                            (vec![(0, content.len())], false)
                         }
-
-                        (merged_ranges, any_omitted)
-                    } else {
-                        // Fallback (shouldn't reach here)
-                        (vec![(0, content.len())], false)
                     }
-                }
+                } else {
+                    // No omission requested
+                    (vec![(0, content.len())], false)
+                };
+
+            // Convert the match ranges for editors before the content is changed
+            let match_ranges_utf16 = if options.utf16_offsets {
+                highlight::utf16_ranges(&content, &match_positions)
             } else {
-                // No omission requested
-                (vec![(0, content.len())], false)
+                Vec::new()
             };
 
+            // Build the preview snippet from the full line before omission and highlighting
+            let snippet = options
+                .snippet_width
+                .zip(match_positions.first())
+                .map(|(width, &first_match)| highlight::snippet(&content, first_match, width));
+
             // Build the final content string using the keep ranges
-            let line_content = if content_omitted {
+            let highlight_markers = options.highlight_markers.as_ref();
+            let mut display_match_ranges = Vec::new();
+            let mut line_content = if content_omitted || highlight_markers.is_some() {
                 let mut result = String::new();
                 let mut last_end = 0;
 
                 for &(start, end) in &keep_ranges {
-                    // Add omission marker if there's a gap
+                    // Add an omission marker for every gap, including one at the start of the
+                    // line, so that omitted content is represented the same on both sides
                     if start > last_end {
-                        if last_end > 0 {
-                            // Don't add marker if we're at the beginning
-                            result.push_str("<omit>");
-                        }
+                        result.push_str("<omit>");
                     }
 
-                    // Add the content from this range
-                    result.push_str(&content[start..end]);
+                    // Add the content from this range, highlighting matches if requested
+                    push_highlighted(
+                        &mut result,
+                        &content,
+                        start,
+                        end,
+                        &match_positions,
+                        highlight_markers,
+                        &mut display_match_ranges,
+                    );
                     last_end = end;
                 }
 
@@ -1584,50 +3862,277 @@ pub fn search_files(
 
                 result
             } else {
-                // No omission, use the original content
+                // No omission, so the matches are displayed at their original positions
+                display_match_ranges = match_positions
+                    .iter()
+                    .copied()
+                    .filter(|(start, end)| start < end)
+                    .collect();
                 content
             };
 
-            result_lines.push(SearchResultLine {
+            let truncated = options.content == ContentMode::Truncated
+                && truncate_displayed(
+                    &mut line_content,
+                    TRUNCATED_CONTENT_MAX_BYTES,
+                    &mut display_match_ranges,
+                    highlight_markers,
+                );
+
+            lines.push(SearchResultLine {
                 file_path: processed_path,
                 line_number,
                 line_content,
-                content_omitted,
+                content_omitted: content_omitted || truncated,
                 is_context: false,
+                enclosing_symbol,
+                match_ranges: match_positions,
+                match_ranges_utf16,
+                display_match_ranges,
+                match_id,
+                blame_author,
+                blame_commit,
+                snippet,
+                from_binary_file,
+                duplicate_count: None,
+                dedent,
             });
         }
+
+        Ok(SearchedFile {
+            lines,
+            uncollected_lines,
+            uncollected_matched_lines,
+            uncollected_occurrences,
+            searched: true,
+            timed_out,
+        })
     }
+}
 
-    // Create the SearchResult with the total count and lines
-    let total_number = result_lines.len();
+/// Keeps the first matching line of each distinct content, counting the duplicates
+fn unique_lines(lines: Vec<SearchResultLine>) -> Vec<SearchResultLine> {
+    let mut first_index: HashMap<String, usize> = HashMap::new();
+    let mut unique: Vec<SearchResultLine> = Vec::new();
+    for mut line in lines.into_iter().filter(|line| !line.is_context) {
+        match first_index.get(&line.line_content) {
+            Some(&index) => {
+                let count = &mut unique[index].duplicate_count;
+                *count = Some(count.unwrap_or(1) + 1);
+            }
+            None => {
+                first_index.insert(line.line_content.clone(), unique.len());
+                line.duplicate_count = Some(1);
+                unique.push(line);
+            }
+        }
+    }
+    unique
+}
 
-    // Create the result and sort it by file path and line number
-    let mut result = SearchResult {
-        total_number,
-        lines: result_lines,
+/// Finds the byte ranges of all matches in a line.
+///
+/// When the line is valid UTF-8, the ranges are widened to character boundaries, so that they
+/// can be used to slice the line and insert markers without splitting multibyte characters.
+/// Widened ranges that overlap (e.g., byte-level matches inside one character) are merged, so
+/// the ranges are sorted and don't overlap.
+pub(crate) fn find_match_ranges(matcher: &RegexMatcher, line: &[u8]) -> Vec<(usize, usize)> {
+    let text = std::str::from_utf8(line).ok();
+    let mut match_ranges: Vec<(usize, usize)> = Vec::new();
+    let _ = matcher.find_iter(line, |m| {
+        let (start, end) = match text {
+            Some(text) => (
+                floor_char_boundary(text, m.start()),
+                ceil_char_boundary(text, m.end()),
+            ),
+            None => (m.start(), m.end()),
+        };
+        match match_ranges.last_mut() {
+            Some(last) if start < last.1 => last.1 = last.1.max(end),
+            _ => match_ranges.push((start, end)),
+        }
+        true // Continue searching
+    });
+    match_ranges
+}
+
+/// A line collected while searching a file:
+/// (line_number, content, is_context, match ranges found while collecting)
+type CollectedLine = (u64, String, bool, Option<Vec<(usize, usize)>>);
+
+/// Removes the common indentation of each block of consecutive lines, for
+/// `SearchOptions::dedent_context`.
+///
+/// The match ranges of matching lines are found on the original content, so that patterns
+/// matching the indentation keep their ranges, and shifted to the dedented content. Ranges
+/// starting within the removed indentation start at the beginning of the dedented line.
+///
+/// # Returns
+///
+/// The number of bytes removed from each line
+fn dedent_blocks(matcher: &RegexMatcher, lines: &mut [CollectedLine]) -> Vec<usize> {
+    let mut dedents = Vec::with_capacity(lines.len());
+    let mut start = 0;
+    while start < lines.len() {
+        let mut end = start + 1;
+        while end < lines.len() && lines[end].0 == lines[end - 1].0 + 1 {
+            end += 1;
+        }
+        let block = &mut lines[start..end];
+
+        // The longest whitespace prefix shared by the lines that aren't blank
+        let indent = block
+            .iter()
+            .map(|(_, content, ..)| content.as_str())
+            .filter(|content| !content.trim().is_empty())
+            .map(|content| &content[..content.len() - content.trim_start().len()])
+            .reduce(|common, indent| &common[..shared_prefix_len(common, indent)])
+            .unwrap_or_default()
+            .to_string();
+
+        for (_, content, is_context, found_ranges) in block {
+            // Blank lines may have less, or different, whitespace than the common indentation
+            let dedent = shared_prefix_len(&indent, content);
+            if !*is_context && found_ranges.is_none() {
+                *found_ranges = Some(find_match_ranges(matcher, content.as_bytes()));
+            }
+            content.drain(..dedent);
+            if let Some(found_ranges) = found_ranges {
+                for (range_start, range_end) in found_ranges {
+                    *range_start = range_start.saturating_sub(dedent);
+                    *range_end = range_end.saturating_sub(dedent);
+                }
+            }
+            dedents.push(dedent);
+        }
+        start = end;
+    }
+    dedents
+}
+
+/// Returns the length in bytes of the longest common prefix of two strings
+fn shared_prefix_len(a: &str, b: &str) -> usize {
+    a.char_indices()
+        .zip(b.chars())
+        .find(|((_, a), b)| a != b)
+        .map_or(a.len().min(b.len()), |((index, _), _)| index)
+}
+
+/// Removes the context lines read for `SearchOptions::context_merge_gap` that don't join
+/// two context windows.
+///
+/// The lines of a file are searched with the after context extended by the merge gap. Context
+/// lines outside of the regular windows of the matches are kept only where they lead without
+/// a break into the window of the next match.
+fn drop_unmerged_context(
+    lines: &mut Vec<CollectedLine>,
+    before_context: usize,
+    after_context: usize,
+) {
+    let match_lines: Vec<u64> = lines
+        .iter()
+        .filter(|(_, _, is_context, _)| !is_context)
+        .map(|(line_number, ..)| *line_number)
+        .collect();
+    let is_extra = |&(line_number, _, is_context, _): &CollectedLine| {
+        let index = match_lines.partition_point(|&m| m < line_number);
+        let after_previous =
+            index > 0 && line_number - match_lines[index - 1] <= after_context as u64;
+        let before_next =
+            index < match_lines.len() && match_lines[index] - line_number <= before_context as u64;
+        is_context && !after_previous && !before_next
     };
 
-    // Sort the results for consistent ordering
-    result.sort_by_path_and_line();
+    let mut keep = vec![true; lines.len()];
+    let mut index = 0;
+    while index < lines.len() {
+        if !is_extra(&lines[index]) {
+            index += 1;
+            continue;
+        }
+        // Find the end of the run of extra lines, and whether the next line continues it
+        let start = index;
+        while index < lines.len() && is_extra(&lines[index]) {
+            index += 1;
+        }
+        let joined = index < lines.len() && lines[index].0 == lines[index - 1].0 + 1;
+        if !joined {
+            keep[start..index].fill(false);
+        }
+    }
 
-    // Apply pagination if skip and take are specified
-    if options.skip.is_some() || options.take.is_some() {
-        // Calculate the 1-based indices for split
-        let from = match options.skip {
-            Some(skip) => skip + 1, // Convert 0-based skip to 1-based from
-            None => 1,              // Start from the first result if skip is None
-        };
+    let mut keep = keep.into_iter();
+    lines.retain(|_| keep.next().unwrap_or(true));
+}
 
-        let to = match options.take {
-            Some(take) => from + take - 1, // Calculate the last index (inclusive)
-            None => result.lines.len(),    // Use all results if take is None
-        };
+/// Reads the first `lines` lines of a file, including their terminators, stopping after
+/// `HEADER_MAX_BYTES` bytes
+fn read_header(file: impl Read, lines: usize, line_terminator: u8) -> std::io::Result<Vec<u8>> {
+    let mut reader = std::io::BufReader::new(file.take(HEADER_MAX_BYTES));
+    let mut header = Vec::new();
+    for _ in 0..lines {
+        if reader.read_until(line_terminator, &mut header)? == 0 {
+            break;
+        }
+    }
+    Ok(header)
+}
 
-        // Use the built-in split method to paginate the results
-        result = result.split(from, to);
+/// Creates the matcher for a pattern with the case sensitivity and size limits of the options.
+pub(crate) fn build_matcher(pattern: &str, options: &SearchOptions) -> Result<RegexMatcher> {
+    let mut matcher_builder = RegexMatcherBuilder::new();
+    if let Some(regex_size_limit) = options.regex_size_limit {
+        matcher_builder.size_limit(regex_size_limit);
     }
+    if let Some(dfa_size_limit) = options.dfa_size_limit {
+        matcher_builder.dfa_size_limit(dfa_size_limit);
+    }
+    matcher_builder
+        .build(&options.effective_pattern(pattern))
+        .context("Failed to create regular expression matcher")
+}
+
+/// A reader recording whether a NUL byte, the criterion of binary detection, has been read.
+struct NulDetector<R> {
+    inner: R,
+    found_nul: bool,
+}
+
+impl<R: Read> NulDetector<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            found_nul: false,
+        }
+    }
+
+    /// Returns whether the content contains a NUL byte.
+    ///
+    /// The content that hasn't been read yet is read in chunks until the first NUL byte.
+    /// Unreadable content is reported as not containing one.
+    fn contains_nul(mut self) -> bool {
+        let mut buffer = vec![0; 64 * 1024];
+        while !self.found_nul {
+            match self.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+        self.found_nul
+    }
+}
+
+impl<R: Read> Read for NulDetector<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.found_nul = self.found_nul || buf[..n].contains(&b'\0');
+        Ok(n)
+    }
+}
 
-    Ok(result)
+/// Returns whether the given cancellation flag has been set.
+fn is_cancelled(cancel: Option<&AtomicBool>) -> bool {
+    cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed))
 }
 
 /// Collects a list of files within the given directory that should be included in the search.
@@ -1670,42 +4175,85 @@ pub fn search_files(
 /// Returns an error if there's an issue accessing the directory or files, or if there's an error
 /// compiling the glob patterns
 fn collect_files(directory: &Path, options: &SearchOptions) -> Result<Vec<PathBuf>> {
-    let include_glob = options.include_glob.as_ref();
+    let files = collect_files_with_modified(directory, options)?;
+    Ok(files.into_iter().map(|(path, _)| path).collect())
+}
+
+/// Collects files like `collect_files`, along with their modification times.
+///
+/// The modification times are only read when the results are sorted by them
+/// (`SearchSort::ModifiedDesc`), and are `None` otherwise.
+fn collect_files_with_modified(
+    directory: &Path,
+    options: &SearchOptions,
+) -> Result<Vec<(PathBuf, Option<SystemTime>)>> {
+    // Read the modification time of each file while it is collected when sorting by it
+    let read_modified = options.effective_sort() == SearchSort::ModifiedDesc;
+    let modified = |path: &Path| {
+        if read_modified {
+            path.metadata()
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        } else {
+            None
+        }
+    };
+
+    // Compile the include and exclude patterns once rather than for every file
+    let exclude_glob = effective_exclude_globs(options);
+    let file_filter = common::FileFilter::new(
+        directory,
+        options.include_glob.as_ref(),
+        exclude_glob.as_ref(),
+        options.case_sensitive,
+    )?;
+
+    // Read the tracked files once; outside a repository, all files are kept
+    let tracked_files = if options.git_tracked_only {
+        TrackedFiles::discover(directory)
+    } else {
+        None
+    };
 
     // Use the generic traverse function directly
+    let walk_config = common::WalkConfig {
+        respect_gitignore: options.respect_gitignore,
+        case_sensitive: options.case_sensitive,
+        max_depth: options.depth,
+        ignore_root_gitignore: options.ignore_root_gitignore,
+        skip_vendored: options.skip_vendored,
+        one_file_system: false,
+        cancel: options.cancel.clone(),
+        pruned_dirs: None,
+    };
     common::traverse_with_callback(
         directory,
-        options.respect_gitignore,
-        options.case_sensitive,
-        options.depth,
-        options.exclude_glob.as_ref(),
+        &walk_config,
+        None,       // include_glob and exclude_glob are both evaluated by file_filter
         Vec::new(), // Start with an empty vector
         |mut files, path| {
-            // If include_glob is specified, only include files that match at least one pattern
-            if let Some(include_patterns) = include_glob {
-                // IMPORTANT: Convert absolute path to relative path for consistent glob matching
-                // This ensures include_glob works the same way as exclude_glob (which also uses relative paths).
-                //
-                // Example: When searching in "/home/user/project":
-                // - Input path: "/home/user/project/src/main.rs"
-                // - Relative path: "src/main.rs"
-                // - Pattern "**/*.rs" will match against "src/main.rs"
-                //
-                // This consistency fix allows users to write the same pattern format for both
-                // include_glob and exclude_glob, making the API more intuitive.
-                let rel_path = path.strip_prefix(directory).unwrap_or(path);
-                
-                // Check if file matches any of the include patterns using the relative path
-                let is_included =
-                    common::path_matches_any_glob(rel_path, include_patterns, options.case_sensitive)?;
-
-                // Only add the file if it matches an include pattern
-                if is_included {
-                    files.push(path.to_path_buf());
+            if let Some(min_depth) = options.min_depth {
+                if path
+                    .strip_prefix(directory)
+                    .unwrap_or(path)
+                    .components()
+                    .count()
+                    < min_depth
+                {
+                    return Ok(files);
+                }
+            }
+
+            if let Some(tracked_files) = &tracked_files {
+                if !tracked_files.contains(path.strip_prefix(directory).unwrap_or(path)) {
+                    return Ok(files);
                 }
-            } else {
-                // No include_glob, so include all files
-                files.push(path.to_path_buf());
+            }
+
+            // include_glob and exclude_glob are matched against the path relative to the
+            // search directory, so both take patterns like `src/**/*.rs`
+            if file_filter.matches(path)? {
+                files.push((path.to_path_buf(), modified(path)));
             }
 
             Ok(files)
@@ -1713,6 +4261,29 @@ fn collect_files(directory: &Path, options: &SearchOptions) -> Result<Vec<PathBu
     )
 }
 
+/// Combines `exclude_glob` with the globs derived from `exclude_dir_names`.
+///
+/// Each directory name is escaped and expanded to `**/<name>/**`, which matches the
+/// directory at any depth when applied to paths relative to the search directory.
+///
+/// # Returns
+///
+/// `None` if there are no exclusions at all, otherwise the full list of exclude patterns
+fn effective_exclude_globs(options: &SearchOptions) -> Option<Vec<String>> {
+    if options.exclude_dir_names.is_empty() {
+        return options.exclude_glob.clone();
+    }
+
+    let mut patterns = options.exclude_glob.clone().unwrap_or_default();
+    patterns.extend(
+        options
+            .exclude_dir_names
+            .iter()
+            .map(|name| format!("**/{}/**", globset::escape(name))),
+    );
+    Some(patterns)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1765,10 +4336,47 @@ mod tests {
             omit_path_prefix: None,
             match_content_omit_num: None,
             depth: None,
+            min_depth: None,
             before_context: 0,
             after_context: 0,
             skip: None,
             take: None,
+            treat_all_as_text: false,
+            exclude_dir_names: Vec::new(),
+            min_matches_per_line: None,
+            enclosing_symbol: false,
+            sort_by_relevance: false,
+            ignore_root_gitignore: false,
+            highlight_markers: None,
+            read_strategy: ReadStrategy::Buffered,
+            line_from: None,
+            line_to: None,
+            line_terminator: b'\n',
+            auto_repo_relative: false,
+            regex_size_limit: None,
+            dfa_size_limit: None,
+            timeout: None,
+            cancel: None,
+            content: ContentMode::Full,
+            natural_sort: false,
+            with_blame: false,
+            skip_vendored: false,
+            auto_omit_common_prefix: false,
+            snippet_width: None,
+            git_tracked_only: false,
+            search_compressed: false,
+            utf16_offsets: false,
+            tilde_home: false,
+            sort: SearchSort::PathAndLine,
+            clean_paths: false,
+            header_lines: None,
+            context_merge_gap: 0,
+            unique_content: false,
+            extract_pdf_text: false,
+            dedent_context: false,
+            collect_extensions: false,
+            first_match_only: false,
+            threads: None,
         }
     }
 
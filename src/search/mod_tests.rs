@@ -81,10 +81,47 @@ fn test_collect_files_with_include_glob() -> Result<()> {
         omit_path_prefix: None,
         match_content_omit_num: None,
         depth: None,
+        min_depth: None,
         before_context: 0,
         after_context: 0,
         skip: None,
         take: None,
+        treat_all_as_text: false,
+        exclude_dir_names: Vec::new(),
+        min_matches_per_line: None,
+        enclosing_symbol: false,
+        sort_by_relevance: false,
+        ignore_root_gitignore: false,
+        highlight_markers: None,
+        read_strategy: ReadStrategy::Buffered,
+        line_from: None,
+        line_to: None,
+        line_terminator: b'\n',
+        auto_repo_relative: false,
+        regex_size_limit: None,
+        dfa_size_limit: None,
+        timeout: None,
+        cancel: None,
+        content: ContentMode::Full,
+        natural_sort: false,
+        with_blame: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        snippet_width: None,
+        git_tracked_only: false,
+        search_compressed: false,
+        utf16_offsets: false,
+        tilde_home: false,
+        sort: SearchSort::PathAndLine,
+        clean_paths: false,
+        header_lines: None,
+        context_merge_gap: 0,
+        unique_content: false,
+        extract_pdf_text: false,
+        dedent_context: false,
+        collect_extensions: false,
+        first_match_only: false,
+        threads: None,
     };
 
     // Test case 1: No include_glob (should include all files)
@@ -289,10 +326,47 @@ fn test_collect_files_with_depth_limit() -> Result<()> {
         omit_path_prefix: None,
         match_content_omit_num: None,
         depth: None, // Will be set in each test case
+        min_depth: None,
         before_context: 0,
         after_context: 0,
         skip: None,
         take: None,
+        treat_all_as_text: false,
+        exclude_dir_names: Vec::new(),
+        min_matches_per_line: None,
+        enclosing_symbol: false,
+        sort_by_relevance: false,
+        ignore_root_gitignore: false,
+        highlight_markers: None,
+        read_strategy: ReadStrategy::Buffered,
+        line_from: None,
+        line_to: None,
+        line_terminator: b'\n',
+        auto_repo_relative: false,
+        regex_size_limit: None,
+        dfa_size_limit: None,
+        timeout: None,
+        cancel: None,
+        content: ContentMode::Full,
+        natural_sort: false,
+        with_blame: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        snippet_width: None,
+        git_tracked_only: false,
+        search_compressed: false,
+        utf16_offsets: false,
+        tilde_home: false,
+        sort: SearchSort::PathAndLine,
+        clean_paths: false,
+        header_lines: None,
+        context_merge_gap: 0,
+        unique_content: false,
+        extract_pdf_text: false,
+        dedent_context: false,
+        collect_extensions: false,
+        first_match_only: false,
+        threads: None,
     };
 
     // Test case 1: First get all files to verify what we're working with
@@ -396,8 +470,10 @@ fn test_collect_files_with_depth_limit() -> Result<()> {
     println!("\nTest case 4: JSON files not in deep directories");
 
     // Get all JSON files
-    let mut json_options = SearchOptions::default();
-    json_options.include_glob = Some(vec!["**/*.json".to_string()]);
+    let json_options = SearchOptions {
+        include_glob: Some(vec!["**/*.json".to_string()]),
+        ..SearchOptions::default()
+    };
     let all_json_files = super::collect_files(temp_path, &json_options)?;
 
     // Filter to include only JSON files not in 'deep' directories
@@ -454,10 +530,47 @@ fn test_collect_files_with_empty_include_glob() -> Result<()> {
         omit_path_prefix: None,
         match_content_omit_num: None,
         depth: None,
+        min_depth: None,
         before_context: 0,
         after_context: 0,
         skip: None,
         take: None,
+        treat_all_as_text: false,
+        exclude_dir_names: Vec::new(),
+        min_matches_per_line: None,
+        enclosing_symbol: false,
+        sort_by_relevance: false,
+        ignore_root_gitignore: false,
+        highlight_markers: None,
+        read_strategy: ReadStrategy::Buffered,
+        line_from: None,
+        line_to: None,
+        line_terminator: b'\n',
+        auto_repo_relative: false,
+        regex_size_limit: None,
+        dfa_size_limit: None,
+        timeout: None,
+        cancel: None,
+        content: ContentMode::Full,
+        natural_sort: false,
+        with_blame: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        snippet_width: None,
+        git_tracked_only: false,
+        search_compressed: false,
+        utf16_offsets: false,
+        tilde_home: false,
+        sort: SearchSort::PathAndLine,
+        clean_paths: false,
+        header_lines: None,
+        context_merge_gap: 0,
+        unique_content: false,
+        extract_pdf_text: false,
+        dedent_context: false,
+        collect_extensions: false,
+        first_match_only: false,
+        threads: None,
     };
 
     println!("Testing with empty include_glob list");
@@ -475,3 +588,86 @@ fn test_collect_files_with_empty_include_glob() -> Result<()> {
     println!("test_collect_files_with_empty_include_glob completed successfully");
     Ok(())
 }
+
+/// Creates files with 10 lines matching "needle" in all, named so that the walk order differs
+/// from the sorted order
+fn create_take_test_files(dir: &Path) -> Result<()> {
+    for (name, matches) in [("c.txt", 3), ("a.txt", 2), ("b.txt", 4), ("d.txt", 1)] {
+        let content: Vec<String> = (0..matches)
+            .map(|i| format!("needle {}\nfiller\n", i))
+            .collect();
+        fs::write(dir.join(name), content.concat())?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_search_take_does_not_build_remaining_lines() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    create_take_test_files(temp_dir.path())?;
+
+    let options = SearchOptions {
+        take: Some(3),
+        threads: Some(1),
+        ..SearchOptions::default()
+    };
+    let (result, counts) = search_files_counted("needle", temp_dir.path(), &options)?;
+    assert_eq!(result.lines.len(), 3);
+    assert_eq!(result.total_number, 10);
+    // Only the lines that are returned are built, the others are only counted
+    assert_eq!(counts.uncollected_lines, 7);
+    assert_eq!(counts.uncollected_occurrences, 7);
+    assert_eq!(counts.files_searched, 4);
+    assert_eq!(counts.files_with_matches, 4);
+
+    // Skipped lines have to be built to be sorted, so nothing is left uncollected
+    let options = SearchOptions {
+        skip: Some(1),
+        ..options
+    };
+    let (result, counts) = search_files_counted("needle", temp_dir.path(), &options)?;
+    assert_eq!(result.lines.len(), 3);
+    assert_eq!(result.total_number, 10);
+    assert_eq!(counts.uncollected_lines, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_search_take_with_default_threads() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    create_take_test_files(temp_dir.path())?;
+
+    // The same lines are returned and counted whatever the number of threads
+    for threads in [None, Some(4)] {
+        let options = SearchOptions {
+            take: Some(3),
+            threads,
+            ..SearchOptions::default()
+        };
+        let (result, counts) = search_files_counted("needle", temp_dir.path(), &options)?;
+        let lines: Vec<_> = result
+            .lines
+            .iter()
+            .map(|line| {
+                (
+                    line.file_path.file_name().unwrap().to_owned(),
+                    line.line_number,
+                )
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("a.txt".into(), 1),
+                ("a.txt".into(), 3),
+                ("b.txt".into(), 1)
+            ]
+        );
+        assert_eq!(result.total_number, 10);
+        assert_eq!(counts.uncollected_lines, 7, "{:?} threads", threads);
+        assert_eq!(counts.uncollected_occurrences, 7, "{:?} threads", threads);
+    }
+
+    Ok(())
+}
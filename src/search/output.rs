@@ -0,0 +1,143 @@
+//! Writing of search results to files.
+//!
+//! Batch jobs often store search results for later processing rather than printing them.
+//! This module writes a `SearchResult` in one of several formats, either to a file or to any
+//! writer.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use super::SearchResult;
+
+/// The format of written search results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OutputFormat {
+    /// The lines as printed by the `lumin search` command: `path:line: content` for matches
+    /// (with the enclosing symbol in brackets, if any), `path:line- content` for context lines,
+    /// and `--` between discontinuous lines. Leading and trailing whitespace of the content is
+    /// trimmed.
+    #[default]
+    Text,
+    /// The whole `SearchResult` as a pretty-printed JSON object
+    Json,
+    /// One JSON object per line, for each `SearchResultLine`
+    Ndjson,
+    /// CSV with a header row and the columns `path`, `line`, `is_context` and `content`.
+    /// Fields containing commas, quotes or line breaks are quoted, with quotes doubled.
+    Csv,
+}
+
+/// Writes search results to a file, replacing its content.
+///
+/// # Arguments
+///
+/// * `result` - The search results to write
+/// * `path` - The file to write to. It is created if it doesn't exist.
+/// * `format` - The output format
+///
+/// # Errors
+///
+/// Returns an error if the file can't be created or written
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::search::{OutputFormat, SearchOptions, search_files, write_results};
+/// use std::path::Path;
+///
+/// let result = search_files("TODO", Path::new("src"), &SearchOptions::default()).unwrap();
+/// write_results(&result, Path::new("todos.csv"), OutputFormat::Csv).unwrap();
+/// ```
+pub fn write_results(result: &SearchResult, path: &Path, format: OutputFormat) -> Result<()> {
+    let file =
+        File::create(path).with_context(|| format!("Failed to create file {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    write_results_to(result, &mut writer, format)?;
+    writer
+        .flush()
+        .with_context(|| format!("Failed to write file {}", path.display()))
+}
+
+/// Writes search results to a writer.
+///
+/// See `write_results`; this variant writes to any writer, such as standard output.
+///
+/// # Errors
+///
+/// Returns an error if writing fails
+pub fn write_results_to<W: Write>(
+    result: &SearchResult,
+    writer: &mut W,
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Text => write_text(result, writer)?,
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, result)
+                .context("Failed to write search results as JSON")?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Ndjson => {
+            for line in &result.lines {
+                serde_json::to_writer(&mut *writer, line)
+                    .context("Failed to write search results as JSON")?;
+                writeln!(writer)?;
+            }
+        }
+        OutputFormat::Csv => {
+            writeln!(writer, "path,line,is_context,content")?;
+            for line in &result.lines {
+                writeln!(
+                    writer,
+                    "{},{},{},{}",
+                    csv_field(&line.file_path.to_string_lossy()),
+                    line.line_number,
+                    line.is_context,
+                    csv_field(&line.line_content)
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes the lines in the format of the `lumin search` command
+fn write_text<W: Write>(result: &SearchResult, writer: &mut W) -> std::io::Result<()> {
+    let mut last: Option<(&Path, u64)> = None;
+    for line in &result.lines {
+        // Separate discontinuous lines
+        if let Some((last_file, last_line_number)) = last {
+            if line.file_path != last_file || line.line_number > last_line_number + 1 {
+                writeln!(writer, "--")?;
+            }
+        }
+        last = Some((&line.file_path, line.line_number));
+
+        let path = line.file_path.display();
+        let content = line.line_content.trim();
+        if line.is_context {
+            writeln!(writer, "{}:{}- {}", path, line.line_number, content)?;
+        } else if let Some(symbol) = &line.enclosing_symbol {
+            writeln!(
+                writer,
+                "{}:{}: [{}] {}",
+                path, line.line_number, symbol, content
+            )?;
+        } else {
+            writeln!(writer, "{}:{}: {}", path, line.line_number, content)?;
+        }
+    }
+    Ok(())
+}
+
+/// Quotes a CSV field if it contains a delimiter, a quote or a line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -75,7 +75,7 @@ pattern in third line
     ) -> Result<()> {
         // Total result count for validation
         let total_count = full_results.total_number;
-        let total_pages = (total_count + page_size - 1) / page_size;
+        let total_pages = total_count.div_ceil(page_size);
 
         println!(
             "Validating pagination with {} total results, page size {}, {} pages",
@@ -85,9 +85,11 @@ pattern in third line
         // Test each page and verify it matches the corresponding slice of full results
         for page in 0..total_pages {
             let skip = page * page_size;
-            let mut options = SearchOptions::default();
-            options.skip = Some(skip);
-            options.take = Some(page_size);
+            let options = SearchOptions {
+                skip: Some(skip),
+                take: Some(page_size),
+                ..SearchOptions::default()
+            };
 
             // Get this page using pagination
             let page_results = search_files(pattern, directory, &options)?;
@@ -180,8 +182,10 @@ pattern in third line
         println!("\nTesting edge cases:");
 
         // Edge case 1: Skip beyond available results
-        let mut options_beyond = SearchOptions::default();
-        options_beyond.skip = Some(full_results.total_number + 10);
+        let options_beyond = SearchOptions {
+            skip: Some(full_results.total_number + 10),
+            ..SearchOptions::default()
+        };
         let beyond_results = search_files(pattern, temp_path, &options_beyond)?;
         assert_eq!(
             beyond_results.lines.len(),
@@ -191,9 +195,11 @@ pattern in third line
         println!("✓ Edge case: Skip beyond available results - Passed");
 
         // Edge case 2: Take more than available after skip
-        let mut options_take_more = SearchOptions::default();
-        options_take_more.skip = Some(full_results.total_number - 2);
-        options_take_more.take = Some(10); // More than what's left
+        let options_take_more = SearchOptions {
+            skip: Some(full_results.total_number - 2),
+            take: Some(10), // More than what's left
+            ..SearchOptions::default()
+        };
         let take_more_results = search_files(pattern, temp_path, &options_take_more)?;
         assert_eq!(
             take_more_results.lines.len(),
@@ -203,9 +209,11 @@ pattern in third line
         println!("✓ Edge case: Take more than available - Passed");
 
         // Edge case 3: Skip 0, take all
-        let mut options_all = SearchOptions::default();
-        options_all.skip = Some(0);
-        options_all.take = Some(full_results.total_number + 10); // More than total
+        let options_all = SearchOptions {
+            skip: Some(0),
+            take: Some(full_results.total_number + 10), // More than total
+            ..SearchOptions::default()
+        };
         let all_results = search_files(pattern, temp_path, &options_all)?;
         assert_eq!(
             all_results.lines.len(),
@@ -218,6 +226,42 @@ pattern in third line
         Ok(())
     }
 
+    #[test]
+    fn test_search_result_page() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut file = File::create(temp_dir.path().join("lines.txt"))?;
+        for i in 1..=25 {
+            writeln!(file, "pattern {}", i)?;
+        }
+
+        let results = search_files("pattern", temp_dir.path(), &SearchOptions::default())?;
+        assert_eq!(results.lines.len(), 25);
+
+        let line_numbers = |page: &SearchResult| -> Vec<u64> {
+            page.lines.iter().map(|line| line.line_number).collect()
+        };
+        let pages: Vec<SearchResult> = (0..4).map(|page| results.page(page, 10)).collect();
+        assert_eq!(line_numbers(&pages[0]), (1..=10).collect::<Vec<_>>());
+        assert_eq!(line_numbers(&pages[1]), (11..=20).collect::<Vec<_>>());
+        assert_eq!(line_numbers(&pages[2]), (21..=25).collect::<Vec<_>>());
+        assert!(pages[3].lines.is_empty());
+        assert!(pages.iter().all(|page| page.total_number == 25));
+
+        // Pages agree with searching again with skip and take
+        let searched = search_files(
+            "pattern",
+            temp_dir.path(),
+            &SearchOptions {
+                skip: Some(10),
+                take: Some(10),
+                ..SearchOptions::default()
+            },
+        )?;
+        assert_eq!(line_numbers(&searched), line_numbers(&pages[1]));
+
+        Ok(())
+    }
+
     #[test]
     fn test_search_sorting_behavior() -> Result<()> {
         // Create a temporary directory with our test files
@@ -34,8 +34,10 @@ mod tests {
         );
 
         // Test case 2: With path prefix removal
-        let mut options_with_prefix = SearchOptions::default();
-        options_with_prefix.omit_path_prefix = Some(temp_path.to_path_buf());
+        let options_with_prefix = SearchOptions {
+            omit_path_prefix: Some(temp_path.to_path_buf()),
+            ..SearchOptions::default()
+        };
         let results_with_prefix = search_files(pattern, temp_path, &options_with_prefix)?;
         assert_eq!(results_with_prefix.total_number, 1, "Should find one match");
         assert_eq!(
@@ -45,9 +47,10 @@ mod tests {
         );
 
         // Test case 3: With non-matching path prefix
-        let mut options_with_nonmatching_prefix = SearchOptions::default();
-        options_with_nonmatching_prefix.omit_path_prefix =
-            Some(PathBuf::from("/non/existing/path"));
+        let options_with_nonmatching_prefix = SearchOptions {
+            omit_path_prefix: Some(PathBuf::from("/non/existing/path")),
+            ..SearchOptions::default()
+        };
         let results_nonmatching =
             search_files(pattern, temp_path, &options_with_nonmatching_prefix)?;
         assert_eq!(results_nonmatching.total_number, 1, "Should find one match");
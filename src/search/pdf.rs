@@ -0,0 +1,89 @@
+//! Text extraction from PDF files for searching.
+//!
+//! PDF files store their text in compressed content streams, so searching their bytes finds
+//! nothing. With the `pdf` cargo feature, the text of each page is extracted with the
+//! `pdf-extract` crate and searched instead, with line numbers that identify the page.
+
+use anyhow::Result;
+use std::path::Path;
+
+/// Line numbers of matches in PDF files are `page * PDF_PAGE_LINE_BASE + line`.
+///
+/// Both the page and the line within the page are 1-based, so the match on line 12 of page 3
+/// is reported with the line number 30012. Pages with more lines than this are not expected.
+pub const PDF_PAGE_LINE_BASE: u64 = 10_000;
+
+/// The extracted text of a PDF file, as searched.
+pub(crate) struct PdfText {
+    /// The text of all pages, each ending with a newline
+    pub(crate) text: String,
+    /// The line number within `text` of the first line of each page (1-based)
+    page_starts: Vec<u64>,
+}
+
+impl PdfText {
+    /// Extracts the text of a PDF file.
+    ///
+    /// Returns `None` if the file is not a PDF file, recognized by its magic bytes, or if the
+    /// `pdf` feature is disabled.
+    pub(crate) fn extract(path: &Path) -> Option<Result<Self>> {
+        if !Self::is_searchable(path) {
+            return None;
+        }
+        extract_pages(path).map(|pages| pages.map(Self::from_pages))
+    }
+
+    /// Returns whether the text of a file would be extracted by `extract`
+    pub(crate) fn is_searchable(path: &Path) -> bool {
+        cfg!(feature = "pdf")
+            && infer::get_from_path(path)
+                .ok()
+                .flatten()
+                .is_some_and(|kind| kind.mime_type() == "application/pdf")
+    }
+
+    /// Joins the pages into one text, recording where each page starts
+    fn from_pages(pages: Vec<String>) -> Self {
+        let mut text = String::new();
+        let mut page_starts = Vec::with_capacity(pages.len());
+        let mut line_count = 0;
+        for page in pages {
+            page_starts.push(line_count + 1);
+            text.push_str(&page);
+            line_count += page.bytes().filter(|&b| b == b'\n').count() as u64;
+            if !page.ends_with('\n') {
+                text.push('\n');
+                line_count += 1;
+            }
+        }
+        Self { text, page_starts }
+    }
+
+    /// Converts a line number within `text` to the page-based line number
+    pub(crate) fn page_line_number(&self, line_number: u64) -> u64 {
+        let page = self
+            .page_starts
+            .partition_point(|&start| start <= line_number);
+        match page {
+            0 => line_number,
+            page => page as u64 * PDF_PAGE_LINE_BASE + line_number - self.page_starts[page - 1] + 1,
+        }
+    }
+}
+
+/// Extracts the text of each page of a PDF file
+#[cfg(feature = "pdf")]
+fn extract_pages(path: &Path) -> Option<Result<Vec<String>>> {
+    use anyhow::Context;
+
+    Some(
+        pdf_extract::extract_text_by_pages(path)
+            .with_context(|| format!("Failed to extract the text of {}", path.display())),
+    )
+}
+
+/// Extracting the text of PDF files requires the `pdf` feature
+#[cfg(not(feature = "pdf"))]
+fn extract_pages(_path: &Path) -> Option<Result<Vec<String>>> {
+    None
+}
@@ -0,0 +1,194 @@
+//! Aggregate counts of a search, like `grep --count`.
+//!
+//! A summary is computed from the same search as `search_files`, but its matching lines are
+//! only counted while searching, without being built, so it stays cheap even when there are
+//! many matches.
+
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::{ContentMode, SearchOptions, SearchResult, SearchSort, search_files_counted};
+
+/// Counts describing the results of a search.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchSummary {
+    /// The number of files that were searched, after all file filters were applied
+    pub files_searched: usize,
+    /// The number of files with at least one matching line
+    pub files_with_matches: usize,
+    /// The number of matching lines, like `grep --count`
+    pub total_lines_matched: usize,
+    /// The number of non-overlapping matches, like `grep --count-matches`
+    pub total_occurrences: usize,
+    /// Whether the search was stopped by `SearchOptions::timeout`, meaning that the counts
+    /// are partial
+    #[serde(default)]
+    pub timed_out: bool,
+    /// Whether the search was cancelled through `SearchOptions::cancel`, meaning that the
+    /// counts may be partial
+    #[serde(default)]
+    pub cancelled: bool,
+}
+
+/// Searches for a pattern and returns only the counts of the results.
+///
+/// This performs a single search with the given options, counting the matched lines without
+/// building them. Context lines are never counted, and all matching lines are counted
+/// regardless of `skip` and `take`. Other options (such as `include_glob`,
+/// `line_from`/`line_to` or `min_matches_per_line`) restrict the counted lines as in
+/// `search_files`.
+///
+/// # Arguments
+///
+/// * `pattern` - The regular expression pattern to search for. Supports the same regex syntax
+///   as `search_files`.
+/// * `directory` - The directory path to search in
+/// * `options` - Configuration options for the search operation, identical to those used by
+///   `search_files`
+///
+/// # Returns
+///
+/// The number of searched files, files with matches, matching lines and occurrences, with
+/// flags telling whether the search timed out or was cancelled before it finished
+///
+/// # Errors
+///
+/// Returns the same errors as `search_files`
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::search::{SearchOptions, summary};
+/// use std::path::Path;
+///
+/// let summary = summary("TODO", Path::new("src"), &SearchOptions::default()).unwrap();
+/// println!(
+///     "{} occurrences on {} lines in {} of {} files",
+///     summary.total_occurrences,
+///     summary.total_lines_matched,
+///     summary.files_with_matches,
+///     summary.files_searched
+/// );
+/// ```
+pub fn summary(pattern: &str, directory: &Path, options: &SearchOptions) -> Result<SearchSummary> {
+    // Without lines to build, every matching line is counted while searching. Paths and sorting
+    // don't affect the counts, and the counting options have no context lines.
+    let search_options = SearchOptions {
+        take: Some(0),
+        ..unmodified_path_options(options)
+    };
+    let (result, counts) = search_files_counted(pattern, directory, &search_options)?;
+
+    Ok(SearchSummary {
+        files_searched: counts.files_searched,
+        files_with_matches: counts.files_with_matches,
+        total_lines_matched: counts.uncollected_lines,
+        total_occurrences: counts.uncollected_occurrences,
+        timed_out: result.timed_out,
+        cancelled: result.cancelled,
+    })
+}
+
+/// Searches for a pattern and returns a histogram of the number of matches per line.
+///
+/// The histogram maps a number of matches to the number of lines with exactly that many
+/// (non-overlapping) matches, across all searched files. Only matching lines are counted, so
+/// lines without matches don't appear in the histogram. As with `summary`, context lines are
+/// never counted and `skip` and `take` are ignored.
+///
+/// # Arguments
+///
+/// * `pattern` - The regular expression pattern to search for. Supports the same regex syntax
+///   as `search_files`.
+/// * `directory` - The directory path to search in
+/// * `options` - Configuration options for the search operation, identical to those used by
+///   `search_files`
+///
+/// # Returns
+///
+/// A map from a number of matches on a line to the number of lines with that many matches
+///
+/// # Errors
+///
+/// Returns the same errors as `search_files`, or an error if the search times out or is
+/// cancelled, as the histogram would be partial
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::search::{SearchOptions, per_line_match_histogram};
+/// use std::path::Path;
+///
+/// let histogram =
+///     per_line_match_histogram("unwrap\\(", Path::new("src"), &SearchOptions::default()).unwrap();
+/// for (matches, lines) in histogram {
+///     println!("{} lines with {} matches", lines, matches);
+/// }
+/// ```
+pub fn per_line_match_histogram(
+    pattern: &str,
+    directory: &Path,
+    options: &SearchOptions,
+) -> Result<BTreeMap<usize, usize>> {
+    let (result, _) = search_files_counted(pattern, directory, &counting_options(options))?;
+    ensure_complete(&result)?;
+
+    let mut histogram = BTreeMap::new();
+    for line in result.lines.iter().filter(|line| !line.is_context) {
+        *histogram.entry(line.match_ranges.len()).or_default() += 1;
+    }
+    Ok(histogram)
+}
+
+/// Returns an error if the search timed out or was cancelled before it finished.
+///
+/// Used by functions returning aggregates of a search, which have no way to flag partial
+/// results.
+pub(crate) fn ensure_complete(result: &SearchResult) -> Result<()> {
+    if result.timed_out {
+        return Err(anyhow!(
+            "The search timed out before all files were searched"
+        ));
+    }
+    if result.cancelled {
+        return Err(anyhow!("The search was cancelled"));
+    }
+    Ok(())
+}
+
+/// Returns options that count over all lines, without building line contents.
+///
+/// Without contents, matching lines can't be told apart, so `unique_content` is disabled.
+pub(crate) fn counting_options(options: &SearchOptions) -> SearchOptions {
+    SearchOptions {
+        skip: None,
+        take: None,
+        before_context: 0,
+        after_context: 0,
+        content: ContentMode::None,
+        enclosing_symbol: false,
+        with_blame: false,
+        snippet_width: None,
+        unique_content: false,
+        ..options.clone()
+    }
+}
+
+/// Returns counting options that report unmodified, unsorted file paths.
+///
+/// The paths of the results can be compared with the searched directory and the collected
+/// files, and callers apply the path display options to their own output.
+pub(crate) fn unmodified_path_options(options: &SearchOptions) -> SearchOptions {
+    SearchOptions {
+        omit_path_prefix: None,
+        auto_omit_common_prefix: false,
+        auto_repo_relative: false,
+        tilde_home: false,
+        clean_paths: false,
+        sort: SearchSort::None,
+        sort_by_relevance: false,
+        ..counting_options(options)
+    }
+}
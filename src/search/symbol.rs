@@ -0,0 +1,126 @@
+//! Detection of the function or type enclosing a search match.
+//!
+//! This module provides a lightweight, regex-based way to find the nearest definition
+//! (function, class, struct, ...) above a given line in a source file. It intentionally
+//! does not parse the source: it scans upward from the matched line and reports the first
+//! line that looks like a definition at the same or a lower indentation level.
+//!
+//! Supported languages are detected by file extension:
+//! - Rust (`.rs`): `fn`, `struct`, `enum`, `trait`, `mod`, `impl`
+//! - JavaScript/TypeScript (`.js`, `.jsx`, `.mjs`, `.cjs`, `.ts`, `.tsx`): `function`,
+//!   `class`, and functions assigned to `const`/`let`/`var`
+//! - Python (`.py`): `def`, `class`
+
+use regex::Regex;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Source languages for which enclosing symbols can be detected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SymbolLanguage {
+    Rust,
+    JavaScript,
+    Python,
+}
+
+impl SymbolLanguage {
+    /// Detects the language of a file from its extension.
+    ///
+    /// Returns `None` for files that are not recognized as supported source files.
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "rs" => Some(SymbolLanguage::Rust),
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => Some(SymbolLanguage::JavaScript),
+            "py" => Some(SymbolLanguage::Python),
+            _ => None,
+        }
+    }
+
+    /// Returns the definition patterns of this language.
+    ///
+    /// Each pattern captures the name of the defined symbol in its first group.
+    fn patterns(&self) -> &'static [Regex] {
+        static RUST: OnceLock<Vec<Regex>> = OnceLock::new();
+        static JAVASCRIPT: OnceLock<Vec<Regex>> = OnceLock::new();
+        static PYTHON: OnceLock<Vec<Regex>> = OnceLock::new();
+
+        let (cell, sources): (&OnceLock<Vec<Regex>>, &[&str]) = match self {
+            SymbolLanguage::Rust => (
+                &RUST,
+                &[
+                    r#"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:(?:const|async|unsafe|extern(?:\s+"[^"]*")?)\s+)*fn\s+([A-Za-z_][A-Za-z0-9_]*)"#,
+                    r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:unsafe\s+)?(?:struct|enum|trait|mod)\s+([A-Za-z_][A-Za-z0-9_]*)",
+                    r"^\s*(?:unsafe\s+)?impl(?:<[^{]*?>)?\s+(?:[A-Za-z_][\w:<>, ]*\s+for\s+)?([A-Za-z_][A-Za-z0-9_]*)",
+                ],
+            ),
+            SymbolLanguage::JavaScript => (
+                &JAVASCRIPT,
+                &[
+                    r"^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?function\s*\*?\s*([A-Za-z_$][\w$]*)",
+                    r"^\s*(?:export\s+)?(?:default\s+)?(?:abstract\s+)?class\s+([A-Za-z_$][\w$]*)",
+                    r"^\s*(?:export\s+)?(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*=\s*(?:async\s+)?(?:function\b|\([^)]*\)\s*=>|[A-Za-z_$][\w$]*\s*=>)",
+                ],
+            ),
+            SymbolLanguage::Python => (
+                &PYTHON,
+                &[
+                    r"^\s*(?:async\s+)?def\s+([A-Za-z_][A-Za-z0-9_]*)",
+                    r"^\s*class\s+([A-Za-z_][A-Za-z0-9_]*)",
+                ],
+            ),
+        };
+
+        cell.get_or_init(|| {
+            sources
+                .iter()
+                .map(|source| Regex::new(source).expect("valid symbol pattern"))
+                .collect()
+        })
+    }
+
+    /// Returns the symbol name if the given line is a definition in this language
+    fn definition_name(&self, line: &str) -> Option<String> {
+        self.patterns()
+            .iter()
+            .find_map(|pattern| pattern.captures(line))
+            .and_then(|captures| captures.get(1))
+            .map(|name| name.as_str().to_string())
+    }
+}
+
+/// Finds the name of the nearest definition enclosing the given line.
+///
+/// Starting from the line itself, lines are scanned upward until a definition is found
+/// whose indentation is not deeper than that of the matched line.
+///
+/// # Arguments
+///
+/// * `lines` - All lines of the file
+/// * `line_number` - The 1-based line number of the match
+/// * `language` - The language used to recognize definitions
+///
+/// # Returns
+///
+/// The name of the enclosing symbol, or `None` if no definition was found above the line.
+pub(crate) fn find_enclosing_symbol(
+    lines: &[&str],
+    line_number: u64,
+    language: SymbolLanguage,
+) -> Option<String> {
+    let index = (line_number as usize).checked_sub(1)?;
+    let matched_line = lines.get(index)?;
+    let max_indent = indentation(matched_line);
+
+    lines[..=index].iter().rev().find_map(|line| {
+        if line.trim().is_empty() || indentation(line) > max_indent {
+            return None;
+        }
+        language.definition_name(line)
+    })
+}
+
+/// Returns the width of the leading whitespace of a line
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
@@ -0,0 +1,53 @@
+//! Restriction of searches to files tracked by git.
+//!
+//! Build artifacts and scratch files are often untracked without being gitignored. The git
+//! index lists exactly the tracked files, so it is read once per search and used to filter
+//! the files found by the directory walk. Reading the index requires the `git` cargo feature.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// The files tracked by the git repository enclosing a search directory.
+#[cfg_attr(not(feature = "git"), allow(dead_code))]
+pub(crate) struct TrackedFiles {
+    /// Tracked file paths, relative to the search directory
+    paths: HashSet<PathBuf>,
+}
+
+impl TrackedFiles {
+    /// Reads the tracked files under `directory` from the index of its enclosing repository.
+    ///
+    /// Returns `None` if `directory` is not inside the working tree of a git repository, or
+    /// the index can't be read.
+    #[cfg(feature = "git")]
+    pub(crate) fn discover(directory: &Path) -> Option<Self> {
+        let repository = git2::Repository::discover(directory).ok()?;
+        let workdir = repository.workdir()?.canonicalize().ok()?;
+        let canonical_directory = directory.canonicalize().ok()?;
+        let index = repository.index().ok()?;
+
+        let paths = index
+            .iter()
+            .filter_map(|entry| {
+                // Index paths are always relative to the working tree root, with `/` separators
+                let path = workdir.join(std::str::from_utf8(&entry.path).ok()?);
+                path.strip_prefix(&canonical_directory)
+                    .ok()
+                    .map(Path::to_path_buf)
+            })
+            .collect();
+
+        Some(Self { paths })
+    }
+
+    /// Reading the git index requires the `git` feature
+    #[cfg(not(feature = "git"))]
+    pub(crate) fn discover(_directory: &Path) -> Option<Self> {
+        None
+    }
+
+    /// Returns `true` if the file at `rel_path`, relative to the search directory, is tracked.
+    pub(crate) fn contains(&self, rel_path: &Path) -> bool {
+        self.paths.contains(rel_path)
+    }
+}
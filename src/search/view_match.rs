@@ -0,0 +1,59 @@
+//! Opening search matches with surrounding lines.
+//!
+//! This module ties the search and view modules together for the common flow of searching
+//! for a pattern and then looking at one of the matches in context.
+
+use anyhow::Result;
+
+use super::SearchResultLine;
+use crate::view::{FileView, ViewOptions, view_file};
+
+/// Views the lines surrounding a search match.
+///
+/// Calls `view_file` on the file of the result line, with `line_from`/`line_to` set to a
+/// window of `context` lines before and after the matched line. The window is clipped to the
+/// start and end of the file. All other view options use their defaults.
+///
+/// Note that the file is opened using `result_line.file_path`, so the search must not have
+/// used `omit_path_prefix` unless the remaining path is still valid from the current directory.
+///
+/// # Arguments
+///
+/// * `result_line` - A line returned by `search_files`
+/// * `context` - Number of lines to include before and after the matched line
+///
+/// # Returns
+///
+/// A `FileView` containing the matched line and its surrounding lines
+///
+/// # Errors
+///
+/// Returns the same errors as `view_file`, e.g. if the file no longer exists
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::search::{SearchOptions, search_files, view_match};
+/// use lumin::view::FileContents;
+/// use std::path::Path;
+///
+/// let results = search_files("TODO", Path::new("src"), &SearchOptions::default()).unwrap();
+/// if let Some(first) = results.lines.first() {
+///     let view = view_match(first, 3).unwrap();
+///     if let FileContents::Text { content, .. } = view.contents {
+///         for line in content.line_contents {
+///             println!("{:>5}: {}", line.line_number, line.line);
+///         }
+///     }
+/// }
+/// ```
+pub fn view_match(result_line: &SearchResultLine, context: usize) -> Result<FileView> {
+    let line_number = result_line.line_number as usize;
+    let options = ViewOptions {
+        line_from: Some(line_number.saturating_sub(context).max(1)),
+        line_to: Some(line_number.saturating_add(context)),
+        ..ViewOptions::default()
+    };
+
+    view_file(&result_line.file_path, &options)
+}
@@ -0,0 +1,134 @@
+//! Grouping of search results into per-match context windows.
+//!
+//! `search_files` returns a flat list in which each context line appears once, even when it
+//! is near several matches. Renderers that show every match in its own block need the context
+//! lines of each match instead, which this module reconstructs.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::{SearchResult, SearchResultLine};
+
+/// A matching line together with its surrounding context lines.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MatchWindow {
+    /// Path to the file containing the match
+    #[serde(with = "crate::paths::serde_os_str")]
+    pub file_path: PathBuf,
+    /// The context lines directly before the match, in line order
+    pub before: Vec<SearchResultLine>,
+    /// The matching line
+    pub match_line: SearchResultLine,
+    /// The context lines directly after the match, in line order
+    pub after: Vec<SearchResultLine>,
+}
+
+impl SearchResult {
+    /// Groups the result lines into one window per matching line.
+    ///
+    /// Each window holds a matching line with the context lines adjacent to it, as determined
+    /// by the file path and line numbers. The lines must be grouped by file in line order, as
+    /// returned by `search_files` (also after `sort_by_path_and_line` or `sort_by_relevance`).
+    ///
+    /// The context lines between two nearby matches appear once in the flat list. They are
+    /// split between the windows using the longest context runs seen before a first match
+    /// and after a last match as the `before_context` / `after_context` sizes, so a line that
+    /// was within reach of both matches is included in both windows, and every context line
+    /// is included in at least one window.
+    ///
+    /// # Returns
+    ///
+    /// The windows in the order of their matching lines
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lumin::search::{SearchOptions, search_files};
+    /// use std::path::Path;
+    ///
+    /// let options = SearchOptions {
+    ///     before_context: 2,
+    ///     after_context: 2,
+    ///     ..SearchOptions::default()
+    /// };
+    /// let results = search_files("TODO", Path::new("src"), &options).unwrap();
+    ///
+    /// for window in results.windows() {
+    ///     println!("--- {}", window.file_path.display());
+    ///     for line in window.before.iter().chain([&window.match_line]).chain(&window.after) {
+    ///         println!("{:>5}: {}", line.line_number, line.line_content);
+    ///     }
+    /// }
+    /// ```
+    pub fn windows(self) -> Vec<MatchWindow> {
+        let blocks = split_blocks(self.lines);
+
+        // Infer the context sizes from the runs that are not shared with another match
+        let before_size = blocks
+            .iter()
+            .filter_map(|block| block.iter().position(|line| !line.is_context))
+            .max()
+            .unwrap_or(0);
+        let after_size = blocks
+            .iter()
+            .filter_map(|block| block.iter().rev().position(|line| !line.is_context))
+            .max()
+            .unwrap_or(0);
+
+        let mut windows = Vec::new();
+        for block in blocks {
+            let match_indices: Vec<usize> = block
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| !line.is_context)
+                .map(|(index, _)| index)
+                .collect();
+
+            for (i, &index) in match_indices.iter().enumerate() {
+                let previous_match = i.checked_sub(1).map(|i| match_indices[i]);
+                let next_match = match_indices.get(i + 1).copied();
+
+                // Context shared with the previous match is split like its "after" context
+                let before_start = match previous_match {
+                    None => 0,
+                    Some(previous) => {
+                        let shared = index - previous - 1;
+                        let previous_after = shared.min(after_size);
+                        index - shared.min(before_size).max(shared - previous_after)
+                    }
+                };
+                let after_end = match next_match {
+                    None => block.len(),
+                    Some(next) => index + 1 + (next - index - 1).min(after_size),
+                };
+
+                let match_line = block[index].clone();
+                windows.push(MatchWindow {
+                    file_path: match_line.file_path.clone(),
+                    before: block[before_start..index].to_vec(),
+                    match_line,
+                    after: block[index + 1..after_end].to_vec(),
+                });
+            }
+        }
+        windows
+    }
+}
+
+/// Splits result lines into blocks of consecutive lines from the same file.
+fn split_blocks(lines: Vec<SearchResultLine>) -> Vec<Vec<SearchResultLine>> {
+    let mut blocks: Vec<Vec<SearchResultLine>> = Vec::new();
+    for line in lines {
+        match blocks.last_mut() {
+            Some(block)
+                if block.last().is_some_and(|last| {
+                    last.file_path == line.file_path && last.line_number + 1 == line.line_number
+                }) =>
+            {
+                block.push(line)
+            }
+            _ => blocks.push(vec![line]),
+        }
+    }
+    blocks
+}
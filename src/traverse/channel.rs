@@ -0,0 +1,112 @@
+//! Streaming directory traversal through a bounded channel.
+//!
+//! This module runs a traversal on a background thread and streams the results through a
+//! bounded channel. This is useful when embedding the library in async runtimes or other
+//! contexts where the blocking `traverse_directory` is undesirable: the consumer controls the
+//! pace of the walk (back-pressure), and can cancel it early by dropping the receiver.
+
+use anyhow::Result;
+use std::path::Path;
+use std::sync::mpsc::{Receiver, sync_channel};
+use std::thread::{self, JoinHandle};
+
+use super::{TraverseOptions, TraverseResult, walk_directory};
+
+/// The default number of results buffered by [`traverse_channel`].
+pub const DEFAULT_CHANNEL_CAPACITY: usize = 64;
+
+/// Traverses a directory on a background thread, streaming results through a bounded channel.
+///
+/// This is equivalent to [`traverse_channel_with_capacity`] with a capacity of
+/// [`DEFAULT_CHANNEL_CAPACITY`].
+///
+/// # Arguments
+///
+/// * `directory` - The directory path to traverse
+/// * `options` - Configuration options for the traversal
+///
+/// # Returns
+///
+/// A receiver yielding the traversal results, and a handle to the background thread that
+/// returns the number of results delivered to the channel.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::traverse::{TraverseOptions, traverse_channel};
+/// use std::path::Path;
+///
+/// let (receiver, handle) = traverse_channel(Path::new("src"), &TraverseOptions::default());
+///
+/// // Only look at the first 10 files; dropping the receiver stops the walk
+/// for result in receiver.into_iter().take(10) {
+///     match result {
+///         Ok(file) => println!("{}", file.file_path.display()),
+///         Err(e) => eprintln!("Traversal error: {}", e),
+///     }
+/// }
+///
+/// let delivered = handle.join().unwrap();
+/// println!("{} results were delivered", delivered);
+/// ```
+pub fn traverse_channel(
+    directory: &Path,
+    options: &TraverseOptions,
+) -> (Receiver<Result<TraverseResult>>, JoinHandle<usize>) {
+    traverse_channel_with_capacity(directory, options, DEFAULT_CHANNEL_CAPACITY)
+}
+
+/// Traverses a directory on a background thread, streaming results through a channel
+/// with the given capacity.
+///
+/// The walk applies the same filtering as `traverse_directory`, but results are sent in the
+/// order they are found by the walker rather than sorted by path.
+///
+/// When the channel is full, the walk blocks until the consumer receives a result, so at most
+/// `capacity` results are buffered. When the receiver is dropped, the walk stops at the next
+/// result it tries to send.
+///
+/// If the traversal cannot be started (e.g., because of an invalid glob pattern), a single
+/// `Err` is sent through the channel. Errors for individual entries are logged and skipped,
+/// as in `traverse_directory`.
+///
+/// # Arguments
+///
+/// * `directory` - The directory path to traverse
+/// * `options` - Configuration options for the traversal
+/// * `capacity` - The maximum number of results buffered in the channel. A capacity of 0
+///   makes every send wait until the consumer receives the result.
+///
+/// # Returns
+///
+/// A receiver yielding the traversal results, and a handle to the background thread that
+/// returns the number of results delivered to the channel.
+pub fn traverse_channel_with_capacity(
+    directory: &Path,
+    options: &TraverseOptions,
+    capacity: usize,
+) -> (Receiver<Result<TraverseResult>>, JoinHandle<usize>) {
+    let (sender, receiver) = sync_channel(capacity);
+    let directory = directory.to_path_buf();
+    let options = options.clone();
+
+    let handle = thread::spawn(move || {
+        let mut delivered = 0;
+        let walk_result = walk_directory(&directory, &options, |result| {
+            // Sending fails only when the receiver has been dropped
+            if sender.send(Ok(result)).is_ok() {
+                delivered += 1;
+                true
+            } else {
+                false
+            }
+        });
+
+        if let Err(e) = walk_result {
+            let _ = sender.send(Err(e));
+        }
+        delivered
+    });
+
+    (receiver, handle)
+}
@@ -3,9 +3,13 @@
 //! This module provides shared functionality for directory traversal operations.
 
 use anyhow::{Context, Result};
-use globset;
-use ignore::WalkBuilder;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::{DirEntry, WalkBuilder};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 use crate::telemetry::{LogMessage, log_with_context};
 
@@ -13,6 +17,8 @@ use crate::telemetry::{LogMessage, log_with_context};
 ///
 /// This function is useful for filtering files based on glob patterns.
 /// It supports standard glob syntax including wildcards, character classes, and brace expansion.
+/// Patterns with a leading `!` are negations, which subtract from the matched paths
+/// (see `build_glob_filter` for the precedence rules).
 ///
 /// ## Path Type Expectations
 ///
@@ -29,7 +35,8 @@ use crate::telemetry::{LogMessage, log_with_context};
 ///
 /// # Returns
 ///
-/// `true` if the path matches any of the provided patterns, `false` otherwise
+/// `true` if the path matches any of the provided patterns and none of the negated ones,
+/// `false` otherwise
 ///
 /// # Errors
 ///
@@ -56,22 +63,509 @@ pub fn path_matches_any_glob(
         return Ok(false);
     }
 
-    let mut builder = globset::GlobSetBuilder::new();
+    let glob_filter = build_glob_filter(glob_patterns, case_sensitive)?;
+    Ok(glob_filter.is_match(path))
+}
+
+/// Compiles glob patterns into a reusable `GlobSet`.
+///
+/// The patterns are compiled with the same semantics the crate uses for `include_glob`,
+/// `exclude_glob` and traversal patterns, so the set can be precompiled once and used to filter
+/// many paths consistently with the crate's own filtering. As with those options, match the
+/// set against paths relative to the directory being searched or traversed.
+///
+/// An empty slice of patterns produces a set that matches nothing.
+///
+/// A `GlobSet` can't express negations, so a leading `!` is matched literally here. Use
+/// `build_glob_filter` to compile lists that may contain negated patterns, as `include_glob`
+/// and `exclude_glob` can.
+///
+/// # Arguments
+///
+/// * `glob_patterns` - A slice of glob patterns to compile
+/// * `case_sensitive` - Whether the glob matching should be case sensitive
+///
+/// # Returns
+///
+/// A `GlobSet` matching a path if any of the patterns matches it
+///
+/// # Errors
+///
+/// Returns an error if any of the patterns is not a valid glob
+///
+/// # Examples
+///
+/// ```
+/// use lumin::traverse::common::build_glob_set;
+/// use std::path::Path;
+///
+/// let patterns = vec!["**/*.rs".to_string(), "docs/**".to_string()];
+/// let glob_set = build_glob_set(&patterns, false).unwrap();
+///
+/// assert!(glob_set.is_match(Path::new("src/main.rs")));
+/// assert!(glob_set.is_match(Path::new("docs/guide/intro.md")));
+/// assert!(!glob_set.is_match(Path::new("README.md")));
+/// ```
+pub fn build_glob_set(glob_patterns: &[String], case_sensitive: bool) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
     for pattern in glob_patterns {
-        let glob = if case_sensitive {
-            globset::GlobBuilder::new(pattern).build()
+        let glob = GlobBuilder::new(pattern)
+            .case_insensitive(!case_sensitive)
+            .build()
+            .with_context(|| format!("Failed to compile glob pattern: {}", pattern))?;
+
+        builder.add(glob);
+    }
+
+    builder.build().context("Failed to build glob set")
+}
+
+/// A compiled list of glob patterns supporting negation.
+///
+/// Created by `build_glob_filter`. A path matches the filter if it matches at least one of
+/// the positive patterns and none of the negated patterns.
+#[derive(Debug, Clone)]
+pub struct GlobFilter {
+    /// The patterns without a leading `!`, or `None` if all patterns are negated
+    positive: Option<GlobSet>,
+    /// The patterns with a leading `!`, without the `!`
+    negated: GlobSet,
+}
+
+impl GlobFilter {
+    /// Returns whether the path matches the filter.
+    pub fn is_match<P: AsRef<Path>>(&self, path: P) -> bool {
+        let path = path.as_ref();
+        let positive_match = match &self.positive {
+            Some(positive) => positive.is_match(path),
+            None => true,
+        };
+        positive_match && !self.negated.is_match(path)
+    }
+}
+
+/// Compiles a list of glob patterns, where patterns with a leading `!` are negations.
+///
+/// This is how the crate evaluates `include_glob` and `exclude_glob` lists. The list is
+/// partitioned into positive and negated patterns, and a path matches if it matches any
+/// positive pattern minus the paths matching any negated pattern:
+///
+/// - Negations always take precedence over positive patterns, regardless of their order in the
+///   list. For example, `["**/*.rs", "!**/generated/**"]` matches all Rust files except those
+///   in `generated` directories.
+/// - A list containing only negations matches every path that isn't negated, so
+///   `["!**/*.md"]` matches everything except Markdown files.
+/// - An empty list matches nothing.
+///
+/// To match a leading `!` literally, wrap it in an alternation as `{!}`, as `escape_glob` does.
+///
+/// # Arguments
+///
+/// * `glob_patterns` - A slice of glob patterns to compile
+/// * `case_sensitive` - Whether the glob matching should be case sensitive
+///
+/// # Returns
+///
+/// A `GlobFilter` evaluating the patterns
+///
+/// # Errors
+///
+/// Returns an error if any of the patterns is not a valid glob
+///
+/// # Examples
+///
+/// ```
+/// use lumin::traverse::common::build_glob_filter;
+/// use std::path::Path;
+///
+/// let patterns = vec!["**/*.rs".to_string(), "!**/generated/**".to_string()];
+/// let glob_filter = build_glob_filter(&patterns, false).unwrap();
+///
+/// assert!(glob_filter.is_match(Path::new("src/main.rs")));
+/// assert!(!glob_filter.is_match(Path::new("src/generated/bindings.rs")));
+/// assert!(!glob_filter.is_match(Path::new("README.md")));
+///
+/// // A leading `!` wrapped in an alternation is matched literally
+/// let glob_filter = build_glob_filter(&["{!}important.txt".to_string()], false).unwrap();
+/// assert!(glob_filter.is_match(Path::new("!important.txt")));
+/// assert!(!glob_filter.is_match(Path::new("important.txt")));
+/// ```
+pub fn build_glob_filter(glob_patterns: &[String], case_sensitive: bool) -> Result<GlobFilter> {
+    let (negated, positive): (Vec<String>, Vec<String>) = glob_patterns
+        .iter()
+        .cloned()
+        .partition(|pattern| pattern.starts_with('!'));
+    let negated: Vec<String> = negated
+        .into_iter()
+        .map(|pattern| pattern[1..].to_string())
+        .collect();
+
+    let positive = if positive.is_empty() && !negated.is_empty() {
+        None
+    } else {
+        Some(build_glob_set(&positive, case_sensitive)?)
+    };
+
+    Ok(GlobFilter {
+        positive,
+        negated: build_glob_set(&negated, case_sensitive)?,
+    })
+}
+
+/// A combined include and exclude filter for the files of a directory walk.
+///
+/// This is how search, traverse and tree evaluate their `include_glob` and `exclude_glob`
+/// options, so the three agree on which files are kept. Both lists are compiled with
+/// `build_glob_filter` (so they may contain negated patterns) and matched against paths
+/// relative to the walked directory:
+///
+/// - A file is kept if it matches the include list and doesn't match the exclude list.
+/// - Exclusion takes precedence, so a file matched by both lists is left out.
+/// - Without an include list, every file that isn't excluded is kept. An empty include list
+///   keeps nothing, and an empty exclude list excludes nothing.
+#[derive(Debug, Clone)]
+pub struct FileFilter {
+    /// The directory the filtered paths are relative to
+    root: PathBuf,
+    /// The compiled include patterns, or `None` to include every file
+    include: Option<GlobFilter>,
+    /// The compiled exclude patterns, or `None` if nothing is excluded
+    exclude: Option<GlobFilter>,
+}
+
+impl FileFilter {
+    /// Compiles a filter for the files below `root`.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The directory being walked, which paths are matched relative to
+    /// * `include_glob` - Optional list of glob patterns a file must match to be kept
+    /// * `exclude_glob` - Optional list of glob patterns of files to leave out
+    /// * `case_sensitive` - Whether the glob matching should be case sensitive
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the patterns is not a valid glob
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lumin::traverse::common::FileFilter;
+    /// use std::path::Path;
+    ///
+    /// let include = vec!["**/*.rs".to_string()];
+    /// let exclude = vec!["**/generated/**".to_string()];
+    /// let filter = FileFilter::new(Path::new("/repo"), Some(&include), Some(&exclude), false)
+    ///     .unwrap();
+    ///
+    /// assert!(filter.matches(Path::new("/repo/src/main.rs")).unwrap());
+    /// assert!(!filter.matches(Path::new("/repo/src/generated/bindings.rs")).unwrap());
+    /// assert!(!filter.matches(Path::new("README.md")).unwrap());
+    /// ```
+    pub fn new(
+        root: &Path,
+        include_glob: Option<&Vec<String>>,
+        exclude_glob: Option<&Vec<String>>,
+        case_sensitive: bool,
+    ) -> Result<Self> {
+        let include = include_glob
+            .map(|patterns| build_glob_filter(patterns, case_sensitive))
+            .transpose()?;
+        let exclude = exclude_glob
+            .filter(|patterns| !patterns.is_empty())
+            .map(|patterns| build_glob_filter(patterns, case_sensitive))
+            .transpose()?;
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            include,
+            exclude,
+        })
+    }
+
+    /// Returns whether the filter keeps a file.
+    ///
+    /// Absolute paths must be below the root directory of the filter, and relative paths are
+    /// taken as relative to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` is absolute and not below the root directory
+    pub fn matches(&self, path: &Path) -> Result<bool> {
+        let rel_path = if path.is_absolute() && self.root.is_absolute() {
+            path.strip_prefix(&self.root).with_context(|| {
+                format!(
+                    "Path {} is not below {}",
+                    path.display(),
+                    self.root.display()
+                )
+            })?
         } else {
-            globset::GlobBuilder::new(pattern)
-                .case_insensitive(true)
-                .build()
+            path.strip_prefix(&self.root).unwrap_or(path)
+        };
+
+        if self
+            .exclude
+            .as_ref()
+            .is_some_and(|exclude| exclude.is_match(rel_path))
+        {
+            return Ok(false);
         }
-        .with_context(|| format!("Failed to compile glob pattern: {}", pattern))?;
+        Ok(self
+            .include
+            .as_ref()
+            .is_none_or(|include| include.is_match(rel_path)))
+    }
+}
 
-        builder.add(glob);
+/// Escapes glob metacharacters so that a string is matched literally.
+///
+/// Each of `*`, `?`, `[`, `]`, `{` and `}` is wrapped in a character class (e.g., `[*]`), which
+/// works regardless of whether the platform supports backslash escapes in globs. A leading `!`
+/// is wrapped in an alternation (`{!}`), so the result isn't read as a negated pattern by
+/// `build_glob_filter`.
+///
+/// This is useful when building glob patterns from user-supplied file names.
+///
+/// # Examples
+///
+/// ```
+/// use lumin::traverse::common::{build_glob_set, escape_glob};
+/// use std::path::Path;
+///
+/// let pattern = format!("**/{}", escape_glob("report[1].txt"));
+/// let glob_set = build_glob_set(&[pattern], false).unwrap();
+///
+/// assert!(glob_set.is_match(Path::new("docs/report[1].txt")));
+/// assert!(!glob_set.is_match(Path::new("docs/report1.txt")));
+/// ```
+pub fn escape_glob(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '*' | '?' | '[' | ']' | '{' | '}' => {
+                escaped.push('[');
+                escaped.push(c);
+                escaped.push(']');
+            }
+            '!' if i == 0 => escaped.push_str("{!}"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Classifies a file as text or binary from configured extension lists.
+///
+/// Extensions are compared case insensitively, and may be given with or without a leading
+/// dot. When an extension appears in both lists, `text_extensions` takes precedence.
+///
+/// # Arguments
+///
+/// * `path` - The file to classify
+/// * `text_extensions` - Extensions of files that are always treated as text
+/// * `binary_extensions` - Extensions of files that are always treated as binary
+///
+/// # Returns
+///
+/// `Some(true)` for text, `Some(false)` for binary, or `None` if the extension of the file is
+/// not listed and its content should be inspected instead.
+///
+/// # Examples
+///
+/// ```
+/// use lumin::traverse::common::extension_override;
+/// use std::path::Path;
+///
+/// let text = vec!["pdf".to_string()];
+/// let binary = vec![".DAT".to_string()];
+///
+/// assert_eq!(extension_override(Path::new("doc.PDF"), &text, &binary), Some(true));
+/// assert_eq!(extension_override(Path::new("table.dat"), &text, &binary), Some(false));
+/// assert_eq!(extension_override(Path::new("notes.txt"), &text, &binary), None);
+/// ```
+pub fn extension_override(
+    path: &Path,
+    text_extensions: &[String],
+    binary_extensions: &[String],
+) -> Option<bool> {
+    let extension = path.extension()?.to_str()?;
+    let listed = |extensions: &[String]| {
+        extensions.iter().any(|listed| {
+            listed
+                .strip_prefix('.')
+                .unwrap_or(listed)
+                .eq_ignore_ascii_case(extension)
+        })
+    };
+
+    if listed(text_extensions) {
+        Some(true)
+    } else if listed(binary_extensions) {
+        Some(false)
+    } else {
+        None
     }
+}
 
-    let glob_set = builder.build().context("Failed to build glob set")?;
-    Ok(glob_set.is_match(path))
+/// Folds the case of a string for case-insensitive comparison.
+///
+/// Characters are lowercased with their full Unicode mappings, and the special case foldings
+/// that lowercasing doesn't cover are applied: `ß` and `ẞ` fold to `ss`, the final sigma `ς`
+/// to `σ`, and the long s `ſ` to `s`. Two strings that differ only in case fold to the same
+/// string, so folded strings can be compared or searched for substrings directly.
+///
+/// # Examples
+///
+/// ```
+/// use lumin::traverse::common::fold_case;
+///
+/// assert_eq!(fold_case("Straße"), fold_case("STRASSE"));
+/// assert_eq!(fold_case("İstanbul"), "i\u{307}stanbul");
+/// assert_eq!(fold_case("ΟΔΟΣ"), fold_case("οδος"));
+/// ```
+pub fn fold_case(s: &str) -> String {
+    let mut folded = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            'ß' | 'ẞ' => folded.push_str("ss"),
+            'ς' => folded.push('σ'),
+            'ſ' => folded.push('s'),
+            _ => folded.extend(c.to_lowercase()),
+        }
+    }
+    folded
+}
+
+/// Scores how well a pattern fuzzily matches a candidate string, like a fuzzy file finder.
+///
+/// The candidate matches when it contains all the characters of the pattern in order, not
+/// necessarily next to each other (a subsequence match). Characters are matched at their
+/// first possible position. Each matched character scores points, with bonuses for runs of
+/// consecutive characters and for characters at the start of a word (after `/`, `_`, `-`,
+/// `.` or a space), and a penalty for each character skipped between two matches. Higher
+/// scores are better matches.
+///
+/// The comparison is exact; fold both strings with `fold_case` for a case-insensitive match.
+///
+/// # Returns
+///
+/// The score, or `None` if the candidate doesn't contain the pattern characters in order
+///
+/// # Examples
+///
+/// ```
+/// use lumin::traverse::common::fuzzy_score;
+///
+/// let main = fuzzy_score("srcmn", "src/main.rs").unwrap();
+/// let common = fuzzy_score("srcmn", "scripts/common.rs").unwrap();
+/// assert!(main > common);
+/// assert_eq!(fuzzy_score("srcmn", "README.md"), None);
+/// ```
+pub fn fuzzy_score(pattern: &str, candidate: &str) -> Option<i64> {
+    const MATCH: i64 = 16;
+    const CONSECUTIVE_BONUS: i64 = 15;
+    const BOUNDARY_BONUS: i64 = 10;
+
+    let mut pattern_chars = pattern.chars().peekable();
+    let mut score = 0;
+    let mut previous_match: Option<usize> = None;
+    let mut previous_char = None;
+
+    for (index, c) in candidate.chars().enumerate() {
+        let Some(&expected) = pattern_chars.peek() else {
+            break;
+        };
+        if c == expected {
+            pattern_chars.next();
+            score += MATCH;
+            match previous_match {
+                Some(previous) if previous + 1 == index => score += CONSECUTIVE_BONUS,
+                Some(previous) => score -= (index - previous - 1) as i64,
+                None => {}
+            }
+            if previous_match.is_none_or(|previous| previous + 1 != index)
+                && previous_char.is_none_or(|p| matches!(p, '/' | '_' | '-' | '.' | ' '))
+            {
+                score += BOUNDARY_BONUS;
+            }
+            previous_match = Some(index);
+        }
+        previous_char = Some(c);
+    }
+
+    pattern_chars.peek().is_none().then_some(score)
+}
+
+/// Names of directories that usually contain vendored dependencies or generated files.
+///
+/// These directories are pruned by walkers built with `skip_vendored` enabled.
+pub const VENDORED_DIR_NAMES: &[&str] =
+    &["node_modules", "vendor", "dist", "build", "target", ".venv"];
+
+/// Returns whether a directory name is one of the `VENDORED_DIR_NAMES`.
+pub fn is_vendored_dir_name(name: &str) -> bool {
+    VENDORED_DIR_NAMES.contains(&name)
+}
+
+/// The settings of a file system walker built by `build_walk`.
+///
+/// The default walks the whole tree, respecting gitignore rules with case-insensitive matching.
+///
+/// # Examples
+///
+/// ```
+/// use lumin::traverse::common::WalkConfig;
+///
+/// let config = WalkConfig {
+///     max_depth: Some(3),
+///     skip_vendored: true,
+///     ..WalkConfig::default()
+/// };
+/// assert!(config.respect_gitignore);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WalkConfig {
+    /// Whether to respect gitignore rules, also skipping hidden files
+    pub respect_gitignore: bool,
+    /// Whether file path matching should be case sensitive
+    pub case_sensitive: bool,
+    /// Optional maximum directory depth to traverse
+    pub max_depth: Option<usize>,
+    /// Whether to skip the `.gitignore` at the walked directory itself while still applying
+    /// `.gitignore` files in its subdirectories. Only has an effect when `respect_gitignore` is
+    /// `true`. In this mode, nested `.gitignore` files apply even outside of a git repository,
+    /// and `.gitignore` files in parent directories of the walked directory are not used.
+    pub ignore_root_gitignore: bool,
+    /// Whether to prune directories named like one of the `VENDORED_DIR_NAMES`. The walked
+    /// directory itself is never pruned, so a vendored directory can still be walked by passing
+    /// it as the directory.
+    pub skip_vendored: bool,
+    /// Whether to stay on the file system of the walked directory, without descending into
+    /// directories on other mounted file systems
+    pub one_file_system: bool,
+    /// Optional flag that stops the walk once set, pruning every entry that hasn't been
+    /// visited yet
+    pub cancel: Option<Arc<AtomicBool>>,
+    /// Optional set of directories whose contents are skipped. Directories can be added while
+    /// walking, since the entries of a directory are only read after the directory itself has
+    /// been yielded.
+    pub pruned_dirs: Option<Arc<Mutex<HashSet<PathBuf>>>>,
+}
+
+impl Default for WalkConfig {
+    fn default() -> Self {
+        Self {
+            respect_gitignore: true,
+            case_sensitive: false,
+            max_depth: None,
+            ignore_root_gitignore: false,
+            skip_vendored: false,
+            one_file_system: false,
+            cancel: None,
+            pruned_dirs: None,
+        }
+    }
 }
 
 /// Builds a configured file system walker based on the provided options.
@@ -79,9 +573,7 @@ pub fn path_matches_any_glob(
 /// # Arguments
 ///
 /// * `directory` - The directory path to traverse
-/// * `respect_gitignore` - Whether to respect gitignore rules
-/// * `case_sensitive` - Whether file path matching should be case sensitive
-/// * `max_depth` - Optional maximum directory depth to traverse
+/// * `config` - The settings of the walker
 ///
 /// # Returns
 ///
@@ -90,14 +582,23 @@ pub fn path_matches_any_glob(
 /// # Errors
 ///
 /// Returns an error if there's an issue setting up the walker
-pub fn build_walk(
-    directory: &Path,
-    respect_gitignore: bool,
-    case_sensitive: bool,
-    max_depth: Option<usize>,
-) -> Result<ignore::Walk> {
+pub fn build_walk(directory: &Path, config: &WalkConfig) -> Result<ignore::Walk> {
+    let WalkConfig {
+        respect_gitignore,
+        case_sensitive,
+        max_depth,
+        ignore_root_gitignore,
+        skip_vendored,
+        one_file_system,
+        ref cancel,
+        ref pruned_dirs,
+    } = *config;
+    let cancel = cancel.clone();
+    let pruned_dirs = pruned_dirs.clone();
+
     // Configure the file traversal
     let mut builder = WalkBuilder::new(directory);
+    builder.same_file_system(one_file_system);
     builder.git_ignore(respect_gitignore);
     // When respecting gitignore, hidden files are skipped; otherwise they're included
     builder.hidden(respect_gitignore);
@@ -114,10 +615,191 @@ pub fn build_walk(
         builder.git_exclude(false); // Don't use git exclude files
         builder.git_global(false); // Don't use global git ignore
     }
+    // Replace the built-in gitignore handling with one that skips the root's .gitignore
+    let gitignore_filter = if respect_gitignore && ignore_root_gitignore {
+        builder.git_ignore(false);
+        Some(nested_gitignore_filter(directory, case_sensitive))
+    } else {
+        None
+    };
+    // The walker supports a single entry filter, so all filters are combined into one
+    if gitignore_filter.is_some() || skip_vendored || cancel.is_some() || pruned_dirs.is_some() {
+        builder.filter_entry(move |entry| {
+            if cancel
+                .as_ref()
+                .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+            {
+                return false;
+            }
+            if let (Some(pruned_dirs), Some(parent)) = (&pruned_dirs, entry.path().parent()) {
+                let pruned_dirs = pruned_dirs.lock().unwrap_or_else(|e| e.into_inner());
+                if pruned_dirs.contains(parent) {
+                    return false;
+                }
+            }
+            let vendored = skip_vendored
+                && entry.depth() > 0
+                && entry.file_type().is_some_and(|ft| ft.is_dir())
+                && is_vendored_dir_name(&entry.file_name().to_string_lossy());
+            !vendored && gitignore_filter.as_ref().is_none_or(|filter| filter(entry))
+        });
+    }
 
     Ok(builder.build())
 }
 
+/// Creates a walker filter that applies `.gitignore` files below the root directory.
+///
+/// The `.gitignore` of the root directory itself is not applied. For each entry, the
+/// `.gitignore` files of its ancestor directories are consulted from the deepest one upward,
+/// and the first one with a matching rule decides whether the entry is ignored (mirroring git's
+/// precedence of more specific `.gitignore` files). Parsed `.gitignore` files are cached per
+/// directory.
+///
+/// # Arguments
+///
+/// * `root` - The root directory of the traversal
+/// * `case_sensitive` - Whether gitignore patterns should be matched case sensitively
+///
+/// # Returns
+///
+/// A predicate returning `false` for entries that should be skipped
+fn nested_gitignore_filter(
+    root: &Path,
+    case_sensitive: bool,
+) -> impl Fn(&DirEntry) -> bool + Send + Sync + 'static {
+    let root = root.to_path_buf();
+    let cache: Mutex<HashMap<PathBuf, Option<Arc<Gitignore>>>> = Mutex::new(HashMap::new());
+
+    move |entry| {
+        if entry.depth() == 0 {
+            return true;
+        }
+        let path = entry.path();
+        let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+
+        for dir in path.ancestors().skip(1) {
+            if dir == root || !dir.starts_with(&root) {
+                break;
+            }
+
+            let gitignore = {
+                let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+                cache
+                    .entry(dir.to_path_buf())
+                    .or_insert_with(|| load_ignore_file(dir, ".gitignore", case_sensitive))
+                    .clone()
+            };
+
+            if let Some(gitignore) = gitignore {
+                let matched = gitignore.matched(path, is_dir);
+                if matched.is_ignore() {
+                    return false;
+                }
+                if matched.is_whitelist() {
+                    return true;
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Finds the ignore rule that excludes a path from traversal, if any.
+///
+/// The `.ignore` and `.gitignore` files of the path's ancestor directories are consulted
+/// from the deepest one upward, following the precedence used by the walker: `.ignore` files
+/// take precedence over `.gitignore` files in the same directory, and more specific (deeper)
+/// files take precedence over shallower ones. `.gitignore` files are only used inside a git
+/// repository, and above `directory` only up to the repository root.
+///
+/// Global gitignore files and `.git/info/exclude` are not considered.
+///
+/// # Arguments
+///
+/// * `directory` - The root directory of the traversal
+/// * `path` - The path to check, which must be located inside `directory`
+/// * `case_sensitive` - Whether ignore patterns should be matched case sensitively
+/// * `ignore_root_gitignore` - Whether the `.gitignore` at `directory` itself (and above) is skipped
+///
+/// # Returns
+///
+/// A description of the matching rule and the file it comes from, or `None` if the path
+/// is not ignored
+pub(crate) fn find_ignore_rule(
+    directory: &Path,
+    path: &Path,
+    case_sensitive: bool,
+    ignore_root_gitignore: bool,
+) -> Option<String> {
+    let directory = directory.canonicalize().ok()?;
+    let path = path.canonicalize().ok()?;
+    let is_dir = path.is_dir();
+    let in_git_repo = directory.ancestors().any(|dir| dir.join(".git").exists());
+
+    for dir in path.ancestors().skip(1) {
+        let below_root = dir != directory && dir.starts_with(&directory);
+
+        let mut ignore_files = vec![".ignore"];
+        if (in_git_repo && !ignore_root_gitignore) || (ignore_root_gitignore && below_root) {
+            ignore_files.push(".gitignore");
+        }
+
+        for file_name in ignore_files {
+            let Some(ignore) = load_ignore_file(dir, file_name, case_sensitive) else {
+                continue;
+            };
+            let matched = ignore.matched_path_or_any_parents(&path, is_dir);
+            if matched.is_whitelist() {
+                return None;
+            }
+            if let Some(glob) = matched.inner().filter(|_| matched.is_ignore()) {
+                let source = glob
+                    .from()
+                    .map(|from| from.display().to_string())
+                    .unwrap_or_else(|| dir.join(file_name).display().to_string());
+                return Some(format!(
+                    "matched {} rule `{}` in {}",
+                    file_name,
+                    glob.original(),
+                    source
+                ));
+            }
+        }
+
+        // Stop at the root of the repository (or the traversal root outside of one)
+        if !below_root && (dir.join(".git").exists() || !in_git_repo) {
+            break;
+        }
+    }
+
+    None
+}
+
+/// Loads an ignore file (e.g. `.gitignore`) of a directory, if it exists and can be parsed
+fn load_ignore_file(dir: &Path, file_name: &str, case_sensitive: bool) -> Option<Arc<Gitignore>> {
+    let gitignore_path = dir.join(file_name);
+    if !gitignore_path.is_file() {
+        return None;
+    }
+
+    let mut builder = GitignoreBuilder::new(dir);
+    if !case_sensitive {
+        builder.case_insensitive(true).ok()?;
+    }
+    if let Some(err) = builder.add(&gitignore_path) {
+        log_with_context(
+            log::Level::Warn,
+            LogMessage {
+                message: format!("Failed to parse ignore file: {}", err),
+                module: "traverse",
+                context: Some(vec![("file_path", gitignore_path.display().to_string())]),
+            },
+        );
+    }
+    builder.build().ok().map(Arc::new)
+}
+
 /// Determines if a path is hidden (starts with a dot or is in a hidden directory).
 ///
 /// # Arguments
@@ -168,9 +850,7 @@ pub fn is_hidden_path(path: &Path) -> bool {
 /// # Arguments
 ///
 /// * `directory` - The directory path to traverse
-/// * `respect_gitignore` - Whether to respect gitignore rules
-/// * `case_sensitive` - Whether file path matching should be case sensitive
-/// * `max_depth` - Optional maximum directory depth to traverse
+/// * `config` - The settings of the walker (see `WalkConfig`)
 /// * `exclude_glob` - Optional list of glob patterns to exclude files from the results (uses relative paths)
 /// * `initial` - The initial value for the result accumulator
 /// * `callback` - A function that processes each entry and updates the accumulator. This function
@@ -191,15 +871,17 @@ pub fn is_hidden_path(path: &Path) -> bool {
 /// Collecting file paths as strings:
 /// ```no_run
 /// use anyhow::Result;
-/// use lumin::traverse::common::traverse_with_callback;
+/// use lumin::traverse::common::{WalkConfig, traverse_with_callback};
 /// use std::path::Path;
 ///
 /// fn collect_file_names(dir: &Path) -> Result<Vec<String>> {
+///     let config = WalkConfig {
+///         max_depth: Some(20),
+///         ..WalkConfig::default()
+///     };
 ///     traverse_with_callback(
 ///         dir,
-///         true,   // respect_gitignore
-///         false,  // case_sensitive
-///         Some(20), // max_depth
+///         &config,
 ///         None,   // exclude_glob
 ///         Vec::new(),
 ///         |mut names, path| {
@@ -215,7 +897,7 @@ pub fn is_hidden_path(path: &Path) -> bool {
 /// Counting lines in all non-binary files:
 /// ```no_run
 /// use anyhow::{Context, Result};
-/// use lumin::traverse::common::traverse_with_callback;
+/// use lumin::traverse::common::{WalkConfig, traverse_with_callback};
 /// use std::fs::File;
 /// use std::io::{BufRead, BufReader};
 /// use std::path::Path;
@@ -223,9 +905,7 @@ pub fn is_hidden_path(path: &Path) -> bool {
 /// fn count_lines(dir: &Path) -> Result<usize> {
 ///     traverse_with_callback(
 ///         dir,
-///         true,   // respect_gitignore
-///         false,  // case_sensitive
-///         None,   // max_depth (no limit)
+///         &WalkConfig::default(),
 ///         Some(&vec!["*.bin".to_string(), "*.jpg".to_string()]),
 ///         0,
 ///         |count, path| {
@@ -240,9 +920,7 @@ pub fn is_hidden_path(path: &Path) -> bool {
 /// ```
 pub fn traverse_with_callback<T, F>(
     directory: &Path,
-    respect_gitignore: bool,
-    case_sensitive: bool,
-    max_depth: Option<usize>,
+    config: &WalkConfig,
     exclude_glob: Option<&Vec<String>>,
     initial: T,
     mut callback: F,
@@ -251,32 +929,10 @@ where
     F: FnMut(T, &Path) -> Result<T>,
 {
     // Use the common walker builder
-    let mut walker = build_walk(directory, respect_gitignore, case_sensitive, max_depth)?;
+    let mut walker = build_walk(directory, config)?;
 
     // Compile exclude glob patterns if provided
-    let glob_set = if let Some(exclude_patterns) = exclude_glob {
-        if !exclude_patterns.is_empty() {
-            let mut builder = globset::GlobSetBuilder::new();
-            for pattern in exclude_patterns {
-                // Build glob with appropriate case sensitivity
-                let glob = if case_sensitive {
-                    globset::GlobBuilder::new(pattern).build()
-                } else {
-                    globset::GlobBuilder::new(pattern)
-                        .case_insensitive(true)
-                        .build()
-                }
-                .with_context(|| format!("Failed to compile glob pattern: {}", pattern))?;
-
-                builder.add(glob);
-            }
-            Some(builder.build().context("Failed to build glob set")?)
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+    let file_filter = FileFilter::new(directory, None, exclude_glob, config.case_sensitive)?;
 
     // Use try_fold to accumulate results
     let result = walker.try_fold(initial, |acc, entry_result| -> Result<T> {
@@ -285,13 +941,8 @@ where
                 let path = entry.path();
                 if path.is_file() {
                     // Skip files that match any of the exclude globs
-                    if let Some(ref glob_set) = glob_set {
-                        // Get the path relative to the search directory for better glob matching
-                        let rel_path = path.strip_prefix(directory).unwrap_or(path);
-                        if glob_set.is_match(rel_path) {
-                            // Skip this file as it matches an exclude pattern
-                            return Ok(acc);
-                        }
+                    if !file_filter.matches(path)? {
+                        return Ok(acc);
                     }
                     // Process this file with the callback
                     callback(acc, path)
@@ -390,11 +1041,15 @@ pub fn collect_files_with_excludes(
     max_depth: Option<usize>,
     exclude_glob: Option<&Vec<String>>,
 ) -> Result<Vec<PathBuf>> {
-    traverse_with_callback(
-        directory,
+    let config = WalkConfig {
         respect_gitignore,
         case_sensitive,
         max_depth,
+        ..WalkConfig::default()
+    };
+    traverse_with_callback(
+        directory,
+        &config,
         exclude_glob,
         Vec::new(),
         |mut files, path| {
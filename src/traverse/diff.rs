@@ -0,0 +1,123 @@
+//! Comparison of two traversal snapshots.
+//!
+//! Traversing the same directory at two points in time and diffing the results gives a
+//! "what changed" view: the files that appeared, disappeared, or were modified in between.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::TraverseResult;
+
+/// The differences between two sets of traversal results.
+///
+/// Each list is sorted by path.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct TraverseDiff {
+    /// Files present only in the new results
+    pub added: Vec<TraverseResult>,
+    /// Files present only in the old results
+    pub removed: Vec<TraverseResult>,
+    /// Files present in both results whose type, size or modification time changed, as found
+    /// in the new results
+    pub modified: Vec<TraverseResult>,
+}
+
+impl TraverseDiff {
+    /// Returns whether the two sets of results are identical.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Compares two sets of traversal results, such as two snapshots of the same directory.
+///
+/// Files are matched by `file_path`, so both sets should be produced with the same directory
+/// and `omit_path_prefix`. A file present in both sets is modified when its `file_type`,
+/// `size_bytes`, `modified` time or `link_target` differs. Traversal results don't carry
+/// content hashes, so changes that keep the size and modification time are not detected; the
+/// size and time are only compared when both results include them (see
+/// `TraverseOptions::include_metadata`).
+///
+/// # Arguments
+///
+/// * `old` - The earlier traversal results
+/// * `new` - The later traversal results
+///
+/// # Returns
+///
+/// The added, removed and modified files, each sorted by path
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::traverse::{TraverseOptions, diff, traverse_directory};
+/// use std::path::Path;
+///
+/// let options = TraverseOptions {
+///     include_metadata: true,
+///     ..TraverseOptions::default()
+/// };
+/// let before = traverse_directory(Path::new("src"), &options).unwrap();
+/// // ... files change ...
+/// let after = traverse_directory(Path::new("src"), &options).unwrap();
+///
+/// let changes = diff(&before, &after);
+/// for file in &changes.added {
+///     println!("+ {}", file.file_path.display());
+/// }
+/// for file in &changes.removed {
+///     println!("- {}", file.file_path.display());
+/// }
+/// for file in &changes.modified {
+///     println!("~ {}", file.file_path.display());
+/// }
+/// ```
+pub fn diff(old: &[TraverseResult], new: &[TraverseResult]) -> TraverseDiff {
+    let old_by_path: HashMap<&PathBuf, &TraverseResult> = old
+        .iter()
+        .map(|result| (&result.file_path, result))
+        .collect();
+    let new_by_path: HashMap<&PathBuf, &TraverseResult> = new
+        .iter()
+        .map(|result| (&result.file_path, result))
+        .collect();
+
+    let mut changes = TraverseDiff::default();
+    for result in new {
+        match old_by_path.get(&result.file_path) {
+            None => changes.added.push(result.clone()),
+            Some(old_result) if is_modified(old_result, result) => {
+                changes.modified.push(result.clone())
+            }
+            Some(_) => {}
+        }
+    }
+    changes.removed = old
+        .iter()
+        .filter(|result| !new_by_path.contains_key(&result.file_path))
+        .cloned()
+        .collect();
+
+    for list in [
+        &mut changes.added,
+        &mut changes.removed,
+        &mut changes.modified,
+    ] {
+        list.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    }
+    changes
+}
+
+/// Returns whether a file differs between two results for the same path
+fn is_modified(old: &TraverseResult, new: &TraverseResult) -> bool {
+    // Metadata missing from either result can't be compared
+    fn differs<T: PartialEq>(old: Option<T>, new: Option<T>) -> bool {
+        matches!((old, new), (Some(old), Some(new)) if old != new)
+    }
+
+    old.file_type != new.file_type
+        || old.link_target != new.link_target
+        || differs(old.size_bytes, new.size_bytes)
+        || differs(old.modified, new.modified)
+}
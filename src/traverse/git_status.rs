@@ -0,0 +1,128 @@
+//! Git status annotation of traversal results.
+//!
+//! Asking git for the status of each file separately rescans the working tree every time, so
+//! the status of the whole repository is read with a single call before the walk, and each
+//! result is looked up in it. Reading the status requires the `git` cargo feature.
+
+#[cfg(feature = "git")]
+use git2::{Repository, Status, StatusOptions};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The git status of a file, as reported by `TraverseResult::git_status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GitStatus {
+    /// The file is tracked and differs from `HEAD`, in the index or in the working tree
+    /// (including renames, type changes and merge conflicts)
+    Modified,
+    /// The file is new in the index
+    Added,
+    /// The file is neither tracked nor ignored
+    Untracked,
+    /// The file is tracked and unchanged
+    Clean,
+    /// The file is ignored by git
+    Ignored,
+}
+
+impl GitStatus {
+    /// Converts the status flags of a file reported by git
+    #[cfg(feature = "git")]
+    fn from_status(status: Status) -> Self {
+        if status.is_index_new() {
+            Self::Added
+        } else if status.is_wt_new() {
+            Self::Untracked
+        } else if status.is_ignored() {
+            Self::Ignored
+        } else if status.is_empty() {
+            Self::Clean
+        } else {
+            Self::Modified
+        }
+    }
+}
+
+/// The status of the files of the git repository enclosing a traversal directory.
+#[cfg_attr(not(feature = "git"), allow(dead_code))]
+pub(crate) struct GitStatuses {
+    /// The canonical traversal directory
+    canonical_directory: PathBuf,
+    /// The statuses of the files that are not clean, by absolute path.
+    /// Untracked and ignored directories whose content is not listed end with a `/`.
+    statuses: HashMap<PathBuf, GitStatus>,
+}
+
+impl GitStatuses {
+    /// Reads the status of the repository enclosing `directory` with a single status call.
+    ///
+    /// Returns `None` if `directory` is not inside the working tree of a git repository, or
+    /// the status can't be read.
+    #[cfg(feature = "git")]
+    pub(crate) fn discover(directory: &Path) -> Option<Self> {
+        let repository = Repository::discover(directory).ok()?;
+        let workdir = repository.workdir()?.canonicalize().ok()?;
+        let canonical_directory = directory.canonicalize().ok()?;
+
+        // Ignored directories are reported as a whole rather than file by file
+        let mut status_options = StatusOptions::new();
+        status_options
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .include_ignored(true)
+            .recurse_ignored_dirs(false);
+        let entries = repository.statuses(Some(&mut status_options)).ok()?;
+
+        let statuses = entries
+            .iter()
+            .filter_map(|entry| {
+                // Status paths are relative to the working tree root, with `/` separators
+                let path = std::str::from_utf8(entry.path_bytes()).ok()?;
+                let mut absolute = workdir.join(path).into_os_string();
+                if path.ends_with('/') {
+                    absolute.push("/");
+                }
+                Some((
+                    PathBuf::from(absolute),
+                    GitStatus::from_status(entry.status()),
+                ))
+            })
+            .collect();
+
+        Some(Self {
+            canonical_directory,
+            statuses,
+        })
+    }
+
+    /// Reading the git status requires the `git` feature
+    #[cfg(not(feature = "git"))]
+    pub(crate) fn discover(_directory: &Path) -> Option<Self> {
+        None
+    }
+
+    /// Returns the status of the file at `rel_path`, relative to the traversal directory.
+    ///
+    /// Files inside a directory reported as a whole take its status, and other files inside
+    /// the working tree are clean. Returns `None` for files outside of the working tree, such
+    /// as the content of the `.git` directory.
+    pub(crate) fn get(&self, rel_path: &Path) -> Option<GitStatus> {
+        let path = self.canonical_directory.join(rel_path);
+        if let Some(&status) = self.statuses.get(&path) {
+            return Some(status);
+        }
+
+        for ancestor in path.ancestors().skip(1) {
+            if ancestor.file_name().is_some_and(|name| name == ".git") {
+                return None;
+            }
+            let mut directory = ancestor.as_os_str().to_owned();
+            directory.push("/");
+            if let Some(&status) = self.statuses.get(Path::new(&directory)) {
+                return Some(status);
+            }
+        }
+        Some(GitStatus::Clean)
+    }
+}
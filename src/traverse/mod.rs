@@ -102,27 +102,58 @@
 /// - Substring matching respects the `case_sensitive` option
 ///
 /// For more examples and detailed usage patterns, see the `traverse_directory` function.
-use anyhow::Result;
-use globset::{GlobBuilder, GlobSetBuilder};
+use anyhow::{Result, anyhow};
 use infer::Infer;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::SystemTime;
 
 // Common utilities for traverse and tree operations
 pub mod common;
-use crate::paths::remove_path_prefix;
+// Streaming traversal through a bounded channel
+mod channel;
+// du-style size aggregation of traversal results
+mod sizes;
+// Run-level report of a traversal
+mod report;
+// Printable table formatting of traversal results
+mod table;
+// Modification time range queries
+mod modified;
+// Comparison of two traversal snapshots
+mod diff;
+// Git status annotation of results
+mod git_status;
+use crate::paths::{PathDisplay, common_prefix, natural_path_cmp, remove_path_prefix};
 use crate::telemetry::{LogMessage, log_with_context};
-use common::{build_walk, is_hidden_path};
+use crate::view::read_shebang_language;
+pub use channel::{DEFAULT_CHANNEL_CAPACITY, traverse_channel, traverse_channel_with_capacity};
+use common::{
+    FileFilter, WalkConfig, build_glob_set, build_walk, extension_override, fold_case, fuzzy_score,
+    is_hidden_path,
+};
+pub use diff::{TraverseDiff, diff};
+pub use git_status::GitStatus;
+use git_status::GitStatuses;
+pub use modified::modified_between;
+pub use report::{TraverseReport, traverse_directory_report};
+pub use sizes::directory_sizes;
+pub use table::{TableColumn, TableOptions, format_table};
 
 /// Configuration options for directory traversal operations.
 ///
 /// Controls the behavior of the traversal functionality, including case sensitivity,
 /// gitignore handling, file type filtering, and pattern matching.
 ///
+/// The options can be loaded from configuration files via serde. Fields missing from the
+/// input take their default values, and unknown fields are rejected.
+///
 /// # Examples
 ///
 /// ```
-/// use lumin::traverse::TraverseOptions;
+/// use lumin::traverse::{PatternMode, TraverseOptions, TraverseSort};
 /// use std::path::PathBuf;
 ///
 /// // Default options: case-insensitive, respect gitignore, only text files, no pattern
@@ -135,7 +166,30 @@ use common::{build_walk, is_hidden_path};
 ///     only_text_files: false,
 ///     pattern: Some("**/*.{rs,toml}".to_string()),
 ///     depth: Some(10),
+///     min_depth: None,
 ///     omit_path_prefix: None,
+///     include_metadata: false,
+///     resolve_target: false,
+///     sort: TraverseSort::Path,
+///     modified_after: None,
+///     modified_before: None,
+///     cancel: None,
+///     include_inode: false,
+///     natural_sort: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     one_file_system: false,
+///     text_extensions: Vec::new(),
+///     binary_extensions: Vec::new(),
+///     glob_against_absolute: false,
+///     include_dirs: false,
+///     tilde_home: false,
+///     with_git_status: false,
+///     clean_paths: false,
+///     pattern_mode: PatternMode::Auto,
+///     include_mode: false,
+///     include_glob: None,
+///     exclude_glob: None,
 /// };
 ///
 /// // Case-insensitive, include all files, with a substring pattern
@@ -145,7 +199,30 @@ use common::{build_walk, is_hidden_path};
 ///     only_text_files: false,
 ///     pattern: Some("config".to_string()),
 ///     depth: None,
+///     min_depth: None,
 ///     omit_path_prefix: None,
+///     include_metadata: false,
+///     resolve_target: false,
+///     sort: TraverseSort::Path,
+///     modified_after: None,
+///     modified_before: None,
+///     cancel: None,
+///     include_inode: false,
+///     natural_sort: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     one_file_system: false,
+///     text_extensions: Vec::new(),
+///     binary_extensions: Vec::new(),
+///     glob_against_absolute: false,
+///     include_dirs: false,
+///     tilde_home: false,
+///     with_git_status: false,
+///     clean_paths: false,
+///     pattern_mode: PatternMode::Auto,
+///     include_mode: false,
+///     include_glob: None,
+///     exclude_glob: None,
 /// };
 ///
 /// // With path prefix removal to show relative paths
@@ -155,10 +232,34 @@ use common::{build_walk, is_hidden_path};
 ///     only_text_files: true,
 ///     pattern: None,
 ///     depth: Some(20),
+///     min_depth: None,
 ///     omit_path_prefix: Some(PathBuf::from("/home/user/projects/myrepo")),
+///     include_metadata: false,
+///     resolve_target: false,
+///     sort: TraverseSort::Path,
+///     modified_after: None,
+///     modified_before: None,
+///     cancel: None,
+///     include_inode: false,
+///     natural_sort: false,
+///     skip_vendored: false,
+///     auto_omit_common_prefix: false,
+///     one_file_system: false,
+///     text_extensions: Vec::new(),
+///     binary_extensions: Vec::new(),
+///     glob_against_absolute: false,
+///     include_dirs: false,
+///     tilde_home: false,
+///     with_git_status: false,
+///     clean_paths: false,
+///     pattern_mode: PatternMode::Auto,
+///     include_mode: false,
+///     include_glob: None,
+///     exclude_glob: None,
 /// };
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct TraverseOptions {
     /// Whether file path matching should be case sensitive.
     ///
@@ -324,6 +425,19 @@ pub struct TraverseOptions {
     /// - With `depth: None`, all subdirectories will be explored regardless of depth
     pub depth: Option<usize>,
 
+    /// Minimum depth of the files to include.
+    ///
+    /// When `Some(min_depth)`, files shallower than `min_depth` are skipped. Depth is counted as
+    /// in `depth`: files directly in the traversal directory have a depth of 1. This is useful to
+    /// list only nested files, for example the packages of a workspace but not its top-level
+    /// files. Default is `None` (no minimum).
+    ///
+    /// # Examples
+    ///
+    /// - With `min_depth: Some(2)`, `README.md` is skipped but `src/main.rs` is included
+    /// - With `min_depth: None`, both files are included
+    pub min_depth: Option<usize>,
+
     /// Optional path prefix to remove from file paths in traversal results.
     ///
     /// When set to `Some(path)`, this prefix will be removed from the beginning of each file path in the results.
@@ -343,6 +457,314 @@ pub struct TraverseOptions {
     /// with the prefix `/home/user/projects/myrepo`, a file path like `/var/log/syslog` would remain
     /// `/var/log/syslog` in the results.
     pub omit_path_prefix: Option<PathBuf>,
+
+    /// Whether to read file metadata (size and modification time) for each result.
+    ///
+    /// When `true`, the `size_bytes` and `modified` fields of each `TraverseResult` are populated
+    /// from the file system. When `false` (default), they are left as `None`, avoiding an extra
+    /// metadata lookup per file.
+    ///
+    /// # Examples
+    ///
+    /// - `include_metadata: true` - Results carry their size and modification time, which can be
+    ///   displayed with `format_table`
+    /// - `include_metadata: false` - Only the path and file type are returned
+    pub include_metadata: bool,
+
+    /// Whether to read the target of symbolic links.
+    ///
+    /// When `true`, for results that are symbolic links, the immediate link target is read and
+    /// stored in the `link_target` field of the `TraverseResult`. The link is not followed any
+    /// further, so the target is reported exactly as stored in the link (it may be relative,
+    /// or point to another link). When `false` (default), `link_target` is always `None`.
+    ///
+    /// Symbolic links to directories and dangling links are not reported by the traversal,
+    /// since only files are included in the results.
+    ///
+    /// # Examples
+    ///
+    /// - `resolve_target: true` - A link `current.txt -> releases/v2.txt` reports
+    ///   `link_target: Some(PathBuf::from("releases/v2.txt"))`
+    /// - `resolve_target: false` - No link targets are read
+    pub resolve_target: bool,
+
+    /// The order of the returned results.
+    ///
+    /// - `TraverseSort::Path` (default) - Results are sorted alphabetically by path
+    /// - `TraverseSort::BreadthFirst` - Results are sorted by depth, then by path, so files
+    ///   closer to the traversal root come first
+    ///
+    /// # Examples
+    ///
+    /// - `sort: TraverseSort::BreadthFirst` - `z.txt` is listed before `a/b/c.txt`
+    /// - `sort: TraverseSort::Path` - `a/b/c.txt` is listed before `z.txt`
+    pub sort: TraverseSort,
+
+    /// Only include files modified at or after this time.
+    ///
+    /// When `Some(time)`, files whose modification time is earlier than `time` are excluded.
+    /// Files whose modification time cannot be read are excluded as well.
+    /// When `None` (default), files are not filtered by modification time.
+    ///
+    /// # Examples
+    ///
+    /// - `modified_after: Some(SystemTime::now() - Duration::from_secs(3600))` - Only files
+    ///   modified within the last hour
+    pub modified_after: Option<SystemTime>,
+
+    /// Only include files modified at or before this time.
+    ///
+    /// When `Some(time)`, files whose modification time is later than `time` are excluded.
+    /// Files whose modification time cannot be read are excluded as well.
+    /// When `None` (default), files are not filtered by modification time.
+    ///
+    /// Combined with `modified_after`, this selects files modified within an inclusive range
+    /// (see `modified_between`).
+    pub modified_before: Option<SystemTime>,
+
+    /// Optional flag for cancelling the traversal from another thread.
+    ///
+    /// The flag is checked for every entry visited. Once it is set to `true`, the traversal
+    /// stops promptly and returns the results found so far. When set to `None` (default),
+    /// the traversal can't be cancelled.
+    ///
+    /// This option is not serialized.
+    #[serde(skip)]
+    pub cancel: Option<Arc<AtomicBool>>,
+
+    /// Whether to read the inode and device numbers of each result.
+    ///
+    /// When `true`, the `inode` and `dev` fields of each `TraverseResult` are populated on Unix
+    /// platforms. Files with the same inode and device numbers are hardlinks to the same data.
+    /// On other platforms, and when `false` (default), both fields are left as `None`.
+    ///
+    /// # Examples
+    ///
+    /// - `include_inode: true` - Results can be grouped by `(dev, inode)` to find hardlinks
+    /// - `include_inode: false` - No inode lookups are performed
+    pub include_inode: bool,
+
+    /// Whether to compare paths in natural order when sorting the results.
+    ///
+    /// When `true`, runs of digits in paths are compared by their numeric value, so `file2`
+    /// is listed before `file10` (see `paths::natural_cmp`). When `false` (default), paths are
+    /// compared character by character. Applies to both `TraverseSort` orders.
+    ///
+    /// # Examples
+    ///
+    /// - `natural_sort: true` - `log2.txt`, `log10.txt`
+    /// - `natural_sort: false` - `log10.txt`, `log2.txt`
+    pub natural_sort: bool,
+
+    /// Whether to skip directories that usually contain vendored or generated files.
+    ///
+    /// When `true`, directories named like one of the `common::VENDORED_DIR_NAMES`
+    /// (`node_modules`, `vendor`, `dist`, `build`, `target` and `.venv`) are not traversed.
+    /// The traversal directory itself is never skipped. When `false` (default), these
+    /// directories are traversed like any other.
+    pub skip_vendored: bool,
+
+    /// Whether to remove the longest directory prefix shared by all result paths.
+    ///
+    /// When `true`, the common directory prefix of the result file paths (see
+    /// `paths::common_prefix`) is removed from every result, after `omit_path_prefix` has been
+    /// applied. When `false` (default), paths are returned as they are.
+    ///
+    /// Since the prefix is only known once all results have been found, this option has no
+    /// effect on `traverse_channel`.
+    ///
+    /// # Examples
+    ///
+    /// - `auto_omit_common_prefix: true` - `/repo/src/a.rs` and `/repo/src/util/b.rs` are
+    ///   reported as `a.rs` and `util/b.rs`
+    pub auto_omit_common_prefix: bool,
+
+    /// Whether to stay on the file system of the traversal directory.
+    ///
+    /// When `true`, directories that are mount points of other file systems are not descended
+    /// into, like `find -xdev`. This keeps a traversal of `/` or a home directory out of
+    /// `/proc`, network shares and mounted disk images. When `false` (default), mount points
+    /// are traversed like any other directory.
+    ///
+    /// # Examples
+    ///
+    /// - `one_file_system: true` - Traversing `/` with a depth of 2 skips `/proc/version`
+    /// - `one_file_system: false` - `/proc/version` is included
+    pub one_file_system: bool,
+
+    /// Extensions of files that are always treated as text by `only_text_files`.
+    ///
+    /// Content-based detection occasionally misclassifies text formats as binary. Files with
+    /// one of these extensions are included without inspecting their content. Extensions are
+    /// compared case insensitively, with or without a leading dot, and take precedence over
+    /// `binary_extensions`. Empty by default.
+    ///
+    /// # Examples
+    ///
+    /// - `text_extensions: vec!["pdf".to_string()]` - A `.pdf` file with a PDF header is
+    ///   included even with `only_text_files: true`
+    pub text_extensions: Vec<String>,
+
+    /// Extensions of files that are always treated as binary by `only_text_files`.
+    ///
+    /// Files with one of these extensions are excluded without inspecting their content when
+    /// `only_text_files` is `true`. Extensions are compared as in `text_extensions`. Empty by
+    /// default.
+    ///
+    /// # Examples
+    ///
+    /// - `binary_extensions: vec!["dat".to_string()]` - `table.dat` is excluded even when it
+    ///   contains only ASCII text
+    pub binary_extensions: Vec<String>,
+
+    /// Whether glob patterns are matched against the absolute path of each file.
+    ///
+    /// By default, glob patterns in `pattern` are matched against the path relative to the
+    /// traversal directory, so they can't refer to components above it. When `true`, globs are
+    /// matched against the absolute path instead, for patterns written as absolute paths.
+    /// Relative traversal directories are made absolute using the current directory, without
+    /// resolving symlinks. Substring patterns are not affected. Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// - `glob_against_absolute: true` with `pattern: Some("/home/user/project/src/*.rs")`
+    ///   matches the Rust files in `src` when traversing `/home/user/project`
+    /// - `glob_against_absolute: false` - The same pattern matches nothing
+    pub glob_against_absolute: bool,
+
+    /// Whether to include directories in the results, in addition to files.
+    ///
+    /// When `true`, every directory below the traversal directory is reported with a
+    /// `file_type` of `"dir"`, including empty directories that contain no files to report.
+    /// `pattern`, `min_depth` and the modification time filters apply to directories as to
+    /// files, while `only_text_files` doesn't. The traversal directory itself is not included.
+    /// Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// - `include_dirs: true` - An empty `assets/` directory is reported as `assets` with
+    ///   the `"dir"` file type, next to the files of the traversal
+    /// - `include_dirs: false` - Only files are reported
+    pub include_dirs: bool,
+
+    /// Whether to abbreviate paths under the home directory with `~`.
+    ///
+    /// When `true`, result paths starting with the home directory of the current user (as
+    /// reported by `dirs::home_dir()`) are displayed as `~/...`, after `omit_path_prefix` has
+    /// been applied. This is meant for user-facing output: the abbreviated paths can't be
+    /// opened directly. Relative paths are never changed. Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// - `tilde_home: true` - `/home/user/notes/todo.md` is reported as `~/notes/todo.md`
+    /// - `tilde_home: false` - The path is reported unchanged
+    pub tilde_home: bool,
+
+    /// Whether to annotate each result with its git status.
+    ///
+    /// When `true` and the traversal directory is inside a git working tree, the `git_status`
+    /// field of each file result is populated. The status of the whole repository is read
+    /// with a single call before the walk, rather than once per file. Results for
+    /// directories (see `include_dirs`), for files inside the `.git` directory, and all
+    /// results outside of a git repository are left as `None`. Reading the status requires the
+    /// `git` cargo feature (enabled by default); without it, traversing with this option is an
+    /// error. Default is `false`.
+    ///
+    /// Note that gitignored files are only reported when `respect_gitignore` is `false`.
+    ///
+    /// # Examples
+    ///
+    /// - `with_git_status: true` - An edited tracked file has `Some(GitStatus::Modified)`
+    /// - `with_git_status: false` - No status is read
+    pub with_git_status: bool,
+
+    /// Whether to collapse redundant separators in result paths.
+    ///
+    /// When `true`, repeated separators are collapsed and trailing separators removed from
+    /// result paths (see `paths::clean_path`), after `omit_path_prefix` has been applied and
+    /// before `tilde_home`. Default is `false`.
+    ///
+    /// # Examples
+    ///
+    /// - `clean_paths: true` - Traversing `project//src` reports `project/src/main.rs`
+    /// - `clean_paths: false` - Traversing `project//src` reports `project//src/main.rs`
+    pub clean_paths: bool,
+
+    /// How `pattern` is matched against file paths.
+    ///
+    /// - `PatternMode::Auto` (default) - Patterns with glob characters are matched as globs,
+    ///   other patterns as substrings (see `pattern`)
+    /// - `PatternMode::Fuzzy` - Files match when their path relative to the traversal
+    ///   directory contains the characters of the pattern in order, like in fuzzy file
+    ///   finders (see `common::fuzzy_score`). Each result carries its `fuzzy_score`, and
+    ///   results are sorted by score, best first, then in the order configured by `sort`.
+    ///   Glob characters have no special meaning, and `case_sensitive` applies as for
+    ///   substring patterns.
+    ///
+    /// # Examples
+    ///
+    /// - `pattern_mode: PatternMode::Fuzzy` with pattern `srcmn` - Matches `src/main.rs`
+    /// - `pattern_mode: PatternMode::Auto` with pattern `srcmn` - Only matches paths
+    ///   containing `srcmn`
+    pub pattern_mode: PatternMode,
+
+    /// Whether to read the permission bits of each result.
+    ///
+    /// When `true`, the `mode` field of each `TraverseResult` is populated on Unix platforms.
+    /// On other platforms, and when `false` (default), it is left as `None`.
+    ///
+    /// # Examples
+    ///
+    /// - `include_mode: true` - Results can be checked with `TraverseResult::is_world_writable`
+    /// - `include_mode: false` - No permission lookups are performed
+    pub include_mode: bool,
+
+    /// Optional list of glob patterns a file must match to be included in the results.
+    ///
+    /// Patterns are matched against paths relative to the traversal directory, in addition
+    /// to `pattern`, and may contain negations. Files matched by `exclude_glob` are left out
+    /// even if they match. When `None` (default), no files are left out by this option.
+    /// Directories reported with `include_dirs` are filtered like files. See
+    /// `common::FileFilter` for the shared semantics with search and tree.
+    ///
+    /// # Examples
+    ///
+    /// - `include_glob: Some(vec!["src/**".to_string()])` - Only files below `src` are reported
+    pub include_glob: Option<Vec<String>>,
+
+    /// Optional list of glob patterns of files to leave out of the results.
+    ///
+    /// Patterns are matched like `include_glob`, and take precedence over it. When `None`
+    /// (default), no files are excluded by this option.
+    ///
+    /// # Examples
+    ///
+    /// - `exclude_glob: Some(vec!["**/*.lock".to_string()])` - Lock files are not reported
+    pub exclude_glob: Option<Vec<String>>,
+}
+
+/// The order of traversal results.
+///
+/// See `TraverseOptions::sort` for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TraverseSort {
+    /// Sort alphabetically by path
+    #[default]
+    Path,
+    /// Sort by depth (number of path components), then alphabetically by path
+    BreadthFirst,
+}
+
+/// How `TraverseOptions::pattern` is matched.
+///
+/// See `TraverseOptions::pattern_mode` for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PatternMode {
+    /// Glob matching for patterns with glob characters, substring matching otherwise
+    #[default]
+    Auto,
+    /// Fuzzy subsequence matching, with results ranked by score
+    Fuzzy,
 }
 
 impl Default for TraverseOptions {
@@ -353,7 +775,30 @@ impl Default for TraverseOptions {
             only_text_files: true,
             pattern: None,
             depth: Some(20),
+            min_depth: None,
             omit_path_prefix: None,
+            include_metadata: false,
+            resolve_target: false,
+            sort: TraverseSort::Path,
+            modified_after: None,
+            modified_before: None,
+            cancel: None,
+            include_inode: false,
+            natural_sort: false,
+            skip_vendored: false,
+            auto_omit_common_prefix: false,
+            one_file_system: false,
+            text_extensions: Vec::new(),
+            binary_extensions: Vec::new(),
+            glob_against_absolute: false,
+            include_dirs: false,
+            tilde_home: false,
+            with_git_status: false,
+            clean_paths: false,
+            pattern_mode: PatternMode::Auto,
+            include_mode: false,
+            include_glob: None,
+            exclude_glob: None,
         }
     }
 }
@@ -387,6 +832,8 @@ pub struct TraverseResult {
     ///
     /// This is the absolute or relative path to the file, depending on the
     /// input provided to the traverse function.
+    /// Paths that are not valid UTF-8 are serialized without loss (see the `paths` module).
+    #[serde(with = "crate::paths::serde_os_str")]
     pub file_path: PathBuf,
 
     /// The detected or inferred file type (typically the file extension).
@@ -394,6 +841,59 @@ pub struct TraverseResult {
     /// This is usually the lowercase file extension (e.g., "txt", "rs", "toml"),
     /// or "unknown" if the type couldn't be determined.
     pub file_type: String,
+
+    /// Size of the file in bytes.
+    ///
+    /// Only populated when `include_metadata` is enabled in the traverse options.
+    pub size_bytes: Option<u64>,
+
+    /// Last modification time of the file.
+    ///
+    /// Only populated when `include_metadata` is enabled in the traverse options
+    /// and the platform supports modification times.
+    pub modified: Option<SystemTime>,
+
+    /// The immediate target of the file if it is a symbolic link.
+    ///
+    /// Only populated when `resolve_target` is enabled in the traverse options.
+    /// `None` for regular files.
+    #[serde(default, with = "crate::paths::serde_os_str::option")]
+    pub link_target: Option<PathBuf>,
+
+    /// The inode number of the file.
+    ///
+    /// Only populated when `include_inode` is enabled in the traverse options
+    /// and the platform is Unix.
+    #[serde(default)]
+    pub inode: Option<u64>,
+
+    /// The ID of the device containing the file.
+    ///
+    /// Only populated when `include_inode` is enabled in the traverse options
+    /// and the platform is Unix.
+    #[serde(default)]
+    pub dev: Option<u64>,
+
+    /// The git status of the file.
+    ///
+    /// Only populated when `with_git_status` is enabled in the traverse options
+    /// and the file is inside a git working tree.
+    #[serde(default)]
+    pub git_status: Option<GitStatus>,
+
+    /// How well the file matched a fuzzy pattern; higher is better.
+    ///
+    /// Only populated when `pattern_mode` is `PatternMode::Fuzzy` in the traverse options
+    /// and a pattern is set.
+    #[serde(default)]
+    pub fuzzy_score: Option<i64>,
+
+    /// The mode of the file, including the file type and permission bits (as in `st_mode`).
+    ///
+    /// Only populated when `include_mode` is enabled in the traverse options
+    /// and the platform is Unix.
+    #[serde(default)]
+    pub mode: Option<u32>,
 }
 
 impl TraverseResult {
@@ -433,6 +933,43 @@ impl TraverseResult {
     pub fn is_hidden(&self) -> bool {
         is_hidden_path(&self.file_path)
     }
+
+    /// Returns the immediate parent directory of the file, as part of `file_path`.
+    ///
+    /// The parent is taken from `file_path` as reported, so it is relative when path display
+    /// options such as `omit_path_prefix` shortened the path. Returns `None` when `file_path`
+    /// has no parent component, such as a file at the traversal root whose prefix was omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lumin::traverse::{TraverseOptions, traverse_directory};
+    /// use std::collections::BTreeMap;
+    /// use std::path::Path;
+    ///
+    /// let results = traverse_directory(Path::new("src"), &TraverseOptions::default()).unwrap();
+    ///
+    /// // Group the files by directory
+    /// let mut by_dir: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    /// for result in &results {
+    ///     by_dir.entry(result.parent_dir()).or_default().push(&result.file_path);
+    /// }
+    /// ```
+    pub fn parent_dir(&self) -> Option<&Path> {
+        self.file_path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+    }
+
+    /// Determines if the file is writable by all users.
+    ///
+    /// # Returns
+    ///
+    /// `Some(true)` if the "others" write permission bit is set, `Some(false)` if it is not,
+    /// and `None` if the mode was not read (see `TraverseOptions::include_mode`)
+    pub fn is_world_writable(&self) -> Option<bool> {
+        self.mode.map(|mode| mode & 0o002 != 0)
+    }
 }
 
 /// Traverses the specified directory and returns a list of files matching the given criteria.
@@ -458,7 +995,7 @@ impl TraverseResult {
 /// - The path to the file
 /// - The detected file type (typically the extension)
 ///
-/// The results are sorted alphabetically by file path.
+/// The results are sorted according to `options.sort` (alphabetically by file path by default).
 ///
 /// # Errors
 ///
@@ -655,14 +1192,109 @@ pub fn traverse_directory(
     options: &TraverseOptions,
 ) -> Result<Vec<TraverseResult>> {
     let mut results = Vec::new();
+    walk_directory(directory, options, |result| {
+        results.push(result);
+        true
+    })?;
+
+    finish_results(&mut results, options);
+
+    Ok(results)
+}
+
+/// Applies the post-processing steps that need all results: common prefix removal and sorting.
+pub(crate) fn finish_results(results: &mut [TraverseResult], options: &TraverseOptions) {
+    if options.auto_omit_common_prefix {
+        let file_paths: Vec<PathBuf> = results.iter().map(|r| r.file_path.clone()).collect();
+        if let Some(prefix) = common_prefix(&file_paths) {
+            for result in results.iter_mut() {
+                result.file_path = remove_path_prefix(&result.file_path, &prefix);
+            }
+        }
+    }
+
+    sort_results(results, options);
+}
+
+/// Sorts traversal results in the order configured by the options.
+fn sort_results(results: &mut [TraverseResult], options: &TraverseOptions) {
+    let compare_paths = |a: &TraverseResult, b: &TraverseResult| {
+        if options.natural_sort {
+            natural_path_cmp(&a.file_path, &b.file_path)
+        } else {
+            a.file_path.cmp(&b.file_path)
+        }
+    };
+
+    let compare_order = |a: &TraverseResult, b: &TraverseResult| match options.sort {
+        TraverseSort::Path => compare_paths(a, b),
+        TraverseSort::BreadthFirst => {
+            let depth = |result: &TraverseResult| result.file_path.components().count();
+            depth(a).cmp(&depth(b)).then_with(|| compare_paths(a, b))
+        }
+    };
+
+    if options.pattern_mode == PatternMode::Fuzzy {
+        // Rank the best fuzzy matches first
+        results.sort_by(|a, b| {
+            b.fuzzy_score
+                .cmp(&a.fuzzy_score)
+                .then_with(|| compare_order(a, b))
+        });
+    } else {
+        results.sort_by(compare_order);
+    }
+}
+
+/// Walks a directory and passes each file matching the options to a callback.
+///
+/// This is the shared implementation of `traverse_directory` and `traverse_channel`.
+/// Files are passed to the callback in the order they are found by the walker.
+///
+/// # Arguments
+///
+/// * `directory` - The directory path to traverse
+/// * `options` - Configuration options for the traversal
+/// * `emit` - A function receiving each result; returning `false` stops the walk early
+///
+/// # Returns
+///
+/// A summary of the walk
+///
+/// # Errors
+///
+/// Returns an error if there's an issue setting up the walker or compiling the pattern, or if
+/// `with_git_status` is set without the `git` feature
+pub(crate) fn walk_directory<F>(
+    directory: &Path,
+    options: &TraverseOptions,
+    mut emit: F,
+) -> Result<WalkSummary>
+where
+    F: FnMut(TraverseResult) -> bool,
+{
+    if options.with_git_status && !cfg!(feature = "git") {
+        return Err(anyhow!(
+            "with_git_status requires lumin to be built with the `git` feature"
+        ));
+    }
+
     let infer = Infer::new();
+    let mut summary = WalkSummary::default();
 
     // Use the common walker builder
     let walker = build_walk(
         directory,
-        options.respect_gitignore,
-        options.case_sensitive,
-        options.depth,
+        &WalkConfig {
+            respect_gitignore: options.respect_gitignore,
+            case_sensitive: options.case_sensitive,
+            max_depth: options.depth,
+            ignore_root_gitignore: false,
+            skip_vendored: options.skip_vendored,
+            one_file_system: options.one_file_system,
+            cancel: None,
+            pruned_dirs: None,
+        },
     )?;
 
     // Set up pattern matching if pattern provided
@@ -673,18 +1305,12 @@ pub fn traverse_directory(
             || pattern.contains('[')
             || pattern.contains(']');
 
-        if is_glob_pattern {
+        if is_glob_pattern && options.pattern_mode == PatternMode::Auto {
             // Use glob pattern matching for patterns with glob syntax
-            let mut builder = GlobSetBuilder::new();
-            let glob = if options.case_sensitive {
-                // Case sensitive matching
-                GlobBuilder::new(pattern).build()?
-            } else {
-                // Case insensitive matching
-                GlobBuilder::new(pattern).case_insensitive(true).build()?
-            };
-            builder.add(glob);
-            Some(builder.build()?)
+            Some(build_glob_set(
+                std::slice::from_ref(pattern),
+                options.case_sensitive,
+            )?)
         } else {
             // For simple substring matching, we'll use String.contains() later
             None
@@ -693,76 +1319,238 @@ pub fn traverse_directory(
         None
     };
 
+    // Compile the include and exclude patterns once rather than for every file
+    let file_filter = FileFilter::new(
+        directory,
+        options.include_glob.as_ref(),
+        options.exclude_glob.as_ref(),
+        options.case_sensitive,
+    )?;
+
+    // Set up the cleaning and home directory abbreviation of result paths
+    let path_display = PathDisplay::new(options.clean_paths, options.tilde_home);
+
+    // Read the git status of the enclosing repository once when annotating results
+    let git_statuses = if options.with_git_status {
+        GitStatuses::discover(directory)
+    } else {
+        None
+    };
+
+    // Fold the case of a substring pattern once, rather than for every file
+    let folded_pattern = options
+        .pattern
+        .as_deref()
+        .filter(|_| !options.case_sensitive)
+        .map(fold_case);
+
     // Walk the directory
     for result in walker {
+        if options
+            .cancel
+            .as_ref()
+            .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+        {
+            // The traversal was cancelled, so return the results found so far
+            return Ok(summary);
+        }
+
         match result {
             Ok(entry) => {
                 let path = entry.path();
-                if path.is_file() {
+
+                // Detect non-empty directories that are not descended into due to the depth limit
+                if !summary.depth_limited
+                    && options.depth == Some(entry.depth())
+                    && entry.file_type().is_some_and(|ft| ft.is_dir())
+                    && std::fs::read_dir(path).is_ok_and(|mut entries| entries.next().is_some())
+                {
+                    summary.depth_limited = true;
+                }
+
+                // Skip files shallower than the minimum depth
+                if options
+                    .min_depth
+                    .is_some_and(|min_depth| entry.depth() < min_depth)
+                {
+                    continue;
+                }
+
+                // Directories below the traversal directory are reported when requested
+                let is_dir = options.include_dirs
+                    && entry.depth() > 0
+                    && entry.file_type().is_some_and(|ft| ft.is_dir());
+
+                if path.is_file() || is_dir {
                     // Check if the path matches the pattern if one is provided
+                    let mut fuzzy_match_score = None;
                     let matches_pattern = if let Some(ref pattern) = options.pattern {
-                        if let Some(ref glob_matcher) = pattern_matcher {
-                            // Use glob matching
+                        if options.pattern_mode == PatternMode::Fuzzy {
+                            // Score the path relative to the traversal directory
                             let rel_path = path.strip_prefix(directory).unwrap_or(path);
-                            glob_matcher.is_match(rel_path)
+                            let rel_path = rel_path.to_string_lossy();
+                            fuzzy_match_score = match &folded_pattern {
+                                Some(folded_pattern) => {
+                                    fuzzy_score(folded_pattern, &fold_case(&rel_path))
+                                }
+                                None => fuzzy_score(pattern, &rel_path),
+                            };
+                            fuzzy_match_score.is_some()
+                        } else if let Some(ref glob_matcher) = pattern_matcher {
+                            // Use glob matching
+                            if options.glob_against_absolute {
+                                std::path::absolute(path)
+                                    .is_ok_and(|abs_path| glob_matcher.is_match(abs_path))
+                            } else {
+                                let rel_path = path.strip_prefix(directory).unwrap_or(path);
+                                glob_matcher.is_match(rel_path)
+                            }
                         } else {
                             // Use simple substring matching on filename and path
                             let path_str = path.to_string_lossy();
-                            if options.case_sensitive {
-                                // Case sensitive substring match
-                                path_str.contains(pattern)
-                            } else {
+                            match &folded_pattern {
                                 // Case insensitive substring match
-                                path_str.to_lowercase().contains(&pattern.to_lowercase())
+                                Some(folded_pattern) => {
+                                    fold_case(&path_str).contains(folded_pattern.as_str())
+                                }
+                                // Case sensitive substring match
+                                None => path_str.contains(pattern),
                             }
                         }
                     } else {
                         true // Include all files if no pattern is specified
                     };
 
-                    // Only proceed if the file matches the pattern
-                    if !matches_pattern {
+                    // Only proceed if the file matches the pattern and the include and
+                    // exclude globs
+                    if !matches_pattern || !file_filter.matches(path)? {
                         continue;
                     }
 
                     // Check if we should include this file based on text/binary filter
-                    let include = if options.only_text_files {
-                        // Read a small amount of the file to determine its type
-                        match std::fs::read(path) {
-                            Ok(_) => {
-                                // If infer can determine a type, it's probably not a text file
-                                match infer.get_from_path(path) {
-                                    Ok(Some(kind)) => kind.mime_type().starts_with("text/"),
-                                    Ok(None) => true, // Consider as text if infer couldn't determine a type
-                                    Err(_) => false,  // Skip files with errors
+                    let include = if options.only_text_files && !is_dir {
+                        if let Some(is_text) = extension_override(
+                            path,
+                            &options.text_extensions,
+                            &options.binary_extensions,
+                        ) {
+                            // The configured extension lists take precedence over the content
+                            is_text
+                        } else {
+                            // Read a small amount of the file to determine its type
+                            match std::fs::read(path) {
+                                Ok(_) => {
+                                    // If infer can determine a type, it's probably not a text file
+                                    match infer.get_from_path(path) {
+                                        Ok(Some(kind)) => kind.mime_type().starts_with("text/"),
+                                        Ok(None) => true, // Consider as text if infer couldn't determine a type
+                                        Err(_) => false,  // Skip files with errors
+                                    }
                                 }
+                                Err(_) => false, // Skip files we can't read
                             }
-                            Err(_) => false, // Skip files we can't read
                         }
                     } else {
                         true
                     };
 
+                    // Check the modification time against the configured range
+                    let filter_modified =
+                        options.modified_after.is_some() || options.modified_before.is_some();
+                    let include = include
+                        && (!filter_modified
+                            || entry
+                                .metadata()
+                                .ok()
+                                .and_then(|metadata| metadata.modified().ok())
+                                .is_some_and(|modified| {
+                                    options.modified_after.is_none_or(|after| modified >= after)
+                                        && options
+                                            .modified_before
+                                            .is_none_or(|before| modified <= before)
+                                }));
+
                     if include {
-                        // Get file type (simplified)
-                        let file_type = if let Some(ext) = path.extension().and_then(|e| e.to_str())
-                        {
+                        // Get file type (simplified), using the shebang for extensionless scripts
+                        let file_type = if is_dir {
+                            "dir".to_string()
+                        } else if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
                             ext.to_lowercase()
+                        } else if let Some(language) = read_shebang_language(path) {
+                            language.to_string()
                         } else {
                             "unknown".to_string()
                         };
 
                         // Apply path prefix removal if configured
                         let processed_path = if let Some(prefix) = &options.omit_path_prefix {
-                            remove_path_prefix(&path.to_path_buf(), prefix)
+                            remove_path_prefix(path, prefix)
                         } else {
                             path.to_path_buf()
                         };
+                        let processed_path = path_display.apply(processed_path);
+
+                        // Read size and modification time if requested
+                        let (size_bytes, modified) = if options.include_metadata {
+                            match entry.metadata() {
+                                Ok(metadata) => (Some(metadata.len()), metadata.modified().ok()),
+                                Err(_) => (None, None),
+                            }
+                        } else {
+                            (None, None)
+                        };
+
+                        // Read the immediate target of symbolic links if requested
+                        let link_target = if options.resolve_target && entry.path_is_symlink() {
+                            std::fs::read_link(path).ok()
+                        } else {
+                            None
+                        };
+
+                        // Read the inode and device numbers for hardlink detection if requested
+                        let (inode, dev) = if options.include_inode {
+                            entry
+                                .metadata()
+                                .map_or((None, None), |metadata| inode_and_device(&metadata))
+                        } else {
+                            (None, None)
+                        };
+
+                        // Look up the git status of files if requested
+                        let git_status =
+                            git_statuses
+                                .as_ref()
+                                .filter(|_| !is_dir)
+                                .and_then(|git_statuses| {
+                                    git_statuses.get(path.strip_prefix(directory).unwrap_or(path))
+                                });
+
+                        // Read the permission bits if requested
+                        let mode = if options.include_mode {
+                            entry
+                                .metadata()
+                                .ok()
+                                .and_then(|metadata| file_mode(&metadata))
+                        } else {
+                            None
+                        };
 
-                        results.push(TraverseResult {
+                        let result = TraverseResult {
                             file_path: processed_path,
                             file_type,
-                        });
+                            size_bytes,
+                            modified,
+                            link_target,
+                            inode,
+                            dev,
+                            git_status,
+                            fuzzy_score: fuzzy_match_score,
+                            mode,
+                        };
+                        if !emit(result) {
+                            // The consumer is no longer interested in results
+                            return Ok(summary);
+                        }
                     }
                 }
             }
@@ -779,10 +1567,40 @@ pub fn traverse_directory(
         }
     }
 
-    // Sort results by path
-    results.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+    Ok(summary)
+}
 
-    Ok(results)
+/// Returns the inode and device numbers of a file.
+#[cfg(unix)]
+fn inode_and_device(metadata: &std::fs::Metadata) -> (Option<u64>, Option<u64>) {
+    use std::os::unix::fs::MetadataExt;
+    (Some(metadata.ino()), Some(metadata.dev()))
+}
+
+/// Returns the inode and device numbers of a file, which are not available on this platform.
+#[cfg(not(unix))]
+fn inode_and_device(_metadata: &std::fs::Metadata) -> (Option<u64>, Option<u64>) {
+    (None, None)
+}
+
+/// Returns the mode of a file.
+#[cfg(unix)]
+fn file_mode(metadata: &std::fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.mode())
+}
+
+/// Returns the mode of a file, which is not available on this platform.
+#[cfg(not(unix))]
+fn file_mode(_metadata: &std::fs::Metadata) -> Option<u32> {
+    None
+}
+
+/// Summary information about a completed walk.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct WalkSummary {
+    /// Whether the depth limit prevented descending into a non-empty directory
+    pub depth_limited: bool,
 }
 
 #[cfg(test)]
@@ -819,7 +1637,30 @@ mod tests {
             only_text_files: true,
             pattern: None,
             depth: None,
+            min_depth: None,
             omit_path_prefix: Some(temp_path.to_path_buf()),
+            include_metadata: false,
+            resolve_target: false,
+            sort: TraverseSort::Path,
+            modified_after: None,
+            modified_before: None,
+            cancel: None,
+            include_inode: false,
+            natural_sort: false,
+            skip_vendored: false,
+            auto_omit_common_prefix: false,
+            one_file_system: false,
+            text_extensions: Vec::new(),
+            binary_extensions: Vec::new(),
+            glob_against_absolute: false,
+            include_dirs: false,
+            tilde_home: false,
+            with_git_status: false,
+            clean_paths: false,
+            pattern_mode: PatternMode::Auto,
+            include_mode: false,
+            include_glob: None,
+            exclude_glob: None,
         };
 
         let results = traverse_directory(temp_path, &options)?;
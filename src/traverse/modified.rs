@@ -0,0 +1,73 @@
+//! Queries for files by modification time.
+//!
+//! This module provides a convenience for audit workflows: listing the files that were
+//! modified within a time range, most recent last.
+
+use anyhow::{Result, anyhow};
+use std::path::Path;
+use std::time::SystemTime;
+
+use super::{TraverseOptions, TraverseResult, traverse_directory};
+
+/// Finds the files modified within an inclusive time range, sorted by modification time.
+///
+/// The directory is traversed with `traverse_directory` using the given options, with
+/// `modified_after` and `modified_before` set to `start` and `end`. Metadata is always read,
+/// so the `modified` (and `size_bytes`) fields of the returned results are populated.
+///
+/// Results with the same modification time are ordered by path.
+///
+/// # Arguments
+///
+/// * `directory` - The directory path to traverse
+/// * `start` - The earliest modification time to include
+/// * `end` - The latest modification time to include
+/// * `options` - Configuration options for the traversal. Its `modified_after`,
+///   `modified_before` and `sort` settings are ignored.
+///
+/// # Returns
+///
+/// The files modified between `start` and `end`, oldest first
+///
+/// # Errors
+///
+/// Returns an error if `start` is later than `end`, or the same errors as `traverse_directory`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::traverse::{TraverseOptions, modified_between};
+/// use std::path::Path;
+/// use std::time::{Duration, SystemTime};
+///
+/// let end = SystemTime::now();
+/// let start = end - Duration::from_secs(24 * 60 * 60);
+///
+/// let results = modified_between(Path::new("."), start, end, &TraverseOptions::default()).unwrap();
+/// for result in results {
+///     println!("{}", result.file_path.display());
+/// }
+/// ```
+pub fn modified_between(
+    directory: &Path,
+    start: SystemTime,
+    end: SystemTime,
+    options: &TraverseOptions,
+) -> Result<Vec<TraverseResult>> {
+    if start > end {
+        return Err(anyhow!("The start of the time range is later than its end"));
+    }
+
+    let traverse_options = TraverseOptions {
+        include_metadata: true,
+        modified_after: Some(start),
+        modified_before: Some(end),
+        ..options.clone()
+    };
+    let mut results = traverse_directory(directory, &traverse_options)?;
+
+    // traverse_directory sorts by path, so the stable sort keeps equal times in path order
+    results.sort_by_key(|result| result.modified);
+
+    Ok(results)
+}
@@ -6,7 +6,7 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 
-use crate::traverse::{TraverseOptions, traverse_directory};
+use crate::traverse::{PatternMode, TraverseOptions, TraverseSort, traverse_directory};
 
 /// Creates a temporary directory with test files for path prefix testing
 fn create_test_files(dir: &Path) -> Result<Vec<String>> {
@@ -55,7 +55,30 @@ fn test_omit_path_prefix_basic() -> Result<()> {
         only_text_files: false,   // Include all files for testing
         pattern: None,
         depth: None,
+        min_depth: None,
         omit_path_prefix: Some(temp_path.to_path_buf()),
+        include_metadata: false,
+        resolve_target: false,
+        sort: TraverseSort::Path,
+        modified_after: None,
+        modified_before: None,
+        cancel: None,
+        include_inode: false,
+        natural_sort: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        one_file_system: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        glob_against_absolute: false,
+        include_dirs: false,
+        tilde_home: false,
+        with_git_status: false,
+        clean_paths: false,
+        pattern_mode: PatternMode::Auto,
+        include_mode: false,
+        include_glob: None,
+        exclude_glob: None,
     };
 
     let results = traverse_directory(temp_path, &options)?;
@@ -108,7 +131,30 @@ fn test_omit_path_prefix_without_removal() -> Result<()> {
         only_text_files: false,
         pattern: None,
         depth: None,
+        min_depth: None,
         omit_path_prefix: None, // No prefix removal
+        include_metadata: false,
+        resolve_target: false,
+        sort: TraverseSort::Path,
+        modified_after: None,
+        modified_before: None,
+        cancel: None,
+        include_inode: false,
+        natural_sort: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        one_file_system: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        glob_against_absolute: false,
+        include_dirs: false,
+        tilde_home: false,
+        with_git_status: false,
+        clean_paths: false,
+        pattern_mode: PatternMode::Auto,
+        include_mode: false,
+        include_glob: None,
+        exclude_glob: None,
     };
 
     let results = traverse_directory(temp_path, &options)?;
@@ -142,13 +188,36 @@ fn test_omit_path_prefix_with_pattern() -> Result<()> {
         only_text_files: false,
         pattern: Some("**/*.rs".to_string()), // Only Rust files
         depth: None,
+        min_depth: None,
         omit_path_prefix: Some(temp_path.to_path_buf()),
+        include_metadata: false,
+        resolve_target: false,
+        sort: TraverseSort::Path,
+        modified_after: None,
+        modified_before: None,
+        cancel: None,
+        include_inode: false,
+        natural_sort: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        one_file_system: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        glob_against_absolute: false,
+        include_dirs: false,
+        tilde_home: false,
+        with_git_status: false,
+        clean_paths: false,
+        pattern_mode: PatternMode::Auto,
+        include_mode: false,
+        include_glob: None,
+        exclude_glob: None,
     };
 
     let results = traverse_directory(temp_path, &options)?;
 
     // Should only find Rust files
-    assert!(results.len() > 0, "Should find some Rust files");
+    assert!(!results.is_empty(), "Should find some Rust files");
     assert!(results.len() < 8, "Should not find all test files");
 
     // Check that only Rust files are included and prefixes are removed
@@ -193,7 +262,30 @@ fn test_omit_path_prefix_partial_match() -> Result<()> {
         only_text_files: false,
         pattern: None,
         depth: None,
+        min_depth: None,
         omit_path_prefix: Some(non_matching_prefix.clone()),
+        include_metadata: false,
+        resolve_target: false,
+        sort: TraverseSort::Path,
+        modified_after: None,
+        modified_before: None,
+        cancel: None,
+        include_inode: false,
+        natural_sort: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        one_file_system: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        glob_against_absolute: false,
+        include_dirs: false,
+        tilde_home: false,
+        with_git_status: false,
+        clean_paths: false,
+        pattern_mode: PatternMode::Auto,
+        include_mode: false,
+        include_glob: None,
+        exclude_glob: None,
     };
 
     let results = traverse_directory(temp_path, &options)?;
@@ -228,13 +320,36 @@ fn test_omit_path_prefix_with_depth_limit() -> Result<()> {
         only_text_files: false,
         pattern: None,
         depth: Some(1), // Only files in the root directory
+        min_depth: None,
         omit_path_prefix: Some(temp_path.to_path_buf()),
+        include_metadata: false,
+        resolve_target: false,
+        sort: TraverseSort::Path,
+        modified_after: None,
+        modified_before: None,
+        cancel: None,
+        include_inode: false,
+        natural_sort: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        one_file_system: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        glob_against_absolute: false,
+        include_dirs: false,
+        tilde_home: false,
+        with_git_status: false,
+        clean_paths: false,
+        pattern_mode: PatternMode::Auto,
+        include_mode: false,
+        include_glob: None,
+        exclude_glob: None,
     };
 
     let results = traverse_directory(temp_path, &options)?;
 
     // Should only find files in the root directory
-    assert!(results.len() > 0, "Should find some files");
+    assert!(!results.is_empty(), "Should find some files");
     assert!(results.len() < 8, "Should not find files in subdirectories");
 
     // Check that paths are correctly processed
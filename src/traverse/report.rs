@@ -0,0 +1,79 @@
+//! Traversal results with run-level metadata.
+//!
+//! `traverse_directory` returns a bare list of results. This module wraps the results in a
+//! serializable report carrying information about the traversal run itself.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Instant;
+
+use super::{TraverseOptions, TraverseResult, finish_results, walk_directory};
+
+/// Traversal results together with metadata about the traversal run.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::traverse::{TraverseOptions, traverse_directory_report};
+/// use std::path::Path;
+///
+/// let report = traverse_directory_report(Path::new("src"), &TraverseOptions::default()).unwrap();
+/// println!("{}", serde_json::to_string_pretty(&report).unwrap());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TraverseReport {
+    /// The traversal results, sorted as returned by `traverse_directory`
+    pub results: Vec<TraverseResult>,
+
+    /// The number of results
+    pub total: usize,
+
+    /// Whether the `depth` limit prevented the traversal from descending into at least one
+    /// non-empty directory, meaning that some files may be missing from the results
+    pub depth_limited: bool,
+
+    /// The time the traversal took, in milliseconds
+    pub elapsed_ms: u64,
+}
+
+/// Traverses a directory and returns the results together with run-level metadata.
+///
+/// This performs the same traversal as `traverse_directory`, but wraps the results in a
+/// `TraverseReport` that also records the number of results, whether the depth limit was hit,
+/// and the time taken.
+///
+/// # Arguments
+///
+/// * `directory` - The directory path to traverse
+/// * `options` - Configuration options for the traversal
+///
+/// # Returns
+///
+/// A `TraverseReport` containing the sorted results and metadata
+///
+/// # Errors
+///
+/// Returns the same errors as `traverse_directory`
+pub fn traverse_directory_report(
+    directory: &Path,
+    options: &TraverseOptions,
+) -> Result<TraverseReport> {
+    let start = Instant::now();
+
+    let mut results = Vec::new();
+    let summary = walk_directory(directory, options, |result| {
+        results.push(result);
+        true
+    })?;
+
+    // Process results as traverse_directory does
+    finish_results(&mut results, options);
+
+    Ok(TraverseReport {
+        total: results.len(),
+        results,
+        depth_limited: summary.depth_limited,
+        elapsed_ms: start.elapsed().as_millis() as u64,
+    })
+}
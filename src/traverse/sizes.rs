@@ -0,0 +1,90 @@
+//! Disk usage aggregation built on directory traversal.
+//!
+//! This module sums file sizes into their ancestor directories, producing a
+//! `du`-style summary of how much space each directory occupies.
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::{TraverseOptions, traverse_directory};
+use crate::paths::remove_path_prefix;
+
+/// Computes the total size of the files within each directory, recursively.
+///
+/// The directory is traversed with `traverse_directory` using the given options, so gitignore
+/// handling, text-only filtering, patterns and depth limits all apply. The size of every file
+/// found is then added to each of its ancestor directories, up to and including `directory`
+/// itself.
+///
+/// Only directories that contain at least one included file (directly or in a subdirectory)
+/// are reported. Sizes are apparent file sizes in bytes, not allocated disk blocks.
+///
+/// # Arguments
+///
+/// * `directory` - The root directory to aggregate sizes for
+/// * `options` - Traverse options controlling which files are counted. `include_metadata` is
+///   always enabled internally, and `omit_path_prefix` is applied to the returned directory paths.
+///
+/// # Returns
+///
+/// A list of `(directory, total_bytes)` pairs sorted by directory path, similar to `du` output.
+///
+/// # Errors
+///
+/// Returns the same errors as `traverse_directory`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::traverse::{TraverseOptions, directory_sizes};
+/// use std::path::Path;
+///
+/// let options = TraverseOptions {
+///     only_text_files: false,
+///     ..TraverseOptions::default()
+/// };
+///
+/// for (dir, size) in directory_sizes(Path::new("."), &options).unwrap() {
+///     println!("{:>10} {}", size, dir.display());
+/// }
+/// ```
+pub fn directory_sizes(directory: &Path, options: &TraverseOptions) -> Result<Vec<(PathBuf, u64)>> {
+    // Traverse with absolute paths and metadata so files can be attributed to their ancestors
+    let traverse_options = TraverseOptions {
+        include_metadata: true,
+        omit_path_prefix: None,
+        ..options.clone()
+    };
+    let results = traverse_directory(directory, &traverse_options)?;
+
+    let mut sizes: BTreeMap<PathBuf, u64> = BTreeMap::new();
+    for result in &results {
+        let size = result.size_bytes.unwrap_or(0);
+
+        // Add the file size to every ancestor up to the root directory
+        let mut current = result.file_path.parent();
+        while let Some(dir) = current {
+            *sizes.entry(dir.to_path_buf()).or_default() += size;
+            if dir == directory || !dir.starts_with(directory) {
+                break;
+            }
+            current = dir.parent();
+        }
+    }
+
+    // Apply path prefix removal if configured
+    let sizes = sizes
+        .into_iter()
+        .map(|(dir, size)| {
+            let dir = if let Some(prefix) = &options.omit_path_prefix {
+                remove_path_prefix(&dir, prefix)
+            } else {
+                dir
+            };
+            (dir, size)
+        })
+        .collect();
+
+    Ok(sizes)
+}
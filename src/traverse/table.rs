@@ -0,0 +1,237 @@
+//! Printable table formatting for traversal results.
+//!
+//! This module turns a list of `TraverseResult` items into an aligned, human-readable
+//! table, similar to the output of `ls -l`. Columns are configurable, and optional
+//! metadata columns (size, modification time) are only shown when at least one result
+//! carries that information.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::TraverseResult;
+
+/// A column that can be displayed by [`format_table`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableColumn {
+    /// A `*` marker for hidden files, blank otherwise
+    Hidden,
+    /// The detected file type (typically the extension)
+    FileType,
+    /// The file size in bytes (right-aligned)
+    Size,
+    /// The last modification time, formatted as `YYYY-MM-DD HH:MM:SS` (UTC)
+    Modified,
+    /// The file path, followed by `-> target` for symbolic links with a resolved target
+    Path,
+}
+
+/// Configuration options for table formatting.
+///
+/// # Examples
+///
+/// ```
+/// use lumin::traverse::{TableColumn, TableOptions};
+///
+/// // Default: all columns, with a header row
+/// let default_options = TableOptions::default();
+///
+/// // Only type and path, without a header
+/// let compact_options = TableOptions {
+///     columns: vec![TableColumn::FileType, TableColumn::Path],
+///     header: false,
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct TableOptions {
+    /// The columns to display, in order.
+    ///
+    /// `Size` and `Modified` columns are dropped automatically when no result has the
+    /// corresponding metadata (see `TraverseOptions::include_metadata`).
+    pub columns: Vec<TableColumn>,
+
+    /// Whether to print a header row with the column names.
+    pub header: bool,
+}
+
+impl Default for TableOptions {
+    fn default() -> Self {
+        Self {
+            columns: vec![
+                TableColumn::Hidden,
+                TableColumn::FileType,
+                TableColumn::Size,
+                TableColumn::Modified,
+                TableColumn::Path,
+            ],
+            header: true,
+        }
+    }
+}
+
+impl TableColumn {
+    /// Returns the header label of this column
+    fn title(&self) -> &'static str {
+        match self {
+            TableColumn::Hidden => "",
+            TableColumn::FileType => "TYPE",
+            TableColumn::Size => "SIZE",
+            TableColumn::Modified => "MODIFIED",
+            TableColumn::Path => "PATH",
+        }
+    }
+
+    /// Returns the cell value of this column for the given result
+    fn cell(&self, result: &TraverseResult) -> String {
+        match self {
+            TableColumn::Hidden => {
+                if result.is_hidden() {
+                    "*".to_string()
+                } else {
+                    " ".to_string()
+                }
+            }
+            TableColumn::FileType => result.file_type.clone(),
+            TableColumn::Size => result
+                .size_bytes
+                .map(|size| size.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            TableColumn::Modified => result
+                .modified
+                .map(format_system_time)
+                .unwrap_or_else(|| "-".to_string()),
+            TableColumn::Path => match &result.link_target {
+                Some(target) => format!("{} -> {}", result.file_path.display(), target.display()),
+                None => result.file_path.display().to_string(),
+            },
+        }
+    }
+
+    /// Whether this column holds data for at least one of the results
+    fn is_present(&self, results: &[TraverseResult]) -> bool {
+        match self {
+            TableColumn::Size => results.iter().any(|r| r.size_bytes.is_some()),
+            TableColumn::Modified => results.iter().any(|r| r.modified.is_some()),
+            _ => true,
+        }
+    }
+}
+
+/// Formats traversal results as an aligned, printable table.
+///
+/// Each configured column is padded to the width of its widest cell so that columns line
+/// up across rows. Sizes are right-aligned, all other columns are left-aligned, and the
+/// last column is never padded. Metadata columns without any data are omitted entirely.
+///
+/// # Arguments
+///
+/// * `results` - The traversal results to format
+/// * `options` - The columns to show and whether to include a header row
+///
+/// # Returns
+///
+/// The formatted table with one line per result, each terminated by a newline.
+/// An empty string is returned when there are no results.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::traverse::{TableOptions, TraverseOptions, format_table, traverse_directory};
+/// use std::path::Path;
+///
+/// let options = TraverseOptions {
+///     include_metadata: true,
+///     ..TraverseOptions::default()
+/// };
+/// let results = traverse_directory(Path::new("src"), &options).unwrap();
+///
+/// print!("{}", format_table(&results, &TableOptions::default()));
+/// ```
+pub fn format_table(results: &[TraverseResult], options: &TableOptions) -> String {
+    if results.is_empty() {
+        return String::new();
+    }
+
+    let columns: Vec<TableColumn> = options
+        .columns
+        .iter()
+        .copied()
+        .filter(|column| column.is_present(results))
+        .collect();
+
+    let mut rows: Vec<Vec<String>> = Vec::with_capacity(results.len() + 1);
+    if options.header {
+        rows.push(columns.iter().map(|c| c.title().to_string()).collect());
+    }
+    for result in results {
+        rows.push(columns.iter().map(|c| c.cell(result)).collect());
+    }
+
+    // Compute the display width of each column
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut output = String::new();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                let is_last = i + 1 == columns.len();
+                match columns[i] {
+                    TableColumn::Size => format!("{:>width$}", cell, width = widths[i]),
+                    _ if is_last => cell.clone(),
+                    _ => format!("{:<width$}", cell, width = widths[i]),
+                }
+            })
+            .collect();
+        output.push_str(cells.join(" ").trim_end());
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Formats a `SystemTime` as `YYYY-MM-DD HH:MM:SS` in UTC.
+///
+/// Times before the Unix epoch are clamped to the epoch.
+fn format_system_time(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let days = (secs / 86_400) as i64;
+    let secs_of_day = secs % 86_400;
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Converts a number of days since 1970-01-01 into a (year, month, day) civil date.
+///
+/// This is Howard Hinnant's `civil_from_days` algorithm for the proleptic Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
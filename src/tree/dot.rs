@@ -0,0 +1,84 @@
+//! Export of directory trees as Graphviz DOT graphs.
+//!
+//! The DOT output can be rendered with `dot -Tsvg` (or any other Graphviz layout engine) to
+//! include a directory structure in documentation.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+
+use super::{DirectoryTree, Entry};
+
+/// Renders directory trees as a Graphviz `digraph`.
+///
+/// Every directory and file becomes a node, identified by its path and labeled with its name.
+/// Directories are drawn as folders and files as notes, with an edge from each directory to
+/// each of its entries. Quotes, backslashes and line breaks in names are escaped, and names
+/// that are not valid UTF-8 are converted lossily.
+///
+/// # Arguments
+///
+/// * `trees` - The directory trees, as returned by `generate_tree`
+///
+/// # Returns
+///
+/// The DOT source of the graph
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::tree::{TreeOptions, generate_tree, to_dot};
+/// use std::path::Path;
+///
+/// let trees = generate_tree(Path::new("src"), &TreeOptions::default()).unwrap();
+/// std::fs::write("tree.dot", to_dot(&trees)).unwrap();
+/// // Render with: dot -Tsvg tree.dot -o tree.svg
+/// ```
+pub fn to_dot(trees: &[DirectoryTree]) -> String {
+    let mut dot = String::from("digraph tree {\n    rankdir=LR;\n    node [shape=box];\n");
+    let mut declared = HashSet::new();
+
+    // Writing to a String can't fail
+    let mut declare = |dot: &mut String, path: &Path, shape: &str| {
+        let id = quote(&path.to_string_lossy());
+        if declared.insert(id.clone()) {
+            let name = path.file_name().unwrap_or(path.as_os_str());
+            let label = quote(&name.to_string_lossy());
+            let _ = writeln!(dot, "    {} [label={}, shape={}];", id, label, shape);
+        }
+        id
+    };
+
+    for tree in trees {
+        let parent = declare(&mut dot, &tree.dir, "folder");
+        for entry in &tree.entries {
+            let path = tree.dir.join(entry.name());
+            let shape = match entry {
+                Entry::Directory { .. } => "folder",
+                Entry::File { .. } => "note",
+            };
+            let child = declare(&mut dot, &path, shape);
+            let _ = writeln!(dot, "    {} -> {};", parent, child);
+        }
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Quotes a string as a DOT identifier
+fn quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,54 @@
+//! Flat file listing with the filtering of `generate_tree`.
+//!
+//! `traverse_directory` has its own filters (such as the text/binary check), so its results
+//! can differ from the files shown in a tree. This module lists exactly the files that
+//! `generate_tree` would show, as a flat list of paths.
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use super::{TreeOptions, walk_tree_entries};
+use crate::paths::remove_path_prefix;
+
+/// Lists the files that `generate_tree` would include, as a flat list of paths.
+///
+/// The same walker and filters as `generate_tree` are used, so `respect_gitignore`, `depth`,
+/// `max_file_size` and `skip_vendored` apply as in the tree, and files are not filtered by
+/// content type. Paths have `omit_path_prefix` removed. `entry_sort` and `max_directories`
+/// don't apply, since no directory structure is built.
+///
+/// # Arguments
+///
+/// * `directory` - The directory to list the files of
+/// * `options` - Configuration options for the tree
+///
+/// # Returns
+///
+/// The file paths, sorted by path
+///
+/// # Errors
+///
+/// Returns an error if there's an issue accessing the directory
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::tree::{TreeOptions, list_files};
+/// use std::path::Path;
+///
+/// for file in list_files(Path::new("src"), &TreeOptions::default()).unwrap() {
+///     println!("{}", file.display());
+/// }
+/// ```
+pub fn list_files(directory: &Path, options: &TreeOptions) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = walk_tree_entries(directory, options, None)?
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| match &options.omit_path_prefix {
+            Some(prefix) => remove_path_prefix(entry.path(), prefix),
+            None => entry.into_path(),
+        })
+        .collect();
+
+    files.sort_by(|a, b| a.as_os_str().cmp(b.as_os_str()));
+    Ok(files)
+}
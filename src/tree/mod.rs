@@ -1,18 +1,37 @@
 use anyhow::Result;
+use ignore::DirEntry;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::ffi::{OsStr, OsString};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 #[cfg(test)]
 mod path_prefix_test;
+// Graphviz DOT export
+mod dot;
+// Flat file listing with tree semantics
+mod files;
+// Tree with run-level metadata
+mod report;
+// Ordering of the entries within each directory
+mod sort;
+pub use dot::to_dot;
+pub use files::list_files;
+pub use report::{TreeReport, generate_tree_report};
+pub use sort::EntrySort;
 
 // Reuse the common traversal logic
 use crate::paths::remove_path_prefix;
 use crate::telemetry::{LogMessage, log_with_context};
-use crate::traverse::common::{build_walk, is_hidden_path};
+use crate::traverse::common::{FileFilter, WalkConfig, build_walk, is_hidden_path};
 
 /// Configuration options for directory tree operations.
-#[derive(Debug, Clone)]
+///
+/// Fields missing from serialized input take their default values, and unknown fields are
+/// rejected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct TreeOptions {
     /// Whether file path matching should be case sensitive
     pub case_sensitive: bool,
@@ -38,6 +57,73 @@ pub struct TreeOptions {
     ///   `/home/user/projects/myrepo/src/util` to `src/util` in the results
     /// - `omit_path_prefix: None` will leave all directory paths unchanged
     pub omit_path_prefix: Option<PathBuf>,
+
+    /// Maximum size in bytes of files to include in the tree.
+    ///
+    /// Files larger than this are omitted from the entries, which keeps huge generated files
+    /// out of the tree. Directories are always listed. When set to `None` (default), files of
+    /// any size are included.
+    pub max_file_size: Option<u64>,
+
+    /// The order of the entries within each directory.
+    ///
+    /// - `EntrySort::Name` (default) - Entries are sorted by name, like `ls`
+    /// - `EntrySort::Extension` - Entries are sorted by extension, then by name, like `ls -X`
+    /// - `EntrySort::Natural` - Entries are sorted by name with numbers compared by value,
+    ///   like `ls -v`
+    ///
+    /// Files and directories are sorted together.
+    ///
+    /// # Examples
+    ///
+    /// - `entry_sort: EntrySort::Natural` - `file2` is listed before `file10`
+    /// - `entry_sort: EntrySort::Extension` - `b.md` is listed before `a.txt`
+    pub entry_sort: EntrySort,
+
+    /// Whether to skip directories that usually contain vendored or generated files.
+    ///
+    /// When `true`, directories named like one of the `traverse::common::VENDORED_DIR_NAMES`
+    /// (`node_modules`, `vendor`, `dist`, `build`, `target` and `.venv`) are left out of the
+    /// tree. The root directory itself is never skipped. When `false` (default), they are
+    /// included.
+    pub skip_vendored: bool,
+
+    /// Optional maximum number of directories in the tree.
+    ///
+    /// When `Some(max)`, directories found after `max` directories (including the root) have
+    /// been collected are still listed as entries of their parent, but their contents are
+    /// left out, and `TreeReport::truncated` is set. This keeps the output bounded when a
+    /// huge directory (such as `/`) is passed by accident. When `None` (default), all
+    /// directories are included.
+    ///
+    /// Directories are collected in the order of the walk, which descends depth first.
+    ///
+    /// # Examples
+    ///
+    /// - `max_directories: Some(1000)` - At most 1000 directories are listed with contents
+    pub max_directories: Option<usize>,
+
+    /// Optional list of glob patterns a file must match to be listed.
+    ///
+    /// Patterns are matched against paths relative to the tree directory, and may contain
+    /// negations. Files matched by `exclude_glob` are left out even if they match.
+    /// Directories are always listed. When `None` (default), all files are listed. See
+    /// `traverse::common::FileFilter` for the shared semantics with search and traverse.
+    ///
+    /// # Examples
+    ///
+    /// - `include_glob: Some(vec!["**/*.rs".to_string()])` - Only Rust files are listed
+    pub include_glob: Option<Vec<String>>,
+
+    /// Optional list of glob patterns of files to leave out of the tree.
+    ///
+    /// Patterns are matched like `include_glob`, and take precedence over it. Directories are
+    /// always listed. When `None` (default), no files are left out.
+    ///
+    /// # Examples
+    ///
+    /// - `exclude_glob: Some(vec!["**/*.snap".to_string()])` - Snapshot files are not listed
+    pub exclude_glob: Option<Vec<String>>,
 }
 
 impl Default for TreeOptions {
@@ -47,26 +133,52 @@ impl Default for TreeOptions {
             respect_gitignore: true,
             depth: Some(20),
             omit_path_prefix: None,
+            max_file_size: None,
+            entry_sort: EntrySort::Name,
+            skip_vendored: false,
+            max_directories: None,
+            include_glob: None,
+            exclude_glob: None,
         }
     }
 }
 
 /// Represents a directory entry in the tree.
+///
+/// Names that are not valid UTF-8 are serialized without loss (see the `paths` module).
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type")]
 pub enum Entry {
     #[serde(rename = "file")]
-    File { name: String },
+    File {
+        #[serde(with = "crate::paths::serde_os_str")]
+        name: OsString,
+    },
 
     #[serde(rename = "directory")]
-    Directory { name: String },
+    Directory {
+        #[serde(with = "crate::paths::serde_os_str")]
+        name: OsString,
+    },
+}
+
+impl Entry {
+    /// Returns the name of the entry.
+    pub fn name(&self) -> &OsStr {
+        match self {
+            Entry::File { name } | Entry::Directory { name } => name,
+        }
+    }
 }
 
 /// Represents a directory and its contents in the tree.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DirectoryTree {
-    /// Path to the directory
-    pub dir: String,
+    /// Path to the directory.
+    ///
+    /// Paths that are not valid UTF-8 are serialized without loss (see the `paths` module).
+    #[serde(with = "crate::paths::serde_os_str")]
+    pub dir: PathBuf,
 
     /// List of entries in this directory
     pub entries: Vec<Entry>,
@@ -87,58 +199,51 @@ pub struct DirectoryTree {
 ///
 /// Returns an error if there's an issue accessing the directory or files
 pub fn generate_tree(directory: &Path, options: &TreeOptions) -> Result<Vec<DirectoryTree>> {
-    // Use the common builder setup from traverse module
-    let walker = build_walk(
-        directory,
-        options.respect_gitignore,
-        options.case_sensitive,
-        options.depth,
-    )?;
+    build_tree(directory, options).map(|(trees, _)| trees)
+}
 
+/// Builds the directory trees of `generate_tree`.
+///
+/// # Returns
+///
+/// The directory trees, and whether directories were left out due to `max_directories`
+fn build_tree(directory: &Path, options: &TreeOptions) -> Result<(Vec<DirectoryTree>, bool)> {
     // Map to organize entries by directory
-    let mut dirs_map: HashMap<String, Vec<Entry>> = HashMap::new();
+    let mut dirs_map: HashMap<PathBuf, Vec<Entry>> = HashMap::new();
+    let mut truncated = false;
+    let at_directory_limit = |dirs_map: &HashMap<PathBuf, Vec<Entry>>| {
+        options
+            .max_directories
+            .is_some_and(|max_directories| dirs_map.len() >= max_directories)
+    };
+
+    // Directories left out due to the limit are pruned from the walk, so their contents are
+    // not read
+    let pruned_dirs = options
+        .max_directories
+        .map(|_| Arc::new(Mutex::new(HashSet::new())));
+    let prune = |path: &Path| {
+        if let Some(pruned_dirs) = &pruned_dirs {
+            let mut pruned_dirs = pruned_dirs.lock().unwrap_or_else(|e| e.into_inner());
+            pruned_dirs.insert(path.to_path_buf());
+        }
+    };
 
     // Process root directory with path prefix removal if configured
     let root_dir_path = if let Some(prefix) = &options.omit_path_prefix {
-        let processed_path = remove_path_prefix(directory, prefix);
-        processed_path
+        remove_path_prefix(directory, prefix)
     } else {
         directory.to_path_buf()
     };
 
     // Add the root directory as the first entry
-    let root_dir_key = root_dir_path.to_string_lossy().to_string();
+    let root_dir_key = root_dir_path;
     dirs_map.insert(root_dir_key.clone(), Vec::new());
 
     // Process each entry from the walker
-    for result in walker {
-        let entry = match result {
-            Ok(entry) => entry,
-            Err(err) => {
-                log_with_context(
-                    log::Level::Warn,
-                    LogMessage {
-                        message: format!("Error walking directory: {}", err),
-                        module: "tree",
-                        context: Some(vec![("directory", directory.display().to_string())]),
-                    },
-                );
-                continue;
-            }
-        };
-
+    for entry in walk_tree_entries(directory, options, pruned_dirs.clone())? {
         let path = entry.path();
 
-        // Skip the directory itself
-        if path == directory {
-            continue;
-        }
-
-        // Skip if respecting gitignore and this is a hidden path
-        if options.respect_gitignore && is_hidden_path(path) {
-            continue;
-        }
-
         // Process the path with prefix removal if configured
         let processed_path = if let Some(prefix) = &options.omit_path_prefix {
             remove_path_prefix(path, prefix)
@@ -151,11 +256,7 @@ pub fn generate_tree(directory: &Path, options: &TreeOptions) -> Result<Vec<Dire
             if parent == directory {
                 if path.is_file() {
                     let entry = Entry::File {
-                        name: path
-                            .file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy()
-                            .to_string(),
+                        name: path.file_name().unwrap_or_default().to_os_string(),
                     };
 
                     dirs_map
@@ -164,22 +265,20 @@ pub fn generate_tree(directory: &Path, options: &TreeOptions) -> Result<Vec<Dire
                         .push(entry);
                 } else if path.is_dir() {
                     // Add directory to root's entries
-                    let dir_name = path
-                        .file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_string();
-                    let entry = Entry::Directory {
-                        name: dir_name.clone(),
-                    };
+                    let dir_name = path.file_name().unwrap_or_default().to_os_string();
+                    let entry = Entry::Directory { name: dir_name };
                     dirs_map
                         .entry(root_dir_key.clone())
                         .or_default()
                         .push(entry);
 
                     // Also create an entry for this directory with processed path
-                    let sub_dir_key = processed_path.to_string_lossy().to_string();
-                    dirs_map.insert(sub_dir_key, Vec::new());
+                    if at_directory_limit(&dirs_map) {
+                        truncated = true;
+                        prune(path);
+                    } else {
+                        dirs_map.insert(processed_path, Vec::new());
+                    }
                 }
             } else {
                 // For entries not directly in root
@@ -195,45 +294,48 @@ pub fn generate_tree(directory: &Path, options: &TreeOptions) -> Result<Vec<Dire
                     }
                 };
 
-                let parent_key = processed_parent.to_string_lossy().to_string();
+                let parent_key = processed_parent;
 
-                // Make sure the parent directory exists in our map
+                // Make sure the parent directory exists in our map, unless the limit is reached
                 if !dirs_map.contains_key(&parent_key) {
+                    if at_directory_limit(&dirs_map) {
+                        truncated = true;
+                        continue;
+                    }
                     dirs_map.insert(parent_key.clone(), Vec::new());
                 }
 
                 if path.is_file() {
                     let entry = Entry::File {
-                        name: path
-                            .file_name()
-                            .unwrap_or_default()
-                            .to_string_lossy()
-                            .to_string(),
+                        name: path.file_name().unwrap_or_default().to_os_string(),
                     };
 
                     dirs_map.entry(parent_key).or_default().push(entry);
                 } else if path.is_dir() {
                     // Add directory to parent's entries
-                    let dir_name = path
-                        .file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy()
-                        .to_string();
+                    let dir_name = path.file_name().unwrap_or_default().to_os_string();
                     let entry = Entry::Directory { name: dir_name };
                     dirs_map.entry(parent_key).or_default().push(entry);
 
                     // Also create an entry for this directory with processed path
-                    let sub_dir_key = processed_path.to_string_lossy().to_string();
-                    dirs_map.insert(sub_dir_key, Vec::new());
+                    if at_directory_limit(&dirs_map) {
+                        truncated = true;
+                        prune(path);
+                    } else {
+                        dirs_map.insert(processed_path, Vec::new());
+                    }
                 }
             }
         }
     }
-    // Convert the map to a vector of DirectoryTree objects
+    // Convert the map to a vector of DirectoryTree objects with sorted entries
     let mut result: Vec<DirectoryTree> = dirs_map
         .into_iter()
         .filter(|(_, entries)| !entries.is_empty()) // Filter out empty directories
-        .map(|(dir, entries)| DirectoryTree { dir, entries })
+        .map(|(dir, mut entries)| {
+            entries.sort_by(|a, b| options.entry_sort.compare(a, b));
+            DirectoryTree { dir, entries }
+        })
         .collect();
 
     // If no directories have entries, add at least the root directory with a placeholder
@@ -246,15 +348,94 @@ pub fn generate_tree(directory: &Path, options: &TreeOptions) -> Result<Vec<Dire
         };
 
         result.push(DirectoryTree {
-            dir: root_dir_path.to_string_lossy().to_string(),
+            dir: root_dir_path,
             entries: vec![Entry::Directory {
-                name: ".".to_string(),
+                name: OsString::from("."),
             }],
         });
     }
 
     // Sort by directory path
-    result.sort_by(|a, b| a.dir.cmp(&b.dir));
+    result.sort_by(|a, b| a.dir.as_os_str().cmp(b.dir.as_os_str()));
+
+    Ok((result, truncated))
+}
+
+/// Walks the entries included in a tree, below the root directory itself.
+///
+/// Hidden entries (when respecting gitignore) and files larger than `max_file_size` are
+/// skipped, as are the contents of the directories in `pruned_dirs`. Errors for individual
+/// entries are logged and skipped.
+fn walk_tree_entries(
+    directory: &Path,
+    options: &TreeOptions,
+    pruned_dirs: Option<Arc<Mutex<HashSet<PathBuf>>>>,
+) -> Result<impl Iterator<Item = DirEntry>> {
+    // Use the common builder setup from traverse module
+    let walker = build_walk(
+        directory,
+        &WalkConfig {
+            respect_gitignore: options.respect_gitignore,
+            case_sensitive: options.case_sensitive,
+            max_depth: options.depth,
+            skip_vendored: options.skip_vendored,
+            pruned_dirs,
+            ..WalkConfig::default()
+        },
+    )?;
+
+    // Compile the include and exclude patterns once rather than for every file
+    let file_filter = FileFilter::new(
+        directory,
+        options.include_glob.as_ref(),
+        options.exclude_glob.as_ref(),
+        options.case_sensitive,
+    )?;
+
+    let directory = directory.to_path_buf();
+    let respect_gitignore = options.respect_gitignore;
+    let max_file_size = options.max_file_size;
+    Ok(walker.filter_map(move |result| {
+        let entry = match result {
+            Ok(entry) => entry,
+            Err(err) => {
+                log_with_context(
+                    log::Level::Warn,
+                    LogMessage {
+                        message: format!("Error walking directory: {}", err),
+                        module: "tree",
+                        context: Some(vec![("directory", directory.display().to_string())]),
+                    },
+                );
+                return None;
+            }
+        };
+
+        let path = entry.path();
+
+        // Skip the directory itself
+        if path == directory {
+            return None;
+        }
+
+        // Skip if respecting gitignore and this is a hidden path
+        if respect_gitignore && is_hidden_path(path) {
+            return None;
+        }
+
+        // Skip files larger than the size limit
+        if let Some(max_file_size) = max_file_size {
+            if path.is_file() && path.metadata().is_ok_and(|m| m.len() > max_file_size) {
+                return None;
+            }
+        }
+
+        // Skip files left out by the include and exclude globs. Walked paths are always below
+        // the directory, so matching can't fail.
+        if path.is_file() && !file_filter.matches(path).unwrap_or(true) {
+            return None;
+        }
 
-    Ok(result)
+        Some(entry)
+    }))
 }
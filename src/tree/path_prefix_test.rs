@@ -6,7 +6,7 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 use tempfile::TempDir;
 
-use crate::tree::{TreeOptions, generate_tree};
+use crate::tree::{EntrySort, TreeOptions, generate_tree};
 
 /// Creates a temporary directory with test files for path prefix testing
 fn create_test_directory_structure(dir: &Path) -> Result<()> {
@@ -55,6 +55,12 @@ fn test_omit_path_prefix_basic() -> Result<()> {
         respect_gitignore: false, // No gitignore in temp dir
         depth: None,
         omit_path_prefix: Some(temp_path.to_path_buf()),
+        max_file_size: None,
+        entry_sort: EntrySort::Name,
+        skip_vendored: false,
+        max_directories: None,
+        include_glob: None,
+        exclude_glob: None,
     };
 
     let tree_result = generate_tree(temp_path, &options)?;
@@ -64,7 +70,7 @@ fn test_omit_path_prefix_basic() -> Result<()> {
 
     // Check that directory paths don't have the temp path prefix
     for dir_tree in &tree_result {
-        let normalized_dir = normalize_path(&dir_tree.dir);
+        let normalized_dir = normalize_path(&dir_tree.dir.to_string_lossy());
         assert!(
             !normalized_dir.contains(temp_path.to_string_lossy().as_ref()),
             "Directory path '{}' should not contain the temp path prefix",
@@ -81,7 +87,10 @@ fn test_omit_path_prefix_basic() -> Result<()> {
     }
 
     // Check that all expected directory structures are present
-    let dir_names: Vec<String> = tree_result.iter().map(|d| normalize_path(&d.dir)).collect();
+    let dir_names: Vec<String> = tree_result
+        .iter()
+        .map(|d| normalize_path(&d.dir.to_string_lossy()))
+        .collect();
 
     // Check for the root directory (could be "" or "." depending on implementation)
     assert!(
@@ -118,13 +127,19 @@ fn test_omit_path_prefix_without_removal() -> Result<()> {
         respect_gitignore: false,
         depth: None,
         omit_path_prefix: None, // No prefix removal
+        max_file_size: None,
+        entry_sort: EntrySort::Name,
+        skip_vendored: false,
+        max_directories: None,
+        include_glob: None,
+        exclude_glob: None,
     };
 
     let tree_result = generate_tree(temp_path, &options)?;
 
     // Verify that directory paths contain the temp path prefix
     for dir_tree in &tree_result {
-        let normalized_dir = normalize_path(&dir_tree.dir);
+        let normalized_dir = normalize_path(&dir_tree.dir.to_string_lossy());
         assert!(
             normalized_dir.starts_with(&normalize_path(&temp_path.to_string_lossy())),
             "Directory path '{}' should start with the temp path prefix",
@@ -156,13 +171,19 @@ fn test_omit_path_prefix_partial_match() -> Result<()> {
         respect_gitignore: false,
         depth: None,
         omit_path_prefix: Some(non_matching_prefix.clone()),
+        max_file_size: None,
+        entry_sort: EntrySort::Name,
+        skip_vendored: false,
+        max_directories: None,
+        include_glob: None,
+        exclude_glob: None,
     };
 
     let tree_result = generate_tree(temp_path, &options)?;
 
     // Verify that directory paths are unchanged (since prefix doesn't match)
     for dir_tree in &tree_result {
-        let normalized_dir = normalize_path(&dir_tree.dir);
+        let normalized_dir = normalize_path(&dir_tree.dir.to_string_lossy());
         assert!(
             normalized_dir.starts_with(&normalize_path(&temp_path.to_string_lossy())),
             "Directory path '{}' should start with the temp path prefix when non-matching prefix is used",
@@ -188,6 +209,12 @@ fn test_omit_path_prefix_with_depth_limit() -> Result<()> {
         respect_gitignore: false,
         depth: Some(1), // Only top-level directories
         omit_path_prefix: Some(temp_path.to_path_buf()),
+        max_file_size: None,
+        entry_sort: EntrySort::Name,
+        skip_vendored: false,
+        max_directories: None,
+        include_glob: None,
+        exclude_glob: None,
     };
 
     let tree_result = generate_tree(temp_path, &options)?;
@@ -197,7 +224,7 @@ fn test_omit_path_prefix_with_depth_limit() -> Result<()> {
 
     // Check that directory paths don't have the temp path prefix
     for dir_tree in &tree_result {
-        let normalized_dir = normalize_path(&dir_tree.dir);
+        let normalized_dir = normalize_path(&dir_tree.dir.to_string_lossy());
         assert!(
             !normalized_dir.contains(temp_path.to_string_lossy().as_ref()),
             "Directory path '{}' should not contain the temp path prefix",
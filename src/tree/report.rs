@@ -0,0 +1,59 @@
+//! Directory trees with run-level metadata.
+//!
+//! `generate_tree` returns a bare list of directory trees. This module wraps them in a
+//! serializable report carrying information about the tree generation itself.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::{DirectoryTree, TreeOptions, build_tree};
+
+/// Directory trees together with metadata about their generation.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::tree::{TreeOptions, generate_tree_report};
+/// use std::path::Path;
+///
+/// let options = TreeOptions {
+///     max_directories: Some(1000),
+///     ..TreeOptions::default()
+/// };
+/// let report = generate_tree_report(Path::new("/"), &options).unwrap();
+/// if report.truncated {
+///     eprintln!("The tree was truncated to 1000 directories");
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TreeReport {
+    /// The directory trees, sorted as returned by `generate_tree`
+    pub trees: Vec<DirectoryTree>,
+
+    /// Whether the contents of some directories were left out because the
+    /// `max_directories` limit was reached
+    pub truncated: bool,
+}
+
+/// Generates a directory tree and returns it together with metadata.
+///
+/// This generates the same trees as `generate_tree`, but wraps them in a `TreeReport` that
+/// also records whether the `max_directories` limit truncated the tree.
+///
+/// # Arguments
+///
+/// * `directory` - The directory path to generate the tree for
+/// * `options` - Configuration options for the operation
+///
+/// # Returns
+///
+/// A `TreeReport` containing the directory trees and metadata
+///
+/// # Errors
+///
+/// Returns the same errors as `generate_tree`
+pub fn generate_tree_report(directory: &Path, options: &TreeOptions) -> Result<TreeReport> {
+    let (trees, truncated) = build_tree(directory, options)?;
+    Ok(TreeReport { trees, truncated })
+}
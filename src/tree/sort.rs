@@ -0,0 +1,51 @@
+//! Ordering of the entries within each directory of a tree.
+//!
+//! The orders mirror common `ls` variants: plain name order, extension order (`ls -X`) and
+//! natural version order (`ls -v`).
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use super::Entry;
+use crate::paths::natural_cmp;
+
+/// The order of the entries within each directory of a tree.
+///
+/// See `TreeOptions::entry_sort` for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EntrySort {
+    /// Sort by name
+    #[default]
+    Name,
+    /// Sort by extension, then by name; entries without an extension come first
+    Extension,
+    /// Sort by name, comparing runs of digits numerically (`file2` before `file10`)
+    Natural,
+}
+
+impl EntrySort {
+    /// Compares two entries in this order.
+    pub(crate) fn compare(self, a: &Entry, b: &Entry) -> Ordering {
+        let (a, b) = (a.name(), b.name());
+        match self {
+            EntrySort::Name => a.cmp(b),
+            // Names that are not valid UTF-8 are compared lossily, falling back to their bytes
+            EntrySort::Extension => extension(&a.to_string_lossy())
+                .cmp(extension(&b.to_string_lossy()))
+                .then_with(|| a.cmp(b)),
+            EntrySort::Natural => {
+                natural_cmp(&a.to_string_lossy(), &b.to_string_lossy()).then_with(|| a.cmp(b))
+            }
+        }
+    }
+}
+
+/// Returns the extension of a name, or an empty string if it has none.
+///
+/// A leading dot (as in `.gitignore`) doesn't start an extension.
+fn extension(name: &str) -> &str {
+    match name.rfind('.') {
+        Some(index) if index > 0 => &name[index + 1..],
+        _ => "",
+    }
+}
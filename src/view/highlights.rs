@@ -0,0 +1,80 @@
+//! Viewing files with search matches marked.
+//!
+//! This module combines the search and view modules for viewers that show a whole file (or a
+//! range of it) with the hits of a search pattern highlighted.
+
+use anyhow::Result;
+use std::path::Path;
+
+use super::{FileContents, FileView, ViewOptions, view_file};
+use crate::search::{SearchOptions, build_matcher, find_match_ranges};
+
+/// Views a file with the matches of a search pattern marked in each line.
+///
+/// The file is viewed with `view_file`, and the `highlights` of each returned line are set to
+/// the byte ranges of the non-empty matches of `pattern` within the line. The pattern is
+/// matched line by line with the same matcher settings as `search_files` (`case_sensitive`,
+/// `regex_size_limit` and `dfa_size_limit`); the file selection options of `search_options`
+/// are not used. Ranges are snapped to character boundaries, so they can be used to slice
+/// the line directly.
+///
+/// Binary and image files are returned unchanged.
+///
+/// # Arguments
+///
+/// * `path` - Path to the file to view
+/// * `pattern` - The regular expression pattern to highlight
+/// * `view_options` - Configuration options for viewing
+/// * `search_options` - Configuration options for matching the pattern
+///
+/// # Returns
+///
+/// A `FileView` whose lines carry the match ranges in `highlights`
+///
+/// # Errors
+///
+/// Returns an error if the pattern is invalid, or the same errors as `view_file`
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::search::SearchOptions;
+/// use lumin::view::{FileContents, ViewOptions, view_with_highlights};
+/// use std::path::Path;
+///
+/// let view = view_with_highlights(
+///     Path::new("src/main.rs"),
+///     "TODO",
+///     &ViewOptions::default(),
+///     &SearchOptions::default(),
+/// )
+/// .unwrap();
+///
+/// if let FileContents::Text { content, .. } = view.contents {
+///     for line in content.line_contents {
+///         for &(start, end) in &line.highlights {
+///             println!("{}: {}", line.line_number, &line.line[start..end]);
+///         }
+///     }
+/// }
+/// ```
+pub fn view_with_highlights(
+    path: &Path,
+    pattern: &str,
+    view_options: &ViewOptions,
+    search_options: &SearchOptions,
+) -> Result<FileView> {
+    let matcher = build_matcher(pattern, search_options)?;
+    let mut view = view_file(path, view_options)?;
+
+    if let FileContents::Text { content, .. } = &mut view.contents {
+        for line in &mut content.line_contents {
+            line.highlights = find_match_ranges(&matcher, line.line.as_bytes())
+                .into_iter()
+                .filter(|&(start, end)| start < end)
+                .collect();
+        }
+    }
+
+    Ok(view)
+}
@@ -0,0 +1,100 @@
+//! Rendering of viewed files as HTML fragments.
+//!
+//! Web viewers need file contents as escaped, line-numbered markup. The fragment produced
+//! here uses fixed class names and no inline styles, so it can be styled by the embedding
+//! page.
+
+use anyhow::Result;
+use std::fmt::Write;
+use std::path::Path;
+
+use super::{FileContents, ViewOptions, view_file};
+
+/// Views a file and renders its contents as an HTML fragment.
+///
+/// Text files are rendered as a `<table class="lumin-view">` with one row per line. Each row
+/// has the id `L<line number>` for linking, a `lumin-line-number` cell for the gutter and a
+/// `lumin-line` cell holding the HTML-escaped line in a `<pre>` element. The options are
+/// applied as in `view_file`, so `line_from` / `line_to` select the rendered lines and the
+/// gutter shows their original line numbers.
+///
+/// Binary and image files are rendered as a `<p class="lumin-view-message">` element holding
+/// the escaped description of the file.
+///
+/// # Arguments
+///
+/// * `path` - Path to the file to view
+/// * `options` - Configuration options for viewing
+///
+/// # Returns
+///
+/// The HTML fragment
+///
+/// # Errors
+///
+/// Returns the same errors as `view_file`
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::view::{ViewOptions, view_file_html};
+/// use std::path::Path;
+///
+/// let html = view_file_html(Path::new("src/main.rs"), &ViewOptions::default()).unwrap();
+/// println!("<html><body>{}</body></html>", html);
+/// ```
+pub fn view_file_html(path: &Path, options: &ViewOptions) -> Result<String> {
+    let view = view_file(path, options)?;
+
+    let content = match view.contents {
+        FileContents::Text { content, .. } => content,
+        FileContents::Binary { message, .. } | FileContents::Image { message, .. } => {
+            return Ok(format!(
+                "<p class=\"lumin-view-message\">{}</p>\n",
+                escape_html(&message)
+            ));
+        }
+        FileContents::Directory { entries } => {
+            let mut html = String::from("<ul class=\"lumin-view-directory\">\n");
+            for entry in &entries {
+                let _ = writeln!(
+                    html,
+                    "<li>{}</li>",
+                    escape_html(&entry.file_path.to_string_lossy())
+                );
+            }
+            html.push_str("</ul>\n");
+            return Ok(html);
+        }
+    };
+
+    let mut html = String::from("<table class=\"lumin-view\">\n");
+    for line in &content.line_contents {
+        // Writing to a String can't fail
+        let _ = writeln!(
+            html,
+            "<tr id=\"L{0}\"><td class=\"lumin-line-number\">{0}</td>\
+             <td class=\"lumin-line\"><pre>{1}</pre></td></tr>",
+            line.line_number,
+            escape_html(&line.line)
+        );
+    }
+    html.push_str("</table>\n");
+    Ok(html)
+}
+
+/// Escapes the characters with a special meaning in HTML text and attribute values.
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,223 @@
+//! Language detection for syntax highlighting and MIME type selection.
+//!
+//! Most files are identified by their extension or file name. Extensionless scripts usually
+//! declare their interpreter in a shebang line (e.g., `#!/usr/bin/env python3`), and a few
+//! formats can be recognized from their first bytes. This module is the single source of
+//! these rules, used by `detect_language`, `view_file`'s MIME type selection and
+//! `TraverseResult::file_type`.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Maximum number of bytes read from the start of a file to find the shebang line
+const SHEBANG_READ_LIMIT: usize = 256;
+
+/// Maximum number of bytes read from the start of a file by `detect_language`
+const SAMPLE_READ_LIMIT: usize = 1024;
+
+/// Detects the language of a file, as a canonical name for syntax highlighting.
+///
+/// The language is determined from, in order of precedence:
+///
+/// 1. The file extension (e.g., `.rs`, `.py`, `.md`), compared case insensitively
+/// 2. Well-known file names (`Makefile`, `Dockerfile`, `CMakeLists.txt`)
+/// 3. A shebang line (see `shebang_language`)
+/// 4. The start of the content (`<?xml` for XML, `<!DOCTYPE html` or `<html` for HTML)
+///
+/// Language names are lowercase, like `"rust"`, `"python"`, `"markdown"`, `"bash"` or
+/// `"plaintext"` for `.txt` files.
+///
+/// # Arguments
+///
+/// * `path` - The file path. The file is only read when no extension or file name matches
+///   and `sample` is `None`.
+/// * `sample` - The first bytes of the file, if already read. The shebang and content checks
+///   only look at the first 1024 bytes.
+///
+/// # Returns
+///
+/// The language name, or `None` if the language can't be determined
+///
+/// # Examples
+///
+/// ```
+/// use lumin::view::detect_language;
+/// use std::path::Path;
+///
+/// assert_eq!(detect_language(Path::new("src/main.rs"), None).as_deref(), Some("rust"));
+/// assert_eq!(detect_language(Path::new("README.md"), None).as_deref(), Some("markdown"));
+/// assert_eq!(
+///     detect_language(Path::new("bin/deploy"), Some(b"#!/usr/bin/env python3\n")).as_deref(),
+///     Some("python")
+/// );
+/// assert_eq!(detect_language(Path::new("data"), Some(b"\x00\x01")), None);
+/// ```
+pub fn detect_language(path: &Path, sample: Option<&[u8]>) -> Option<String> {
+    if let Some(language) = extension_language(path).or_else(|| file_name_language(path)) {
+        return Some(language.to_string());
+    }
+
+    let read_sample;
+    let sample = match sample {
+        Some(sample) => sample,
+        None => {
+            read_sample = read_prefix(path, SAMPLE_READ_LIMIT)?;
+            &read_sample
+        }
+    };
+    let sample = &sample[..sample.len().min(SAMPLE_READ_LIMIT)];
+
+    let first_line = sample.split(|&b| b == b'\n').next()?;
+    if let Some(language) = std::str::from_utf8(first_line)
+        .ok()
+        .and_then(|line| shebang_language(line.trim_end()))
+    {
+        return Some(language.to_string());
+    }
+
+    content_language(sample).map(str::to_string)
+}
+
+/// Returns the language of a file from its extension
+pub(crate) fn extension_language(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    let language = match extension.as_str() {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "js" | "mjs" | "cjs" | "jsx" => "javascript",
+        "ts" | "mts" | "cts" | "tsx" => "typescript",
+        "md" | "markdown" => "markdown",
+        "txt" => "plaintext",
+        "toml" => "toml",
+        "yml" | "yaml" => "yaml",
+        "json" => "json",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "xml" => "xml",
+        "sh" | "bash" | "zsh" => "bash",
+        "rb" => "ruby",
+        "pl" | "pm" => "perl",
+        "go" => "go",
+        "java" => "java",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "cpp",
+        "sql" => "sql",
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// Returns the language of a file from a well-known file name
+fn file_name_language(path: &Path) -> Option<&'static str> {
+    match path.file_name()?.to_str()? {
+        "Makefile" | "GNUmakefile" | "makefile" => Some("makefile"),
+        "Dockerfile" => Some("dockerfile"),
+        "CMakeLists.txt" => Some("cmake"),
+        _ => None,
+    }
+}
+
+/// Recognizes markup languages from the start of a file's content
+fn content_language(sample: &[u8]) -> Option<&'static str> {
+    let start = sample.iter().position(|b| !b.is_ascii_whitespace())?;
+    let head = &sample[start..];
+    let starts_with = |prefix: &[u8]| {
+        head.len() >= prefix.len() && head[..prefix.len()].eq_ignore_ascii_case(prefix)
+    };
+
+    if starts_with(b"<?xml") {
+        Some("xml")
+    } else if starts_with(b"<!doctype html") || starts_with(b"<html") {
+        Some("html")
+    } else {
+        None
+    }
+}
+
+/// Infers the language of a script from its shebang line.
+///
+/// The interpreter is taken from the shebang path, or from the first command argument when
+/// the script is run through `env` (e.g., `#!/usr/bin/env -S python3 -u`). Version suffixes
+/// like `python3.12` are ignored.
+///
+/// # Arguments
+///
+/// * `first_line` - The first line of the file
+///
+/// # Returns
+///
+/// The language name (`"python"`, `"bash"`, `"javascript"`, `"ruby"` or `"perl"`), or `None`
+/// if the line is not a shebang or the interpreter is not recognized.
+///
+/// # Examples
+///
+/// ```
+/// use lumin::view::shebang_language;
+///
+/// assert_eq!(shebang_language("#!/usr/bin/env python3"), Some("python"));
+/// assert_eq!(shebang_language("#!/bin/sh"), Some("bash"));
+/// assert_eq!(shebang_language("#!/usr/bin/env node"), Some("javascript"));
+/// assert_eq!(shebang_language("print('no shebang')"), None);
+/// ```
+pub fn shebang_language(first_line: &str) -> Option<&'static str> {
+    let command = first_line.strip_prefix("#!")?;
+    let mut words = command.split_whitespace();
+
+    let mut interpreter = basename(words.next()?);
+    if interpreter == "env" {
+        // Skip env options (e.g., -S) and environment variable assignments
+        interpreter = basename(words.find(|word| !word.starts_with('-') && !word.contains('='))?);
+    }
+
+    // Ignore version suffixes such as "python3.12" or "ruby2.7"
+    let name = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+
+    match name {
+        "python" | "pypy" => Some("python"),
+        "bash" | "sh" | "dash" | "zsh" | "ksh" => Some("bash"),
+        "node" | "nodejs" => Some("javascript"),
+        "ruby" => Some("ruby"),
+        "perl" => Some("perl"),
+        _ => None,
+    }
+}
+
+/// Returns the MIME type used for files of the given language
+pub(crate) fn language_mime_type(language: &str) -> &'static str {
+    match language {
+        "python" => "text/x-python",
+        "bash" => "text/x-shellscript",
+        "javascript" => "text/javascript",
+        "ruby" => "text/x-ruby",
+        "perl" => "text/x-perl",
+        "html" => "text/html",
+        "css" => "text/css",
+        _ => "text/plain",
+    }
+}
+
+/// Reads the first line of a file and infers its language from a shebang.
+///
+/// Returns `None` if the file cannot be read or doesn't start with a recognized shebang.
+pub(crate) fn read_shebang_language(path: &Path) -> Option<&'static str> {
+    let buffer = read_prefix(path, SHEBANG_READ_LIMIT)?;
+    let first_line = buffer.split(|&b| b == b'\n').next()?;
+    shebang_language(std::str::from_utf8(first_line).ok()?.trim_end())
+}
+
+/// Reads up to `limit` bytes from the start of a file
+fn read_prefix(path: &Path, limit: usize) -> Option<Vec<u8>> {
+    let mut buffer = Vec::with_capacity(limit);
+    File::open(path)
+        .ok()?
+        .take(limit as u64)
+        .read_to_end(&mut buffer)
+        .ok()?;
+    Some(buffer)
+}
+
+/// Returns the last component of an interpreter path
+fn basename(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
@@ -0,0 +1,263 @@
+//! Rendering of Markdown files as plain text.
+//!
+//! Markdown source is cluttered with markup when previewed in a terminal. With the `markdown`
+//! cargo feature, files are parsed with the `pulldown-cmark` crate and rendered as plain text
+//! that keeps the structure of the document: headings, lists, block quotes and code blocks.
+
+use anyhow::{Result, anyhow};
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+use std::path::Path;
+
+use super::{FileContents, ViewOptions, view_file};
+
+/// Renders a Markdown file as plain text for terminal display.
+///
+/// The markup is removed while the structure is kept:
+///
+/// - Headings are written on their own line, with level 1 and 2 headings underlined with
+///   `=` and `-`
+/// - List items are prefixed with `- ` or their number, and nested lists are indented
+/// - Code blocks are indented by four spaces, with their content unchanged
+/// - Block quotes are prefixed with `> `, and table cells are separated with ` | `
+/// - Link and image destinations are written in parentheses after their text
+///
+/// Blocks are separated by blank lines. The lines are selected as by `view_file`, so a
+/// line range renders part of the document.
+///
+/// # Arguments
+///
+/// * `path` - Path to the Markdown file to render
+/// * `options` - Configuration options, as for `view_file`
+///
+/// # Returns
+///
+/// The rendered text, ending with a newline unless it's empty
+///
+/// # Errors
+///
+/// Returns the same errors as `view_file`, and an error if the file is not a text file
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::view::{ViewOptions, render_markdown};
+/// use std::path::Path;
+///
+/// let text = render_markdown(Path::new("README.md"), &ViewOptions::default()).unwrap();
+/// print!("{}", text);
+/// ```
+pub fn render_markdown(path: &Path, options: &ViewOptions) -> Result<String> {
+    let content = match view_file(path, options)?.contents {
+        FileContents::Text { content, .. } => content,
+        _ => return Err(anyhow!("Not a text file: {}", path.display())),
+    };
+    let source = content
+        .line_contents
+        .iter()
+        .map(|line| line.line.as_str())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let parser = Parser::new_ext(
+        &source,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS,
+    );
+    let mut renderer = PlainText::default();
+    for event in parser {
+        renderer.event(event);
+    }
+    Ok(renderer.finish())
+}
+
+/// The state of the rendering of Markdown events as plain text
+#[derive(Default)]
+struct PlainText {
+    /// The text rendered so far
+    out: String,
+    /// The prefix of each line, from the enclosing list items, block quotes and code blocks
+    indent: String,
+    /// The lengths of `indent` outside of each enclosing block, to restore at its end
+    indent_stack: Vec<usize>,
+    /// Whether text has been written on the current line
+    mid_line: bool,
+    /// Whether a blank line separates the next block from the previous one
+    blank_pending: bool,
+    /// The enclosing lists, with the number of the next item of ordered lists
+    lists: Vec<Option<u64>>,
+    /// The start of the heading being rendered within `out`, and whether it's underlined
+    heading: Option<(usize, Option<char>)>,
+    /// The destinations of the enclosing links and images, with the start of their text
+    links: Vec<(String, usize)>,
+    /// Whether the next table cell is the first of its row
+    first_cell: bool,
+}
+
+impl PlainText {
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) | Event::Code(text) | Event::Html(text) | Event::InlineHtml(text) => {
+                self.write(&text)
+            }
+            Event::SoftBreak | Event::HardBreak => self.write("\n"),
+            Event::Rule => {
+                self.start_block();
+                self.write("----");
+                self.end_block();
+            }
+            Event::TaskListMarker(checked) => self.write(if checked { "[x] " } else { "[ ] " }),
+            Event::FootnoteReference(label) => self.write(&format!("[{}]", label)),
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph | Tag::HtmlBlock => self.start_block(),
+            Tag::Heading { level, .. } => {
+                self.start_block();
+                let underline = match level {
+                    HeadingLevel::H1 => Some('='),
+                    HeadingLevel::H2 => Some('-'),
+                    _ => None,
+                };
+                self.heading = Some((self.out.len(), underline));
+            }
+            Tag::BlockQuote(_) => {
+                self.start_block();
+                self.push_indent("> ");
+            }
+            Tag::CodeBlock(_) => {
+                self.start_block();
+                self.push_indent("    ");
+            }
+            Tag::List(first_number) => {
+                // Nested lists continue their item without a blank line
+                if self.lists.is_empty() {
+                    self.start_block();
+                } else {
+                    self.end_line();
+                }
+                self.lists.push(first_number);
+            }
+            Tag::Item => {
+                self.start_block();
+                let marker = match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.write(&marker);
+                self.push_indent(&" ".repeat(marker.len()));
+            }
+            Tag::Table(_) => self.start_block(),
+            Tag::TableHead | Tag::TableRow => {
+                self.end_line();
+                self.first_cell = true;
+            }
+            Tag::TableCell => {
+                if !self.first_cell {
+                    self.write(" | ");
+                }
+                self.first_cell = false;
+            }
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.links.push((dest_url.to_string(), self.out.len()));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::HtmlBlock | TagEnd::Table => self.end_block(),
+            TagEnd::Heading(_) => {
+                if let Some((start, Some(underline))) = self.heading.take() {
+                    let width = self.out[start..].chars().count();
+                    self.write("\n");
+                    self.write(&underline.to_string().repeat(width));
+                }
+                self.end_block();
+            }
+            TagEnd::BlockQuote(_) | TagEnd::CodeBlock => {
+                self.pop_indent();
+                self.end_block();
+            }
+            TagEnd::List(_) => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.end_block();
+                }
+            }
+            TagEnd::Item => {
+                self.end_line();
+                self.pop_indent();
+            }
+            TagEnd::TableHead | TagEnd::TableRow => self.end_line(),
+            TagEnd::Link | TagEnd::Image => {
+                if let Some((url, start)) = self.links.pop() {
+                    if !url.is_empty() && self.out[start..] != url {
+                        self.write(&format!(" ({})", url));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Writes text, prefixing each new line with the indentation
+    fn write(&mut self, text: &str) {
+        for piece in text.split_inclusive('\n') {
+            if !self.mid_line {
+                if piece == "\n" {
+                    self.out.push_str(self.indent.trim_end());
+                } else {
+                    self.out.push_str(&self.indent);
+                }
+            }
+            self.out.push_str(piece);
+            self.mid_line = !piece.ends_with('\n');
+        }
+    }
+
+    /// Ends the current line, if text has been written on it
+    fn end_line(&mut self) {
+        if self.mid_line {
+            self.write("\n");
+        }
+    }
+
+    /// Starts a block on a new line, after a blank line if the previous block ended
+    fn start_block(&mut self) {
+        self.end_line();
+        if self.blank_pending && !self.out.is_empty() {
+            self.write("\n");
+        }
+        self.blank_pending = false;
+    }
+
+    /// Ends a block, so that the next block is separated by a blank line
+    fn end_block(&mut self) {
+        self.end_line();
+        self.blank_pending = true;
+    }
+
+    fn push_indent(&mut self, indent: &str) {
+        self.indent_stack.push(self.indent.len());
+        self.indent.push_str(indent);
+    }
+
+    fn pop_indent(&mut self) {
+        if let Some(len) = self.indent_stack.pop() {
+            self.indent.truncate(len);
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.end_line();
+        self.out
+    }
+}
@@ -3,14 +3,49 @@
 //! This module provides tools to view file contents with automatic type detection,
 //! handling different file types (text, binary, image) appropriately with metadata.
 
+// Search match highlighting
+mod highlights;
+// Rendering as HTML fragments
+mod html;
+// Language detection from shebang lines
+mod language;
+// Plain text rendering of Markdown files
+#[cfg(feature = "markdown")]
+mod markdown;
+// Incremental paged viewing
+mod pages;
+// Coarse token spans of source lines
+mod tokens;
+// Streaming output to writers
+mod write;
+
+pub use highlights::view_with_highlights;
+pub use html::view_file_html;
+pub(crate) use language::read_shebang_language;
+pub use language::{detect_language, shebang_language};
+#[cfg(feature = "markdown")]
+pub use markdown::render_markdown;
+pub use pages::view_pages;
+pub use tokens::{LineTokens, Token, TokenKind, tokenize};
+pub use write::write_file;
+
 use anyhow::{Context, Result, anyhow};
+use base64::prelude::{BASE64_STANDARD, Engine as _};
 use infer::Infer;
 use serde::{Deserialize, Serialize};
 use std::fs::File;
-use std::io::Read;
+use std::io::{BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
 
+use crate::traverse::common::extension_override;
+use crate::traverse::{TraverseOptions, TraverseResult, traverse_directory};
+
 /// Configuration options for file viewing operations.
+///
+/// Fields missing from serialized input take their default values, and unknown fields are
+/// rejected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ViewOptions {
     /// Maximum file size to read in bytes.
     /// Files larger than this will be rejected to prevent excessive memory usage.
@@ -26,6 +61,77 @@ pub struct ViewOptions {
     /// Only applied for text files. If None, includes until the last line.
     /// If the specified line is beyond the file's content, only available lines up to the end will be included.
     pub line_to: Option<usize>,
+
+    /// Optional number of lines to preview (like `head -n`).
+    /// Only applied for text files. When set, only the first N lines of the selected range
+    /// (starting at `line_from`, or the first line) are read from disk, and `FileView::has_more`
+    /// reports whether the file continues beyond them. The `max_size` limit applies to the
+    /// previewed content rather than to the whole file.
+    pub preview: Option<usize>,
+
+    /// Whether to decode text files lossily when they contain invalid UTF-8.
+    /// By default, a text file that is not valid UTF-8 is reported as binary. When `true`,
+    /// invalid byte sequences are replaced with U+FFFD (the replacement character), the file is
+    /// returned as text, and `TextMetadata::had_invalid_utf8` is set.
+    pub lossy: bool,
+
+    /// Extensions of files that are always viewed as text.
+    /// Content-based detection occasionally misclassifies text formats as binary; files with
+    /// one of these extensions skip it. Extensions are compared case insensitively, with or
+    /// without a leading dot, and take precedence over `binary_extensions`. A file that turns
+    /// out not to be valid UTF-8 is still reported as binary unless `lossy` is set.
+    pub text_extensions: Vec<String>,
+
+    /// Extensions of files that are always viewed as binary, without inspecting their content.
+    /// Extensions are compared as in `text_extensions`.
+    pub binary_extensions: Vec<String>,
+
+    /// Byte offset within a text file to view the lines around (0-based).
+    /// Useful to jump to a position reported as a byte offset, such as in compiler errors.
+    /// When set, the line containing the byte is returned with `context_lines` lines before and
+    /// after it, and is flagged with `LineContent::is_target`. A newline byte belongs to the
+    /// line it ends, and offsets past the end of the file select the last line. Takes
+    /// precedence over `line_from`, `line_to` and `preview`.
+    pub byte_offset: Option<usize>,
+
+    /// Number of lines to include before and after the line selected by `byte_offset`.
+    /// `None` returns only the selected line. Ignored when `byte_offset` is not set.
+    pub context_lines: Option<usize>,
+
+    /// What `view_file` does when the path is a directory.
+    /// With `DirMode::Error` (default), viewing a directory is an error. With
+    /// `DirMode::ListEntries`, the immediate entries of the directory are returned as
+    /// `FileContents::Directory`, listed as `traverse_directory` would with `include_dirs`
+    /// (so gitignored and hidden entries are left out). Other functions of this module, such
+    /// as `write_file`, always reject directories.
+    pub on_directory: DirMode,
+
+    /// Whether to replace control characters in the lines of text files.
+    /// Control characters such as ANSI escape sequences can corrupt a terminal when the lines
+    /// are printed. When `true`, every control character except tab in `LineContent::line` is
+    /// replaced with its caret notation (`^[` for ESC, `^@` for NUL, `^?` for DEL), and C1
+    /// control characters with `M-` followed by the caret notation, as `cat -v` shows them.
+    /// Metadata still describes the original content.
+    pub sanitize_control: bool,
+
+    /// Whether to include the content of binary and image files, encoded as base64.
+    /// Useful for transmitting small binary files in JSON. When `true`, the `base64` field of
+    /// `FileContents::Binary` and `FileContents::Image` holds the standard (padded) base64
+    /// encoding of the whole file. The `max_size` limit applies to the raw file size, and
+    /// should be set, since the encoded content is a third larger than the file.
+    pub binary_as_base64: bool,
+}
+
+/// How `view_file` handles a directory path.
+///
+/// See `ViewOptions::on_directory` for details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DirMode {
+    /// Return a "Not a file" error
+    #[default]
+    Error,
+    /// Return the entries of the directory
+    ListEntries,
 }
 
 impl Default for ViewOptions {
@@ -34,6 +140,15 @@ impl Default for ViewOptions {
             max_size: Some(10 * 1024 * 1024), // Default to 10MB limit
             line_from: None,
             line_to: None,
+            preview: None,
+            lossy: false,
+            text_extensions: Vec::new(),
+            binary_extensions: Vec::new(),
+            byte_offset: None,
+            context_lines: None,
+            on_directory: DirMode::Error,
+            sanitize_control: false,
+            binary_as_base64: false,
         }
     }
 }
@@ -44,6 +159,7 @@ impl Default for ViewOptions {
 /// - `Text` for text files with content and metadata
 /// - `Binary` for binary files with a description message and metadata
 /// - `Image` for image files with a description message and metadata
+/// - `Directory` for directories viewed with `DirMode::ListEntries`
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type")]
 pub enum FileContents {
@@ -63,6 +179,9 @@ pub enum FileContents {
         message: String,
         /// Metadata about the binary file
         metadata: BinaryMetadata,
+        /// The file content encoded as base64, only present with `binary_as_base64`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base64: Option<String>,
     },
 
     /// Image file representation with a descriptive message
@@ -72,6 +191,17 @@ pub enum FileContents {
         message: String,
         /// Metadata about the image file
         metadata: ImageMetadata,
+        /// The file content encoded as base64, only present with `binary_as_base64`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base64: Option<String>,
+    },
+
+    /// Directory listing, returned instead of an error with `DirMode::ListEntries`
+    #[serde(rename = "directory")]
+    Directory {
+        /// The immediate entries of the directory, sorted by path.
+        /// Subdirectories have the file type `"dir"`.
+        entries: Vec<TraverseResult>,
     },
 }
 
@@ -101,14 +231,18 @@ impl TextContent {
             .collect::<Vec<_>>()
             .join("\n")
     }
+}
 
-    /// Convert the content to a string
-    pub fn to_string(&self) -> String {
-        self.line_contents
+impl std::fmt::Display for TextContent {
+    /// Formats the content as a string, joining lines with newlines
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = self
+            .line_contents
             .iter()
-            .map(|line| line.line.clone())
+            .map(|line| line.line.as_str())
             .collect::<Vec<_>>()
-            .join("\n")
+            .join("\n");
+        f.write_str(&text)
     }
 }
 
@@ -119,6 +253,14 @@ pub struct LineContent {
     pub line_number: usize,
     /// The content of the line without trailing newlines
     pub line: String,
+    /// Byte ranges `(start, end)` of the search matches within `line`.
+    ///
+    /// Only populated by `view_with_highlights`; empty otherwise.
+    #[serde(default)]
+    pub highlights: Vec<(usize, usize)>,
+    /// Whether this is the line containing `ViewOptions::byte_offset`.
+    #[serde(default)]
+    pub is_target: bool,
 }
 
 /// Metadata for text files.
@@ -128,6 +270,8 @@ pub struct TextMetadata {
     pub line_count: usize,
     /// Number of characters in the text file
     pub char_count: usize,
+    /// Whether invalid UTF-8 sequences were replaced during lossy decoding
+    pub had_invalid_utf8: bool,
 }
 
 /// Metadata for binary files.
@@ -156,13 +300,103 @@ pub struct ImageMetadata {
 #[derive(Serialize, Debug)]
 pub struct FileView {
     /// Path to the viewed file
+    #[serde(with = "crate::paths::serde_os_str")]
     pub file_path: PathBuf,
     /// MIME type or file type descriptor
     pub file_type: String,
     /// The contents of the file, represented as an appropriate variant of FileContents
     pub contents: FileContents,
-    /// Total number of lines in the file, only present for text files
+    /// Total number of lines in the file, only present for text files.
+    /// In preview mode this is only known (and present) when the whole file was read.
+    pub total_line_num: Option<usize>,
+    /// Whether the file has more lines than were returned in preview mode.
+    /// Always `false` when `preview` is not set in the options.
+    pub has_more: bool,
+}
+
+impl FileView {
+    /// Summarizes the view as a compact preview, such as for the entries of a file picker.
+    ///
+    /// For text files, the first `lines` viewed lines are kept. Binary and image files are
+    /// summarized by their description message, and directories by the paths of their first
+    /// `lines` entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The maximum number of lines to keep in `FilePreview::first_lines`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lumin::view::{ViewOptions, view_file};
+    /// use std::path::Path;
+    ///
+    /// let view = view_file(Path::new("README.md"), &ViewOptions::default()).unwrap();
+    /// let preview = view.preview(3);
+    /// println!("{} ({} bytes)", preview.file_path.display(), preview.size_bytes);
+    /// for line in &preview.first_lines {
+    ///     println!("  {}", line);
+    /// }
+    /// ```
+    pub fn preview(&self, lines: usize) -> FilePreview {
+        let (first_lines, size_bytes) = match &self.contents {
+            FileContents::Text { content, .. } => (
+                content
+                    .line_contents
+                    .iter()
+                    .take(lines)
+                    .map(|line| line.line.clone())
+                    .collect(),
+                // Account for the newline characters, as for the `max_size` checks
+                content
+                    .line_contents
+                    .iter()
+                    .map(|line| line.line.len() as u64 + 1)
+                    .sum(),
+            ),
+            FileContents::Binary {
+                message, metadata, ..
+            } => (vec![message.clone()], metadata.size_bytes),
+            FileContents::Image {
+                message, metadata, ..
+            } => (vec![message.clone()], metadata.size_bytes),
+            FileContents::Directory { entries } => (
+                entries
+                    .iter()
+                    .take(lines)
+                    .map(|entry| entry.file_path.to_string_lossy().into_owned())
+                    .collect(),
+                0,
+            ),
+        };
+
+        FilePreview {
+            file_path: self.file_path.clone(),
+            file_type: self.file_type.clone(),
+            first_lines,
+            total_line_num: self.total_line_num,
+            size_bytes,
+        }
+    }
+}
+
+/// A compact summary of a `FileView`, returned by `FileView::preview`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FilePreview {
+    /// Path to the viewed file
+    #[serde(with = "crate::paths::serde_os_str")]
+    pub file_path: PathBuf,
+    /// MIME type or file type descriptor
+    pub file_type: String,
+    /// The first lines of a text file, the description of a binary or image file, or the
+    /// paths of the first entries of a directory
+    pub first_lines: Vec<String>,
+    /// Total number of lines in the file, as in `FileView::total_line_num`
     pub total_line_num: Option<usize>,
+    /// Size in bytes of the file for binary and image files. For text files, the size of the
+    /// viewed lines with a newline each, which is the file size when the whole file was viewed
+    /// with `\n` line endings. Always 0 for directories.
+    pub size_bytes: u64,
 }
 
 /// Reads and processes a file, detecting its type and returning an appropriate representation.
@@ -175,6 +409,11 @@ pub struct FileView {
 ///   - `max_size`: Optional maximum file size limit
 ///   - `line_from`: Optional starting line number (1-based, inclusive)
 ///   - `line_to`: Optional ending line number (1-based, inclusive)
+///   - `preview`: Optional number of leading lines to read (streams only those lines)
+///   - `lossy`: Whether to decode invalid UTF-8 in text files lossily instead of reporting binary
+///   - `byte_offset`/`context_lines`: Optional byte position to view the surrounding lines of
+///   - `on_directory`: Whether to list the entries of a directory instead of failing
+///   - `sanitize_control`: Whether to replace control characters with visible representations
 ///
 /// # Returns
 ///
@@ -192,10 +431,17 @@ pub struct FileView {
 /// - Metadata still represents the whole file regardless of filtering
 /// - The `total_line_num` field provides the total number of lines in the original file
 ///
+/// When `preview` is set for a text file:
+/// - Only the first N lines of the selected range are read from disk
+/// - `has_more` is `true` if the file has further lines in the selected range
+/// - Metadata describes only the lines that were read, since the rest of the file is not loaded
+/// - `total_line_num` is `None` unless the whole file was read
+///
 /// # Errors
 ///
 /// Returns an error if:
-/// - The file does not exist or is not a regular file
+/// - The file does not exist or is not a regular file (unless it is a directory and
+///   `on_directory` is `DirMode::ListEntries`)
 /// - The file is larger than the maximum size specified in options (when not using line filters)
 /// - The filtered content is larger than the maximum size (when using line filters)
 /// - Failed to read file metadata or content
@@ -206,6 +452,10 @@ pub fn view_file(path: &Path, options: &ViewOptions) -> Result<FileView> {
         return Err(anyhow!("File not found: {}", path.display()));
     }
 
+    if path.is_dir() && options.on_directory == DirMode::ListEntries {
+        return view_directory(path);
+    }
+
     if !path.is_file() {
         return Err(anyhow!("Not a file: {}", path.display()));
     }
@@ -218,7 +468,10 @@ pub fn view_file(path: &Path, options: &ViewOptions) -> Result<FileView> {
     // Check file size if a limit is set and no line filters are applied
     // When line filters are applied, we'll only process a subset of the file,
     // so we skip the initial size check and validate the filtered content size later
-    let using_line_filters = options.line_from.is_some() || options.line_to.is_some();
+    let using_line_filters = options.line_from.is_some()
+        || options.line_to.is_some()
+        || options.preview.is_some()
+        || options.byte_offset.is_some();
 
     if let Some(max_size) = options.max_size {
         if !using_line_filters && metadata.len() > max_size as u64 {
@@ -231,58 +484,21 @@ pub fn view_file(path: &Path, options: &ViewOptions) -> Result<FileView> {
         }
     }
 
-    // Infer file type using both extension and content analysis
-    let infer = Infer::new();
+    let file_type = detect_file_type(path, options)?;
 
-    // First try to get a type hint from the extension
-    let extension_type = path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| match ext.to_lowercase().as_str() {
-            "txt" | "md" | "rs" | "toml" | "yml" | "yaml" | "json" => Some("text/plain"),
-            "py" => Some("text/x-python"),
-            "js" => Some("text/javascript"),
-            "html" => Some("text/html"),
-            "css" => Some("text/css"),
-            _ => None,
-        })
-        .unwrap_or(None);
-
-    // Then try content-based detection
-    let file_type = match infer.get_from_path(path) {
-        Ok(Some(kind)) => kind.mime_type().to_string(),
-        Ok(None) => {
-            // If infer couldn't determine type but we have an extension hint, use that
-            if let Some(ext_type) = extension_type {
-                ext_type.to_string()
-            } else {
-                // Read a small sample to check if it's probably text
-                match std::fs::read(path) {
-                    Ok(bytes) if bytes.len() <= 1024 => {
-                        // Check if the content looks like text (mostly ASCII or UTF-8)
-                        let text_likelihood = bytes
-                            .iter()
-                            .filter(|b| {
-                                **b >= 32 && **b <= 126
-                                    || **b == b'\n'
-                                    || **b == b'\r'
-                                    || **b == b'\t'
-                            })
-                            .count() as f64
-                            / bytes.len() as f64;
-
-                        if text_likelihood > 0.8 {
-                            "text/plain".to_string()
-                        } else {
-                            "application/octet-stream".to_string()
-                        }
-                    }
-                    _ => "application/octet-stream".to_string(), // Default to binary for larger files or errors
+    // In preview mode, stream only the needed lines of text files
+    if let Some(preview_lines) = options.preview.filter(|_| options.byte_offset.is_none()) {
+        if file_type.starts_with("text/") {
+            if let Some(mut view) =
+                preview_text_file(path, file_type.clone(), preview_lines, options)?
+            {
+                if options.sanitize_control {
+                    sanitize_view(&mut view);
                 }
+                return Ok(view);
             }
         }
-        Err(e) => return Err(anyhow!("Failed to determine file type: {}", e)),
-    };
+    }
 
     // Read file content
     let mut file =
@@ -296,17 +512,45 @@ pub fn view_file(path: &Path, options: &ViewOptions) -> Result<FileView> {
 
     // Process contents based on file type
     let contents = if file_type.starts_with("text/") {
-        // Handle text files
-        match String::from_utf8(content.clone()) {
-            Ok(text) => {
+        // Handle text files, decoding lossily if requested
+        let decoded = match String::from_utf8(content) {
+            Ok(text) => Ok((text, false)),
+            Err(e) if options.lossy => {
+                Ok((String::from_utf8_lossy(e.as_bytes()).into_owned(), true))
+            }
+            Err(e) => Err(e.into_bytes()),
+        };
+
+        match decoded {
+            Ok((text, had_invalid_utf8)) => {
                 // Count lines for information
                 let all_lines: Vec<&str> = text.lines().collect();
                 let line_count = all_lines.len();
                 let char_count = text.chars().count();
 
+                // Find the line containing the byte offset, if one is given
+                let target_line = options.byte_offset.map(|offset| {
+                    let offset = offset.min(text.len());
+                    let newlines = text.as_bytes()[..offset]
+                        .iter()
+                        .filter(|&&b| b == b'\n')
+                        .count();
+                    (newlines + 1).min(line_count.max(1))
+                });
+
                 // Apply line filtering if requested, silently adjusting for boundaries
-                let from_line = options.line_from.unwrap_or(1).max(1);
-                let to_line = options.line_to.unwrap_or(line_count).min(line_count);
+                let (line_from, line_to) = match target_line {
+                    Some(target_line) => {
+                        let context_lines = options.context_lines.unwrap_or(0);
+                        (
+                            Some(target_line.saturating_sub(context_lines)),
+                            Some(target_line.saturating_add(context_lines)),
+                        )
+                    }
+                    None => (options.line_from, options.line_to),
+                };
+                let from_line = line_from.unwrap_or(1).max(1);
+                let to_line = line_to.unwrap_or(line_count).min(line_count);
 
                 // If from_line is beyond file content or greater than to_line, adjust silently
                 let (effective_from, effective_to) =
@@ -328,6 +572,8 @@ pub fn view_file(path: &Path, options: &ViewOptions) -> Result<FileView> {
                     .map(|(idx, line)| LineContent {
                         line_number: idx + 1, // Convert to 1-based index
                         line: line.to_string().trim_end_matches('\n').to_string(),
+                        highlights: Vec::new(),
+                        is_target: target_line == Some(idx + 1),
                     })
                     .collect();
 
@@ -360,10 +606,11 @@ pub fn view_file(path: &Path, options: &ViewOptions) -> Result<FileView> {
                     metadata: TextMetadata {
                         line_count,
                         char_count,
+                        had_invalid_utf8,
                     },
                 }
             }
-            Err(_) => {
+            Err(content) => {
                 // Text detection was wrong, it's actually binary
                 FileContents::Binary {
                     message: format!("Binary file detected, size: {} bytes", metadata.len()),
@@ -372,6 +619,7 @@ pub fn view_file(path: &Path, options: &ViewOptions) -> Result<FileView> {
                         size_bytes: metadata.len(),
                         mime_type: None,
                     },
+                    base64: encode_base64(path, &content, options)?,
                 }
             }
         }
@@ -391,12 +639,13 @@ pub fn view_file(path: &Path, options: &ViewOptions) -> Result<FileView> {
         }
 
         FileContents::Image {
-            message: format!("Image file detected: {}", file_type),
+            message: image_message(&file_type),
             metadata: ImageMetadata {
                 binary: true,
                 size_bytes: metadata.len(),
                 media_type: "image".to_string(),
             },
+            base64: encode_base64(path, &content, options)?,
         }
     } else {
         // For other binary files
@@ -414,16 +663,13 @@ pub fn view_file(path: &Path, options: &ViewOptions) -> Result<FileView> {
         }
 
         FileContents::Binary {
-            message: format!(
-                "Binary file detected, size: {} bytes, type: {}",
-                metadata.len(),
-                file_type
-            ),
+            message: binary_message(metadata.len(), &file_type),
             metadata: BinaryMetadata {
                 binary: true,
                 size_bytes: metadata.len(),
                 mime_type: Some(file_type.clone()),
             },
+            base64: encode_base64(path, &content, options)?,
         }
     };
 
@@ -433,12 +679,324 @@ pub fn view_file(path: &Path, options: &ViewOptions) -> Result<FileView> {
         _ => None,
     };
 
-    let result = FileView {
+    let mut result = FileView {
         file_path: path.to_path_buf(),
         file_type,
         contents,
         total_line_num,
+        has_more: false,
     };
+    if options.sanitize_control {
+        sanitize_view(&mut result);
+    }
 
     Ok(result)
 }
+
+/// Reads a text file and returns its content as a single string.
+///
+/// This is a convenience over `view_file` for callers that only need the text: the selected
+/// lines are joined with `\n`, without a trailing newline (see `TextContent`'s `Display`
+/// implementation). All options of `view_file` apply, so `line_from`, `line_to`, `preview` and
+/// `byte_offset` select the returned lines, and `lossy` allows invalid UTF-8.
+///
+/// # Arguments
+///
+/// * `path` - Path to the file to read
+/// * `options` - Configuration options, as for `view_file`
+///
+/// # Errors
+///
+/// Returns the same errors as `view_file`, and an error if the file is not a text file
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::view::{ViewOptions, read_text};
+/// use std::path::Path;
+///
+/// let options = ViewOptions {
+///     line_from: Some(10),
+///     line_to: Some(20),
+///     ..ViewOptions::default()
+/// };
+/// let text = read_text(Path::new("src/main.rs"), &options).unwrap();
+/// println!("{}", text);
+/// ```
+pub fn read_text(path: &Path, options: &ViewOptions) -> Result<String> {
+    match view_file(path, options)?.contents {
+        FileContents::Text { content, .. } => Ok(content.to_string()),
+        _ => Err(anyhow!("Not a text file: {}", path.display())),
+    }
+}
+
+/// Lists the immediate entries of a directory as a `FileView`
+fn view_directory(path: &Path) -> Result<FileView> {
+    let traverse_options = TraverseOptions {
+        only_text_files: false,
+        depth: Some(1),
+        include_dirs: true,
+        ..TraverseOptions::default()
+    };
+    let entries = traverse_directory(path, &traverse_options)
+        .with_context(|| format!("Failed to list directory: {}", path.display()))?;
+
+    Ok(FileView {
+        file_path: path.to_path_buf(),
+        file_type: "inode/directory".to_string(),
+        contents: FileContents::Directory { entries },
+        total_line_num: None,
+        has_more: false,
+    })
+}
+
+/// Encodes the content of a binary or image file as base64, if `binary_as_base64` is set.
+///
+/// The size limit is checked again on the content that was read, in case the file grew since
+/// its metadata was read.
+fn encode_base64(path: &Path, content: &[u8], options: &ViewOptions) -> Result<Option<String>> {
+    if !options.binary_as_base64 {
+        return Ok(None);
+    }
+    if let Some(max_size) = options.max_size {
+        if content.len() > max_size {
+            return Err(anyhow!(
+                "File is too large to encode: {} (size: {}, limit: {})",
+                path.display(),
+                content.len(),
+                max_size
+            ));
+        }
+    }
+    Ok(Some(BASE64_STANDARD.encode(content)))
+}
+
+/// Replaces the control characters in the lines of a text view (see `escape_control`)
+fn sanitize_view(view: &mut FileView) {
+    if let FileContents::Text { content, .. } = &mut view.contents {
+        for line in &mut content.line_contents {
+            line.line = escape_control(&line.line);
+        }
+    }
+}
+
+/// Replaces control characters other than tab with their caret notation, as `cat -v` does.
+///
+/// C0 characters and DEL become `^@`..`^_` and `^?`, and C1 characters (U+0080..U+009F) become
+/// `M-` followed by the caret notation of the corresponding C0 character.
+pub(crate) fn escape_control(line: &str) -> String {
+    if !line.chars().any(|c| c.is_control() && c != '\t') {
+        return line.to_string();
+    }
+
+    let mut escaped = String::with_capacity(line.len() + 8);
+    for c in line.chars() {
+        match c as u32 {
+            0x09 => escaped.push(c),
+            code @ 0x00..=0x1F => {
+                escaped.push('^');
+                escaped.push(char::from(code as u8 + 0x40));
+            }
+            0x7F => escaped.push_str("^?"),
+            code @ 0x80..=0x9F => {
+                escaped.push_str("M-^");
+                escaped.push(char::from(code as u8 - 0x40));
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns the description of an image file
+fn image_message(file_type: &str) -> String {
+    format!("Image file detected: {}", file_type)
+}
+
+/// Returns the description of a binary file
+fn binary_message(size_bytes: u64, file_type: &str) -> String {
+    format!(
+        "Binary file detected, size: {} bytes, type: {}",
+        size_bytes, file_type
+    )
+}
+
+/// Determines the MIME type of a file from its extension, shebang line and content.
+///
+/// The `text_extensions` and `binary_extensions` of the options take precedence over the
+/// detection.
+fn detect_file_type(path: &Path, options: &ViewOptions) -> Result<String> {
+    // Infer file type using both extension and content analysis
+    let infer = Infer::new();
+
+    // First try to get a type hint from the extension
+    let extension_type = language::extension_language(path).map(language::language_mime_type);
+
+    // Extensionless scripts declare their language in a shebang line
+    let shebang_type = if extension_type.is_none() {
+        read_shebang_language(path).map(language::language_mime_type)
+    } else {
+        None
+    };
+
+    // Then try content-based detection, unless the extension is configured as text or binary
+    let file_type = match (
+        extension_override(path, &options.text_extensions, &options.binary_extensions),
+        shebang_type,
+    ) {
+        (Some(true), _) => extension_type.unwrap_or("text/plain").to_string(),
+        (Some(false), _) => "application/octet-stream".to_string(),
+        (None, Some(mime_type)) => mime_type.to_string(),
+        (None, None) => match infer.get_from_path(path) {
+            Ok(Some(kind)) => kind.mime_type().to_string(),
+            Ok(None) => {
+                // If infer couldn't determine type but we have an extension hint, use that
+                if let Some(ext_type) = extension_type {
+                    ext_type.to_string()
+                } else {
+                    // Read a small sample to check if it's probably text
+                    match std::fs::read(path) {
+                        Ok(bytes) if bytes.len() <= 1024 => {
+                            // Check if the content looks like text (mostly ASCII or UTF-8)
+                            let text_likelihood = bytes
+                                .iter()
+                                .filter(|b| {
+                                    **b >= 32 && **b <= 126
+                                        || **b == b'\n'
+                                        || **b == b'\r'
+                                        || **b == b'\t'
+                                })
+                                .count() as f64
+                                / bytes.len() as f64;
+
+                            if text_likelihood > 0.8 {
+                                "text/plain".to_string()
+                            } else {
+                                "application/octet-stream".to_string()
+                            }
+                        }
+                        _ => "application/octet-stream".to_string(), // Default to binary for larger files or errors
+                    }
+                }
+            }
+            Err(e) => return Err(anyhow!("Failed to determine file type: {}", e)),
+        },
+    };
+
+    Ok(file_type)
+}
+
+/// Reads only the first lines of a text file for preview mode.
+///
+/// Lines are streamed from disk until `preview_lines` lines of the selected range have been
+/// collected, plus one more line to determine whether the file continues.
+///
+/// # Returns
+///
+/// `Ok(None)` if the file turns out not to be valid UTF-8 and `lossy` is not set, so the
+/// caller can fall back to the regular (binary) handling. Otherwise a `FileView` with the
+/// previewed lines.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or the previewed content exceeds `max_size`.
+fn preview_text_file(
+    path: &Path,
+    file_type: String,
+    preview_lines: usize,
+    options: &ViewOptions,
+) -> Result<Option<FileView>> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to open file {}", path.display()))?;
+    let mut reader = BufReader::new(file);
+
+    let from_line = options.line_from.unwrap_or(1).max(1);
+    let to_line = options.line_to.unwrap_or(usize::MAX);
+
+    let mut line_contents = Vec::new();
+    let mut has_more = false;
+    let mut reached_eof = false;
+    let mut had_invalid_utf8 = false;
+    let mut line_number = 0;
+    let mut buffer = Vec::new();
+
+    while line_number < to_line {
+        buffer.clear();
+        let bytes_read = reader
+            .read_until(b'\n', &mut buffer)
+            .with_context(|| format!("Failed to read file {}", path.display()))?;
+        if bytes_read == 0 {
+            reached_eof = true;
+            break;
+        }
+        line_number += 1;
+
+        if line_number < from_line {
+            continue;
+        }
+        if line_contents.len() == preview_lines {
+            // One more line exists in the selected range beyond the preview
+            has_more = true;
+            break;
+        }
+
+        let line = match std::str::from_utf8(&buffer) {
+            Ok(line) => line.to_string(),
+            Err(_) if options.lossy => {
+                had_invalid_utf8 = true;
+                String::from_utf8_lossy(&buffer).into_owned()
+            }
+            Err(_) => return Ok(None),
+        };
+
+        line_contents.push(LineContent {
+            line_number,
+            line: line
+                .trim_end_matches('\n')
+                .trim_end_matches('\r')
+                .to_string(),
+            highlights: Vec::new(),
+            is_target: false,
+        });
+    }
+
+    // Check the previewed content size, including newline characters
+    if let Some(max_size) = options.max_size {
+        let preview_size = line_contents
+            .iter()
+            .map(|line| line.line.len() + 1)
+            .sum::<usize>();
+        if preview_size > max_size {
+            return Err(anyhow!(
+                "Previewed content is too large: {} (preview size: {}, limit: {})",
+                path.display(),
+                preview_size,
+                max_size
+            ));
+        }
+    }
+
+    let char_count = line_contents
+        .iter()
+        .map(|line| line.line.chars().count())
+        .sum();
+    let metadata = TextMetadata {
+        line_count: line_contents.len(),
+        char_count,
+        had_invalid_utf8,
+    };
+
+    // The total is only known if we read through to the end of the file
+    let total_line_num = if reached_eof { Some(line_number) } else { None };
+
+    Ok(Some(FileView {
+        file_path: path.to_path_buf(),
+        file_type,
+        contents: FileContents::Text {
+            content: TextContent { line_contents },
+            metadata,
+        },
+        total_line_num,
+        has_more,
+    }))
+}
@@ -0,0 +1,243 @@
+//! Paged viewing of large text files.
+//!
+//! `view_file` reads the selected lines into memory at once, which is impractical for very
+//! large files shown in a TUI. This module reads a file incrementally, one page of lines
+//! at a time, so only the pages that are actually displayed are read from disk.
+
+use anyhow::{Context, Result, anyhow};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek};
+use std::path::{Path, PathBuf};
+
+use super::{LineContent, ViewOptions, escape_control};
+
+/// Views a text file as a lazy sequence of pages of lines.
+///
+/// The file is read incrementally: each call to `next` reads the lines of one page from disk.
+/// The options are applied as follows:
+///
+/// - `line_from` / `line_to` select the range of lines that is paged through
+/// - `byte_offset` / `context_lines` select the line containing the byte and the lines around
+///   it as in `view_file`, taking precedence over `line_from` and `line_to`. The selected line
+///   is flagged with `LineContent::is_target`. Finding it reads the file up to the byte once.
+/// - `lossy` replaces invalid UTF-8 sequences instead of failing
+/// - `max_size` limits the content size of each page rather than of the whole file
+/// - `preview` is ignored, since the caller decides how many pages to read
+/// - `sanitize_control` replaces control characters in each line, after the page size check
+///
+/// If a page cannot be read (an I/O error, invalid UTF-8 without `lossy`, or a page exceeding
+/// `max_size`), an `Err` is yielded for it and the iteration ends.
+///
+/// # Arguments
+///
+/// * `path` - Path to the text file to view
+/// * `page_size` - Number of lines per page. The last page may contain fewer lines.
+/// * `options` - Configuration options for viewing
+///
+/// # Returns
+///
+/// An iterator yielding the pages of the file. No pages are yielded for an empty range.
+///
+/// # Errors
+///
+/// Returns an error if `page_size` is 0 or the file cannot be opened or read up to
+/// `byte_offset`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::view::{ViewOptions, view_pages};
+/// use std::path::Path;
+///
+/// let pages = view_pages(Path::new("large.log"), 100, &ViewOptions::default()).unwrap();
+///
+/// // Only the first two pages are read from disk
+/// for page in pages.take(2) {
+///     for line in page.unwrap() {
+///         println!("{}: {}", line.line_number, line.line);
+///     }
+/// }
+/// ```
+pub fn view_pages(
+    path: &Path,
+    page_size: usize,
+    options: &ViewOptions,
+) -> Result<impl Iterator<Item = Result<Vec<LineContent>>> + use<>> {
+    if page_size == 0 {
+        return Err(anyhow!("Page size must be greater than 0"));
+    }
+
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to open file {}", path.display()))?;
+
+    // The line containing the byte offset and its context take precedence over the line range
+    let target_line = options
+        .byte_offset
+        .map(|offset| byte_offset_line(&mut file, offset))
+        .transpose()
+        .with_context(|| format!("Failed to read file {}", path.display()))?;
+    let (from_line, to_line) = match target_line {
+        Some(target_line) => {
+            let context_lines = options.context_lines.unwrap_or(0);
+            (
+                target_line.saturating_sub(context_lines).max(1),
+                target_line.saturating_add(context_lines),
+            )
+        }
+        None => (
+            options.line_from.unwrap_or(1).max(1),
+            options.line_to.unwrap_or(usize::MAX),
+        ),
+    };
+
+    Ok(Pages {
+        reader: BufReader::new(file),
+        path: path.to_path_buf(),
+        page_size,
+        from_line,
+        to_line,
+        target_line,
+        max_size: options.max_size,
+        lossy: options.lossy,
+        sanitize_control: options.sanitize_control,
+        line_number: 0,
+        finished: false,
+    })
+}
+
+/// Returns the number (1-based) of the line containing a byte of a file, as `view_file` selects
+/// it for `ViewOptions::byte_offset`, and rewinds the file.
+///
+/// A newline byte belongs to the line it ends, and offsets past the end of the file select the
+/// last line.
+fn byte_offset_line(file: &mut File, offset: usize) -> std::io::Result<usize> {
+    let mut reader = BufReader::new((&*file).take(offset as u64));
+    let mut newlines = 0;
+    let mut last_byte = None;
+    loop {
+        let buffer = reader.fill_buf()?;
+        let Some(&last) = buffer.last() else {
+            break;
+        };
+        newlines += buffer.iter().filter(|&&b| b == b'\n').count();
+        last_byte = Some(last);
+        let length = buffer.len();
+        reader.consume(length);
+    }
+    file.rewind()?;
+
+    // Past the end of the file, a final newline doesn't start another line
+    let past_end = offset as u64 >= file.metadata()?.len();
+    Ok(if past_end && last_byte == Some(b'\n') {
+        newlines.max(1)
+    } else {
+        newlines + 1
+    })
+}
+
+/// Iterator state of `view_pages`
+struct Pages {
+    reader: BufReader<File>,
+    path: PathBuf,
+    page_size: usize,
+    from_line: usize,
+    to_line: usize,
+    /// The line containing `ViewOptions::byte_offset`
+    target_line: Option<usize>,
+    max_size: Option<usize>,
+    lossy: bool,
+    sanitize_control: bool,
+    /// Number of lines read so far
+    line_number: usize,
+    finished: bool,
+}
+
+impl Pages {
+    /// Reads the next page, or `None` at the end of the selected range
+    fn read_page(&mut self) -> Result<Option<Vec<LineContent>>> {
+        let mut page = Vec::with_capacity(self.page_size);
+        let mut page_bytes = 0;
+        let mut buffer = Vec::new();
+
+        while page.len() < self.page_size && self.line_number < self.to_line {
+            buffer.clear();
+            let bytes_read = self
+                .reader
+                .read_until(b'\n', &mut buffer)
+                .with_context(|| format!("Failed to read file {}", self.path.display()))?;
+            if bytes_read == 0 {
+                self.finished = true;
+                break;
+            }
+            self.line_number += 1;
+
+            if self.line_number < self.from_line {
+                continue;
+            }
+
+            let line = match std::str::from_utf8(&buffer) {
+                Ok(line) => line.to_string(),
+                Err(_) if self.lossy => String::from_utf8_lossy(&buffer).into_owned(),
+                Err(_) => {
+                    return Err(anyhow!(
+                        "Invalid UTF-8 at line {} of {}",
+                        self.line_number,
+                        self.path.display()
+                    ));
+                }
+            };
+            let line = line
+                .trim_end_matches('\n')
+                .trim_end_matches('\r')
+                .to_string();
+
+            // Check the page size, including newline characters
+            page_bytes += line.len() + 1;
+            if let Some(max_size) = self.max_size {
+                if page_bytes > max_size {
+                    return Err(anyhow!(
+                        "Page is too large: {} (page ending at line {}, limit: {})",
+                        self.path.display(),
+                        self.line_number,
+                        max_size
+                    ));
+                }
+            }
+
+            page.push(LineContent {
+                line_number: self.line_number,
+                line: if self.sanitize_control {
+                    escape_control(&line)
+                } else {
+                    line
+                },
+                highlights: Vec::new(),
+                is_target: self.target_line == Some(self.line_number),
+            });
+        }
+
+        if self.line_number >= self.to_line {
+            self.finished = true;
+        }
+
+        Ok(if page.is_empty() { None } else { Some(page) })
+    }
+}
+
+impl Iterator for Pages {
+    type Item = Result<Vec<LineContent>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        match self.read_page() {
+            Ok(page) => page.map(Ok),
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+//! Coarse tokenization of source lines.
+//!
+//! Custom highlighters often only need to tell comments, string literals and keywords apart
+//! from the rest of the code. This module splits each line into such spans with a few regular
+//! expressions per language, without the grammars of a full highlighting engine.
+
+use anyhow::{Result, anyhow};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::{FileContents, ViewOptions, detect_language, view_file};
+
+/// The kind of a token span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TokenKind {
+    /// A comment, up to the end of the line or of a block comment
+    Comment,
+    /// A string or character literal
+    String,
+    /// A keyword of the language
+    Keyword,
+    /// Any other text, including whitespace
+    Other,
+}
+
+/// A span of a line with the same token kind.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    /// The kind of the span
+    pub kind: TokenKind,
+    /// Byte offset of the start of the span within the line
+    pub start: usize,
+    /// Byte offset of the end of the span within the line (exclusive)
+    pub end: usize,
+}
+
+/// The token spans of a line.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct LineTokens {
+    /// Line number (1-based index)
+    pub line_number: usize,
+    /// Adjacent spans covering the whole line, in order. Empty for an empty line.
+    pub tokens: Vec<Token>,
+}
+
+/// Splits the lines of a text file into coarse token spans.
+///
+/// The language is detected with `detect_language`. For Rust, Python, JavaScript, TypeScript,
+/// Go, Java, C, C++, Bash and Ruby, comments, string literals and keywords are recognized;
+/// lines of other languages are returned as a single `TokenKind::Other` span. Each line is
+/// tokenized on its own, so the continuation lines of block comments and multi-line strings
+/// are not recognized.
+///
+/// The lines are selected as by `view_file`, and the spans are byte ranges within
+/// `LineContent::line` as `view_file` returns it (so they account for `sanitize_control`).
+///
+/// # Arguments
+///
+/// * `path` - Path to the file to tokenize
+/// * `options` - Configuration options, as for `view_file`
+///
+/// # Returns
+///
+/// The token spans of each selected line
+///
+/// # Errors
+///
+/// Returns the same errors as `view_file`, and an error if the file is not a text file
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::view::{TokenKind, ViewOptions, tokenize};
+/// use std::path::Path;
+///
+/// for line in tokenize(Path::new("src/main.rs"), &ViewOptions::default()).unwrap() {
+///     let comments = line
+///         .tokens
+///         .iter()
+///         .filter(|token| token.kind == TokenKind::Comment)
+///         .count();
+///     println!("{}: {} comments", line.line_number, comments);
+/// }
+/// ```
+pub fn tokenize(path: &Path, options: &ViewOptions) -> Result<Vec<LineTokens>> {
+    let content = match view_file(path, options)?.contents {
+        FileContents::Text { content, .. } => content,
+        _ => return Err(anyhow!("Not a text file: {}", path.display())),
+    };
+    let rules = detect_language(path, None)
+        .as_deref()
+        .and_then(language_rules)
+        .map(|rules| Regex::new(&rules))
+        .transpose()?;
+
+    Ok(content
+        .line_contents
+        .iter()
+        .map(|line| LineTokens {
+            line_number: line.line_number,
+            tokens: tokenize_line(&line.line, rules.as_ref()),
+        })
+        .collect())
+}
+
+/// Splits a line into spans with the `comment`, `string` and `keyword` groups of `rules`
+fn tokenize_line(line: &str, rules: Option<&Regex>) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut push = |kind, start, end| {
+        if start < end {
+            tokens.push(Token { kind, start, end });
+        }
+    };
+
+    let mut cursor = 0;
+    if let Some(rules) = rules {
+        for captures in rules.captures_iter(line) {
+            let (kind, span) = if let Some(span) = captures.name("comment") {
+                (TokenKind::Comment, span)
+            } else if let Some(span) = captures.name("string") {
+                (TokenKind::String, span)
+            } else if let Some(span) = captures.name("keyword") {
+                (TokenKind::Keyword, span)
+            } else {
+                continue;
+            };
+            push(TokenKind::Other, cursor, span.start());
+            push(kind, span.start(), span.end());
+            cursor = span.end();
+        }
+    }
+    push(TokenKind::Other, cursor, line.len());
+    tokens
+}
+
+/// Returns the token regex of a language, or `None` if the language is not supported
+fn language_rules(language: &str) -> Option<String> {
+    // Strings in double quotes, with backslash escapes
+    const DOUBLE_QUOTED: &str = r#""(?:\\.|[^"\\])*"?"#;
+    // Strings in single quotes, with backslash escapes
+    const SINGLE_QUOTED: &str = r"'(?:\\.|[^'\\])*'?";
+    // Character literals, which must not match Rust lifetimes
+    const CHAR_LITERAL: &str = r"'(?:\\.[^']*|[^'\\])'";
+    // Line and block comments of C-like languages
+    const C_COMMENT: &str = r"//.*|/\*.*?(?:\*/|$)";
+
+    let (comment, strings, keywords): (&str, Vec<&str>, &[&str]) = match language {
+        "rust" => (
+            C_COMMENT,
+            vec![r#"b?(?:r#*)?"(?:\\.|[^"\\])*"?#*"#, CHAR_LITERAL],
+            &[
+                "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else",
+                "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match",
+                "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static", "struct",
+                "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+            ],
+        ),
+        "python" => (
+            r"#.*",
+            vec![DOUBLE_QUOTED, SINGLE_QUOTED],
+            &[
+                "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class",
+                "continue", "def", "del", "elif", "else", "except", "finally", "for", "from",
+                "global", "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass",
+                "raise", "return", "try", "while", "with", "yield",
+            ],
+        ),
+        "javascript" | "typescript" => (
+            C_COMMENT,
+            vec![DOUBLE_QUOTED, SINGLE_QUOTED, r"`(?:\\.|[^`\\])*`?"],
+            &[
+                "async",
+                "await",
+                "break",
+                "case",
+                "catch",
+                "class",
+                "const",
+                "continue",
+                "default",
+                "delete",
+                "do",
+                "else",
+                "export",
+                "extends",
+                "false",
+                "finally",
+                "for",
+                "from",
+                "function",
+                "if",
+                "import",
+                "in",
+                "instanceof",
+                "interface",
+                "let",
+                "new",
+                "null",
+                "return",
+                "switch",
+                "this",
+                "throw",
+                "true",
+                "try",
+                "type",
+                "typeof",
+                "undefined",
+                "var",
+                "void",
+                "while",
+                "yield",
+            ],
+        ),
+        "go" => (
+            C_COMMENT,
+            vec![DOUBLE_QUOTED, CHAR_LITERAL, r"`[^`]*`?"],
+            &[
+                "break",
+                "case",
+                "chan",
+                "const",
+                "continue",
+                "default",
+                "defer",
+                "else",
+                "fallthrough",
+                "for",
+                "func",
+                "go",
+                "goto",
+                "if",
+                "import",
+                "interface",
+                "map",
+                "nil",
+                "package",
+                "range",
+                "return",
+                "select",
+                "struct",
+                "switch",
+                "type",
+                "var",
+            ],
+        ),
+        "c" | "cpp" | "java" => (
+            C_COMMENT,
+            vec![DOUBLE_QUOTED, CHAR_LITERAL],
+            &[
+                "break",
+                "case",
+                "catch",
+                "char",
+                "class",
+                "const",
+                "continue",
+                "default",
+                "do",
+                "double",
+                "else",
+                "enum",
+                "extends",
+                "false",
+                "float",
+                "for",
+                "if",
+                "import",
+                "int",
+                "long",
+                "namespace",
+                "new",
+                "null",
+                "nullptr",
+                "package",
+                "private",
+                "protected",
+                "public",
+                "return",
+                "short",
+                "static",
+                "struct",
+                "switch",
+                "this",
+                "throw",
+                "true",
+                "try",
+                "typedef",
+                "union",
+                "unsigned",
+                "void",
+                "while",
+            ],
+        ),
+        "bash" => (
+            r"(?:^|\s)#.*",
+            vec![DOUBLE_QUOTED, r"'[^']*'?"],
+            &[
+                "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function",
+                "if", "in", "local", "return", "then", "until", "while",
+            ],
+        ),
+        "ruby" => (
+            r"#.*",
+            vec![DOUBLE_QUOTED, SINGLE_QUOTED],
+            &[
+                "begin", "class", "def", "do", "else", "elsif", "end", "ensure", "false", "for",
+                "if", "in", "module", "next", "nil", "require", "rescue", "return", "self", "true",
+                "unless", "until", "when", "while", "yield",
+            ],
+        ),
+        _ => return None,
+    };
+
+    Some(format!(
+        r"(?P<comment>{})|(?P<string>{})|\b(?P<keyword>{})\b",
+        comment,
+        strings.join("|"),
+        keywords.join("|")
+    ))
+}
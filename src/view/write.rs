@@ -0,0 +1,131 @@
+//! Streaming of viewed files to writers.
+//!
+//! `view_file` collects the selected lines into a `FileView`, which is wasteful when the
+//! lines are only displayed. This module writes the same `path:line:content` output as the
+//! `lumin view` command directly to a writer, reading the file one page at a time.
+
+use anyhow::{Context, Result, anyhow};
+use std::io::Write;
+use std::path::Path;
+
+use super::{ViewOptions, binary_message, detect_file_type, image_message, view_pages};
+
+/// Number of lines read from disk at a time
+const WRITE_PAGE_SIZE: usize = 1024;
+
+/// Views a file and writes it to a writer in the format of the `lumin view` command.
+///
+/// Each line of a text file is written as `{path}:{line number}:{line}`. For binary and image
+/// files, a single `{path}: {description}` line is written instead. The options are applied
+/// as follows:
+///
+/// - `line_from` / `line_to` select the range of lines that is written
+/// - `preview` limits the number of lines written
+/// - `byte_offset` / `context_lines` select the line containing the byte and the lines around
+///   it, taking precedence over `line_from`, `line_to` and `preview`
+/// - `lossy` replaces invalid UTF-8 sequences instead of failing
+/// - `sanitize_control` replaces control characters in the written lines
+/// - `text_extensions` / `binary_extensions` override the file type detection
+/// - `max_size` limits the size of a text file unless lines are selected with `line_from`,
+///   `line_to`, `preview` or `byte_offset`, as in `view_file`. The selected lines are not
+///   limited, since the content is never held in memory as a whole.
+///
+/// Unlike `view_file`, a text file that turns out not to be valid UTF-8 is not reported as
+/// binary, since its preceding lines have already been written. An error is returned instead,
+/// unless `lossy` is set.
+///
+/// # Arguments
+///
+/// * `path` - Path to the file to view
+/// * `options` - Configuration options for viewing
+/// * `writer` - The writer to write the output to
+///
+/// # Errors
+///
+/// Returns an error if the file does not exist or cannot be read, is larger than `max_size`,
+/// contains invalid UTF-8 without `lossy`, or if writing fails
+///
+/// # Examples
+///
+/// ```no_run
+/// use lumin::view::{ViewOptions, write_file};
+/// use std::path::Path;
+///
+/// let mut stdout = std::io::stdout().lock();
+/// write_file(Path::new("large.log"), &ViewOptions::default(), &mut stdout).unwrap();
+/// ```
+pub fn write_file(path: &Path, options: &ViewOptions, writer: &mut dyn Write) -> Result<()> {
+    if !path.exists() {
+        return Err(anyhow!("File not found: {}", path.display()));
+    }
+    if !path.is_file() {
+        return Err(anyhow!("Not a file: {}", path.display()));
+    }
+
+    let file_path = path.to_string_lossy();
+    let file_type = detect_file_type(path, options)?;
+
+    if !file_type.starts_with("text/") {
+        let message = if file_type.starts_with("image/") {
+            image_message(&file_type)
+        } else {
+            let metadata = path
+                .metadata()
+                .with_context(|| format!("Failed to read file metadata for {}", path.display()))?;
+            binary_message(metadata.len(), &file_type)
+        };
+        writeln!(writer, "{}: {}", file_path, message)?;
+        return Ok(());
+    }
+
+    let using_line_filters = options.line_from.is_some()
+        || options.line_to.is_some()
+        || options.preview.is_some()
+        || options.byte_offset.is_some();
+    if let Some(max_size) = options.max_size.filter(|_| !using_line_filters) {
+        let size = path
+            .metadata()
+            .with_context(|| format!("Failed to read file metadata for {}", path.display()))?
+            .len();
+        if size > max_size as u64 {
+            return Err(anyhow!(
+                "File is too large: {} (size: {}, limit: {})",
+                path.display(),
+                size,
+                max_size
+            ));
+        }
+    }
+
+    // The byte offset selects the lines in `view_pages`, regardless of the preview
+    let page_options = if options.byte_offset.is_some() {
+        ViewOptions {
+            max_size: None,
+            ..options.clone()
+        }
+    } else {
+        // Limit the selected range to the previewed lines
+        let from_line = options.line_from.unwrap_or(1).max(1);
+        let to_line = match options.preview {
+            Some(0) => return Ok(()),
+            Some(preview) => options
+                .line_to
+                .unwrap_or(usize::MAX)
+                .min(from_line.saturating_add(preview - 1)),
+            None => options.line_to.unwrap_or(usize::MAX),
+        };
+        ViewOptions {
+            line_from: Some(from_line),
+            line_to: Some(to_line),
+            max_size: None,
+            ..options.clone()
+        }
+    };
+
+    for page in view_pages(path, WRITE_PAGE_SIZE, &page_options)? {
+        for line in page? {
+            writeln!(writer, "{}:{}:{}", file_path, line.line_number, line.line)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use std::fs;
+use std::process::Command;
+use tempfile::TempDir;
+
+/// Tests for the command line interface
+#[cfg(test)]
+mod cli_tests {
+    use super::*;
+
+    /// Runs the lumin binary and parses its standard output as JSON
+    fn run_json(args: &[&str]) -> Result<serde_json::Value> {
+        let output = Command::new(env!("CARGO_BIN_EXE_lumin"))
+            .args(args)
+            .output()?;
+        assert!(output.status.success(), "lumin {:?} failed", args);
+        Ok(serde_json::from_slice(&output.stdout)?)
+    }
+
+    /// Test that --explain prints the resolved search options instead of searching
+    #[test]
+    fn test_cli_explain_search() -> Result<()> {
+        let resolved = run_json(&[
+            "search",
+            "TODO",
+            "tests/fixtures",
+            "--max-depth",
+            "0",
+            "-B",
+            "2",
+            "--explain",
+        ])?;
+
+        assert_eq!(resolved["command"], "search");
+        assert_eq!(resolved["arguments"]["pattern"], "TODO");
+        assert_eq!(resolved["arguments"]["directory"], "tests/fixtures");
+
+        let options = &resolved["options"];
+        // A max depth of 0 means unlimited depth
+        assert!(options["depth"].is_null());
+        assert_eq!(options["before_context"], 2);
+        assert_eq!(options["respect_gitignore"], true);
+
+        Ok(())
+    }
+
+    /// Test that --search-pdf fails instead of silently skipping PDF files without the feature
+    #[cfg(not(feature = "pdf"))]
+    #[test]
+    fn test_cli_search_pdf_requires_feature() -> Result<()> {
+        let output = Command::new(env!("CARGO_BIN_EXE_lumin"))
+            .args(["search", "TODO", "tests/fixtures", "--search-pdf"])
+            .output()?;
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("`pdf` feature"));
+
+        Ok(())
+    }
+
+    /// Test that --dry-run is accepted before the subcommand as well
+    #[test]
+    fn test_cli_dry_run_tree() -> Result<()> {
+        let resolved = run_json(&["--dry-run", "tree", "tests/fixtures", "--no-ignore"])?;
+
+        assert_eq!(resolved["command"], "tree");
+        assert_eq!(resolved["options"]["depth"], 20);
+        assert_eq!(resolved["options"]["respect_gitignore"], false);
+
+        Ok(())
+    }
+
+    /// Test that --unsorted overrides sort_by_relevance from the configuration file
+    #[test]
+    fn test_cli_unsorted_overrides_config_relevance() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let config_path = temp_dir.path().join(".lumin.toml");
+        fs::write(&config_path, "[search]\nsort_by_relevance = true\n")?;
+        let config_path = config_path.to_string_lossy();
+
+        let resolved = run_json(&[
+            "--config",
+            &config_path,
+            "--dry-run",
+            "search",
+            "TODO",
+            "tests/fixtures",
+        ])?;
+        assert_eq!(resolved["options"]["sort_by_relevance"], true);
+
+        let resolved = run_json(&[
+            "--config",
+            &config_path,
+            "--dry-run",
+            "search",
+            "TODO",
+            "tests/fixtures",
+            "--unsorted",
+        ])?;
+        assert_eq!(resolved["options"]["sort"], "None");
+        assert_eq!(resolved["options"]["sort_by_relevance"], false);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::Result;
+use lumin::config::{LuminConfig, load, merge};
+use lumin::search::SearchOptions;
+use serde_json::json;
+use std::fs;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use tempfile::TempDir;
+
+/// Tests for loading configuration files and merging them with explicit options
+#[cfg(test)]
+mod config_tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[search]
+case_sensitive = true
+before_context = 3
+depth = 5
+exclude_dir_names = ["target"]
+
+[tree]
+max_file_size = 1000
+"#;
+
+    #[test]
+    fn test_load_config() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join(".lumin.toml");
+        fs::write(&path, CONFIG)?;
+
+        let config = load(&path)?;
+        assert!(config.search.case_sensitive);
+        assert_eq!(config.search.before_context, 3);
+        assert_eq!(config.search.depth, Some(5));
+        assert_eq!(config.search.exclude_dir_names, vec!["target".to_string()]);
+        // Fields missing from a section take their default values
+        assert!(config.search.respect_gitignore);
+        assert_eq!(config.tree.max_file_size, Some(1000));
+        // Missing sections take their default values
+        assert_eq!(config.traverse.depth, Some(20));
+        assert_eq!(config.view.line_from, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_precedence() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let path = temp_dir.path().join(".lumin.toml");
+        fs::write(&path, CONFIG)?;
+        let config = load(&path)?;
+
+        // Explicit options override the file, other fields keep the file values
+        let options: SearchOptions = merge(
+            &config.search,
+            json!({ "before_context": 1, "depth": null, "treat_all_as_text": true }),
+        )?;
+        assert_eq!(options.before_context, 1);
+        assert_eq!(options.depth, None);
+        assert!(options.treat_all_as_text);
+        assert!(options.case_sensitive);
+        assert_eq!(options.exclude_dir_names, vec!["target".to_string()]);
+
+        // Without overrides, the file values are used as-is
+        let options: SearchOptions = merge(&config.search, json!({}))?;
+        assert_eq!(options.before_context, 3);
+        assert_eq!(options.depth, Some(5));
+
+        // Fields that are not serialized keep their values from the base options
+        let cancel = Arc::new(AtomicBool::new(false));
+        let base = SearchOptions {
+            cancel: Some(cancel.clone()),
+            ..config.search.clone()
+        };
+        let options: SearchOptions = merge(&base, json!({ "before_context": 1 }))?;
+        assert!(options.cancel.is_some_and(|flag| Arc::ptr_eq(&flag, &cancel)));
+
+        // Unknown options are rejected
+        assert!(merge(&config.search, json!({ "no_such_option": true })).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_invalid_config() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        let path = temp_dir.path().join("unknown_section.toml");
+        fs::write(&path, "[serach]\ncase_sensitive = true\n")?;
+        assert!(load(&path).is_err());
+
+        // Misspelled fields are rejected in every section
+        for section in ["search", "traverse", "tree", "view"] {
+            let path = temp_dir
+                .path()
+                .join(format!("unknown_{}_field.toml", section));
+            fs::write(&path, format!("[{}]\ncase_sensitiv = true\n", section))?;
+            let error = load(&path).unwrap_err();
+            assert!(
+                format!("{:#}", error).contains("case_sensitiv"),
+                "{:#}",
+                error
+            );
+        }
+
+        let path = temp_dir.path().join("wrong_type.toml");
+        fs::write(&path, "[search]\ndepth = \"deep\"\n")?;
+        assert!(load(&path).is_err());
+
+        assert!(load(&temp_dir.path().join("missing.toml")).is_err());
+
+        // An empty file is a default configuration
+        let path = temp_dir.path().join("empty.toml");
+        fs::write(&path, "")?;
+        let config = load(&path)?;
+        assert_eq!(
+            format!("{:?}", config),
+            format!("{:?}", LuminConfig::default())
+        );
+
+        Ok(())
+    }
+}
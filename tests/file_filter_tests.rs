@@ -0,0 +1,175 @@
+use anyhow::Result;
+use lumin::search::{SearchOptions, search_files};
+use lumin::traverse::{TraverseOptions, traverse_directory};
+use lumin::tree::{Entry, TreeOptions, generate_tree};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use tempfile::{Builder, TempDir};
+
+/// Tests for the include and exclude globs shared by search, traverse and tree
+#[cfg(test)]
+mod file_filter_tests {
+    use super::*;
+
+    /// Creates `src/main.rs`, `src/generated/api.rs`, `docs/guide.md` and `README.md`,
+    /// each containing the word "needle"
+    fn create_files() -> Result<TempDir> {
+        // The default `.tmp` prefix would make every path hidden to tree
+        let temp_dir = Builder::new().prefix("file_filter").tempdir()?;
+        for file in [
+            "src/main.rs",
+            "src/generated/api.rs",
+            "docs/guide.md",
+            "README.md",
+        ] {
+            let path = temp_dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, "needle\n")?;
+        }
+        Ok(temp_dir)
+    }
+
+    /// The include list, the exclude list and the files expected to be kept
+    type Case = (Option<Vec<String>>, Option<Vec<String>>, Vec<&'static str>);
+
+    /// The cases checked by each module
+    fn cases() -> Vec<Case> {
+        vec![
+            // Include only
+            (
+                Some(vec!["**/*.rs".to_string()]),
+                None,
+                vec!["src/generated/api.rs", "src/main.rs"],
+            ),
+            // Exclude only
+            (
+                None,
+                Some(vec!["**/*.md".to_string()]),
+                vec!["src/generated/api.rs", "src/main.rs"],
+            ),
+            // Combined, where the exclusion takes precedence
+            (
+                Some(vec!["src/**".to_string(), "**/*.md".to_string()]),
+                Some(vec!["**/generated/**".to_string(), "README.md".to_string()]),
+                vec!["docs/guide.md", "src/main.rs"],
+            ),
+        ]
+    }
+
+    fn relative_names<'a>(
+        directory: &Path,
+        paths: impl Iterator<Item = &'a Path>,
+    ) -> BTreeSet<String> {
+        paths
+            .map(|path| {
+                path.strip_prefix(directory)
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    fn expected(files: &[&str]) -> BTreeSet<String> {
+        files.iter().map(|file| file.to_string()).collect()
+    }
+
+    #[test]
+    fn test_search_include_exclude_globs() -> Result<()> {
+        let temp_dir = create_files()?;
+        let directory = temp_dir.path();
+
+        for (include_glob, exclude_glob, files) in cases() {
+            let options = SearchOptions {
+                include_glob: include_glob.clone(),
+                exclude_glob: exclude_glob.clone(),
+                ..SearchOptions::default()
+            };
+            let result = search_files("needle", directory, &options)?;
+            let found = relative_names(
+                directory,
+                result.lines.iter().map(|line| line.file_path.as_path()),
+            );
+            assert_eq!(
+                found,
+                expected(&files),
+                "include {:?}, exclude {:?}",
+                include_glob,
+                exclude_glob
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_traverse_include_exclude_globs() -> Result<()> {
+        let temp_dir = create_files()?;
+        let directory = temp_dir.path();
+
+        for (include_glob, exclude_glob, files) in cases() {
+            let options = TraverseOptions {
+                include_glob: include_glob.clone(),
+                exclude_glob: exclude_glob.clone(),
+                ..TraverseOptions::default()
+            };
+            let results = traverse_directory(directory, &options)?;
+            let found = relative_names(
+                directory,
+                results.iter().map(|result| result.file_path.as_path()),
+            );
+            assert_eq!(
+                found,
+                expected(&files),
+                "include {:?}, exclude {:?}",
+                include_glob,
+                exclude_glob
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_include_exclude_globs() -> Result<()> {
+        let temp_dir = create_files()?;
+        let directory = temp_dir.path();
+
+        for (include_glob, exclude_glob, files) in cases() {
+            let options = TreeOptions {
+                include_glob: include_glob.clone(),
+                exclude_glob: exclude_glob.clone(),
+                ..TreeOptions::default()
+            };
+            let trees = generate_tree(directory, &options)?;
+            let file_paths: Vec<_> = trees
+                .iter()
+                .flat_map(|tree| {
+                    tree.entries.iter().filter_map(|entry| match entry {
+                        Entry::File { name } => Some(tree.dir.join(name)),
+                        _ => None,
+                    })
+                })
+                .collect();
+            let found = relative_names(directory, file_paths.iter().map(|path| path.as_path()));
+            assert_eq!(
+                found,
+                expected(&files),
+                "include {:?}, exclude {:?}",
+                include_glob,
+                exclude_glob
+            );
+
+            // Directories are listed even when none of their files are kept
+            assert!(trees.iter().any(|tree| {
+                tree.dir == directory.join("src")
+                    && tree.entries.iter().any(
+                        |entry| matches!(entry, Entry::Directory { name } if name == "generated"),
+                    )
+            }));
+        }
+
+        Ok(())
+    }
+}
@@ -93,9 +93,11 @@ mod integration_tests {
         let _env = TestEnvironment::setup()?;
 
         // Configure traversal to include binary files
-        let mut traverse_options = TraverseOptions::default();
-        traverse_options.only_text_files = false;
-        traverse_options.respect_gitignore = false;
+        let traverse_options = TraverseOptions {
+            only_text_files: false,
+            respect_gitignore: false,
+            ..TraverseOptions::default()
+        };
 
         let files = traverse_directory(Path::new(TEST_DIR), &traverse_options)?;
 
@@ -139,29 +141,33 @@ mod integration_tests {
         let view_options = ViewOptions::default();
 
         // View a Rust file
-        let rust_view = view_file(&rust_files[0], &view_options)?;
+        let rust_view = view_file(rust_files[0], &view_options)?;
         assert!(rust_view.file_type.starts_with("text/"));
 
         // View a Markdown file
-        let md_view = view_file(&markdown_files[0], &view_options)?;
+        let md_view = view_file(markdown_files[0], &view_options)?;
         assert!(md_view.file_type.starts_with("text/"));
 
         // View a Python file
-        let py_view = view_file(&python_files[0], &view_options)?;
+        let py_view = view_file(python_files[0], &view_options)?;
         assert!(py_view.file_type.starts_with("text/"));
 
         // View a binary file if available
         if !binary_files.is_empty() {
-            let bin_view = view_file(&binary_files[0], &view_options)?;
+            let bin_view = view_file(binary_files[0], &view_options)?;
 
             // Check the contents using enum match
             match &bin_view.contents {
-                FileContents::Binary { message, metadata } => {
+                FileContents::Binary {
+                    message, metadata, ..
+                } => {
                     assert!(message.contains("Binary file"));
                     assert!(metadata.binary);
                     assert!(metadata.size_bytes > 0);
                 }
-                FileContents::Image { message, metadata } => {
+                FileContents::Image {
+                    message, metadata, ..
+                } => {
                     assert!(message.contains("Image file"));
                     assert!(metadata.binary);
                     assert!(metadata.size_bytes > 0);
@@ -176,7 +182,9 @@ mod integration_tests {
 
                 // Check the contents using enum match
                 match &bin_view.contents {
-                    FileContents::Binary { message, metadata } => {
+                    FileContents::Binary {
+                        message, metadata, ..
+                    } => {
                         assert!(message.contains("Binary file"));
                         assert!(metadata.binary);
                         assert!(metadata.size_bytes > 0);
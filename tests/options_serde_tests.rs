@@ -0,0 +1,104 @@
+use anyhow::Result;
+use lumin::search::{ReadStrategy, SearchOptions};
+use lumin::traverse::TraverseOptions;
+use lumin::tree::TreeOptions;
+use lumin::view::ViewOptions;
+use std::path::PathBuf;
+
+/// Tests for serializing and deserializing the options structs
+#[cfg(test)]
+mod options_serde_tests {
+    use super::*;
+
+    /// Serializes a value to JSON and back, returning the Debug output of both
+    fn round_trip<T>(value: &T) -> Result<(String, String)>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        let json = serde_json::to_string(value)?;
+        let restored: T = serde_json::from_str(&json)?;
+        Ok((format!("{:?}", value), format!("{:?}", restored)))
+    }
+
+    #[test]
+    fn test_search_options_round_trip() -> Result<()> {
+        let options = SearchOptions {
+            case_sensitive: true,
+            exclude_glob: Some(vec!["**/target/**".to_string()]),
+            omit_path_prefix: Some(PathBuf::from("/home/user/project")),
+            before_context: 2,
+            highlight_markers: Some(("<<".to_string(), ">>".to_string())),
+            read_strategy: ReadStrategy::Auto,
+            line_terminator: b'\0',
+            ..SearchOptions::default()
+        };
+
+        let (original, restored) = round_trip(&options)?;
+        assert_eq!(original, restored);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_traverse_options_round_trip() -> Result<()> {
+        let options = TraverseOptions {
+            pattern: Some("*.rs".to_string()),
+            depth: None,
+            include_metadata: true,
+            ..TraverseOptions::default()
+        };
+
+        let (original, restored) = round_trip(&options)?;
+        assert_eq!(original, restored);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tree_options_round_trip() -> Result<()> {
+        let options = TreeOptions {
+            respect_gitignore: false,
+            max_file_size: Some(1024),
+            ..TreeOptions::default()
+        };
+
+        let (original, restored) = round_trip(&options)?;
+        assert_eq!(original, restored);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_view_options_round_trip() -> Result<()> {
+        let options = ViewOptions {
+            line_from: Some(10),
+            line_to: Some(20),
+            lossy: true,
+            ..ViewOptions::default()
+        };
+
+        let (original, restored) = round_trip(&options)?;
+        assert_eq!(original, restored);
+
+        Ok(())
+    }
+
+    /// Fields missing from a configuration take their default values
+    #[test]
+    fn test_partial_options_use_defaults() -> Result<()> {
+        let options: SearchOptions =
+            serde_json::from_str(r#"{ "case_sensitive": true, "include_glob": ["**/*.rs"] }"#)?;
+        assert!(options.case_sensitive);
+        assert_eq!(options.include_glob, Some(vec!["**/*.rs".to_string()]));
+        assert_eq!(options.depth, SearchOptions::default().depth);
+        assert!(options.respect_gitignore);
+
+        let options: TraverseOptions = serde_json::from_str("{}")?;
+        assert_eq!(
+            format!("{:?}", options),
+            format!("{:?}", TraverseOptions::default())
+        );
+
+        Ok(())
+    }
+}
@@ -43,8 +43,10 @@ mod search_after_context_tests {
         let _env = TestEnvironment::setup()?;
 
         let pattern = "fn main";
-        let mut options = SearchOptions::default();
-        options.after_context = 3; // Show 3 lines after each match
+        let options = SearchOptions {
+            after_context: 3, // Show 3 lines after each match
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -91,7 +93,7 @@ mod search_after_context_tests {
                 // Only verify exact context count if we're not at the end of the file
                 // and if this match doesn't immediately precede another match
                 if i + context_count + 1 < results.lines.len()
-                    && results.lines[i + context_count + 1].is_context == false
+                    && !results.lines[i + context_count + 1].is_context
                 {
                     assert_eq!(context_count, options.after_context);
                 }
@@ -109,8 +111,10 @@ mod search_after_context_tests {
 
         // Search for pattern that might have adjacent matches
         let pattern = "#";
-        let mut options = SearchOptions::default();
-        options.after_context = 2; // Show 2 lines after each match
+        let options = SearchOptions {
+            after_context: 2, // Show 2 lines after each match
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -169,8 +173,10 @@ mod search_after_context_tests {
         let _env = TestEnvironment::setup()?;
 
         let pattern = "fn main";
-        let mut options = SearchOptions::default();
-        options.after_context = 100; // Much larger than file sizes
+        let options = SearchOptions {
+            after_context: 100, // Much larger than file sizes
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -214,9 +220,11 @@ mod search_after_context_tests {
         let _env = TestEnvironment::setup()?;
 
         let pattern = "fn main";
-        let mut options = SearchOptions::default();
-        options.after_context = 3; // Show 3 lines after each match
-        options.match_content_omit_num = Some(10); // Only show 10 chars around matches
+        let options = SearchOptions {
+            after_context: 3,                 // Show 3 lines after each match
+            match_content_omit_num: Some(10), // Only show 10 chars around matches
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -0,0 +1,62 @@
+use anyhow::Result;
+use lumin::search::{SearchOptions, aggregate_by_directory};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use tempfile::TempDir;
+
+/// Tests for aggregating match counts by directory
+#[cfg(test)]
+mod search_aggregate_tests {
+    use super::*;
+
+    /// Test that a directory's count is the sum of the matches beneath it
+    #[test]
+    fn test_aggregate_by_directory_sums_descendants() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        fs::create_dir_all(root.join("src/nested"))?;
+        fs::create_dir_all(root.join("docs"))?;
+        fs::write(root.join("top.txt"), "needle\n")?;
+        fs::write(root.join("src/one.txt"), "needle\nhay\nneedle\n")?;
+        fs::write(root.join("src/nested/two.txt"), "needle\nneedle\nneedle\n")?;
+        fs::write(root.join("docs/none.txt"), "hay\n")?;
+
+        let options = SearchOptions {
+            before_context: 1,
+            ..SearchOptions::default()
+        };
+        let counts = aggregate_by_directory("needle", root, &options)?;
+
+        let count_of = |dir: &Path| {
+            counts
+                .iter()
+                .find(|(path, _)| path == dir)
+                .map(|(_, count)| *count)
+        };
+
+        // Each ancestor sums its own files and all of its subdirectories
+        assert_eq!(count_of(&root.join("src/nested")), Some(3));
+        assert_eq!(count_of(&root.join("src")), Some(2 + 3));
+        assert_eq!(count_of(root), Some(1 + 2 + 3));
+
+        // Directories without matches are not reported
+        assert_eq!(count_of(&root.join("docs")), None);
+        assert_eq!(counts.len(), 3);
+
+        // The root count matches the total of a regular search
+        let results = lumin::search::search_files("needle", root, &SearchOptions::default())?;
+        assert_eq!(count_of(root), Some(results.total_number));
+
+        // A cancelled search is an error rather than partial counts
+        let options = SearchOptions {
+            cancel: Some(Arc::new(AtomicBool::new(true))),
+            ..SearchOptions::default()
+        };
+        assert!(aggregate_by_directory("needle", root, &options).is_err());
+
+        Ok(())
+    }
+}
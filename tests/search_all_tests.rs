@@ -0,0 +1,69 @@
+use anyhow::Result;
+use lumin::search::{SearchOptions, files_matching_all};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use tempfile::TempDir;
+
+/// Tests for finding files that match every one of several patterns
+#[cfg(test)]
+mod search_all_tests {
+    use super::*;
+
+    /// Test that only files containing all patterns are returned
+    #[test]
+    fn test_files_matching_all_patterns() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        fs::create_dir_all(root.join("nested"))?;
+        fs::write(
+            root.join("both.txt"),
+            "TODO: split\nsome code\nFIXME: leak\n",
+        )?;
+        fs::write(root.join("same_line.txt"), "TODO and FIXME\n")?;
+        fs::write(root.join("todo_only.txt"), "TODO: later\n")?;
+        fs::write(root.join("fixme_only.txt"), "FIXME: now\n")?;
+        fs::write(root.join("neither.txt"), "nothing to do\n")?;
+        fs::write(root.join("nested/both.txt"), "FIXME first\nTODO second\n")?;
+
+        let options = SearchOptions {
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..SearchOptions::default()
+        };
+        let patterns = vec!["TODO".to_string(), "FIXME".to_string()];
+        let files = files_matching_all(&patterns, root, &options)?;
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("both.txt"),
+                PathBuf::from("nested/both.txt"),
+                PathBuf::from("same_line.txt"),
+            ]
+        );
+
+        // A single pattern returns every file it matches
+        let files = files_matching_all(&patterns[..1], root, &options)?;
+        assert_eq!(files.len(), 4);
+
+        // A pattern that matches nowhere leaves no files
+        let patterns = vec!["TODO".to_string(), "XXX".to_string()];
+        assert!(files_matching_all(&patterns, root, &options)?.is_empty());
+
+        // Invalid patterns and an empty pattern list are errors
+        let patterns = vec!["TODO".to_string(), "(".to_string()];
+        assert!(files_matching_all(&patterns, root, &options).is_err());
+        assert!(files_matching_all(&[], root, &options).is_err());
+
+        // A cancelled search is an error rather than a partial list of files
+        let options = SearchOptions {
+            cancel: Some(Arc::new(AtomicBool::new(true))),
+            ..options
+        };
+        let patterns = vec!["TODO".to_string(), "FIXME".to_string()];
+        assert!(files_matching_all(&patterns, root, &options).is_err());
+
+        Ok(())
+    }
+}
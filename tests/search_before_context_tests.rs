@@ -44,8 +44,10 @@ mod search_before_context_tests {
 
         // Using a pattern that has content before it in the test files
         let pattern = "This is a dummy function";
-        let mut options = SearchOptions::default();
-        options.before_context = 3; // Show 3 lines before each match
+        let options = SearchOptions {
+            before_context: 3, // Show 3 lines before each match
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -124,8 +126,10 @@ mod search_before_context_tests {
 
         // Search for pattern that might have adjacent matches
         let pattern = "#";
-        let mut options = SearchOptions::default();
-        options.before_context = 2; // Show 2 lines before each match
+        let options = SearchOptions {
+            before_context: 2, // Show 2 lines before each match
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -155,8 +159,7 @@ mod search_before_context_tests {
                 if is_context {
                     // Find the match that this context line belongs to
                     let mut found_parent = false;
-                    for j in i + 1..sorted_lines.len() {
-                        let (parent_line, parent_is_context) = sorted_lines[j];
+                    for &(parent_line, parent_is_context) in &sorted_lines[i + 1..] {
                         if !parent_is_context {
                             // This is a match, check if our context line is within range
                             if line_num >= parent_line - options.before_context as u64 {
@@ -184,8 +187,10 @@ mod search_before_context_tests {
         let _env = TestEnvironment::setup()?;
 
         let pattern = "fn main";
-        let mut options = SearchOptions::default();
-        options.before_context = 100; // Much larger than file sizes
+        let options = SearchOptions {
+            before_context: 100, // Much larger than file sizes
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -228,9 +233,11 @@ mod search_before_context_tests {
         let _env = TestEnvironment::setup()?;
 
         let pattern = "fn main";
-        let mut options = SearchOptions::default();
-        options.before_context = 3; // Show 3 lines before each match
-        options.match_content_omit_num = Some(10); // Only show 10 chars around matches
+        let options = SearchOptions {
+            before_context: 3,                // Show 3 lines before each match
+            match_content_omit_num: Some(10), // Only show 10 chars around matches
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -18,9 +18,11 @@ mod search_combined_context_tests {
         let _env = TestEnvironment::setup()?;
 
         let pattern = "fn main";
-        let mut options = SearchOptions::default();
-        options.before_context = 2; // Show 2 lines before each match
-        options.after_context = 3; // Show 3 lines after each match
+        let options = SearchOptions {
+            before_context: 2, // Show 2 lines before each match
+            after_context: 3,  // Show 3 lines after each match
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -131,9 +133,11 @@ mod search_combined_context_tests {
 
         // First let's find a file with multiple matches close together
         let pattern = "fn";
-        let mut options = SearchOptions::default();
-        options.before_context = 3;
-        options.after_context = 3;
+        let options = SearchOptions {
+            before_context: 3,
+            after_context: 3,
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -203,9 +207,11 @@ mod search_combined_context_tests {
         let _env = TestEnvironment::setup()?;
 
         let pattern = "fn main";
-        let mut options = SearchOptions::default();
-        options.before_context = 100; // Much larger than file sizes
-        options.after_context = 100; // Much larger than file sizes
+        let options = SearchOptions {
+            before_context: 100, // Much larger than file sizes
+            after_context: 100,  // Much larger than file sizes
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -213,7 +219,7 @@ mod search_combined_context_tests {
         assert!(!results.lines.is_empty());
 
         // Find a match and verify the entire file is included as context
-        for (_i, result) in results.lines.iter().enumerate() {
+        for result in results.lines.iter() {
             if !result.is_context && result.line_content.contains(pattern) {
                 // Found a match
                 let file_path = &result.file_path;
@@ -248,10 +254,12 @@ mod search_combined_context_tests {
         let _env = TestEnvironment::setup()?;
 
         let pattern = "fn main";
-        let mut options = SearchOptions::default();
-        options.before_context = 2; // Show 2 lines before each match
-        options.after_context = 2; // Show 2 lines after each match
-        options.match_content_omit_num = Some(10); // Only show 10 chars around matches
+        let options = SearchOptions {
+            before_context: 2,                // Show 2 lines before each match
+            after_context: 2,                 // Show 2 lines after each match
+            match_content_omit_num: Some(10), // Only show 10 chars around matches
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -283,4 +291,63 @@ mod search_combined_context_tests {
 
         Ok(())
     }
+
+    /// Test grouping matches and their context lines into windows
+    #[test]
+    fn test_search_result_windows() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        std::fs::write(
+            temp_dir.path().join("windows.txt"),
+            "a\nMATCH 2\nb\nc\nMATCH 5\nMATCH 6\nd\ne\nf\nMATCH 10\n",
+        )?;
+
+        let options = SearchOptions {
+            before_context: 1,
+            after_context: 1,
+            ..SearchOptions::default()
+        };
+        let windows = search_files("MATCH", temp_dir.path(), &options)?.windows();
+        let line_numbers = |lines: &[lumin::search::SearchResultLine]| -> Vec<u64> {
+            lines.iter().map(|line| line.line_number).collect()
+        };
+
+        let summary: Vec<(Vec<u64>, u64, Vec<u64>)> = windows
+            .iter()
+            .map(|window| {
+                assert!(!window.match_line.is_context);
+                assert!(
+                    window
+                        .before
+                        .iter()
+                        .chain(&window.after)
+                        .all(|l| l.is_context)
+                );
+                assert_eq!(window.file_path, window.match_line.file_path);
+                (
+                    line_numbers(&window.before),
+                    window.match_line.line_number,
+                    line_numbers(&window.after),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (vec![1], 2, vec![3]),
+                (vec![4], 5, vec![]),
+                (vec![], 6, vec![7]),
+                (vec![9], 10, vec![]),
+            ]
+        );
+
+        // A context line within reach of two matches belongs to both windows
+        std::fs::write(temp_dir.path().join("windows.txt"), "x\nHIT\ny\nHIT\nz\n")?;
+        let windows = search_files("HIT", temp_dir.path(), &options)?.windows();
+        assert_eq!(windows.len(), 2);
+        assert_eq!(line_numbers(&windows[0].after), vec![3]);
+        assert_eq!(line_numbers(&windows[1].before), vec![3]);
+        assert_eq!(line_numbers(&windows[1].after), vec![5]);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,93 @@
+use anyhow::Result;
+use lumin::search::{SearchOptions, search_files};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+/// Tests for searching the decompressed content of compressed files
+#[cfg(test)]
+mod search_compressed_tests {
+    use super::*;
+
+    /// Compresses `content` into `target` with a command line tool.
+    ///
+    /// Returns `false` if the tool is not installed, so the test can be skipped.
+    fn compress(program: &str, content: &str, target: &Path) -> Result<bool> {
+        let source = target.with_extension("source");
+        fs::write(&source, content)?;
+        let output = match Command::new(program)
+            .args(["-c", "-q"])
+            .arg(&source)
+            .output()
+        {
+            Ok(output) => output,
+            Err(_) => return Ok(false),
+        };
+        assert!(output.status.success(), "{} failed", program);
+        fs::remove_file(&source)?;
+        fs::write(target, output.stdout)?;
+        Ok(true)
+    }
+
+    /// Searches `directory` and returns the matched file names and line numbers
+    fn matches(directory: &Path, search_compressed: bool) -> Result<Vec<(String, u64)>> {
+        let options = SearchOptions {
+            respect_gitignore: false,
+            search_compressed,
+            ..SearchOptions::default()
+        };
+        let results = search_files("needle", directory, &options)?;
+        Ok(results
+            .lines
+            .iter()
+            .map(|line| {
+                let name = line.file_path.file_name().unwrap().to_string_lossy();
+                (name.into_owned(), line.line_number)
+            })
+            .collect())
+    }
+
+    #[test]
+    fn test_search_gzip_file() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        // Mislabeled: gzip content with a plain text extension
+        let file = temp_dir.path().join("server.log");
+        if !compress("gzip", "start\nfound a needle\nstop\n", &file)? {
+            return Ok(());
+        }
+
+        assert!(matches(temp_dir.path(), false)?.is_empty());
+        assert_eq!(
+            matches(temp_dir.path(), true)?,
+            vec![("server.log".to_string(), 2)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_zstd_file() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        // Extensionless zstd content
+        let file = temp_dir.path().join("archive");
+        if !compress("zstd", "one\ntwo\nthree needle\n", &file)? {
+            return Ok(());
+        }
+        fs::write(temp_dir.path().join("plain.txt"), "needle in plain text\n")?;
+
+        // Plain files are searched as usual alongside compressed ones
+        assert_eq!(
+            matches(temp_dir.path(), false)?,
+            vec![("plain.txt".to_string(), 1)]
+        );
+        let mut found = matches(temp_dir.path(), true)?;
+        found.sort();
+        assert_eq!(
+            found,
+            vec![("archive".to_string(), 3), ("plain.txt".to_string(), 1)]
+        );
+
+        Ok(())
+    }
+}
@@ -3,7 +3,6 @@ use lumin::search::{SearchOptions, search_files};
 use serial_test::serial;
 use std::fs::File;
 use std::io::Write;
-use std::path::Path;
 use tempfile::tempdir;
 
 /// Tests for the search edge cases
@@ -24,8 +23,10 @@ mod search_edge_case_tests {
         writeln!(file, "{}", content)?;
 
         // Search with before_context = 3 (shouldn't have any effect since match is on first line)
-        let mut options = SearchOptions::default();
-        options.before_context = 3;
+        let options = SearchOptions {
+            before_context: 3,
+            ..SearchOptions::default()
+        };
 
         let results = search_files("match the pattern", &file_path, &options)?;
 
@@ -66,8 +67,10 @@ mod search_edge_case_tests {
         writeln!(file, "{}", content)?;
 
         // Search with after_context = 3 (shouldn't have any effect since match is on last line)
-        let mut options = SearchOptions::default();
-        options.after_context = 3;
+        let options = SearchOptions {
+            after_context: 3,
+            ..SearchOptions::default()
+        };
 
         let results = search_files("match the pattern", &file_path, &options)?;
 
@@ -108,9 +111,11 @@ mod search_edge_case_tests {
         writeln!(file, "{}", content)?;
 
         // Search with both before and after context
-        let mut options = SearchOptions::default();
-        options.before_context = 2;
-        options.after_context = 2;
+        let options = SearchOptions {
+            before_context: 2,
+            after_context: 2,
+            ..SearchOptions::default()
+        };
 
         let results = search_files("MATCH_THIS", &file_path, &options)?;
 
@@ -186,9 +191,11 @@ mod search_edge_case_tests {
         writeln!(file, "{}", content)?;
 
         // Search with both before and after context
-        let mut options = SearchOptions::default();
-        options.before_context = 3; // Should be ignored since there are no lines before
-        options.after_context = 3; // Should be ignored since there are no lines after
+        let options = SearchOptions {
+            before_context: 3, // Should be ignored since there are no lines before
+            after_context: 3,  // Should be ignored since there are no lines after
+            ..SearchOptions::default()
+        };
 
         let results = search_files("match pattern", &file_path, &options)?;
 
@@ -208,4 +215,94 @@ mod search_edge_case_tests {
 
         Ok(())
     }
+
+    // Test that a match located after a NUL byte is only found when treating all files as text
+    #[test]
+    #[serial]
+    fn test_treat_all_as_text_finds_match_after_nul() -> Result<()> {
+        // Create a temporary directory
+        let temp_dir = tempdir()?;
+        let file_path = temp_dir.path().join("mixed.dat");
+
+        // Create a mostly-text file with a NUL byte before the matching line
+        let mut file = File::create(&file_path)?;
+        file.write_all(b"header line\n\x00\x01\x02 record separator\nthe needle is here\n")?;
+
+        // With default binary detection, searching stops at the NUL byte
+        let default_results = search_files("needle", &file_path, &SearchOptions::default())?;
+        assert!(
+            default_results.lines.is_empty(),
+            "Match after NUL byte should not be found with default binary detection"
+        );
+
+        // With treat_all_as_text, the whole file is searched
+        let options = SearchOptions {
+            treat_all_as_text: true,
+            ..SearchOptions::default()
+        };
+        let results = search_files("needle", &file_path, &options)?;
+
+        assert_eq!(
+            results.lines.len(),
+            1,
+            "Should find the match after the NUL byte"
+        );
+        assert_eq!(results.lines[0].line_number, 3);
+        assert_eq!(results.lines[0].line_content, "the needle is here");
+
+        Ok(())
+    }
+
+    // Test that lines from NUL-containing files searched as text are flagged
+    #[test]
+    fn test_from_binary_file_flag() -> Result<()> {
+        let temp_dir = tempdir()?;
+        let mut file = File::create(temp_dir.path().join("mixed.dat"))?;
+        file.write_all(b"header line\n\x00\x01 record\nthe needle is here\n")?;
+        let mut file = File::create(temp_dir.path().join("plain.txt"))?;
+        file.write_all(b"another needle\n")?;
+
+        let options = SearchOptions {
+            treat_all_as_text: true,
+            ..SearchOptions::default()
+        };
+        let mut results = search_files("needle", temp_dir.path(), &options)?;
+        results.sort_by_path_and_line();
+        let flags: Vec<(String, bool)> = results
+            .lines
+            .iter()
+            .map(|line| (line.line_content.clone(), line.from_binary_file))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                ("the needle is here".to_string(), true),
+                ("another needle".to_string(), false),
+            ]
+        );
+
+        // NUL bytes past the point where the search stopped are still detected
+        let mut file = File::create(temp_dir.path().join("late.dat"))?;
+        file.write_all(
+            b"needle first
+filler
+\x00 trailer
+",
+        )?;
+        let options = SearchOptions {
+            first_match_only: true,
+            ..options
+        };
+        let results = search_files("needle first", temp_dir.path(), &options)?;
+        assert_eq!(results.lines.len(), 1);
+        assert!(results.lines[0].from_binary_file);
+        std::fs::remove_file(temp_dir.path().join("late.dat"))?;
+
+        // Without forced text mode, the binary file isn't searched and nothing is flagged
+        let results = search_files("needle", temp_dir.path(), &SearchOptions::default())?;
+        assert_eq!(results.lines.len(), 1);
+        assert!(!results.lines[0].from_binary_file);
+
+        Ok(())
+    }
 }
@@ -1,7 +1,9 @@
 use anyhow::Result;
 use lumin::search::{SearchOptions, search_files};
 use serial_test::serial;
+use std::fs;
 use std::path::Path;
+use tempfile::TempDir;
 
 mod test_helpers;
 use test_helpers::{TEST_DIR, TestEnvironment, setup_multiple_file_types};
@@ -73,8 +75,10 @@ mod search_exclude_glob_tests {
         let pattern = "content";
 
         // Exclude both JSON and YAML files
-        let mut options = SearchOptions::default();
-        options.exclude_glob = Some(vec!["*.json".to_string(), "*.yaml".to_string()]);
+        let options = SearchOptions {
+            exclude_glob: Some(vec!["*.json".to_string(), "*.yaml".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -122,8 +126,10 @@ mod search_exclude_glob_tests {
         );
 
         // Exclude all files in the docs directory and subdirectories
-        let mut options = SearchOptions::default();
-        options.exclude_glob = Some(vec!["docs/**".to_string()]);
+        let options = SearchOptions {
+            exclude_glob: Some(vec!["docs/**".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -172,9 +178,11 @@ mod search_exclude_glob_tests {
         let pattern = "content";
 
         // Test with case-sensitive mode
-        let mut options = SearchOptions::default();
-        options.case_sensitive = true;
-        options.exclude_glob = Some(vec!["*.json".to_string()]);
+        let options = SearchOptions {
+            case_sensitive: true,
+            exclude_glob: Some(vec!["*.json".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -188,9 +196,11 @@ mod search_exclude_glob_tests {
         );
 
         // Case-insensitive mode test
-        let mut options = SearchOptions::default();
-        options.case_sensitive = false;
-        options.exclude_glob = Some(vec!["*.json".to_string()]);
+        let options = SearchOptions {
+            case_sensitive: false,
+            exclude_glob: Some(vec!["*.json".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -204,8 +214,10 @@ mod search_exclude_glob_tests {
         );
 
         // Test with explicit patterns for both uppercase and mixed case
-        let mut options = SearchOptions::default();
-        options.exclude_glob = Some(vec!["*.JSON".to_string(), "*.JsonML".to_string()]);
+        let options = SearchOptions {
+            exclude_glob: Some(vec!["*.JSON".to_string(), "*.JsonML".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -238,8 +250,10 @@ mod search_exclude_glob_tests {
         let pattern = "content";
 
         // Create options with an empty exclude_glob list
-        let mut options = SearchOptions::default();
-        options.exclude_glob = Some(vec![]);
+        let options = SearchOptions {
+            exclude_glob: Some(vec![]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -273,8 +287,10 @@ mod search_exclude_glob_tests {
         let pattern = "content";
 
         // Use exclude_glob with respect_gitignore=true (default)
-        let mut options = SearchOptions::default();
-        options.exclude_glob = Some(vec!["*.md".to_string()]);
+        let options = SearchOptions {
+            exclude_glob: Some(vec!["*.md".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -303,4 +319,55 @@ mod search_exclude_glob_tests {
 
         Ok(())
     }
+
+    /// Test excluding directories by name at any depth
+    #[test]
+    fn test_exclude_dir_names() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        // Create matching files both inside and outside the excluded directories
+        let files = [
+            "src/main.rs",
+            "target/debug/build.rs",
+            "crates/inner/target/release/out.rs",
+            "node_modules/pkg/index.js",
+            "web/node_modules/dep/lib.js",
+            "web/app.js",
+        ];
+        for file in &files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(&path, "needle\n")?;
+        }
+
+        let options = SearchOptions {
+            respect_gitignore: false,
+            exclude_dir_names: vec!["target".to_string(), "node_modules".to_string()],
+            ..SearchOptions::default()
+        };
+
+        let results = search_files("needle", root, &options)?;
+
+        let mut found: Vec<String> = results
+            .lines
+            .iter()
+            .map(|r| {
+                r.file_path
+                    .strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        found.sort();
+
+        assert_eq!(
+            found,
+            vec!["src/main.rs".to_string(), "web/app.js".to_string()],
+            "Only files outside target and node_modules directories should be searched"
+        );
+
+        Ok(())
+    }
 }
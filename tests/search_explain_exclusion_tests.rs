@@ -0,0 +1,254 @@
+use anyhow::Result;
+use lumin::search::{SearchOptions, explain_exclusion};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Tests for explaining why files are excluded from a search
+#[cfg(test)]
+mod search_explain_exclusion_tests {
+    use super::*;
+
+    /// Creates a git repository layout with files excluded for different reasons
+    fn setup_repository() -> Result<TempDir> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        fs::create_dir(root.join(".git"))?;
+        fs::create_dir_all(root.join("src/nested/deep"))?;
+        fs::create_dir_all(root.join("target/debug"))?;
+        fs::create_dir(root.join(".config"))?;
+
+        fs::write(root.join(".gitignore"), "*.log\n")?;
+        fs::write(root.join("src/main.rs"), "fn main() {}\n")?;
+        fs::write(root.join("src/nested/deep/file.rs"), "// deep\n")?;
+        fs::write(root.join("app.log"), "log line\n")?;
+        fs::write(root.join("target/debug/build.rs"), "// build\n")?;
+        fs::write(root.join(".config/settings.toml"), "key = 1\n")?;
+        fs::write(root.join("notes.md"), "notes\n")?;
+        fs::write(root.join("data.bin"), b"header\x00payload")?;
+
+        Ok(temp_dir)
+    }
+
+    #[test]
+    fn test_explain_not_excluded() -> Result<()> {
+        let temp_dir = setup_repository()?;
+        let options = SearchOptions::default();
+
+        let reason = explain_exclusion(Path::new("src/main.rs"), temp_dir.path(), &options)?;
+        assert_eq!(reason, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_gitignore() -> Result<()> {
+        let temp_dir = setup_repository()?;
+        let options = SearchOptions::default();
+
+        let reason = explain_exclusion(Path::new("app.log"), temp_dir.path(), &options)?;
+        let reason = reason.expect("app.log should be excluded");
+        assert!(reason.contains(".gitignore"), "reason: {}", reason);
+        assert!(reason.contains("*.log"), "reason: {}", reason);
+
+        // Not excluded when gitignore is not respected
+        let options = SearchOptions {
+            respect_gitignore: false,
+            ..SearchOptions::default()
+        };
+        assert_eq!(
+            explain_exclusion(Path::new("app.log"), temp_dir.path(), &options)?,
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_hidden() -> Result<()> {
+        let temp_dir = setup_repository()?;
+        let options = SearchOptions::default();
+
+        let reason = explain_exclusion(
+            Path::new(".config/settings.toml"),
+            temp_dir.path(),
+            &options,
+        )?;
+        assert!(
+            reason
+                .expect("hidden file should be excluded")
+                .contains("hidden")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_exclude_glob() -> Result<()> {
+        let temp_dir = setup_repository()?;
+        let options = SearchOptions {
+            exclude_glob: Some(vec!["*.md".to_string(), "**/target/**".to_string()]),
+            ..SearchOptions::default()
+        };
+
+        let reason = explain_exclusion(
+            Path::new("target/debug/build.rs"),
+            temp_dir.path(),
+            &options,
+        )?;
+        assert_eq!(
+            reason.as_deref(),
+            Some("matched exclude_glob `**/target/**`")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_exclude_dir_names() -> Result<()> {
+        let temp_dir = setup_repository()?;
+        let options = SearchOptions {
+            exclude_dir_names: vec!["target".to_string()],
+            ..SearchOptions::default()
+        };
+
+        let reason = explain_exclusion(
+            Path::new("target/debug/build.rs"),
+            temp_dir.path(),
+            &options,
+        )?;
+        assert_eq!(
+            reason.as_deref(),
+            Some("inside excluded directory `target`")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_include_glob() -> Result<()> {
+        let temp_dir = setup_repository()?;
+        let options = SearchOptions {
+            include_glob: Some(vec!["**/*.rs".to_string()]),
+            ..SearchOptions::default()
+        };
+
+        let reason = explain_exclusion(Path::new("notes.md"), temp_dir.path(), &options)?;
+        assert!(
+            reason
+                .expect("notes.md should be excluded")
+                .starts_with("not matched by include_glob")
+        );
+        assert_eq!(
+            explain_exclusion(Path::new("src/main.rs"), temp_dir.path(), &options)?,
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_binary() -> Result<()> {
+        let temp_dir = setup_repository()?;
+
+        let reason = explain_exclusion(
+            Path::new("data.bin"),
+            temp_dir.path(),
+            &SearchOptions::default(),
+        )?;
+        assert!(
+            reason
+                .expect("data.bin should be excluded")
+                .starts_with("binary")
+        );
+
+        let options = SearchOptions {
+            treat_all_as_text: true,
+            ..SearchOptions::default()
+        };
+        assert_eq!(
+            explain_exclusion(Path::new("data.bin"), temp_dir.path(), &options)?,
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_depth() -> Result<()> {
+        let temp_dir = setup_repository()?;
+        let options = SearchOptions {
+            depth: Some(2),
+            ..SearchOptions::default()
+        };
+
+        let reason = explain_exclusion(
+            Path::new("src/nested/deep/file.rs"),
+            temp_dir.path(),
+            &options,
+        )?;
+        assert!(
+            reason
+                .expect("deep file should be excluded")
+                .starts_with("exceeds the maximum depth 2")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_outside_directory() -> Result<()> {
+        let temp_dir = setup_repository()?;
+        let other_dir = TempDir::new()?;
+        let other_file = other_dir.path().join("other.txt");
+        fs::write(&other_file, "other")?;
+
+        let reason = explain_exclusion(&other_file, temp_dir.path(), &SearchOptions::default())?;
+        assert!(
+            reason
+                .expect("file should be outside")
+                .starts_with("outside of the search directory")
+        );
+
+        // Missing files are reported as errors
+        assert!(
+            explain_exclusion(
+                Path::new("missing.txt"),
+                temp_dir.path(),
+                &SearchOptions::default()
+            )
+            .is_err()
+        );
+
+        Ok(())
+    }
+
+    /// The explanations agree with the files actually searched
+    #[test]
+    fn test_explain_consistent_with_search() -> Result<()> {
+        let temp_dir = setup_repository()?;
+        let options = SearchOptions {
+            exclude_glob: Some(vec!["*.md".to_string()]),
+            ..SearchOptions::default()
+        };
+
+        let results = lumin::search::search_files(".", temp_dir.path(), &options)?;
+        for file in [
+            "src/main.rs",
+            "app.log",
+            "notes.md",
+            ".config/settings.toml",
+            "target/debug/build.rs",
+        ] {
+            let searched = results
+                .lines
+                .iter()
+                .any(|line| line.file_path.ends_with(file));
+            let excluded = explain_exclusion(Path::new(file), temp_dir.path(), &options)?;
+            assert_eq!(searched, excluded.is_none(), "{}: {:?}", file, excluded);
+        }
+
+        Ok(())
+    }
+}
@@ -80,8 +80,10 @@ mod search_include_glob_tests {
         let pattern = "content";
 
         // Include only JSON and YAML files
-        let mut options = SearchOptions::default();
-        options.include_glob = Some(vec!["*.json".to_string(), "*.yaml".to_string()]);
+        let options = SearchOptions {
+            include_glob: Some(vec!["*.json".to_string(), "*.yaml".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -129,9 +131,11 @@ mod search_include_glob_tests {
         );
 
         // Include only files in the docs directory and subdirectories
-        let mut options = SearchOptions::default();
-        // Use the path format that would match our test directory structure
-        options.include_glob = Some(vec!["**/docs/**".to_string()]);
+        let options = SearchOptions {
+            // Use the path format that would match our test directory structure
+            include_glob: Some(vec!["**/docs/**".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -180,9 +184,11 @@ mod search_include_glob_tests {
         let pattern = "content";
 
         // Test with case-sensitive mode
-        let mut options = SearchOptions::default();
-        options.case_sensitive = true;
-        options.include_glob = Some(vec!["*.json".to_string()]);
+        let options = SearchOptions {
+            case_sensitive: true,
+            include_glob: Some(vec!["*.json".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -196,9 +202,11 @@ mod search_include_glob_tests {
         );
 
         // Case-insensitive mode test
-        let mut options = SearchOptions::default();
-        options.case_sensitive = false;
-        options.include_glob = Some(vec!["*.json".to_string()]);
+        let options = SearchOptions {
+            case_sensitive: false,
+            include_glob: Some(vec!["*.json".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -239,8 +247,10 @@ mod search_include_glob_tests {
         );
 
         // Create options with an empty include_glob list
-        let mut options = SearchOptions::default();
-        options.include_glob = Some(vec![]);
+        let options = SearchOptions {
+            include_glob: Some(vec![]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -276,8 +286,10 @@ mod search_include_glob_tests {
         );
 
         // With include_glob = Some(vec![]), should find nothing
-        let mut empty_options = SearchOptions::default();
-        empty_options.include_glob = Some(vec![]);
+        let empty_options = SearchOptions {
+            include_glob: Some(vec![]),
+            ..SearchOptions::default()
+        };
         let empty_results = search_files(pattern, Path::new(TEST_DIR), &empty_options)?;
         assert!(
             empty_results.lines.is_empty(),
@@ -302,8 +314,10 @@ mod search_include_glob_tests {
         let pattern = "content";
 
         // Use include_glob with respect_gitignore=true (default)
-        let mut options = SearchOptions::default();
-        options.include_glob = Some(vec!["*.md".to_string(), "*.log".to_string()]);
+        let options = SearchOptions {
+            include_glob: Some(vec!["*.md".to_string(), "*.log".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -343,9 +357,11 @@ mod search_include_glob_tests {
         let pattern = "content";
 
         // Include all text files but exclude those in the docs directory
-        let mut options = SearchOptions::default();
-        options.include_glob = Some(vec!["**/*.txt".to_string()]);
-        options.exclude_glob = Some(vec!["docs/**".to_string()]);
+        let options = SearchOptions {
+            include_glob: Some(vec!["**/*.txt".to_string()]),
+            exclude_glob: Some(vec!["docs/**".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -412,8 +428,10 @@ mod search_include_glob_tests {
         let pattern = "content";
 
         // Test 1: Brace expansion - match multiple extensions
-        let mut options = SearchOptions::default();
-        options.include_glob = Some(vec!["**/*.{rs,py}".to_string()]);
+        let options = SearchOptions {
+            include_glob: Some(vec!["**/*.{rs,py}".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -430,8 +448,10 @@ mod search_include_glob_tests {
         );
 
         // Test 2: Character class - match test[digit].rs
-        let mut options = SearchOptions::default();
-        options.include_glob = Some(vec!["**/test[0-9].rs".to_string()]);
+        let options = SearchOptions {
+            include_glob: Some(vec!["**/test[0-9].rs".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -448,8 +468,10 @@ mod search_include_glob_tests {
         );
 
         // Test 3: Double asterisk - find files in any directory depth
-        let mut options = SearchOptions::default();
-        options.include_glob = Some(vec!["**/file.txt".to_string()]);
+        let options = SearchOptions {
+            include_glob: Some(vec!["**/file.txt".to_string()]),
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -481,4 +503,57 @@ mod search_include_glob_tests {
 
         Ok(())
     }
+
+    /// Test negated patterns subtracting from the included files
+    #[test]
+    fn test_include_glob_negation() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src/generated"))?;
+        std::fs::write(root.join("src/main.rs"), "// marker\n")?;
+        std::fs::write(root.join("src/generated/bindings.rs"), "// marker\n")?;
+        std::fs::write(root.join("notes.md"), "marker\n")?;
+
+        let searched_files = |include_glob: Vec<&str>| -> Result<Vec<String>> {
+            let options = SearchOptions {
+                include_glob: Some(include_glob.into_iter().map(String::from).collect()),
+                omit_path_prefix: Some(root.to_path_buf()),
+                ..SearchOptions::default()
+            };
+            let results = search_files("marker", root, &options)?;
+            Ok(results
+                .lines
+                .iter()
+                .map(|r| r.file_path.to_string_lossy().replace('\\', "/"))
+                .collect())
+        };
+
+        // The negation applies regardless of its position in the list
+        assert_eq!(
+            searched_files(vec!["**/*.rs", "!**/generated/**"])?,
+            vec!["src/main.rs"]
+        );
+        assert_eq!(
+            searched_files(vec!["!**/generated/**", "**/*.rs"])?,
+            vec!["src/main.rs"]
+        );
+
+        // A list of only negations includes everything else
+        assert_eq!(
+            searched_files(vec!["!**/generated/**"])?,
+            vec!["notes.md", "src/main.rs"]
+        );
+
+        // Negations work the same way in exclude_glob
+        let options = SearchOptions {
+            exclude_glob: Some(vec!["**/*.rs".to_string(), "!src/main.rs".to_string()]),
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..SearchOptions::default()
+        };
+        let results = search_files("marker", root, &options)?;
+        let files: Vec<_> = results.lines.iter().map(|r| r.file_path.clone()).collect();
+        assert_eq!(files, vec![Path::new("notes.md"), Path::new("src/main.rs")]);
+
+        Ok(())
+    }
 }
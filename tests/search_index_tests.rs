@@ -0,0 +1,58 @@
+use anyhow::Result;
+use lumin::search::{FileIndex, SearchOptions, search_files};
+use std::fs;
+use tempfile::TempDir;
+
+/// Tests for searching through a prebuilt file index
+#[cfg(test)]
+mod search_index_tests {
+    use super::*;
+
+    /// Test that searches reuse the indexed files without walking the directory again
+    #[test]
+    fn test_file_index_searches_without_rewalking() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::create_dir(root.join("src"))?;
+        fs::write(root.join("src/one.txt"), "needle\nhay\n")?;
+        fs::write(root.join("src/two.txt"), "hay\nneedle\nneedle\n")?;
+        fs::write(root.join("notes.md"), "needle\n")?;
+
+        let options = SearchOptions {
+            include_glob: Some(vec!["**/*.txt".to_string()]),
+            ..SearchOptions::default()
+        };
+        let mut index = FileIndex::build(root, &options)?;
+        assert_eq!(index.walk_count(), 1);
+        assert_eq!(index.files().len(), 2);
+
+        // Searches give the same results as search_files, without another walk
+        let expected = search_files("needle", root, &options)?;
+        let first = index.search("needle", &options)?;
+        let second = index.search("hay", &options)?;
+        assert_eq!(first.total_number, expected.total_number);
+        for (line, expected) in first.lines.iter().zip(&expected.lines) {
+            assert_eq!(line.file_path, expected.file_path);
+            assert_eq!(line.line_number, expected.line_number);
+        }
+        assert_eq!(second.total_number, 2);
+        assert_eq!(index.walk_count(), 1);
+
+        // Modified files are searched with their new content, and removed files are dropped
+        fs::write(root.join("src/one.txt"), "needle\nneedle\nneedle\nneedle\n")?;
+        fs::remove_file(root.join("src/two.txt"))?;
+        fs::write(root.join("src/three.txt"), "needle\n")?;
+        let result = index.search("needle", &options)?;
+        assert_eq!(result.total_number, 4);
+        assert_eq!(index.files().len(), 1);
+        assert_eq!(index.files()[0].size_bytes, 28);
+        assert_eq!(index.walk_count(), 1);
+
+        // New files are only found after refreshing the index
+        index.refresh()?;
+        assert_eq!(index.walk_count(), 2);
+        assert_eq!(index.search("needle", &options)?.total_number, 5);
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::Result;
-use lumin::search::{SearchOptions, search_files};
-use std::fs::File;
+use lumin::search::{
+    ContentMode, ReadStrategy, SearchOptions, SearchSort, TRUNCATED_CONTENT_MAX_BYTES, search_files,
+};
+use std::fs::{self, File};
 use std::io::Write;
 
 use tempfile::tempdir;
@@ -26,17 +28,54 @@ fn test_content_omission() -> Result<()> {
         omit_path_prefix: None,
         match_content_omit_num: None,
         depth: Some(20),
+        min_depth: None,
         before_context: 0,
         after_context: 0,
         skip: None,
         take: None,
+        treat_all_as_text: false,
+        exclude_dir_names: Vec::new(),
+        min_matches_per_line: None,
+        enclosing_symbol: false,
+        sort_by_relevance: false,
+        ignore_root_gitignore: false,
+        highlight_markers: None,
+        read_strategy: ReadStrategy::Buffered,
+        line_from: None,
+        line_to: None,
+        line_terminator: b'\n',
+        auto_repo_relative: false,
+        regex_size_limit: None,
+        dfa_size_limit: None,
+        timeout: None,
+        cancel: None,
+        content: ContentMode::Full,
+        natural_sort: false,
+        with_blame: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        snippet_width: None,
+        git_tracked_only: false,
+        search_compressed: false,
+        utf16_offsets: false,
+        tilde_home: false,
+        sort: SearchSort::PathAndLine,
+        clean_paths: false,
+        header_lines: None,
+        context_merge_gap: 0,
+        unique_content: false,
+        extract_pdf_text: false,
+        dedent_context: false,
+        collect_extensions: false,
+        first_match_only: false,
+        threads: None,
     };
 
     let results = search_files("pattern", temp_dir.path(), &options)?;
 
     // Verify results without omission
     assert_eq!(results.lines.len(), 1);
-    assert_eq!(results.lines[0].content_omitted, false);
+    assert!(!results.lines[0].content_omitted);
     assert_eq!(results.lines[0].line_content.trim(), content);
 
     // Test with content omission enabled (5 characters before and after match)
@@ -48,19 +87,56 @@ fn test_content_omission() -> Result<()> {
         omit_path_prefix: None,
         match_content_omit_num: Some(5),
         depth: Some(20),
+        min_depth: None,
         before_context: 0,
         after_context: 0,
         skip: None,
         take: None,
+        treat_all_as_text: false,
+        exclude_dir_names: Vec::new(),
+        min_matches_per_line: None,
+        enclosing_symbol: false,
+        sort_by_relevance: false,
+        ignore_root_gitignore: false,
+        highlight_markers: None,
+        read_strategy: ReadStrategy::Buffered,
+        line_from: None,
+        line_to: None,
+        line_terminator: b'\n',
+        auto_repo_relative: false,
+        regex_size_limit: None,
+        dfa_size_limit: None,
+        timeout: None,
+        cancel: None,
+        content: ContentMode::Full,
+        natural_sort: false,
+        with_blame: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        snippet_width: None,
+        git_tracked_only: false,
+        search_compressed: false,
+        utf16_offsets: false,
+        tilde_home: false,
+        sort: SearchSort::PathAndLine,
+        clean_paths: false,
+        header_lines: None,
+        context_merge_gap: 0,
+        unique_content: false,
+        extract_pdf_text: false,
+        dedent_context: false,
+        collect_extensions: false,
+        first_match_only: false,
+        threads: None,
     };
 
     let omitted_results = search_files("pattern", temp_dir.path(), &omit_options)?;
 
     // Verify results with omission
     assert_eq!(omitted_results.lines.len(), 1);
-    assert_eq!(omitted_results.lines[0].content_omitted, true);
+    assert!(omitted_results.lines[0].content_omitted);
 
-    // The result should contain "<omit>vwxyz_PATTERN_01234<omit>"
+    // The result should contain "<omit>wxyz_PATTERN_0123<omit>"
     let omitted_content = omitted_results.lines[0].line_content.trim();
     println!("Original content: {}", content);
     println!("Omitted content: {}", omitted_content);
@@ -76,14 +152,14 @@ fn test_content_omission() -> Result<()> {
         "Omitted content should contain the matched pattern"
     );
     assert!(
-        omitted_content.contains("vwxyz_PATTERN_"),
+        omitted_content.contains("wxyz_PATTERN_"),
         "Omitted content should contain context before pattern"
     );
     assert!(
-        omitted_content.ends_with("<omit>"),
-        "Omitted content should end with <omit> marker"
+        omitted_content.starts_with("<omit>") && omitted_content.ends_with("<omit>"),
+        "Omitted content should start and end with <omit> markers"
     );
-    assert_eq!(omitted_content, "vwxyz_PATTERN_0123<omit>");
+    assert_eq!(omitted_content, "<omit>wxyz_PATTERN_0123<omit>");
 
     // Test with content omission (20 characters)
     let omit_options2 = SearchOptions {
@@ -94,17 +170,54 @@ fn test_content_omission() -> Result<()> {
         omit_path_prefix: None,
         match_content_omit_num: Some(20),
         depth: Some(20),
+        min_depth: None,
         before_context: 0,
         after_context: 0,
         skip: None,
         take: None,
+        treat_all_as_text: false,
+        exclude_dir_names: Vec::new(),
+        min_matches_per_line: None,
+        enclosing_symbol: false,
+        sort_by_relevance: false,
+        ignore_root_gitignore: false,
+        highlight_markers: None,
+        read_strategy: ReadStrategy::Buffered,
+        line_from: None,
+        line_to: None,
+        line_terminator: b'\n',
+        auto_repo_relative: false,
+        regex_size_limit: None,
+        dfa_size_limit: None,
+        timeout: None,
+        cancel: None,
+        content: ContentMode::Full,
+        natural_sort: false,
+        with_blame: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        snippet_width: None,
+        git_tracked_only: false,
+        search_compressed: false,
+        utf16_offsets: false,
+        tilde_home: false,
+        sort: SearchSort::PathAndLine,
+        clean_paths: false,
+        header_lines: None,
+        context_merge_gap: 0,
+        unique_content: false,
+        extract_pdf_text: false,
+        dedent_context: false,
+        collect_extensions: false,
+        first_match_only: false,
+        threads: None,
     };
 
     let omitted_results2 = search_files("pattern", temp_dir.path(), &omit_options2)?;
 
     // Verify result has more context but still omits some content
     assert_eq!(omitted_results2.lines.len(), 1);
-    assert_eq!(omitted_results2.lines[0].content_omitted, true);
+    assert!(omitted_results2.lines[0].content_omitted);
 
     // The result should contain more context around the pattern
     let omitted_content2 = omitted_results2.lines[0].line_content.trim();
@@ -143,10 +256,47 @@ fn test_content_omission() -> Result<()> {
         omit_path_prefix: None,
         match_content_omit_num: Some(3), // Only 3 chars, much smaller than "VERYLONGPATTERNSTRING"
         depth: Some(20),
+        min_depth: None,
         before_context: 0,
         after_context: 0,
         skip: None,
         take: None,
+        treat_all_as_text: false,
+        exclude_dir_names: Vec::new(),
+        min_matches_per_line: None,
+        enclosing_symbol: false,
+        sort_by_relevance: false,
+        ignore_root_gitignore: false,
+        highlight_markers: None,
+        read_strategy: ReadStrategy::Buffered,
+        line_from: None,
+        line_to: None,
+        line_terminator: b'\n',
+        auto_repo_relative: false,
+        regex_size_limit: None,
+        dfa_size_limit: None,
+        timeout: None,
+        cancel: None,
+        content: ContentMode::Full,
+        natural_sort: false,
+        with_blame: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        snippet_width: None,
+        git_tracked_only: false,
+        search_compressed: false,
+        utf16_offsets: false,
+        tilde_home: false,
+        sort: SearchSort::PathAndLine,
+        clean_paths: false,
+        header_lines: None,
+        context_merge_gap: 0,
+        unique_content: false,
+        extract_pdf_text: false,
+        dedent_context: false,
+        collect_extensions: false,
+        first_match_only: false,
+        threads: None,
     };
 
     let long_match_results = search_files(
@@ -169,7 +319,7 @@ fn test_content_omission() -> Result<()> {
     );
 
     // Verify that the content was truncated
-    assert_eq!(long_match_result.content_omitted, true);
+    assert!(long_match_result.content_omitted);
 
     // Our implementation keeps the entire match string intact, even if it's longer than omit_num
     // This actually makes sense for usability, as truncating the match itself would make it hard to identify
@@ -227,3 +377,357 @@ fn test_content_omission() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_highlight_multibyte_characters() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let file_path = temp_dir.path().join("multibyte.txt");
+
+    // Emoji and CJK characters on both sides of the match
+    let content = "日本語テキスト🦀🦀 PATTERN 🎉中文字符串";
+    let mut file = File::create(&file_path)?;
+    writeln!(file, "{}", content)?;
+
+    let options = SearchOptions {
+        highlight_markers: Some(("[".to_string(), "]".to_string())),
+        ..SearchOptions::default()
+    };
+    let results = search_files("pattern", temp_dir.path(), &options)?;
+    assert_eq!(results.lines.len(), 1);
+
+    // Markers wrap exactly the match
+    let line = &results.lines[0];
+    assert_eq!(
+        line.line_content,
+        "日本語テキスト🦀🦀 [PATTERN] 🎉中文字符串"
+    );
+
+    // Match ranges are byte offsets on character boundaries of the original line
+    assert_eq!(line.match_ranges.len(), 1);
+    let (start, end) = line.match_ranges[0];
+    assert_eq!(&content[start..end], "PATTERN");
+
+    // Combined with omission, markers are placed on the kept content
+    let omit_options = SearchOptions {
+        match_content_omit_num: Some(2),
+        highlight_markers: Some(("[".to_string(), "]".to_string())),
+        ..SearchOptions::default()
+    };
+    let results = search_files("pattern", temp_dir.path(), &omit_options)?;
+    let line = &results.lines[0];
+    assert!(line.content_omitted);
+    assert_eq!(line.line_content, "<omit>🦀 [PATTERN] 🎉<omit>");
+    assert!(std::str::from_utf8(line.line_content.as_bytes()).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_highlight_match_inside_multibyte_text() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let file_path = temp_dir.path().join("cjk.txt");
+
+    // Matches made of multibyte characters, adjacent to other multibyte characters
+    let mut file = File::create(&file_path)?;
+    writeln!(file, "東京と京都と東京🗼")?;
+
+    let options = SearchOptions {
+        highlight_markers: Some(("<<".to_string(), ">>".to_string())),
+        ..SearchOptions::default()
+    };
+    let results = search_files("東京", temp_dir.path(), &options)?;
+
+    assert_eq!(results.lines.len(), 1);
+    assert_eq!(results.lines[0].line_content, "<<東京>>と京都と<<東京>>🗼");
+    assert_eq!(results.lines[0].match_ranges, vec![(0, 6), (18, 24)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_display_match_ranges() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let file_path = temp_dir.path().join("display.txt");
+
+    let mut file = File::create(&file_path)?;
+    writeln!(
+        file,
+        "a long prefix before the PATTERN and a long suffix between PATTERN and the end"
+    )?;
+
+    let check_display_ranges = |options: &SearchOptions| -> Result<()> {
+        let results = search_files("PATTERN", temp_dir.path(), options)?;
+        assert_eq!(results.lines.len(), 1);
+        let line = &results.lines[0];
+        assert_eq!(line.display_match_ranges.len(), 2);
+        for &(start, end) in &line.display_match_ranges {
+            assert_eq!(&line.line_content[start..end], "PATTERN");
+        }
+        Ok(())
+    };
+
+    // Without omission or highlighting, display ranges equal the match ranges
+    let results = search_files("PATTERN", temp_dir.path(), &SearchOptions::default())?;
+    assert_eq!(
+        results.lines[0].display_match_ranges,
+        results.lines[0].match_ranges
+    );
+
+    check_display_ranges(&SearchOptions {
+        match_content_omit_num: Some(4),
+        ..SearchOptions::default()
+    })?;
+    check_display_ranges(&SearchOptions {
+        highlight_markers: Some(("<<".to_string(), ">>".to_string())),
+        ..SearchOptions::default()
+    })?;
+    check_display_ranges(&SearchOptions {
+        match_content_omit_num: Some(4),
+        highlight_markers: Some(("[".to_string(), "]".to_string())),
+        ..SearchOptions::default()
+    })?;
+
+    Ok(())
+}
+
+#[test]
+fn test_content_mode_none() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let file_path = temp_dir.path().join("lines.txt");
+
+    let mut file = File::create(&file_path)?;
+    writeln!(file, "first line")?;
+    writeln!(file, "the PATTERN is here")?;
+    writeln!(file, "middle line")?;
+    writeln!(file, "PATTERN again, PATTERN")?;
+
+    let options = SearchOptions {
+        content: ContentMode::None,
+        before_context: 1,
+        highlight_markers: Some(("[".to_string(), "]".to_string())),
+        ..SearchOptions::default()
+    };
+    let results = search_files("PATTERN", temp_dir.path(), &options)?;
+
+    // Line numbers and match ranges are reported, but no content
+    let line_numbers: Vec<u64> = results.lines.iter().map(|l| l.line_number).collect();
+    assert_eq!(line_numbers, vec![1, 2, 3, 4]);
+    assert!(results.lines.iter().all(|l| l.line_content.is_empty()));
+    assert!(results.lines.iter().all(|l| !l.content_omitted));
+
+    let matches: Vec<_> = results.lines.iter().filter(|l| !l.is_context).collect();
+    assert_eq!(matches.len(), 2);
+    assert_eq!(matches[0].match_ranges, vec![(4, 11)]);
+    assert_eq!(matches[1].match_ranges, vec![(0, 7), (15, 22)]);
+    assert!(matches[1].display_match_ranges.is_empty());
+
+    // The match IDs are the same as with full content
+    let full = search_files("PATTERN", temp_dir.path(), &SearchOptions::default())?;
+    let full_ids: Vec<&str> = full.lines.iter().map(|l| l.match_id.as_str()).collect();
+    let ids: Vec<&str> = matches.iter().map(|l| l.match_id.as_str()).collect();
+    assert_eq!(ids, full_ids);
+
+    Ok(())
+}
+
+#[test]
+fn test_content_mode_truncated() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let file_path = temp_dir.path().join("long.txt");
+
+    // A short line, and a long line whose second highlighted match straddles the cut
+    let padding = "x".repeat(TRUNCATED_CONTENT_MAX_BYTES - 13);
+    let mut file = File::create(&file_path)?;
+    writeln!(file, "short PATTERN")?;
+    writeln!(file, "PATTERN {}PATTERN {}", padding, padding)?;
+
+    let options = SearchOptions {
+        content: ContentMode::Truncated,
+        highlight_markers: Some(("[".to_string(), "]".to_string())),
+        ..SearchOptions::default()
+    };
+    let results = search_files("PATTERN", temp_dir.path(), &options)?;
+    assert_eq!(results.lines.len(), 2);
+
+    let short = &results.lines[0];
+    assert_eq!(short.line_content, "short [PATTERN]");
+    assert!(!short.content_omitted);
+
+    // The cut is moved before the match it would split
+    let long = &results.lines[1];
+    assert!(long.content_omitted);
+    assert_eq!(long.line_content, format!("[PATTERN] {}<omit>", padding));
+    assert_eq!(long.display_match_ranges, vec![(1, 8)]);
+    assert_eq!(long.match_ranges.len(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_snippet_width() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let file_path = temp_dir.path().join("snippet.txt");
+    let mut file = File::create(&file_path)?;
+    writeln!(file, "the quick brown fox jumps over")?;
+    writeln!(file, "short fox")?;
+
+    let snippets = |pattern: &str, width: usize| -> Result<Vec<Option<String>>> {
+        let options = SearchOptions {
+            snippet_width: Some(width),
+            ..SearchOptions::default()
+        };
+        let results = search_files(pattern, temp_dir.path(), &options)?;
+        Ok(results.lines.into_iter().map(|line| line.snippet).collect())
+    };
+
+    // Centered on the first match, with ellipses where the line continues
+    assert_eq!(
+        snippets("fox", 11)?,
+        vec![
+            Some("…own fox jum…".to_string()),
+            Some("short fox".to_string())
+        ]
+    );
+
+    // Space unused before or after the match is given to the other side
+    assert_eq!(snippets("the", 9)?, vec![Some("the quick…".to_string())]);
+    assert_eq!(snippets("over", 10)?, vec![Some("…jumps over".to_string())]);
+
+    // Matches longer than the width are cut at their end
+    assert_eq!(
+        snippets("quick brown", 5)?,
+        vec![Some("…quick…".to_string())]
+    );
+
+    // The snippet keeps the same characters as omission with half the remaining width
+    let options = SearchOptions {
+        snippet_width: Some(11),
+        match_content_omit_num: Some(4),
+        ..SearchOptions::default()
+    };
+    let results = search_files("fox", temp_dir.path(), &options)?;
+    assert_eq!(results.lines[0].line_content, "<omit>own fox jum<omit>");
+    assert_eq!(
+        results.lines[0]
+            .snippet
+            .as_deref()
+            .map(|s| s.replace('…', "<omit>")),
+        Some(results.lines[0].line_content.clone())
+    );
+
+    // No snippets by default; line content is unaffected
+    let results = search_files("fox", temp_dir.path(), &SearchOptions::default())?;
+    assert!(results.lines.iter().all(|line| line.snippet.is_none()));
+    let results = search_files(
+        "fox",
+        temp_dir.path(),
+        &SearchOptions {
+            snippet_width: Some(3),
+            ..SearchOptions::default()
+        },
+    )?;
+    assert_eq!(
+        results.lines[0].line_content,
+        "the quick brown fox jumps over"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_utf16_offsets() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let file_path = temp_dir.path().join("utf16.txt");
+    let mut file = File::create(&file_path)?;
+    // "ï" is 2 bytes and 1 UTF-16 unit, "🦊" is 4 bytes and 2 UTF-16 units
+    writeln!(file, "naïve 🦊 fox, fox")?;
+
+    let options = SearchOptions {
+        utf16_offsets: true,
+        ..SearchOptions::default()
+    };
+    let results = search_files("fox", temp_dir.path(), &options)?;
+    let line = &results.lines[0];
+    assert_eq!(line.match_ranges, vec![(12, 15), (17, 20)]);
+    assert_eq!(line.match_ranges_utf16, vec![(9, 12), (14, 17)]);
+
+    // The UTF-16 offsets select the matches in the UTF-16 encoded line
+    let utf16: Vec<u16> = line.line_content.encode_utf16().collect();
+    for &(start, end) in &line.match_ranges_utf16 {
+        assert_eq!(String::from_utf16(&utf16[start..end])?, "fox");
+    }
+
+    // Not computed by default
+    let results = search_files("fox", temp_dir.path(), &SearchOptions::default())?;
+    assert!(results.lines[0].match_ranges_utf16.is_empty());
+
+    // Byte-level matches inside a multibyte character are merged into one range
+    let temp_dir = tempdir()?;
+    fs::write(temp_dir.path().join("bytes.txt"), "naïve fox\n")?;
+    let results = search_files("(?-u:.)", temp_dir.path(), &options)?;
+    let line = &results.lines[0];
+    assert_eq!(line.match_ranges.len(), 9);
+    assert_eq!(line.match_ranges[2], (2, 4));
+    assert_eq!(line.match_ranges_utf16[2], (2, 3));
+    assert_eq!(line.match_ranges_utf16.last(), Some(&(8, 9)));
+
+    Ok(())
+}
+
+/// Omission markers for matches at the start, in the middle and at the end of a line
+#[test]
+fn test_omission_markers_by_match_position() -> Result<()> {
+    let cases = [
+        // Match at the start of the line: nothing is omitted before it
+        ("PATTERN0123456789", 3, "PATTERN012<omit>"),
+        ("PATTERN0123456789", 0, "PATTERN<omit>"),
+        // Match at the end of the line: nothing is omitted after it
+        ("0123456789PATTERN", 3, "<omit>789PATTERN"),
+        ("0123456789PATTERN", 0, "<omit>PATTERN"),
+        // Match in the middle: content is omitted on both sides
+        (
+            "0123456789PATTERN0123456789",
+            3,
+            "<omit>789PATTERN012<omit>",
+        ),
+        ("0123456789PATTERN0123456789", 0, "<omit>PATTERN<omit>"),
+        (
+            "0123456789PATTERN0123456789",
+            20,
+            "0123456789PATTERN0123456789",
+        ),
+        // Several matches: every gap is marked
+        (
+            "aaaaaaaaaaPATTERNbbbbbbbbbbPATTERNcccccccccc",
+            2,
+            "<omit>aaPATTERNbb<omit>bbPATTERNcc<omit>",
+        ),
+    ];
+
+    for (content, omit_num, expected) in cases {
+        let temp_dir = tempdir()?;
+        let mut file = File::create(temp_dir.path().join("line.txt"))?;
+        writeln!(file, "{}", content)?;
+
+        let options = SearchOptions {
+            match_content_omit_num: Some(omit_num),
+            case_sensitive: true,
+            ..SearchOptions::default()
+        };
+        let results = search_files("PATTERN", temp_dir.path(), &options)?;
+        let line = &results.lines[0];
+        assert_eq!(line.line_content, expected, "{} with {}", content, omit_num);
+        assert_eq!(line.content_omitted, expected != content);
+
+        // The displayed match ranges point at the matches within the omitted content
+        for &(start, end) in &line.display_match_ranges {
+            assert_eq!(&line.line_content[start..end], "PATTERN");
+        }
+        assert_eq!(
+            line.display_match_ranges.len(),
+            content.matches("PATTERN").count()
+        );
+    }
+
+    Ok(())
+}
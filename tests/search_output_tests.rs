@@ -0,0 +1,106 @@
+use anyhow::Result;
+use lumin::search::{OutputFormat, SearchOptions, SearchResultLine, search_files, write_results};
+use std::fs;
+use tempfile::TempDir;
+
+/// Parses CSV records, handling quoted fields with doubled quotes and line breaks
+fn parse_csv(input: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', _) => in_quotes = !in_quotes,
+            (',', false) => record.push(std::mem::take(&mut field)),
+            ('\n', false) => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    records
+}
+
+/// Tests for writing search results to files
+#[cfg(test)]
+mod search_output_tests {
+    use super::*;
+
+    /// Test that CSV output can be parsed back into the original lines
+    #[test]
+    fn test_write_results_csv_round_trip() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::write(
+            root.join("data.txt"),
+            "before\nneedle, with \"quotes\"\nneedle\rcarriage\nafter\n",
+        )?;
+
+        let options = SearchOptions {
+            after_context: 1,
+            ..SearchOptions::default()
+        };
+        let result = search_files("needle", root, &options)?;
+        let output = root.join("results.csv");
+        write_results(&result, &output, OutputFormat::Csv)?;
+
+        let records = parse_csv(&fs::read_to_string(&output)?);
+        assert_eq!(records[0], ["path", "line", "is_context", "content"]);
+        assert_eq!(records.len(), result.lines.len() + 1);
+        for (record, line) in records[1..].iter().zip(&result.lines) {
+            assert_eq!(record[0], line.file_path.to_string_lossy());
+            assert_eq!(record[1], line.line_number.to_string());
+            assert_eq!(record[2], line.is_context.to_string());
+            assert_eq!(record[3], line.line_content);
+        }
+        assert_eq!(records[1][3], "needle, with \"quotes\"");
+        assert_eq!(records[2][3], "needle\rcarriage");
+        assert_eq!(records[3][2], "true");
+
+        Ok(())
+    }
+
+    /// Test the text, JSON and NDJSON formats
+    #[test]
+    fn test_write_results_other_formats() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::write(root.join("a.txt"), "needle\nhay\nhay\nneedle\n")?;
+
+        let options = SearchOptions {
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..SearchOptions::default()
+        };
+        let result = search_files("needle", root, &options)?;
+
+        let output = root.join("results.txt");
+        write_results(&result, &output, OutputFormat::Text)?;
+        assert_eq!(
+            fs::read_to_string(&output)?,
+            "a.txt:1: needle\n--\na.txt:4: needle\n"
+        );
+
+        write_results(&result, &output, OutputFormat::Json)?;
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&output)?)?;
+        assert_eq!(json["total_number"], 2);
+        assert_eq!(json["lines"].as_array().map(Vec::len), Some(2));
+
+        write_results(&result, &output, OutputFormat::Ndjson)?;
+        let lines = fs::read_to_string(&output)?
+            .lines()
+            .map(serde_json::from_str::<SearchResultLine>)
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].line_number, 4);
+
+        Ok(())
+    }
+}
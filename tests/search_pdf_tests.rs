@@ -0,0 +1,124 @@
+#![cfg(feature = "pdf")]
+
+use anyhow::Result;
+use lumin::search::{PDF_PAGE_LINE_BASE, SearchOptions, explain_exclusion, search_files};
+use std::fs;
+use tempfile::TempDir;
+
+/// Builds a PDF document with one line of Helvetica text per page
+fn build_pdf(pages: &[&str]) -> Vec<u8> {
+    build_pdf_with_header(b"%PDF-1.4\n", pages)
+}
+
+/// Builds a PDF document as `build_pdf`, starting with the given header lines
+fn build_pdf_with_header(header: &[u8], pages: &[&str]) -> Vec<u8> {
+    let font_id = 3 + 2 * pages.len();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            (0..pages.len())
+                .map(|i| format!("{} 0 R", 3 + 2 * i))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        ),
+    ];
+    for (i, text) in pages.iter().enumerate() {
+        let stream = format!("BT /F1 12 Tf 72 720 Td ({}) Tj ET", text);
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+             /Resources << /Font << /F1 {} 0 R >> >> /Contents {} 0 R >>",
+            font_id,
+            4 + 2 * i
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            stream.len(),
+            stream
+        ));
+    }
+    objects.push(
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    );
+
+    let mut pdf = header.to_vec();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).into_bytes());
+    }
+    let xref_offset = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+    for offset in offsets {
+        pdf.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+    }
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        )
+        .into_bytes(),
+    );
+    pdf
+}
+
+/// Tests for searching the text of PDF files
+#[cfg(test)]
+mod search_pdf_tests {
+    use super::*;
+
+    /// Test that a phrase in a PDF is found with a page-based line number
+    #[test]
+    fn test_search_pdf_text() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(
+            temp_dir.path().join("manual.pdf"),
+            build_pdf(&["Introduction", "The quick brown fox"]),
+        )?;
+
+        let options = SearchOptions {
+            extract_pdf_text: true,
+            ..SearchOptions::default()
+        };
+        let results = search_files("brown fox", temp_dir.path(), &options)?;
+        assert_eq!(results.lines.len(), 1);
+        let line = &results.lines[0];
+        assert!(line.line_content.contains("The quick brown fox"));
+        assert_eq!(line.line_number / PDF_PAGE_LINE_BASE, 2);
+        assert!(!line.from_binary_file);
+
+        // By default, the raw file is searched, which finds the uncompressed content stream
+        let results = search_files("brown fox", temp_dir.path(), &SearchOptions::default())?;
+        assert_eq!(results.lines.len(), 1);
+        assert!(results.lines[0].line_content.contains("Tj"));
+        assert!(results.lines[0].line_number < PDF_PAGE_LINE_BASE);
+
+        Ok(())
+    }
+
+    /// Test that PDF files whose text is extracted are not explained as binary
+    #[test]
+    fn test_explain_exclusion_pdf() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        // A comment with a NUL byte after the header makes the raw file binary
+        let pdf = build_pdf_with_header(b"%PDF-1.4\n%\x00\n", &["The quick brown fox"]);
+        let path = temp_dir.path().join("manual.pdf");
+        fs::write(&path, pdf)?;
+
+        let reason = explain_exclusion(&path, temp_dir.path(), &SearchOptions::default())?;
+        assert!(reason.is_some_and(|reason| reason.starts_with("binary")));
+
+        let options = SearchOptions {
+            extract_pdf_text: true,
+            ..SearchOptions::default()
+        };
+        assert_eq!(explain_exclusion(&path, temp_dir.path(), &options)?, None);
+        let results = search_files("brown fox", temp_dir.path(), &options)?;
+        assert_eq!(results.lines.len(), 1);
+
+        Ok(())
+    }
+}
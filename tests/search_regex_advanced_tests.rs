@@ -7,7 +7,6 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 
 /// Advanced regex pattern tests that cover the full range of documented patterns
-
 mod search_regex_advanced_tests {
     use super::*;
 
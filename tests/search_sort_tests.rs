@@ -3,11 +3,10 @@ use lumin::search::{SearchOptions, SearchResult, SearchResultLine, search_files}
 use serial_test::serial;
 use std::fs::File;
 use std::io::Write;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use tempfile::tempdir;
 
 mod test_helpers;
-use test_helpers::{TEST_DIR, TestEnvironment};
 
 #[cfg(test)]
 mod search_sort_tests {
@@ -112,6 +111,17 @@ mod search_sort_tests {
                     line_content: "test".to_string(),
                     content_omitted: false,
                     is_context: false,
+                    enclosing_symbol: None,
+                    match_ranges: Vec::new(),
+                    match_ranges_utf16: Vec::new(),
+                    display_match_ranges: Vec::new(),
+                    match_id: String::new(),
+                    blame_author: None,
+                    blame_commit: None,
+                    snippet: None,
+                    from_binary_file: false,
+                    duplicate_count: None,
+                    dedent: 0,
                 },
                 SearchResultLine {
                     file_path: temp_dir.path().join("a_file.txt"),
@@ -119,6 +129,17 @@ mod search_sort_tests {
                     line_content: "test".to_string(),
                     content_omitted: false,
                     is_context: false,
+                    enclosing_symbol: None,
+                    match_ranges: Vec::new(),
+                    match_ranges_utf16: Vec::new(),
+                    display_match_ranges: Vec::new(),
+                    match_id: String::new(),
+                    blame_author: None,
+                    blame_commit: None,
+                    snippet: None,
+                    from_binary_file: false,
+                    duplicate_count: None,
+                    dedent: 0,
                 },
                 SearchResultLine {
                     file_path: temp_dir.path().join("a_file.txt"),
@@ -126,6 +147,17 @@ mod search_sort_tests {
                     line_content: "test".to_string(),
                     content_omitted: false,
                     is_context: false,
+                    enclosing_symbol: None,
+                    match_ranges: Vec::new(),
+                    match_ranges_utf16: Vec::new(),
+                    display_match_ranges: Vec::new(),
+                    match_id: String::new(),
+                    blame_author: None,
+                    blame_commit: None,
+                    snippet: None,
+                    from_binary_file: false,
+                    duplicate_count: None,
+                    dedent: 0,
                 },
                 SearchResultLine {
                     file_path: temp_dir.path().join("z_file.txt"),
@@ -133,6 +165,17 @@ mod search_sort_tests {
                     line_content: "test".to_string(),
                     content_omitted: false,
                     is_context: false,
+                    enclosing_symbol: None,
+                    match_ranges: Vec::new(),
+                    match_ranges_utf16: Vec::new(),
+                    display_match_ranges: Vec::new(),
+                    match_id: String::new(),
+                    blame_author: None,
+                    blame_commit: None,
+                    snippet: None,
+                    from_binary_file: false,
+                    duplicate_count: None,
+                    dedent: 0,
                 },
                 SearchResultLine {
                     file_path: temp_dir.path().join("m_file.txt"),
@@ -140,6 +183,17 @@ mod search_sort_tests {
                     line_content: "test".to_string(),
                     content_omitted: false,
                     is_context: false,
+                    enclosing_symbol: None,
+                    match_ranges: Vec::new(),
+                    match_ranges_utf16: Vec::new(),
+                    display_match_ranges: Vec::new(),
+                    match_id: String::new(),
+                    blame_author: None,
+                    blame_commit: None,
+                    snippet: None,
+                    from_binary_file: false,
+                    duplicate_count: None,
+                    dedent: 0,
                 },
                 SearchResultLine {
                     file_path: temp_dir.path().join("m_file.txt"),
@@ -147,8 +201,22 @@ mod search_sort_tests {
                     line_content: "test".to_string(),
                     content_omitted: false,
                     is_context: false,
+                    enclosing_symbol: None,
+                    match_ranges: Vec::new(),
+                    match_ranges_utf16: Vec::new(),
+                    display_match_ranges: Vec::new(),
+                    match_id: String::new(),
+                    blame_author: None,
+                    blame_commit: None,
+                    snippet: None,
+                    from_binary_file: false,
+                    duplicate_count: None,
+                    dedent: 0,
                 },
             ],
+            timed_out: false,
+            cancelled: false,
+            extensions_searched: Vec::new(),
         };
 
         // Sort the results
@@ -185,4 +253,109 @@ mod search_sort_tests {
 
         Ok(())
     }
+
+    /// Test that sort_by_relevance ranks files with more matches first
+    #[test]
+    fn test_search_results_sorted_by_relevance() -> Result<()> {
+        let temp_dir = tempdir()?;
+
+        // "a_file.txt" sorts first by path but has fewer matches
+        let mut file = File::create(temp_dir.path().join("a_file.txt"))?;
+        writeln!(file, "pattern to find")?;
+        writeln!(file, "no match")?;
+
+        let mut file = File::create(temp_dir.path().join("b_file.txt"))?;
+        writeln!(file, "pattern to find")?;
+        writeln!(file, "no match")?;
+        writeln!(file, "pattern to find")?;
+        writeln!(file, "pattern to find")?;
+
+        let options = SearchOptions {
+            sort_by_relevance: true,
+            ..SearchOptions::default()
+        };
+        let result = search_files("pattern to find", temp_dir.path(), &options)?;
+
+        assert_eq!(result.total_number, 4);
+        let files_and_lines: Vec<(String, u64)> = result
+            .lines
+            .iter()
+            .map(|line| {
+                (
+                    line.file_path
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string(),
+                    line.line_number,
+                )
+            })
+            .collect();
+
+        // The file with 3 matches precedes the one with 1, keeping line order within files
+        assert_eq!(
+            files_and_lines,
+            vec![
+                ("b_file.txt".to_string(), 1),
+                ("b_file.txt".to_string(), 3),
+                ("b_file.txt".to_string(), 4),
+                ("a_file.txt".to_string(), 1),
+            ]
+        );
+
+        Ok(())
+    }
+
+    /// Test that natural sorting orders numbered files by value
+    #[test]
+    fn test_search_results_natural_sort() -> Result<()> {
+        let temp_dir = tempdir()?;
+        for name in ["file10.txt", "file2.txt", "file1.txt"] {
+            let mut file = File::create(temp_dir.path().join(name))?;
+            writeln!(file, "pattern to find")?;
+            writeln!(file, "pattern to find")?;
+        }
+
+        let file_names = |result: &SearchResult| -> Vec<String> {
+            result
+                .lines
+                .iter()
+                .map(|line| {
+                    line.file_path
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .collect()
+        };
+
+        let options = SearchOptions {
+            natural_sort: true,
+            ..SearchOptions::default()
+        };
+        let result = search_files("pattern to find", temp_dir.path(), &options)?;
+        assert_eq!(
+            file_names(&result),
+            vec![
+                "file1.txt",
+                "file1.txt",
+                "file2.txt",
+                "file2.txt",
+                "file10.txt",
+                "file10.txt"
+            ]
+        );
+        assert!(result.lines[0].line_number < result.lines[1].line_number);
+
+        // The default order compares paths character by character
+        let result = search_files(
+            "pattern to find",
+            temp_dir.path(),
+            &SearchOptions::default(),
+        )?;
+        assert_eq!(file_names(&result)[2], "file10.txt");
+
+        Ok(())
+    }
 }
@@ -1,7 +1,17 @@
 use anyhow::Result;
-use lumin::search::{SearchOptions, search_files};
+use lumin::search::{
+    AUTO_MMAP_THRESHOLD_BYTES, ContentMode, HEADER_MAX_BYTES, ReadStrategy, SearchOptions,
+    SearchSort, SearchSummary, count_occurrences, files_matching_all, per_line_match_histogram,
+    search_files, search_files_total_match_line_number, summary,
+};
+use lumin::traverse::{TraverseOptions, traverse_channel};
 use serial_test::serial;
+use std::fs;
 use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tempfile::TempDir;
 
 mod test_helpers;
 use test_helpers::{TEST_DIR, TestEnvironment};
@@ -48,8 +58,10 @@ mod search_tests {
         let _env = TestEnvironment::setup()?;
 
         let pattern = "Fn"; // Capital F
-        let mut options = SearchOptions::default();
-        options.case_sensitive = true;
+        let options = SearchOptions {
+            case_sensitive: true,
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -66,8 +78,10 @@ mod search_tests {
         let _env = TestEnvironment::setup()?;
 
         let pattern = "FN"; // All caps
-        let mut options = SearchOptions::default();
-        options.case_sensitive = false;
+        let options = SearchOptions {
+            case_sensitive: false,
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -121,8 +135,10 @@ mod search_tests {
 
         // Search without respecting gitignore
         let pattern = "API_KEY";
-        let mut options = SearchOptions::default();
-        options.respect_gitignore = false;
+        let options = SearchOptions {
+            respect_gitignore: false,
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -190,10 +206,12 @@ mod search_tests {
 
         // Use a pattern that should exist in the test files
         let pattern = "fn";
-        let mut options = SearchOptions::default();
-        // Set context values to ensure we get context lines
-        options.before_context = 2;
-        options.after_context = 2;
+        let options = SearchOptions {
+            // Set context values to ensure we get context lines
+            before_context: 2,
+            after_context: 2,
+            ..SearchOptions::default()
+        };
 
         let results = search_files(pattern, Path::new(TEST_DIR), &options)?;
 
@@ -214,4 +232,1395 @@ mod search_tests {
 
         Ok(())
     }
+
+    /// Test dropping lines with fewer matches than min_matches_per_line
+    #[test]
+    fn test_search_min_matches_per_line() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("counts.txt");
+        fs::write(
+            &file_path,
+            "one foo here\nfoo and foo\nfoo foo foo\nno match\n",
+        )?;
+
+        let options = SearchOptions {
+            min_matches_per_line: Some(2),
+            ..SearchOptions::default()
+        };
+
+        let results = search_files("foo", &file_path, &options)?;
+
+        // Only the lines with 2 and 3 occurrences should remain
+        let line_numbers: Vec<u64> = results.lines.iter().map(|r| r.line_number).collect();
+        assert_eq!(line_numbers, vec![2, 3]);
+        assert_eq!(results.total_number, 2);
+
+        // Without a threshold, all three matching lines are reported
+        let all_results = search_files("foo", &file_path, &SearchOptions::default())?;
+        assert_eq!(all_results.total_number, 3);
+
+        Ok(())
+    }
+
+    /// Test reporting the function enclosing a match in a Rust file
+    #[test]
+    fn test_search_enclosing_symbol() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("lib.rs");
+        fs::write(
+            &file_path,
+            "use std::fmt;\n\npub fn parse_config(input: &str) -> usize {\n    let needle = input.len();\n    needle\n}\n",
+        )?;
+
+        let options = SearchOptions {
+            enclosing_symbol: true,
+            ..SearchOptions::default()
+        };
+
+        let results = search_files("let needle", &file_path, &options)?;
+        assert_eq!(results.total_number, 1);
+        assert_eq!(
+            results.lines[0].enclosing_symbol.as_deref(),
+            Some("parse_config")
+        );
+
+        // A match above any definition has no enclosing symbol
+        let results = search_files("use std", &file_path, &options)?;
+        assert_eq!(results.lines[0].enclosing_symbol, None);
+
+        // Without the option, no symbol is reported
+        let results = search_files("let needle", &file_path, &SearchOptions::default())?;
+        assert_eq!(results.lines[0].enclosing_symbol, None);
+
+        Ok(())
+    }
+
+    /// Test skipping the root .gitignore while honoring nested ones
+    #[test]
+    fn test_search_ignore_root_gitignore() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        // Mark the directory as a git repository so gitignore files are honored by default
+        fs::create_dir(root.join(".git"))?;
+        fs::create_dir(root.join("sub"))?;
+        fs::write(root.join(".gitignore"), "root_ignored.txt\n")?;
+        fs::write(root.join("sub/.gitignore"), "nested_ignored.txt\n")?;
+        fs::write(root.join("root_ignored.txt"), "needle\n")?;
+        fs::write(root.join("sub/nested_ignored.txt"), "needle\n")?;
+        fs::write(root.join("sub/kept.txt"), "needle\n")?;
+
+        let file_names = |options: &SearchOptions| -> Result<Vec<String>> {
+            let results = search_files("needle", root, options)?;
+            Ok(results
+                .lines
+                .iter()
+                .map(|r| {
+                    r.file_path
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .collect())
+        };
+
+        // By default both the root and nested .gitignore files apply
+        assert_eq!(file_names(&SearchOptions::default())?, vec!["kept.txt"]);
+
+        // Only the nested rules apply when the root .gitignore is skipped
+        let options = SearchOptions {
+            ignore_root_gitignore: true,
+            ..SearchOptions::default()
+        };
+        assert_eq!(file_names(&options)?, vec!["root_ignored.txt", "kept.txt"]);
+
+        Ok(())
+    }
+
+    /// Test viewing a search match with a window of surrounding lines
+    #[test]
+    fn test_search_then_view_match() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("window.txt");
+        fs::write(
+            &file_path,
+            "line 1\nline 2\nline 3\nneedle 4\nline 5\nline 6\nline 7\n",
+        )?;
+
+        let results = search_files("needle", &file_path, &SearchOptions::default())?;
+        assert_eq!(results.lines.len(), 1);
+
+        let view = lumin::search::view_match(&results.lines[0], 2)?;
+        match view.contents {
+            lumin::view::FileContents::Text { content, .. } => {
+                let line_numbers: Vec<usize> = content
+                    .line_contents
+                    .iter()
+                    .map(|l| l.line_number)
+                    .collect();
+                assert_eq!(line_numbers, vec![2, 3, 4, 5, 6]);
+                assert_eq!(content.line_contents[2].line, "needle 4");
+            }
+            _ => panic!("Expected text content"),
+        }
+
+        Ok(())
+    }
+
+    /// Test that buffered and memory-mapped reads produce identical results
+    #[test]
+    fn test_search_read_strategies_identical() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+
+        // A small file and a file above the auto mmap threshold
+        fs::write(
+            temp_dir.path().join("small.txt"),
+            "alpha needle\nbeta\nneedle gamma\n",
+        )?;
+        let mut large = String::new();
+        let mut line_number = 0;
+        while (large.len() as u64) < AUTO_MMAP_THRESHOLD_BYTES + 1024 {
+            line_number += 1;
+            if line_number % 1000 == 0 {
+                large.push_str(&format!("line {} has a needle\n", line_number));
+            } else {
+                large.push_str(&format!("line {} is filler text\n", line_number));
+            }
+        }
+        fs::write(temp_dir.path().join("large.txt"), &large)?;
+
+        let search_with =
+            |read_strategy: ReadStrategy| -> Result<Vec<(String, u64, String, bool)>> {
+                let options = SearchOptions {
+                    read_strategy,
+                    before_context: 1,
+                    after_context: 1,
+                    ..SearchOptions::default()
+                };
+                let results = search_files("needle", temp_dir.path(), &options)?;
+                Ok(results
+                    .lines
+                    .into_iter()
+                    .map(|r| {
+                        (
+                            r.file_path.display().to_string(),
+                            r.line_number,
+                            r.line_content,
+                            r.is_context,
+                        )
+                    })
+                    .collect())
+            };
+
+        let buffered = search_with(ReadStrategy::Buffered)?;
+        assert!(buffered.iter().filter(|r| !r.3).count() > 2);
+        assert_eq!(search_with(ReadStrategy::Mmap)?, buffered);
+        assert_eq!(search_with(ReadStrategy::Auto)?, buffered);
+
+        Ok(())
+    }
+
+    /// Test restricting matches to a line window of each file
+    #[test]
+    fn test_search_line_range() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(
+            temp_dir.path().join("todo.txt"),
+            "TODO one\nTODO two\nplain\nTODO four\nTODO five\nTODO six\n",
+        )?;
+
+        let options = SearchOptions {
+            line_from: Some(2),
+            line_to: Some(4),
+            ..SearchOptions::default()
+        };
+        let results = search_files("TODO", temp_dir.path(), &options)?;
+        let line_numbers: Vec<u64> = results.lines.iter().map(|r| r.line_number).collect();
+        assert_eq!(line_numbers, vec![2, 4]);
+        assert_eq!(results.total_number, 2);
+
+        // Context lines outside of the window are not reported either
+        let options = SearchOptions {
+            line_from: Some(2),
+            line_to: Some(4),
+            before_context: 1,
+            after_context: 1,
+            ..SearchOptions::default()
+        };
+        let results = search_files("TODO", temp_dir.path(), &options)?;
+        let lines: Vec<(u64, bool)> = results
+            .lines
+            .iter()
+            .map(|r| (r.line_number, r.is_context))
+            .collect();
+        assert_eq!(lines, vec![(2, false), (3, true), (4, false)]);
+
+        Ok(())
+    }
+
+    /// Test summarizing a search over the fixture files
+    #[test]
+    fn test_search_summary() -> Result<()> {
+        let fixtures = Path::new("tests/fixtures/text_files");
+        let options = SearchOptions {
+            after_context: 2,
+            take: Some(1),
+            ..SearchOptions::default()
+        };
+
+        assert_eq!(
+            summary("test", fixtures, &options)?,
+            SearchSummary {
+                files_searched: 5,
+                files_with_matches: 2,
+                total_lines_matched: 7,
+                total_occurrences: 7,
+                timed_out: false,
+                cancelled: false,
+            }
+        );
+
+        // Lines with several matches count once as a line but for each occurrence
+        assert_eq!(
+            summary("line|pattern", fixtures, &options)?,
+            SearchSummary {
+                files_searched: 5,
+                files_with_matches: 5,
+                total_lines_matched: 14,
+                total_occurrences: 18,
+                timed_out: false,
+                cancelled: false,
+            }
+        );
+
+        Ok(())
+    }
+
+    /// Test that `header_lines` only searches the first lines of each file
+    #[test]
+    fn test_search_header_lines() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut content = String::from("// Copyright 2024\n// SPDX-License-Identifier: MIT\n");
+        content.push_str(&"fn body() {}\n".repeat(20));
+        content.push_str("// SPDX-License-Identifier: quoted later\n");
+        fs::write(temp_dir.path().join("lib.rs"), &content)?;
+        fs::write(
+            temp_dir.path().join("late.rs"),
+            "fn a() {}\n\n\n\n\n// SPDX-License-Identifier: MIT\n",
+        )?;
+
+        let options = SearchOptions {
+            header_lines: Some(5),
+            after_context: 10,
+            omit_path_prefix: Some(temp_dir.path().to_path_buf()),
+            ..SearchOptions::default()
+        };
+        let results = search_files("SPDX-License-Identifier", temp_dir.path(), &options)?;
+
+        // Only the header match is reported, and context stops at the end of the header
+        let matches: Vec<_> = results
+            .lines
+            .iter()
+            .filter(|line| !line.is_context)
+            .map(|line| {
+                (
+                    line.file_path.to_string_lossy().into_owned(),
+                    line.line_number,
+                )
+            })
+            .collect();
+        assert_eq!(matches, vec![("lib.rs".to_string(), 2)]);
+        assert!(results.lines.iter().all(|line| line.line_number <= 5));
+
+        // `line_to` narrows the header further
+        let options = SearchOptions {
+            line_to: Some(1),
+            ..options
+        };
+        assert!(
+            search_files("SPDX", temp_dir.path(), &options)?
+                .lines
+                .is_empty()
+        );
+
+        // Reading a header stops after `HEADER_MAX_BYTES`, even within a line
+        fs::write(
+            temp_dir.path().join("long.txt"),
+            format!("{}SPDX\n", "x".repeat(HEADER_MAX_BYTES as usize)),
+        )?;
+        let options = SearchOptions {
+            header_lines: Some(1),
+            ..SearchOptions::default()
+        };
+        assert!(
+            search_files("SPDX", temp_dir.path(), &options)?
+                .lines
+                .is_empty()
+        );
+
+        Ok(())
+    }
+
+    /// Test that context windows separated by at most `context_merge_gap` lines are merged
+    #[test]
+    fn test_search_context_merge_gap() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(
+            temp_dir.path().join("a.txt"),
+            "match\nb\nc\nd\ne\nmatch\ng\nh\ni\nj\n",
+        )?;
+        let line_numbers = |options: &SearchOptions| -> Result<Vec<(u64, bool)>> {
+            let results = search_files("match", temp_dir.path(), options)?;
+            Ok(results
+                .lines
+                .iter()
+                .map(|line| (line.line_number, line.is_context))
+                .collect())
+        };
+
+        // The windows 1-2 and 5-7 are 2 lines apart
+        let options = SearchOptions {
+            before_context: 1,
+            after_context: 1,
+            context_merge_gap: 2,
+            ..SearchOptions::default()
+        };
+        let merged: Vec<_> = (1..=7).map(|line| (line, line != 1 && line != 6)).collect();
+        assert_eq!(line_numbers(&options)?, merged);
+
+        // A smaller gap leaves the windows separate, without the extra lines read after them
+        let options = SearchOptions {
+            context_merge_gap: 1,
+            ..options
+        };
+        assert_eq!(
+            line_numbers(&options)?,
+            vec![(1, false), (2, true), (5, true), (6, false), (7, true)]
+        );
+
+        // Nearby matches are joined without context
+        let options = SearchOptions {
+            before_context: 0,
+            after_context: 0,
+            context_merge_gap: 4,
+            ..options
+        };
+        let merged: Vec<_> = (1..=6).map(|line| (line, line != 1 && line != 6)).collect();
+        assert_eq!(line_numbers(&options)?, merged);
+
+        Ok(())
+    }
+
+    /// Test that `extract_pdf_text` is an error when PDF text can't be extracted
+    #[cfg(not(feature = "pdf"))]
+    #[test]
+    fn test_search_pdf_requires_feature() {
+        let options = SearchOptions {
+            extract_pdf_text: true,
+            ..SearchOptions::default()
+        };
+        assert!(search_files("test", Path::new("tests/fixtures/text_files"), &options).is_err());
+    }
+
+    /// Test that the git options are errors when git repositories can't be read
+    #[cfg(not(feature = "git"))]
+    #[test]
+    fn test_search_git_options_require_feature() {
+        let fixtures = Path::new("tests/fixtures/text_files");
+        for options in [
+            SearchOptions {
+                with_blame: true,
+                ..SearchOptions::default()
+            },
+            SearchOptions {
+                git_tracked_only: true,
+                ..SearchOptions::default()
+            },
+        ] {
+            assert!(search_files("test", fixtures, &options).is_err());
+        }
+    }
+
+    /// Test that `unique_content` reports identical lines once with their count
+    #[test]
+    fn test_search_unique_content() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        for name in ["a.rs", "b.rs", "c.rs"] {
+            fs::write(
+                temp_dir.path().join(name),
+                format!("// Licensed under MIT\nfn {}() {{}}\n", &name[..1]),
+            )?;
+        }
+        fs::write(
+            temp_dir.path().join("d.rs"),
+            "// Licensed under Apache-2.0\n",
+        )?;
+
+        let options = SearchOptions {
+            unique_content: true,
+            after_context: 1,
+            ..SearchOptions::default()
+        };
+        let results = search_files("Licensed", temp_dir.path(), &options)?;
+
+        let lines: Vec<_> = results
+            .lines
+            .iter()
+            .map(|line| (line.line_content.as_str(), line.duplicate_count))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("// Licensed under MIT", Some(3)),
+                ("// Licensed under Apache-2.0", Some(1)),
+            ]
+        );
+        assert_eq!(results.total_number, 2);
+        // The first occurrence is kept
+        assert!(results.lines[0].file_path.ends_with("a.rs"));
+
+        // Contents can't be compared without being built
+        let no_content = SearchOptions {
+            content: ContentMode::None,
+            ..options.clone()
+        };
+        assert!(search_files("Licensed", temp_dir.path(), &no_content).is_err());
+
+        // Counting functions count every line
+        let counts = summary("fn ", temp_dir.path(), &options)?;
+        assert_eq!(counts.files_with_matches, 3);
+        let patterns = ["fn ".to_string(), "MIT".to_string()];
+        assert_eq!(
+            files_matching_all(&patterns, temp_dir.path(), &options)?.len(),
+            3
+        );
+
+        // Lines are not deduplicated by default
+        let results = search_files("Licensed", temp_dir.path(), &SearchOptions::default())?;
+        assert_eq!(results.total_number, 4);
+        assert!(
+            results
+                .lines
+                .iter()
+                .all(|line| line.duplicate_count.is_none())
+        );
+
+        Ok(())
+    }
+
+    /// Test that `dedent_context` removes the common indentation of each block
+    #[test]
+    fn test_search_dedent_context() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(
+            temp_dir.path().join("lib.rs"),
+            "fn outer() {\n    if ready {\n\n        target();\n    }\n}\n// end\n\n\t\tfirst();\n\t\tother_target();\n",
+        )?;
+
+        let options = SearchOptions {
+            before_context: 2,
+            after_context: 1,
+            dedent_context: true,
+            ..SearchOptions::default()
+        };
+        let results = search_files("target", temp_dir.path(), &options)?;
+
+        let lines: Vec<_> = results
+            .lines
+            .iter()
+            .map(|line| (line.line_number, line.line_content.as_str(), line.dedent))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                // The blank line doesn't count towards the common indentation of the block
+                (2, "if ready {", 4),
+                (3, "", 0),
+                (4, "    target();", 4),
+                (5, "}", 4),
+                // A separate block is dedented on its own
+                (8, "", 0),
+                (9, "first();", 2),
+                (10, "other_target();", 2),
+            ]
+        );
+
+        // Match ranges refer to the dedented line
+        assert_eq!(results.lines[2].match_ranges, vec![(4, 10)]);
+
+        // Without context, each match is its own block
+        let options = SearchOptions {
+            dedent_context: true,
+            ..SearchOptions::default()
+        };
+        let results = search_files("target", temp_dir.path(), &options)?;
+        let lines: Vec<_> = results
+            .lines
+            .iter()
+            .map(|line| (line.line_content.as_str(), line.dedent))
+            .collect();
+        assert_eq!(lines, vec![("target();", 8), ("other_target();", 2)]);
+        assert_eq!(results.lines[1].match_ranges, vec![(6, 12)]);
+
+        // Patterns matching the indentation keep their ranges, from the start of the line
+        let results = search_files(r"^\s+target", temp_dir.path(), &options)?;
+        assert_eq!(results.lines.len(), 1);
+        assert_eq!(results.lines[0].line_content, "target();");
+        assert_eq!(results.lines[0].dedent, 8);
+        assert_eq!(results.lines[0].match_ranges, vec![(0, 6)]);
+
+        Ok(())
+    }
+
+    /// Test that `collect_extensions` lists the extensions of the searched files
+    #[test]
+    fn test_search_collect_extensions() -> Result<()> {
+        let directory = Path::new("tests/fixtures");
+        let options = SearchOptions {
+            collect_extensions: true,
+            ..SearchOptions::default()
+        };
+
+        // Files are listed whether or not they match, including binary files
+        let results = search_files("no such text in the fixtures", directory, &options)?;
+        assert!(results.lines.is_empty());
+        assert_eq!(
+            results.extensions_searched,
+            vec!["bin", "jpg", "md", "rs", "toml", "txt"]
+        );
+
+        // Files left out by the filtering options are not searched
+        let options = SearchOptions {
+            include_glob: Some(vec!["text_files/**".to_string()]),
+            ..options
+        };
+        let results = search_files("pattern", directory, &options)?;
+        assert_eq!(results.extensions_searched, vec!["md", "toml", "txt"]);
+
+        // Nothing is collected by default
+        let results = search_files("pattern", directory, &SearchOptions::default())?;
+        assert!(results.extensions_searched.is_empty());
+
+        Ok(())
+    }
+
+    /// Test that `first_match_only` reports one line per matching file
+    #[test]
+    fn test_search_first_match_only() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(
+            temp_dir.path().join("a.rs"),
+            "// SPDX-License-Identifier: MIT\nfn a() {}\n// SPDX-License-Identifier: MIT\n",
+        )?;
+        fs::write(
+            temp_dir.path().join("b.rs"),
+            "fn b() {}\n// SPDX-License-Identifier: Apache-2.0\n",
+        )?;
+        fs::write(temp_dir.path().join("c.rs"), "fn c() {}\n")?;
+
+        let options = SearchOptions {
+            first_match_only: true,
+            before_context: 1,
+            after_context: 1,
+            ..SearchOptions::default()
+        };
+        let results = search_files("SPDX", temp_dir.path(), &options)?;
+
+        let lines: Vec<_> = results
+            .lines
+            .iter()
+            .map(|line| {
+                (
+                    line.file_path
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned(),
+                    line.line_number,
+                    line.is_context,
+                )
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("a.rs".to_string(), 1, false),
+                ("b.rs".to_string(), 2, false)
+            ]
+        );
+        assert_eq!(results.total_number, 2);
+
+        // The first line with enough matches is reported, after lines with too few
+        fs::write(
+            temp_dir.path().join("d.rs"),
+            "// SPDX
+// SPDX SPDX
+// SPDX SPDX
+",
+        )?;
+        let options = SearchOptions {
+            first_match_only: true,
+            min_matches_per_line: Some(2),
+            ..SearchOptions::default()
+        };
+        let results = search_files("SPDX", temp_dir.path(), &options)?;
+        let lines: Vec<_> = results
+            .lines
+            .iter()
+            .map(|line| {
+                (
+                    line.file_path.file_name().unwrap().to_owned(),
+                    line.line_number,
+                )
+            })
+            .collect();
+        assert_eq!(lines, vec![("d.rs".into(), 2)]);
+        fs::remove_file(temp_dir.path().join("d.rs"))?;
+
+        // Every match is reported by default
+        let results = search_files("SPDX", temp_dir.path(), &SearchOptions::default())?;
+        assert_eq!(results.total_number, 3);
+
+        Ok(())
+    }
+
+    /// Test that searching on several threads gives the same results as searching sequentially
+    #[test]
+    fn test_search_threads() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        for i in 0..40 {
+            let directory = temp_dir.path().join(format!("dir{}", i % 4));
+            fs::create_dir_all(&directory)?;
+            fs::write(
+                directory.join(format!("file{}.txt", i)),
+                format!("match {}\nnothing\nmatch again {}\n", i, i),
+            )?;
+        }
+
+        let search = |options: &SearchOptions| -> Result<Vec<(String, u64)>> {
+            let results = search_files("match", temp_dir.path(), options)?;
+            assert_eq!(results.total_number, 80);
+            Ok(results
+                .lines
+                .iter()
+                .map(|line| (line.file_path.display().to_string(), line.line_number))
+                .collect())
+        };
+
+        for sort in [SearchSort::PathAndLine, SearchSort::None] {
+            let sequential = search(&SearchOptions {
+                threads: Some(1),
+                sort,
+                ..SearchOptions::default()
+            })?;
+            assert_eq!(sequential.len(), 80);
+            for threads in [Some(4), None] {
+                let parallel = search(&SearchOptions {
+                    threads,
+                    sort,
+                    ..SearchOptions::default()
+                })?;
+                assert_eq!(
+                    parallel, sequential,
+                    "threads {:?}, sort {:?}",
+                    threads, sort
+                );
+            }
+        }
+
+        // Pagination applies to the merged results
+        let options = SearchOptions {
+            threads: Some(4),
+            skip: Some(10),
+            take: Some(5),
+            ..SearchOptions::default()
+        };
+        let page = search_files("match", temp_dir.path(), &options)?;
+        let sequential = search_files(
+            "match",
+            temp_dir.path(),
+            &SearchOptions {
+                threads: Some(1),
+                ..options
+            },
+        )?;
+        assert_eq!(page.total_number, 80);
+        assert_eq!(
+            page.lines
+                .iter()
+                .map(|line| &line.match_id)
+                .collect::<Vec<_>>(),
+            sequential
+                .lines
+                .iter()
+                .map(|line| &line.match_id)
+                .collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    /// Test the histogram of the number of matches per line
+    #[test]
+    fn test_per_line_match_histogram() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(
+            temp_dir.path().join("a.txt"),
+            "no match here\nfoo\nfoo foo\nbar\nfoo and foo\n",
+        )?;
+        fs::write(temp_dir.path().join("b.txt"), "foo\nfoo foo foo\n")?;
+
+        let options = SearchOptions {
+            before_context: 1,
+            take: Some(1),
+            ..SearchOptions::default()
+        };
+        let histogram = per_line_match_histogram("foo", temp_dir.path(), &options)?;
+
+        // Lines without matches and context lines are not counted
+        assert_eq!(
+            histogram.into_iter().collect::<Vec<_>>(),
+            vec![(1, 2), (2, 2), (3, 1)]
+        );
+
+        Ok(())
+    }
+
+    /// Test that `take` without `skip` builds only the requested lines but counts all of them
+    #[test]
+    fn test_search_take_counts_remaining_lines() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        // Files are named so that the walk order differs from the sorted order
+        for (name, matches) in [("c.txt", 3), ("a.txt", 2), ("b.txt", 4), ("d.txt", 1)] {
+            let content: Vec<String> = (0..matches)
+                .map(|i| format!("needle {}\nfiller\n", i))
+                .collect();
+            fs::write(temp_dir.path().join(name), content.concat())?;
+        }
+
+        for options in [
+            SearchOptions::default(),
+            SearchOptions {
+                before_context: 1,
+                ..SearchOptions::default()
+            },
+            SearchOptions {
+                min_matches_per_line: Some(2),
+                ..SearchOptions::default()
+            },
+        ] {
+            let all = search_files("needle|filler", temp_dir.path(), &options)?;
+            let first = search_files(
+                "needle|filler",
+                temp_dir.path(),
+                &SearchOptions {
+                    take: Some(3),
+                    ..options.clone()
+                },
+            )?;
+
+            // The first lines are the same as with a full search, and the total is exact
+            assert_eq!(first.total_number, all.total_number);
+            assert_eq!(first.lines.len(), 3.min(all.lines.len()));
+            for (line, expected) in first.lines.iter().zip(&all.lines) {
+                assert_eq!(line.file_path, expected.file_path);
+                assert_eq!(line.line_number, expected.line_number);
+                assert_eq!(line.line_content, expected.line_content);
+            }
+        }
+
+        let result = search_files(
+            "needle",
+            temp_dir.path(),
+            &SearchOptions {
+                take: Some(3),
+                ..SearchOptions::default()
+            },
+        )?;
+        assert_eq!(result.lines.len(), 3);
+        assert_eq!(result.total_number, 10);
+        assert!(
+            result
+                .lines
+                .iter()
+                .all(|l| l.file_path.ends_with("a.txt") || l.file_path.ends_with("b.txt"))
+        );
+        // That the lines past `take` are counted without being built is checked by
+        // `test_search_take_does_not_build_remaining_lines` in the search module
+
+        Ok(())
+    }
+
+    /// Test that unsorted results keep the walker order of files and the line order within them
+    #[test]
+    fn test_search_sort_none() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        for (name, matches) in [("c.txt", 3), ("a.txt", 2), ("b.txt", 4), ("d.txt", 1)] {
+            let content: Vec<String> = (0..matches)
+                .map(|i| format!("needle {}\nfiller\n", i))
+                .collect();
+            fs::write(temp_dir.path().join(name), content.concat())?;
+        }
+
+        let sorted = search_files("needle", temp_dir.path(), &SearchOptions::default())?;
+        let unsorted = search_files(
+            "needle",
+            temp_dir.path(),
+            &SearchOptions {
+                sort: SearchSort::None,
+                ..SearchOptions::default()
+            },
+        )?;
+
+        // The same lines are found, and the lines of each file stay in line number order
+        assert_eq!(unsorted.total_number, sorted.total_number);
+        let mut lines: Vec<_> = unsorted
+            .lines
+            .iter()
+            .map(|l| (l.file_path.clone(), l.line_number))
+            .collect();
+        for pair in lines.windows(2) {
+            if pair[0].0 == pair[1].0 {
+                assert!(pair[0].1 < pair[1].1, "{:?}", pair);
+            }
+        }
+
+        // Files are not sorted across each other: they follow the order of the directory walk
+        let mut file_order: Vec<_> = lines.iter().map(|(path, _)| path.clone()).collect();
+        file_order.dedup();
+        let (receiver, _) = traverse_channel(temp_dir.path(), &TraverseOptions::default());
+        let walk_order: Vec<_> = receiver
+            .into_iter()
+            .map(|result| result.map(|file| file.file_path))
+            .collect::<Result<_>>()?;
+        assert_eq!(file_order, walk_order);
+
+        lines.sort();
+        let expected: Vec<_> = sorted
+            .lines
+            .iter()
+            .map(|l| (l.file_path.clone(), l.line_number))
+            .collect();
+        assert_eq!(lines, expected);
+
+        Ok(())
+    }
+
+    /// Test listing the matches of the most recently modified files first
+    #[test]
+    fn test_search_sort_modified_desc() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let now = std::time::SystemTime::now();
+        // The older file comes first in path order
+        for (name, age) in [("a_old.txt", 3600), ("b_new.txt", 60)] {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, "needle 1\nfiller\nneedle 2\n")?;
+            fs::File::options()
+                .write(true)
+                .open(&path)?
+                .set_modified(now - Duration::from_secs(age))?;
+        }
+
+        let options = SearchOptions {
+            sort: SearchSort::ModifiedDesc,
+            ..SearchOptions::default()
+        };
+        let result = search_files("needle", temp_dir.path(), &options)?;
+        let lines: Vec<_> = result
+            .lines
+            .iter()
+            .map(|l| {
+                let name = l.file_path.file_name().unwrap().to_string_lossy();
+                (name.into_owned(), l.line_number)
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("b_new.txt".to_string(), 1),
+                ("b_new.txt".to_string(), 3),
+                ("a_old.txt".to_string(), 1),
+                ("a_old.txt".to_string(), 3),
+            ]
+        );
+
+        // Only the first results are built, in the same order
+        let first = search_files(
+            "needle",
+            temp_dir.path(),
+            &SearchOptions {
+                take: Some(1),
+                ..options
+            },
+        )?;
+        assert_eq!(first.total_number, 4);
+        assert!(first.lines[0].file_path.ends_with("b_new.txt"));
+
+        Ok(())
+    }
+
+    /// Test collapsing redundant separators in result paths
+    #[test]
+    fn test_search_clean_paths() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::create_dir(temp_dir.path().join("src"))?;
+        fs::write(temp_dir.path().join("src/main.rs"), "needle\n")?;
+
+        // A directory built by joining strings with a redundant separator
+        let directory = format!("{}//src/", temp_dir.path().display());
+        let raw = search_files("needle", Path::new(&directory), &SearchOptions::default())?;
+        assert!(raw.lines[0].file_path.to_string_lossy().contains("//"));
+
+        let options = SearchOptions {
+            clean_paths: true,
+            ..SearchOptions::default()
+        };
+        let result = search_files("needle", Path::new(&directory), &options)?;
+        let path = result.lines[0].file_path.to_string_lossy().into_owned();
+        assert!(!path.contains("//"), "{}", path);
+        assert_eq!(
+            result.lines[0].file_path,
+            temp_dir.path().join("src/main.rs")
+        );
+
+        Ok(())
+    }
+
+    /// Test counting occurrences rather than matching lines
+    #[test]
+    fn test_count_occurrences() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(
+            temp_dir.path().join("notes.txt"),
+            "TODO: one TODO: two\nnothing here\nTODO: three\n",
+        )?;
+
+        let options = SearchOptions {
+            before_context: 1,
+            take: Some(1),
+            ..SearchOptions::default()
+        };
+        let occurrences = count_occurrences("TODO", temp_dir.path(), &options)?;
+        let lines = search_files_total_match_line_number(
+            "TODO",
+            temp_dir.path(),
+            &SearchOptions::default(),
+        )?;
+
+        assert_eq!(lines, 2);
+        assert_eq!(occurrences, 3);
+        assert!(occurrences > lines);
+
+        Ok(())
+    }
+
+    /// Test searching NUL-delimited records
+    #[test]
+    fn test_search_nul_line_terminator() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(
+            temp_dir.path().join("files.list"),
+            "src/main.rs\0docs/guide.md\0src/lib.rs\0README\nwith newline\0",
+        )?;
+
+        let options = SearchOptions {
+            line_terminator: b'\0',
+            ..SearchOptions::default()
+        };
+
+        let results = search_files(r"\.rs", temp_dir.path(), &options)?;
+        let records: Vec<(u64, &str)> = results
+            .lines
+            .iter()
+            .map(|r| (r.line_number, r.line_content.as_str()))
+            .collect();
+        assert_eq!(records, vec![(1, "src/main.rs"), (3, "src/lib.rs")]);
+
+        // Newlines are part of a record
+        let results = search_files("newline", temp_dir.path(), &options)?;
+        assert_eq!(results.lines.len(), 1);
+        assert_eq!(results.lines[0].line_number, 4);
+        assert_eq!(results.lines[0].line_content, "README\nwith newline");
+
+        Ok(())
+    }
+
+    /// Test that match IDs are stable across searches and distinguish matches
+    #[test]
+    fn test_search_match_id_stable() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(
+            temp_dir.path().join("a.txt"),
+            "needle one\nhay\nneedle two needle\n",
+        )?;
+        fs::write(temp_dir.path().join("b.txt"), "needle one\n")?;
+
+        let options = SearchOptions {
+            after_context: 1,
+            ..SearchOptions::default()
+        };
+        let first = search_files("needle", temp_dir.path(), &options)?;
+        let second = search_files("needle", temp_dir.path(), &options)?;
+
+        let ids = |result: &lumin::search::SearchResult| -> Vec<String> {
+            result.lines.iter().map(|r| r.match_id.clone()).collect()
+        };
+        assert_eq!(ids(&first), ids(&second));
+
+        // Identifiers are unique within the results, even for identical line contents
+        let mut unique = ids(&first);
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), first.lines.len());
+        assert!(first.lines.iter().all(|r| r.match_id.len() == 16));
+
+        Ok(())
+    }
+
+    /// Test reporting paths relative to the enclosing repository root
+    #[test]
+    fn test_search_auto_repo_relative() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let repo = temp_dir.path().join("repo");
+        fs::create_dir_all(repo.join(".git"))?;
+        fs::create_dir_all(repo.join("crates/core/src"))?;
+        fs::write(repo.join("crates/core/src/lib.rs"), "// needle\n")?;
+
+        let options = SearchOptions {
+            auto_repo_relative: true,
+            // A prefix that doesn't match is overridden by the repository root
+            omit_path_prefix: Some(Path::new("/does/not/match").to_path_buf()),
+            ..SearchOptions::default()
+        };
+
+        // The search directory is a subdirectory of the repository
+        let results = search_files("needle", &repo.join("crates/core"), &options)?;
+        assert_eq!(results.lines.len(), 1);
+        assert_eq!(
+            results.lines[0].file_path,
+            Path::new("crates/core/src/lib.rs")
+        );
+
+        // Non-canonical search directories resolve to the same repository-relative paths
+        let results = search_files("needle", &repo.join("crates/core/src/.."), &options)?;
+        assert_eq!(
+            results.lines[0].file_path,
+            Path::new("crates/core/src/lib.rs")
+        );
+
+        Ok(())
+    }
+
+    /// Test that patterns exceeding the regex size limit fail gracefully
+    #[test]
+    fn test_search_regex_size_limit() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(temp_dir.path().join("data.txt"), "some words here\n")?;
+
+        let options = SearchOptions {
+            regex_size_limit: Some(64 * 1024),
+            dfa_size_limit: Some(64 * 1024),
+            ..SearchOptions::default()
+        };
+
+        // A pathological pattern is rejected with a clear error
+        let error = search_files(r"(\w{100}){100}", temp_dir.path(), &options)
+            .expect_err("pattern should exceed the size limit");
+        let message = format!("{:#}", error);
+        assert!(message.contains("size limit"), "message: {}", message);
+
+        // Ordinary patterns work within the limits
+        let results = search_files(r"w\w+s", temp_dir.path(), &options)?;
+        assert_eq!(results.lines.len(), 1);
+
+        Ok(())
+    }
+
+    /// Test that a search stops with partial results when the timeout is exceeded
+    #[test]
+    fn test_search_timeout() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let content = "needle in a haystack\n".repeat(100);
+        for dir in 0..20 {
+            let dir_path = temp_dir.path().join(format!("dir{}", dir));
+            fs::create_dir(&dir_path)?;
+            for file in 0..20 {
+                fs::write(dir_path.join(format!("file{}.txt", file)), &content)?;
+            }
+        }
+
+        let complete = search_files("needle", temp_dir.path(), &SearchOptions::default())?;
+        assert!(!complete.timed_out);
+        assert_eq!(complete.lines.len(), 20 * 20 * 100);
+
+        let options = SearchOptions {
+            timeout: Some(Duration::from_nanos(1)),
+            ..SearchOptions::default()
+        };
+        let partial = search_files("needle", temp_dir.path(), &options)?;
+        assert!(partial.timed_out);
+        assert!(partial.lines.len() < complete.lines.len());
+
+        Ok(())
+    }
+
+    /// Test that a search cancelled from another thread returns early
+    #[test]
+    fn test_search_cancel() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        for file in 0..50 {
+            fs::write(
+                temp_dir.path().join(format!("file{}.txt", file)),
+                "needle\n".repeat(10),
+            )?;
+        }
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        let options = SearchOptions {
+            cancel: Some(Arc::clone(&cancel)),
+            ..SearchOptions::default()
+        };
+
+        // Not cancelled yet, so all files are searched
+        let results = search_files("needle", temp_dir.path(), &options)?;
+        assert_eq!(results.lines.len(), 500);
+        assert!(!results.cancelled);
+
+        let canceller = Arc::clone(&cancel);
+        std::thread::spawn(move || canceller.store(true, Ordering::Relaxed))
+            .join()
+            .unwrap();
+
+        // Cancelled searches return early without searching the remaining files
+        let results = search_files("needle", temp_dir.path(), &options)?;
+        assert!(results.lines.is_empty());
+        assert!(!results.timed_out);
+        assert!(results.cancelled);
+        assert!(summary("needle", temp_dir.path(), &options)?.cancelled);
+
+        Ok(())
+    }
+
+    /// Test annotating matches with the author and commit from git blame
+    #[cfg(feature = "git")]
+    #[test]
+    fn test_search_with_blame() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let repo_dir = temp_dir.path();
+        let repository = git2::Repository::init(repo_dir)?;
+
+        // Commit a file with a known author
+        fs::write(repo_dir.join("notes.txt"), "first needle\nno match\n")?;
+        let mut index = repository.index()?;
+        index.add_path(Path::new("notes.txt"))?;
+        index.write()?;
+        let tree = repository.find_tree(index.write_tree()?)?;
+        let author = git2::Signature::now("Jane Doe", "jane@example.com")?;
+        let commit_id =
+            repository.commit(Some("HEAD"), &author, &author, "Add notes", &tree, &[])?;
+
+        // An uncommitted line and an untracked file are not annotated
+        fs::write(
+            repo_dir.join("notes.txt"),
+            "first needle\nno match\nnew needle\n",
+        )?;
+        fs::write(repo_dir.join("untracked.txt"), "untracked needle\n")?;
+
+        let options = SearchOptions {
+            with_blame: true,
+            ..SearchOptions::default()
+        };
+        let results = search_files("needle", repo_dir, &options)?;
+        assert_eq!(results.lines.len(), 3);
+
+        let committed = &results.lines[0];
+        assert_eq!(committed.line_number, 1);
+        assert_eq!(committed.blame_author.as_deref(), Some("Jane Doe"));
+        assert_eq!(
+            committed.blame_commit.as_deref(),
+            Some(commit_id.to_string().as_str())
+        );
+
+        let uncommitted = &results.lines[1];
+        assert_eq!(uncommitted.line_number, 3);
+        assert_eq!(uncommitted.blame_author, None);
+        assert_eq!(uncommitted.blame_commit, None);
+        assert_eq!(results.lines[2].blame_author, None);
+
+        // Blame is opt-in
+        let results = search_files("needle", repo_dir, &SearchOptions::default())?;
+        assert!(results.lines.iter().all(|line| line.blame_author.is_none()));
+
+        Ok(())
+    }
+
+    /// Test skipping vendored and generated directories
+    #[test]
+    fn test_search_skip_vendored() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("vendor/github.com/dep"))?;
+        fs::create_dir_all(root.join("web/node_modules/pkg"))?;
+        fs::create_dir_all(root.join("src"))?;
+        fs::write(root.join("vendor/github.com/dep/dep.go"), "needle")?;
+        fs::write(root.join("web/node_modules/pkg/index.js"), "needle")?;
+        fs::write(root.join("src/main.go"), "needle")?;
+        // Only directories are skipped, not files with the same names
+        fs::write(root.join("src/build"), "needle")?;
+
+        let options = SearchOptions {
+            skip_vendored: true,
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..SearchOptions::default()
+        };
+        let results = search_files("needle", root, &options)?;
+        let paths: Vec<&Path> = results
+            .lines
+            .iter()
+            .map(|l| l.file_path.as_path())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![Path::new("src/build"), Path::new("src/main.go")]
+        );
+
+        let reason = lumin::search::explain_exclusion(
+            Path::new("vendor/github.com/dep/dep.go"),
+            root,
+            &options,
+        )?;
+        assert!(reason.unwrap().contains("vendored directory `vendor`"));
+
+        // A vendored directory can still be searched directly
+        let results = search_files("needle", &root.join("vendor"), &options)?;
+        assert_eq!(results.lines.len(), 1);
+
+        // Vendored directories are searched by default
+        let results = search_files("needle", root, &SearchOptions::default())?;
+        assert_eq!(results.lines.len(), 4);
+
+        Ok(())
+    }
+
+    /// Test removing the directory prefix shared by all results
+    #[test]
+    fn test_search_auto_omit_common_prefix() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let project = temp_dir.path().join("workspace/project");
+        fs::create_dir_all(project.join("src/util"))?;
+        fs::write(project.join("src/main.rs"), "needle")?;
+        fs::write(project.join("src/util/strings.rs"), "needle")?;
+
+        let options = SearchOptions {
+            auto_omit_common_prefix: true,
+            ..SearchOptions::default()
+        };
+        let results = search_files("needle", temp_dir.path(), &options)?;
+        let paths: Vec<&Path> = results
+            .lines
+            .iter()
+            .map(|l| l.file_path.as_path())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![Path::new("main.rs"), Path::new("util/strings.rs")]
+        );
+
+        // Match IDs are computed from the shortened paths
+        let relative = search_files(
+            "needle",
+            temp_dir.path(),
+            &SearchOptions {
+                omit_path_prefix: Some(project.join("src")),
+                ..SearchOptions::default()
+            },
+        )?;
+        assert_eq!(results.lines[0].match_id, relative.lines[0].match_id);
+
+        Ok(())
+    }
+
+    /// Test restricting a search to files tracked by git
+    #[cfg(feature = "git")]
+    #[test]
+    fn test_search_git_tracked_only() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let repo_dir = temp_dir.path();
+        let repository = git2::Repository::init(repo_dir)?;
+
+        // Stage one file; the other stays untracked without being gitignored
+        fs::create_dir(repo_dir.join("src"))?;
+        fs::write(repo_dir.join("src/tracked.txt"), "needle\n")?;
+        fs::write(repo_dir.join("untracked.txt"), "needle\n")?;
+        let mut index = repository.index()?;
+        index.add_path(Path::new("src/tracked.txt"))?;
+        index.write()?;
+
+        let options = SearchOptions {
+            git_tracked_only: true,
+            omit_path_prefix: Some(repo_dir.to_path_buf()),
+            ..SearchOptions::default()
+        };
+        let results = search_files("needle", repo_dir, &options)?;
+        let paths: Vec<&Path> = results
+            .lines
+            .iter()
+            .map(|l| l.file_path.as_path())
+            .collect();
+        assert_eq!(paths, vec![Path::new("src/tracked.txt")]);
+
+        // Tracked paths are matched relative to a search directory below the repository root
+        let results = search_files("needle", &repo_dir.join("src"), &options)?;
+        assert_eq!(results.lines.len(), 1);
+
+        let reason =
+            lumin::search::explain_exclusion(Path::new("untracked.txt"), repo_dir, &options)?;
+        assert!(reason.unwrap().contains("not tracked by git"));
+
+        // Outside a repository, all files are searched
+        let plain_dir = TempDir::new()?;
+        fs::write(plain_dir.path().join("file.txt"), "needle\n")?;
+        let results = search_files("needle", plain_dir.path(), &options)?;
+        assert_eq!(results.lines.len(), 1);
+
+        Ok(())
+    }
+
+    /// Test skipping files shallower than a minimum depth
+    #[test]
+    fn test_search_min_depth() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("crates/core/src"))?;
+        fs::write(root.join("top.txt"), "needle\n")?;
+        fs::write(root.join("crates/nested.txt"), "needle\n")?;
+        fs::write(root.join("crates/core/src/deep.txt"), "needle\n")?;
+
+        let options = SearchOptions {
+            respect_gitignore: false,
+            min_depth: Some(2),
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..SearchOptions::default()
+        };
+        let results = search_files("needle", root, &options)?;
+        let mut paths: Vec<&Path> = results
+            .lines
+            .iter()
+            .map(|l| l.file_path.as_path())
+            .collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("crates/core/src/deep.txt"),
+                Path::new("crates/nested.txt")
+            ]
+        );
+
+        let reason = lumin::search::explain_exclusion(Path::new("top.txt"), root, &options)?;
+        assert!(reason.unwrap().starts_with("below the minimum depth 2"));
+
+        Ok(())
+    }
 }
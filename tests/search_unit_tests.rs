@@ -1,5 +1,5 @@
 use anyhow::Result;
-use lumin::search::{SearchOptions, search_files};
+use lumin::search::{ContentMode, ReadStrategy, SearchOptions, SearchSort, search_files};
 use std::path::Path;
 
 #[test]
@@ -14,10 +14,47 @@ fn test_search_pattern_case_sensitive() -> Result<()> {
         omit_path_prefix: None,
         match_content_omit_num: None,
         depth: Some(20),
+        min_depth: None,
         before_context: 0,
         after_context: 0,
         skip: None,
         take: None,
+        treat_all_as_text: false,
+        exclude_dir_names: Vec::new(),
+        min_matches_per_line: None,
+        enclosing_symbol: false,
+        sort_by_relevance: false,
+        ignore_root_gitignore: false,
+        highlight_markers: None,
+        read_strategy: ReadStrategy::Buffered,
+        line_from: None,
+        line_to: None,
+        line_terminator: b'\n',
+        auto_repo_relative: false,
+        regex_size_limit: None,
+        dfa_size_limit: None,
+        timeout: None,
+        cancel: None,
+        content: ContentMode::Full,
+        natural_sort: false,
+        with_blame: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        snippet_width: None,
+        git_tracked_only: false,
+        search_compressed: false,
+        utf16_offsets: false,
+        tilde_home: false,
+        sort: SearchSort::PathAndLine,
+        clean_paths: false,
+        header_lines: None,
+        context_merge_gap: 0,
+        unique_content: false,
+        extract_pdf_text: false,
+        dedent_context: false,
+        collect_extensions: false,
+        first_match_only: false,
+        threads: None,
     };
 
     let results = search_files(pattern, directory, &options)?;
@@ -70,10 +107,47 @@ fn test_search_pattern_case_insensitive() -> Result<()> {
         omit_path_prefix: None,
         match_content_omit_num: None,
         depth: Some(20),
+        min_depth: None,
         before_context: 0,
         after_context: 0,
         skip: None,
         take: None,
+        treat_all_as_text: false,
+        exclude_dir_names: Vec::new(),
+        min_matches_per_line: None,
+        enclosing_symbol: false,
+        sort_by_relevance: false,
+        ignore_root_gitignore: false,
+        highlight_markers: None,
+        read_strategy: ReadStrategy::Buffered,
+        line_from: None,
+        line_to: None,
+        line_terminator: b'\n',
+        auto_repo_relative: false,
+        regex_size_limit: None,
+        dfa_size_limit: None,
+        timeout: None,
+        cancel: None,
+        content: ContentMode::Full,
+        natural_sort: false,
+        with_blame: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        snippet_width: None,
+        git_tracked_only: false,
+        search_compressed: false,
+        utf16_offsets: false,
+        tilde_home: false,
+        sort: SearchSort::PathAndLine,
+        clean_paths: false,
+        header_lines: None,
+        context_merge_gap: 0,
+        unique_content: false,
+        extract_pdf_text: false,
+        dedent_context: false,
+        collect_extensions: false,
+        first_match_only: false,
+        threads: None,
     };
 
     let results = search_files(pattern, directory, &options)?;
@@ -111,10 +185,47 @@ fn test_search_with_gitignore_respect() -> Result<()> {
         omit_path_prefix: None,
         match_content_omit_num: None,
         depth: Some(20),
+        min_depth: None,
         before_context: 0,
         after_context: 0,
         skip: None,
         take: None,
+        treat_all_as_text: false,
+        exclude_dir_names: Vec::new(),
+        min_matches_per_line: None,
+        enclosing_symbol: false,
+        sort_by_relevance: false,
+        ignore_root_gitignore: false,
+        highlight_markers: None,
+        read_strategy: ReadStrategy::Buffered,
+        line_from: None,
+        line_to: None,
+        line_terminator: b'\n',
+        auto_repo_relative: false,
+        regex_size_limit: None,
+        dfa_size_limit: None,
+        timeout: None,
+        cancel: None,
+        content: ContentMode::Full,
+        natural_sort: false,
+        with_blame: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        snippet_width: None,
+        git_tracked_only: false,
+        search_compressed: false,
+        utf16_offsets: false,
+        tilde_home: false,
+        sort: SearchSort::PathAndLine,
+        clean_paths: false,
+        header_lines: None,
+        context_merge_gap: 0,
+        unique_content: false,
+        extract_pdf_text: false,
+        dedent_context: false,
+        collect_extensions: false,
+        first_match_only: false,
+        threads: None,
     };
 
     let results = search_files(pattern, directory, &options)?;
@@ -156,10 +267,47 @@ fn test_search_without_gitignore_respect() -> Result<()> {
         omit_path_prefix: None,
         match_content_omit_num: None,
         depth: Some(20),
+        min_depth: None,
         before_context: 0,
         after_context: 0,
         skip: None,
         take: None,
+        treat_all_as_text: false,
+        exclude_dir_names: Vec::new(),
+        min_matches_per_line: None,
+        enclosing_symbol: false,
+        sort_by_relevance: false,
+        ignore_root_gitignore: false,
+        highlight_markers: None,
+        read_strategy: ReadStrategy::Buffered,
+        line_from: None,
+        line_to: None,
+        line_terminator: b'\n',
+        auto_repo_relative: false,
+        regex_size_limit: None,
+        dfa_size_limit: None,
+        timeout: None,
+        cancel: None,
+        content: ContentMode::Full,
+        natural_sort: false,
+        with_blame: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        snippet_width: None,
+        git_tracked_only: false,
+        search_compressed: false,
+        utf16_offsets: false,
+        tilde_home: false,
+        sort: SearchSort::PathAndLine,
+        clean_paths: false,
+        header_lines: None,
+        context_merge_gap: 0,
+        unique_content: false,
+        extract_pdf_text: false,
+        dedent_context: false,
+        collect_extensions: false,
+        first_match_only: false,
+        threads: None,
     };
 
     let results = search_files(pattern, directory, &options)?;
@@ -176,3 +324,31 @@ fn test_search_without_gitignore_respect() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_effective_pattern() -> Result<()> {
+    let insensitive = SearchOptions::default();
+    let sensitive = SearchOptions {
+        case_sensitive: true,
+        ..SearchOptions::default()
+    };
+
+    assert_eq!(
+        insensitive.effective_pattern("fn\\s+main"),
+        "(?i)fn\\s+main"
+    );
+    assert_eq!(sensitive.effective_pattern("fn\\s+main"), "fn\\s+main");
+    // The pattern is passed through unchanged otherwise, even when it has its own flags
+    assert_eq!(insensitive.effective_pattern("(?-i)Main"), "(?i)(?-i)Main");
+    assert_eq!(insensitive.effective_pattern(""), "(?i)");
+
+    // Searching with the effective pattern case sensitively gives the same results
+    let directory = Path::new("tests/fixtures/text_files");
+    let effective = insensitive.effective_pattern("pattern");
+    assert_eq!(
+        search_files("pattern", directory, &insensitive)?.total_number,
+        search_files(&effective, directory, &sensitive)?.total_number
+    );
+
+    Ok(())
+}
@@ -1,3 +1,6 @@
+// Shared by several test crates, each of which uses only a subset of these helpers
+#![allow(dead_code)]
+
 use anyhow::Result;
 use std::fs::{self, File};
 use std::io::Write;
@@ -81,7 +84,7 @@ pub fn setup_ignored_files() -> Result<Vec<PathBuf>> {
         file,
         "This is a hidden file that should be ignored by default when respecting gitignore."
     )?;
-    writeln!(file, "")?;
+    writeln!(file)?;
     writeln!(file, "It contains some sensitive information:")?;
     writeln!(file, "API_KEY=test_key_12345")?;
     writeln!(file, "SECRET=test_secret_67890")?;
@@ -111,7 +114,6 @@ pub fn setup_ignored_files() -> Result<Vec<PathBuf>> {
     let gitignore_path = PathBuf::from(TEST_DIR).join(".gitignore");
     if !gitignore_path.exists() || !fs::read_to_string(&gitignore_path)?.contains(".hidden") {
         let mut file = fs::OpenOptions::new()
-            .write(true)
             .create(true)
             .append(true)
             .open(&gitignore_path)?;
@@ -0,0 +1,60 @@
+use anyhow::Result;
+use lumin::search::{SearchOptions, search_files};
+use lumin::traverse::{TraverseOptions, traverse_directory};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Tests for abbreviating the home directory in result paths.
+///
+/// The home directory is mocked through the `HOME` environment variable, so this file holds a
+/// single test to keep other tests from observing the change.
+#[cfg(all(test, unix))]
+mod tilde_home_tests {
+    use super::*;
+
+    #[test]
+    fn test_tilde_home() -> Result<()> {
+        let home = TempDir::new()?;
+        let project = home.path().join("project");
+        fs::create_dir(&project)?;
+        fs::write(project.join("notes.txt"), "needle\n")?;
+
+        // SAFETY: No other thread of this test binary reads or writes the environment
+        unsafe { std::env::set_var("HOME", home.path()) };
+
+        let results = search_files(
+            "needle",
+            &project,
+            &SearchOptions {
+                tilde_home: true,
+                ..SearchOptions::default()
+            },
+        )?;
+        assert_eq!(results.lines[0].file_path, Path::new("~/project/notes.txt"));
+
+        let results = traverse_directory(
+            &project,
+            &TraverseOptions {
+                tilde_home: true,
+                ..TraverseOptions::default()
+            },
+        )?;
+        assert_eq!(results[0].file_path, Path::new("~/project/notes.txt"));
+
+        // Paths are unchanged by default, and relative paths are never abbreviated
+        let results = search_files("needle", &project, &SearchOptions::default())?;
+        assert_eq!(results.lines[0].file_path, project.join("notes.txt"));
+        let results = traverse_directory(
+            &project,
+            &TraverseOptions {
+                tilde_home: true,
+                omit_path_prefix: Some(project.clone()),
+                ..TraverseOptions::default()
+            },
+        )?;
+        assert_eq!(results[0].file_path, Path::new("notes.txt"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use anyhow::Result;
+use lumin::traverse::{
+    TraverseOptions, traverse_channel, traverse_channel_with_capacity, traverse_directory,
+};
+use std::fs;
+use tempfile::TempDir;
+
+/// Tests for streaming traversal through a bounded channel
+#[cfg(test)]
+mod traverse_channel_tests {
+    use super::*;
+
+    fn setup_files(count: usize) -> Result<TempDir> {
+        let temp_dir = TempDir::new()?;
+        for i in 0..count {
+            fs::write(
+                temp_dir.path().join(format!("file_{:03}.txt", i)),
+                "content",
+            )?;
+        }
+        Ok(temp_dir)
+    }
+
+    /// Consuming the whole channel yields the same files as traverse_directory
+    #[test]
+    fn test_traverse_channel_yields_all_results() -> Result<()> {
+        let temp_dir = setup_files(20)?;
+        let options = TraverseOptions::default();
+
+        let (receiver, handle) = traverse_channel(temp_dir.path(), &options);
+        let mut streamed = receiver
+            .into_iter()
+            .map(|result| result.map(|r| r.file_path))
+            .collect::<Result<Vec<_>>>()?;
+        streamed.sort();
+
+        let expected: Vec<_> = traverse_directory(temp_dir.path(), &options)?
+            .into_iter()
+            .map(|r| r.file_path)
+            .collect();
+
+        assert_eq!(streamed, expected);
+        assert_eq!(handle.join().unwrap(), 20);
+
+        Ok(())
+    }
+
+    /// Dropping the receiver early stops the walk
+    #[test]
+    fn test_traverse_channel_stops_when_receiver_dropped() -> Result<()> {
+        let temp_dir = setup_files(200)?;
+
+        let (receiver, handle) =
+            traverse_channel_with_capacity(temp_dir.path(), &TraverseOptions::default(), 1);
+
+        let first = receiver.recv()?;
+        assert!(first.is_ok());
+        drop(receiver);
+
+        // At most the received result, the buffered one, and one in flight are delivered
+        let delivered = handle.join().unwrap();
+        assert!(delivered <= 3, "walk did not stop: {} delivered", delivered);
+
+        Ok(())
+    }
+
+    /// Setup errors are reported through the channel
+    #[test]
+    fn test_traverse_channel_reports_errors() -> Result<()> {
+        let temp_dir = setup_files(1)?;
+        let options = TraverseOptions {
+            pattern: Some("[invalid".to_string()),
+            ..TraverseOptions::default()
+        };
+
+        let (receiver, handle) = traverse_channel(temp_dir.path(), &options);
+        let results: Vec<_> = receiver.into_iter().collect();
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+        assert_eq!(handle.join().unwrap(), 0);
+
+        Ok(())
+    }
+}
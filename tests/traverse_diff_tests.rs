@@ -0,0 +1,48 @@
+use anyhow::Result;
+use lumin::traverse::{TraverseOptions, TraverseResult, diff, traverse_directory};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Tests for comparing two traversal snapshots
+#[cfg(test)]
+mod traverse_diff_tests {
+    use super::*;
+
+    /// Test detecting an added, a removed and a changed file between two snapshots
+    #[test]
+    fn test_diff_added_removed_modified() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+        fs::write(root.join("kept.txt"), "unchanged")?;
+        fs::write(root.join("changed.txt"), "short")?;
+        fs::write(root.join("removed.txt"), "removed")?;
+
+        let options = TraverseOptions {
+            respect_gitignore: false,
+            include_metadata: true,
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..TraverseOptions::default()
+        };
+        let before = traverse_directory(root, &options)?;
+
+        fs::write(root.join("changed.txt"), "a longer content")?;
+        fs::remove_file(root.join("removed.txt"))?;
+        fs::write(root.join("added.txt"), "added")?;
+        let after = traverse_directory(root, &options)?;
+
+        fn paths(results: &[TraverseResult]) -> Vec<&Path> {
+            results.iter().map(|r| r.file_path.as_path()).collect()
+        }
+        let changes = diff(&before, &after);
+        assert_eq!(paths(&changes.added), vec![Path::new("added.txt")]);
+        assert_eq!(paths(&changes.removed), vec![Path::new("removed.txt")]);
+        assert_eq!(paths(&changes.modified), vec![Path::new("changed.txt")]);
+        assert_eq!(changes.modified[0].size_bytes, Some(16));
+
+        // Identical snapshots have no differences
+        assert!(diff(&after, &after).is_empty());
+
+        Ok(())
+    }
+}
@@ -268,7 +268,7 @@ mod traverse_glob_tests {
                 .to_string_lossy();
             let digit_char = filename.chars().nth(filename.len() - 5).unwrap();
             assert!(
-                digit_char.is_digit(10),
+                digit_char.is_ascii_digit(),
                 "Character before .txt should be a digit: {}",
                 digit_char
             );
@@ -1493,4 +1493,70 @@ mod traverse_glob_tests {
 
         Ok(())
     }
+
+    /// Test precompiling a glob set and reusing it for many paths
+    #[test]
+    fn test_build_glob_set_reuse() -> Result<()> {
+        let patterns = vec!["**/*.rs".to_string(), "docs/**/*.MD".to_string()];
+
+        let glob_set = lumin::traverse::common::build_glob_set(&patterns, false)?;
+        assert!(glob_set.is_match(Path::new("main.rs")));
+        assert!(glob_set.is_match(Path::new("src/nested/lib.rs")));
+        assert!(glob_set.is_match(Path::new("docs/guide/intro.md")));
+        assert!(!glob_set.is_match(Path::new("README.md")));
+        assert!(!glob_set.is_match(Path::new("src/main.rs.bak")));
+
+        // Case sensitive sets only match the exact case
+        let glob_set = lumin::traverse::common::build_glob_set(&patterns, true)?;
+        assert!(!glob_set.is_match(Path::new("docs/guide/intro.md")));
+        assert!(glob_set.is_match(Path::new("docs/guide/intro.MD")));
+
+        // Empty pattern lists match nothing, and invalid patterns are errors
+        let empty_set = lumin::traverse::common::build_glob_set(&[], false)?;
+        assert!(!empty_set.is_match(Path::new("main.rs")));
+        assert!(lumin::traverse::common::build_glob_set(&["[invalid".to_string()], false).is_err());
+
+        Ok(())
+    }
+
+    /// Test that escaped file names are matched literally
+    #[test]
+    fn test_escape_glob() -> Result<()> {
+        use lumin::traverse::common::{build_glob_filter, escape_glob};
+
+        assert_eq!(escape_glob("a*b?.txt"), "a[*]b[?].txt");
+        assert_eq!(escape_glob("[x]{y}"), "[[]x[]][{]y[}]");
+        assert_eq!(escape_glob("!notes!.md"), "{!}notes!.md");
+
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        fs::write(root.join("report[1].txt"), "escaped")?;
+        fs::write(root.join("report1.txt"), "unescaped")?;
+
+        // Unescaped, the brackets form a character class matching "report1.txt"
+        let options = TraverseOptions {
+            pattern: Some("report[1].txt".to_string()),
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..TraverseOptions::default()
+        };
+        let results = traverse_directory(root, &options)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].file_path, Path::new("report1.txt"));
+
+        // Escaped, the file name is matched literally
+        let options = TraverseOptions {
+            pattern: Some(escape_glob("report[1].txt")),
+            ..options
+        };
+        let results = traverse_directory(root, &options)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].file_path, Path::new("report[1].txt"));
+
+        // An escaped leading "!" is not a negation
+        let filter = build_glob_filter(&[escape_glob("!important.txt")], false)?;
+        assert!(filter.is_match(Path::new("!important.txt")));
+        assert!(!filter.is_match(Path::new("other.txt")));
+
+        Ok(())
+    }
 } // Close the traverse_glob_tests module
@@ -0,0 +1,48 @@
+use anyhow::Result;
+use lumin::traverse::{TraverseOptions, directory_sizes};
+use std::fs;
+use tempfile::TempDir;
+
+/// Tests for du-style directory size aggregation
+#[cfg(test)]
+mod traverse_sizes_tests {
+    use super::*;
+
+    /// Test that a directory's size is the sum of the files beneath it
+    #[test]
+    fn test_directory_sizes_aggregates_subdirectories() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let root = temp_dir.path();
+
+        fs::create_dir_all(root.join("sub/nested"))?;
+        fs::write(root.join("top.txt"), "a".repeat(10))?;
+        fs::write(root.join("sub/one.txt"), "b".repeat(100))?;
+        fs::write(root.join("sub/nested/two.txt"), "c".repeat(1000))?;
+
+        let options = TraverseOptions {
+            respect_gitignore: false,
+            ..TraverseOptions::default()
+        };
+        let sizes = directory_sizes(root, &options)?;
+
+        let size_of = |dir: &std::path::Path| {
+            sizes
+                .iter()
+                .find(|(path, _)| path == dir)
+                .map(|(_, size)| *size)
+        };
+
+        assert_eq!(size_of(&root.join("sub/nested")), Some(1000));
+        assert_eq!(size_of(&root.join("sub")), Some(1100));
+        assert_eq!(size_of(root), Some(1110));
+        assert_eq!(sizes.len(), 3);
+
+        // Results are sorted by path
+        let paths: Vec<_> = sizes.iter().map(|(path, _)| path.clone()).collect();
+        let mut sorted = paths.clone();
+        sorted.sort();
+        assert_eq!(paths, sorted);
+
+        Ok(())
+    }
+}
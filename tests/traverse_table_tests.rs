@@ -0,0 +1,75 @@
+use lumin::traverse::{TableColumn, TableOptions, TraverseResult, format_table};
+use std::path::PathBuf;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Tests for formatting traverse results as a printable table
+#[cfg(test)]
+mod traverse_table_tests {
+    use super::*;
+
+    fn result(path: &str, file_type: &str, size: Option<u64>) -> TraverseResult {
+        TraverseResult {
+            file_path: PathBuf::from(path),
+            file_type: file_type.to_string(),
+            size_bytes: size,
+            modified: size.map(|_| UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            link_target: None,
+            inode: None,
+            dev: None,
+            git_status: None,
+            fuzzy_score: None,
+            mode: None,
+        }
+    }
+
+    /// Test that columns line up across rows with mixed metadata
+    #[test]
+    fn test_format_table_alignment() {
+        let results = vec![
+            result("src/main.rs", "rs", Some(1234)),
+            result("README.md", "markdown", Some(7)),
+            result(".hidden/config", "unknown", None),
+        ];
+
+        let table = format_table(&results, &TableOptions::default());
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 4, "Header plus one line per result");
+        assert_eq!(
+            lines[0].split_whitespace().collect::<Vec<_>>(),
+            vec!["TYPE", "SIZE", "MODIFIED", "PATH"]
+        );
+
+        // The path column starts at the same offset on every row
+        let path_offset = lines[0].find("PATH").unwrap();
+        for (line, path) in lines[1..]
+            .iter()
+            .zip(["src/main.rs", "README.md", ".hidden/config"])
+        {
+            assert_eq!(line.find(path), Some(path_offset), "Misaligned row: {line}");
+        }
+
+        // Sizes are right-aligned and missing metadata is shown as '-'
+        let size_end = lines[0].find("SIZE").unwrap() + "SIZE".len();
+        assert_eq!(&lines[1][size_end - 4..size_end], "1234");
+        assert_eq!(&lines[2][size_end - 1..size_end], "7");
+        assert_eq!(&lines[3][size_end - 1..size_end], "-");
+
+        assert!(lines[1].contains("2023-11-14 22:13:20"));
+        assert!(lines[3].starts_with('*'), "Hidden files are marked");
+    }
+
+    /// Test that metadata columns are dropped when no result carries them
+    #[test]
+    fn test_format_table_omits_missing_columns() {
+        let results = vec![result("a.txt", "txt", None), result("dir/b.rs", "rs", None)];
+
+        let options = TableOptions {
+            columns: vec![TableColumn::FileType, TableColumn::Size, TableColumn::Path],
+            header: false,
+        };
+        let table = format_table(&results, &options);
+
+        assert_eq!(table, "txt a.txt\nrs  dir/b.rs\n");
+    }
+}
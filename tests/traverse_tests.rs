@@ -1,8 +1,11 @@
 use anyhow::Result;
-use defer::defer;
-use lumin::traverse::{TraverseOptions, traverse_directory};
+use lumin::traverse::{
+    PatternMode, TraverseOptions, TraverseSort, modified_between, traverse_directory,
+};
 use serial_test::serial;
 use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 mod test_helpers;
 use test_helpers::{TEST_DIR, TestEnvironment};
@@ -68,8 +71,10 @@ mod traverse_tests {
     fn test_traverse_include_binary() -> Result<()> {
         let _env = TestEnvironment::setup()?;
 
-        let mut options = TraverseOptions::default();
-        options.only_text_files = false;
+        let options = TraverseOptions {
+            only_text_files: false,
+            ..TraverseOptions::default()
+        };
 
         let results = traverse_directory(Path::new(TEST_DIR), &options)?;
 
@@ -137,8 +142,10 @@ mod traverse_tests {
         let _env = TestEnvironment::setup()?;
 
         // Configure traversal to ignore gitignore
-        let mut options = TraverseOptions::default();
-        options.respect_gitignore = false;
+        let options = TraverseOptions {
+            respect_gitignore: false,
+            ..TraverseOptions::default()
+        };
 
         let results = traverse_directory(Path::new(TEST_DIR), &options)?;
 
@@ -175,8 +182,10 @@ mod traverse_tests {
     fn test_is_hidden() -> Result<()> {
         let _env = TestEnvironment::setup()?;
 
-        let mut options = TraverseOptions::default();
-        options.respect_gitignore = false; // To include hidden files
+        let options = TraverseOptions {
+            respect_gitignore: false, // To include hidden files
+            ..TraverseOptions::default()
+        };
 
         let results = traverse_directory(Path::new(TEST_DIR), &options)?;
 
@@ -199,8 +208,10 @@ mod traverse_tests {
     fn test_traverse_case_sensitive() -> Result<()> {
         let _env = TestEnvironment::setup()?;
 
-        let mut options = TraverseOptions::default();
-        options.case_sensitive = true;
+        let options = TraverseOptions {
+            case_sensitive: true,
+            ..TraverseOptions::default()
+        };
 
         let results = traverse_directory(Path::new(TEST_DIR), &options)?;
 
@@ -217,8 +228,10 @@ mod traverse_tests {
         let _env = TestEnvironment::setup()?;
 
         // Test with glob pattern matching .rs files
-        let mut options = TraverseOptions::default();
-        options.pattern = Some("**/*.rs".to_string());
+        let options = TraverseOptions {
+            pattern: Some("**/*.rs".to_string()),
+            ..TraverseOptions::default()
+        };
 
         let results = traverse_directory(Path::new(TEST_DIR), &options)?;
 
@@ -227,8 +240,10 @@ mod traverse_tests {
         assert!(results.iter().all(|r| r.file_type == "rs"));
 
         // Test with glob pattern matching .md files
-        let mut options = TraverseOptions::default();
-        options.pattern = Some("**/*.md".to_string());
+        let options = TraverseOptions {
+            pattern: Some("**/*.md".to_string()),
+            ..TraverseOptions::default()
+        };
 
         let results = traverse_directory(Path::new(TEST_DIR), &options)?;
 
@@ -237,8 +252,10 @@ mod traverse_tests {
         assert!(results.iter().all(|r| r.file_type == "md"));
 
         // Test with glob pattern matching files in specific directory
-        let mut options = TraverseOptions::default();
-        options.pattern = Some("**/docs/**".to_string());
+        let options = TraverseOptions {
+            pattern: Some("**/docs/**".to_string()),
+            ..TraverseOptions::default()
+        };
 
         let results = traverse_directory(Path::new(TEST_DIR), &options)?;
 
@@ -251,8 +268,10 @@ mod traverse_tests {
         );
 
         // Test with plain text substring matching (non-glob pattern)
-        let mut options = TraverseOptions::default();
-        options.pattern = Some("README".to_string()); // Use a filename we know exists
+        let options = TraverseOptions {
+            pattern: Some("README".to_string()), // Use a filename we know exists
+            ..TraverseOptions::default()
+        };
 
         let results = traverse_directory(Path::new(TEST_DIR), &options)?;
 
@@ -265,9 +284,11 @@ mod traverse_tests {
         );
 
         // Test with plain text substring matching (case insensitive)
-        let mut options = TraverseOptions::default();
-        options.pattern = Some("contributing".to_string()); // Different pattern for case insensitive test
-        options.case_sensitive = false;
+        let options = TraverseOptions {
+            pattern: Some("contributing".to_string()), // Different pattern for case insensitive test
+            case_sensitive: false,
+            ..TraverseOptions::default()
+        };
 
         let results = traverse_directory(Path::new(TEST_DIR), &options)?;
 
@@ -314,8 +335,10 @@ mod traverse_tests {
         });
 
         // Test root-level prefix matching
-        let mut options = TraverseOptions::default();
-        options.pattern = Some("test_prefix_*".to_string());
+        let options = TraverseOptions {
+            pattern: Some("test_prefix_*".to_string()),
+            ..TraverseOptions::default()
+        };
 
         let results = traverse_directory(Path::new(TEST_DIR), &options)?;
 
@@ -343,8 +366,10 @@ mod traverse_tests {
         }));
 
         // Test recursive prefix matching
-        let mut options = TraverseOptions::default();
-        options.pattern = Some("**/test_prefix_*".to_string());
+        let options = TraverseOptions {
+            pattern: Some("**/test_prefix_*".to_string()),
+            ..TraverseOptions::default()
+        };
 
         let results = traverse_directory(Path::new(TEST_DIR), &options)?;
 
@@ -363,4 +388,743 @@ mod traverse_tests {
 
         Ok(())
     }
+
+    /// Test reading the immediate target of symlinked files
+    #[cfg(unix)]
+    #[test]
+    fn test_traverse_resolve_link_target() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join("releases"))?;
+        std::fs::write(root.join("releases/v2.txt"), "release notes")?;
+        std::os::unix::fs::symlink("releases/v2.txt", root.join("current.txt"))?;
+
+        let options = TraverseOptions {
+            resolve_target: true,
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..TraverseOptions::default()
+        };
+        let results = traverse_directory(root, &options)?;
+
+        let link = results
+            .iter()
+            .find(|r| r.file_path == Path::new("current.txt"))
+            .expect("symlink should be reported");
+        assert_eq!(
+            link.link_target.as_deref(),
+            Some(Path::new("releases/v2.txt"))
+        );
+
+        // Regular files have no link target
+        let regular = results
+            .iter()
+            .find(|r| r.file_path == Path::new("releases/v2.txt"))
+            .expect("regular file should be reported");
+        assert_eq!(regular.link_target, None);
+
+        // Link targets are only read when requested
+        let results = traverse_directory(root, &TraverseOptions::default())?;
+        assert!(results.iter().all(|r| r.link_target.is_none()));
+
+        Ok(())
+    }
+
+    /// Test that permission bits are reported and world-writable files are recognized
+    #[cfg(unix)]
+    #[test]
+    fn test_traverse_include_mode() -> Result<()> {
+        use std::fs::Permissions;
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::write(root.join("shared.txt"), "anyone can write")?;
+        std::fs::write(root.join("private.txt"), "owner only")?;
+        std::fs::set_permissions(root.join("shared.txt"), Permissions::from_mode(0o666))?;
+        std::fs::set_permissions(root.join("private.txt"), Permissions::from_mode(0o600))?;
+
+        let options = TraverseOptions {
+            include_mode: true,
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..TraverseOptions::default()
+        };
+        let results = traverse_directory(root, &options)?;
+        let find = |name: &str| {
+            results
+                .iter()
+                .find(|r| r.file_path == Path::new(name))
+                .expect("file should be reported")
+        };
+
+        let shared = find("shared.txt");
+        assert_eq!(shared.mode.map(|mode| mode & 0o777), Some(0o666));
+        assert_eq!(shared.is_world_writable(), Some(true));
+        assert_eq!(find("private.txt").is_world_writable(), Some(false));
+
+        // Modes are only read when requested
+        let results = traverse_directory(root, &TraverseOptions::default())?;
+        assert!(results.iter().all(|r| r.is_world_writable().is_none()));
+
+        Ok(())
+    }
+
+    /// Test that hardlinks are reported with the same inode and device
+    #[cfg(unix)]
+    #[test]
+    fn test_traverse_include_inode() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::write(root.join("original.txt"), "shared data")?;
+        std::fs::hard_link(root.join("original.txt"), root.join("hardlink.txt"))?;
+        std::fs::write(root.join("separate.txt"), "shared data")?;
+
+        let options = TraverseOptions {
+            include_inode: true,
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..TraverseOptions::default()
+        };
+        let results = traverse_directory(root, &options)?;
+        let identity = |name: &str| {
+            let result = results
+                .iter()
+                .find(|r| r.file_path == Path::new(name))
+                .expect("file should be reported");
+            (result.dev, result.inode)
+        };
+
+        let original = identity("original.txt");
+        assert!(original.0.is_some() && original.1.is_some());
+        assert_eq!(identity("hardlink.txt"), original);
+        assert_ne!(identity("separate.txt"), original);
+
+        // Inodes are only read when requested
+        let results = traverse_directory(root, &TraverseOptions::default())?;
+        assert!(results.iter().all(|r| r.inode.is_none() && r.dev.is_none()));
+
+        Ok(())
+    }
+
+    /// Test the traversal report wrapper
+    #[test]
+    fn test_traverse_directory_report() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("a/b"))?;
+        std::fs::write(root.join("top.txt"), "top")?;
+        std::fs::write(root.join("a/middle.txt"), "middle")?;
+        std::fs::write(root.join("a/b/bottom.txt"), "bottom")?;
+
+        let report = lumin::traverse::traverse_directory_report(root, &TraverseOptions::default())?;
+        assert_eq!(report.total, report.results.len());
+        assert_eq!(report.total, 3);
+        assert!(!report.depth_limited);
+
+        // With a depth limit, the report tells that files may be missing
+        let options = TraverseOptions {
+            depth: Some(2),
+            ..TraverseOptions::default()
+        };
+        let report = lumin::traverse::traverse_directory_report(root, &options)?;
+        assert_eq!(report.total, report.results.len());
+        assert_eq!(report.total, 2);
+        assert!(report.depth_limited);
+
+        Ok(())
+    }
+
+    /// Test that extensionless scripts are typed from their shebang line
+    #[test]
+    fn test_traverse_shebang_file_type() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("deploy"),
+            "#!/usr/bin/env python3\nprint('deploy')\n",
+        )?;
+        std::fs::write(root.join("README"), "no shebang here\n")?;
+
+        let results = traverse_directory(root, &TraverseOptions::default())?;
+        let file_type = |name: &str| {
+            results
+                .iter()
+                .find(|r| r.file_path.ends_with(name))
+                .map(|r| r.file_type.clone())
+        };
+        assert_eq!(file_type("deploy").as_deref(), Some("python"));
+        assert_eq!(file_type("README").as_deref(), Some("unknown"));
+
+        Ok(())
+    }
+
+    /// Test breadth-first ordering of the results
+    #[test]
+    fn test_traverse_breadth_first() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("a/b"))?;
+        std::fs::write(root.join("a/b/deep.txt"), "deep")?;
+        std::fs::write(root.join("a/middle.txt"), "middle")?;
+        std::fs::write(root.join("zzz.txt"), "top")?;
+
+        let options = TraverseOptions {
+            omit_path_prefix: Some(root.to_path_buf()),
+            sort: TraverseSort::BreadthFirst,
+            ..TraverseOptions::default()
+        };
+        let results = traverse_directory(root, &options)?;
+        let paths: Vec<&Path> = results.iter().map(|r| r.file_path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("zzz.txt"),
+                Path::new("a/middle.txt"),
+                Path::new("a/b/deep.txt")
+            ]
+        );
+
+        // The default order is alphabetical
+        let options = TraverseOptions {
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..TraverseOptions::default()
+        };
+        let results = traverse_directory(root, &options)?;
+        assert_eq!(results[0].file_path, Path::new("a/b/deep.txt"));
+
+        Ok(())
+    }
+
+    /// Test finding files modified within a time range
+    #[test]
+    fn test_traverse_modified_between() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        let base = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        let day = std::time::Duration::from_secs(24 * 60 * 60);
+
+        // Files modified 0 to 4 days after the base time, written out of order
+        for (name, days) in [
+            ("c.txt", 2),
+            ("a.txt", 0),
+            ("e.txt", 4),
+            ("b.txt", 3),
+            ("d.txt", 1),
+        ] {
+            let path = root.join(name);
+            std::fs::write(&path, name)?;
+            std::fs::File::options()
+                .write(true)
+                .open(&path)?
+                .set_modified(base + day * days)?;
+        }
+
+        let options = TraverseOptions {
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..TraverseOptions::default()
+        };
+
+        // The range is inclusive, and results are sorted by modification time
+        let results = modified_between(root, base + day, base + day * 3, &options)?;
+        let paths: Vec<&Path> = results.iter().map(|r| r.file_path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![Path::new("d.txt"), Path::new("c.txt"), Path::new("b.txt")]
+        );
+        assert_eq!(results[0].modified, Some(base + day));
+
+        // An inverted range is an error
+        assert!(modified_between(root, base + day, base, &options).is_err());
+
+        Ok(())
+    }
+
+    /// Test that a traversal cancelled from another thread returns early
+    #[test]
+    fn test_traverse_cancel() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        for file in 0..20 {
+            std::fs::write(temp_dir.path().join(format!("file{}.txt", file)), "text")?;
+        }
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        let options = TraverseOptions {
+            cancel: Some(Arc::clone(&cancel)),
+            ..TraverseOptions::default()
+        };
+        assert_eq!(traverse_directory(temp_dir.path(), &options)?.len(), 20);
+
+        let canceller = Arc::clone(&cancel);
+        std::thread::spawn(move || canceller.store(true, Ordering::Relaxed))
+            .join()
+            .unwrap();
+        assert!(traverse_directory(temp_dir.path(), &options)?.is_empty());
+
+        Ok(())
+    }
+
+    /// Test natural ordering of numbered files
+    #[test]
+    fn test_traverse_natural_sort() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("dir10"))?;
+        std::fs::create_dir_all(root.join("dir2"))?;
+        for name in ["file10.txt", "file2.txt", "dir10/a.txt", "dir2/a.txt"] {
+            std::fs::write(root.join(name), name)?;
+        }
+
+        let options = TraverseOptions {
+            omit_path_prefix: Some(root.to_path_buf()),
+            natural_sort: true,
+            ..TraverseOptions::default()
+        };
+        let results = traverse_directory(root, &options)?;
+        let paths: Vec<&Path> = results.iter().map(|r| r.file_path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("dir2/a.txt"),
+                Path::new("dir10/a.txt"),
+                Path::new("file2.txt"),
+                Path::new("file10.txt")
+            ]
+        );
+
+        // Breadth-first ordering uses the natural order within each depth
+        let options = TraverseOptions {
+            sort: TraverseSort::BreadthFirst,
+            ..options
+        };
+        let results = traverse_directory(root, &options)?;
+        assert_eq!(results[0].file_path, Path::new("file2.txt"));
+        assert_eq!(results[1].file_path, Path::new("file10.txt"));
+
+        Ok(())
+    }
+
+    /// Test removing the directory prefix shared by all results
+    #[test]
+    fn test_traverse_auto_omit_common_prefix() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let project = temp_dir.path().join("workspace/project");
+        std::fs::create_dir_all(project.join("docs"))?;
+        std::fs::create_dir_all(project.join("src"))?;
+        std::fs::write(project.join("docs/guide.md"), "guide")?;
+        std::fs::write(project.join("src/lib.rs"), "lib")?;
+
+        let options = TraverseOptions {
+            auto_omit_common_prefix: true,
+            ..TraverseOptions::default()
+        };
+        let results = traverse_directory(temp_dir.path(), &options)?;
+        let paths: Vec<&Path> = results.iter().map(|r| r.file_path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![Path::new("docs/guide.md"), Path::new("src/lib.rs")]
+        );
+
+        Ok(())
+    }
+
+    /// Test staying on the file system of the traversal directory
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_traverse_one_file_system() -> Result<()> {
+        use std::os::unix::fs::MetadataExt;
+
+        // /proc is a separate file system on Linux; skip the test where it isn't mounted
+        let root = Path::new("/");
+        let proc_version = Path::new("/proc/version");
+        if !proc_version.is_file() || root.metadata()?.dev() == Path::new("/proc").metadata()?.dev()
+        {
+            return Ok(());
+        }
+
+        let traverse = |one_file_system: bool| {
+            traverse_directory(
+                root,
+                &TraverseOptions {
+                    respect_gitignore: false,
+                    only_text_files: false,
+                    depth: Some(2),
+                    pattern: Some("/proc/version".to_string()),
+                    one_file_system,
+                    ..TraverseOptions::default()
+                },
+            )
+        };
+
+        let results = traverse(false)?;
+        assert!(results.iter().any(|r| r.file_path == proc_version));
+
+        let results = traverse(true)?;
+        assert!(results.is_empty());
+
+        Ok(())
+    }
+
+    /// Test including directories, and in particular empty ones, in the results
+    #[test]
+    fn test_traverse_include_dirs() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src/empty"))?;
+        std::fs::write(root.join("src/lib.rs"), "pub fn lib() {}\n")?;
+
+        let traverse = |include_dirs: bool| -> Result<Vec<(String, String)>> {
+            let results = traverse_directory(
+                root,
+                &TraverseOptions {
+                    respect_gitignore: false,
+                    include_dirs,
+                    omit_path_prefix: Some(root.to_path_buf()),
+                    ..TraverseOptions::default()
+                },
+            )?;
+            Ok(results
+                .into_iter()
+                .map(|r| (r.file_path.to_string_lossy().into_owned(), r.file_type))
+                .collect())
+        };
+
+        assert_eq!(
+            traverse(true)?,
+            vec![
+                ("src".to_string(), "dir".to_string()),
+                ("src/empty".to_string(), "dir".to_string()),
+                ("src/lib.rs".to_string(), "rs".to_string()),
+            ]
+        );
+        assert_eq!(
+            traverse(false)?,
+            vec![("src/lib.rs".to_string(), "rs".to_string())]
+        );
+
+        Ok(())
+    }
+
+    /// Test skipping files shallower than a minimum depth
+    #[test]
+    fn test_traverse_min_depth() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src/nested"))?;
+        std::fs::write(root.join("README.md"), "readme\n")?;
+        std::fs::write(root.join("src/lib.rs"), "pub fn lib() {}\n")?;
+        std::fs::write(root.join("src/nested/mod.rs"), "mod nested;\n")?;
+
+        let results = traverse_directory(
+            root,
+            &TraverseOptions {
+                respect_gitignore: false,
+                min_depth: Some(2),
+                omit_path_prefix: Some(root.to_path_buf()),
+                ..TraverseOptions::default()
+            },
+        )?;
+        let paths: Vec<&Path> = results.iter().map(|r| r.file_path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![Path::new("src/lib.rs"), Path::new("src/nested/mod.rs")]
+        );
+
+        Ok(())
+    }
+
+    /// Test case-insensitive substring matching with Unicode case folding
+    #[test]
+    fn test_traverse_unicode_case_folding() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::write(root.join("Straße.txt"), "street\n")?;
+        std::fs::write(root.join("İstanbul.txt"), "city\n")?;
+        std::fs::write(root.join("other.txt"), "other\n")?;
+
+        let file_names = |pattern: &str, case_sensitive: bool| -> Result<Vec<String>> {
+            let results = traverse_directory(
+                root,
+                &TraverseOptions {
+                    respect_gitignore: false,
+                    case_sensitive,
+                    pattern: Some(pattern.to_string()),
+                    ..TraverseOptions::default()
+                },
+            )?;
+            Ok(results
+                .iter()
+                .map(|r| {
+                    r.file_path
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect())
+        };
+
+        // The eszett folds to "ss" in both directions
+        assert_eq!(file_names("STRASSE", false)?, vec!["Straße.txt"]);
+        assert_eq!(file_names("strasse", false)?, vec!["Straße.txt"]);
+        assert_eq!(file_names("STRAẞE", false)?, vec!["Straße.txt"]);
+        // The dotted capital I folds to "i" with a combining dot above
+        assert_eq!(file_names("İSTANBUL", false)?, vec!["İstanbul.txt"]);
+        assert_eq!(file_names("i\u{307}stanbul", false)?, vec!["İstanbul.txt"]);
+
+        // Case-sensitive matching doesn't fold
+        assert!(file_names("STRASSE", true)?.is_empty());
+
+        Ok(())
+    }
+
+    /// Test matching a glob pattern against absolute paths
+    #[test]
+    fn test_traverse_glob_against_absolute() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join("src"))?;
+        std::fs::create_dir(root.join("tests"))?;
+        std::fs::write(root.join("src/lib.rs"), "pub fn lib() {}\n")?;
+        std::fs::write(root.join("tests/lib.rs"), "#[test]\nfn t() {}\n")?;
+
+        let traverse = |glob_against_absolute: bool| {
+            traverse_directory(
+                root,
+                &TraverseOptions {
+                    respect_gitignore: false,
+                    pattern: Some(format!("{}/src/*.rs", root.display())),
+                    glob_against_absolute,
+                    ..TraverseOptions::default()
+                },
+            )
+        };
+
+        // Relative paths never match an absolute pattern
+        assert!(traverse(false)?.is_empty());
+
+        let results = traverse(true)?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].file_path, root.join("src/lib.rs"));
+
+        Ok(())
+    }
+
+    /// Test forcing the text/binary classification of extensions
+    #[test]
+    fn test_traverse_extension_overrides() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        // A text file that content detection classifies as a PDF document
+        std::fs::write(root.join("notes.pdf"), "%PDF-1.4 header notes\n")?;
+        std::fs::write(root.join("table.dat"), "plain text\n")?;
+
+        let file_names = |options: &TraverseOptions| -> Result<Vec<String>> {
+            Ok(traverse_directory(root, options)?
+                .iter()
+                .map(|r| {
+                    r.file_path
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect())
+        };
+
+        let options = TraverseOptions {
+            respect_gitignore: false,
+            ..TraverseOptions::default()
+        };
+        assert_eq!(file_names(&options)?, vec!["table.dat"]);
+
+        let options = TraverseOptions {
+            text_extensions: vec!["pdf".to_string()],
+            binary_extensions: vec!["dat".to_string()],
+            ..options
+        };
+        assert_eq!(file_names(&options)?, vec!["notes.pdf"]);
+
+        Ok(())
+    }
+
+    /// Test annotating results with their git status
+    #[cfg(feature = "git")]
+    #[test]
+    fn test_traverse_with_git_status() -> Result<()> {
+        use lumin::traverse::GitStatus;
+
+        let temp_dir = tempfile::TempDir::new()?;
+        let repo_dir = temp_dir.path();
+        let repository = git2::Repository::init(repo_dir)?;
+
+        // Commit two files, then modify one of them
+        std::fs::create_dir(repo_dir.join("src"))?;
+        std::fs::write(repo_dir.join("src/clean.txt"), "clean\n")?;
+        std::fs::write(repo_dir.join("src/modified.txt"), "before\n")?;
+        let mut index = repository.index()?;
+        index.add_path(Path::new("src/clean.txt"))?;
+        index.add_path(Path::new("src/modified.txt"))?;
+        index.write()?;
+        let tree = repository.find_tree(index.write_tree()?)?;
+        let author = git2::Signature::now("Jane Doe", "jane@example.com")?;
+        repository.commit(Some("HEAD"), &author, &author, "Initial", &tree, &[])?;
+        std::fs::write(repo_dir.join("src/modified.txt"), "after\n")?;
+
+        // Stage a new file, and leave others untracked or ignored
+        std::fs::write(repo_dir.join("added.txt"), "added\n")?;
+        let mut index = repository.index()?;
+        index.add_path(Path::new("added.txt"))?;
+        index.write()?;
+        std::fs::create_dir(repo_dir.join("new"))?;
+        std::fs::write(repo_dir.join("new/untracked.txt"), "untracked\n")?;
+        std::fs::write(repo_dir.join(".gitignore"), "build/\n")?;
+        std::fs::create_dir(repo_dir.join("build"))?;
+        std::fs::write(repo_dir.join("build/output.txt"), "output\n")?;
+
+        let options = TraverseOptions {
+            respect_gitignore: false,
+            with_git_status: true,
+            ..TraverseOptions::default()
+        };
+        let results = traverse_directory(repo_dir, &options)?;
+
+        // Files of the .git directory itself have no status
+        assert!(
+            results
+                .iter()
+                .filter(|result| result.file_path.starts_with(repo_dir.join(".git")))
+                .all(|result| result.git_status.is_none())
+        );
+
+        let statuses: Vec<_> = results
+            .into_iter()
+            .map(|result| {
+                let path = result
+                    .file_path
+                    .strip_prefix(repo_dir)
+                    .unwrap()
+                    .to_path_buf();
+                (path, result.git_status)
+            })
+            // Skip .git and .gitignore
+            .filter(|(path, _)| !path.to_string_lossy().starts_with('.'))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (Path::new("added.txt").to_path_buf(), Some(GitStatus::Added)),
+                (
+                    Path::new("build/output.txt").to_path_buf(),
+                    Some(GitStatus::Ignored)
+                ),
+                (
+                    Path::new("new/untracked.txt").to_path_buf(),
+                    Some(GitStatus::Untracked)
+                ),
+                (
+                    Path::new("src/clean.txt").to_path_buf(),
+                    Some(GitStatus::Clean)
+                ),
+                (
+                    Path::new("src/modified.txt").to_path_buf(),
+                    Some(GitStatus::Modified)
+                ),
+            ]
+        );
+
+        // Statuses are only read when requested
+        let results = traverse_directory(repo_dir, &TraverseOptions::default())?;
+        assert!(results.iter().all(|result| result.git_status.is_none()));
+
+        Ok(())
+    }
+
+    /// Test fuzzy pattern matching with results ranked by score
+    #[test]
+    fn test_traverse_fuzzy_pattern() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        for file in [
+            "src/main.rs",
+            "scripts/common.rs",
+            "README.md",
+            "Src/Mine.rs",
+        ] {
+            let path = root.join(file);
+            std::fs::create_dir_all(path.parent().unwrap())?;
+            std::fs::write(path, "content\n")?;
+        }
+
+        let options = TraverseOptions {
+            pattern: Some("srcmn".to_string()),
+            pattern_mode: PatternMode::Fuzzy,
+            respect_gitignore: false,
+            ..TraverseOptions::default()
+        };
+        let results = traverse_directory(root, &options)?;
+        let ranked: Vec<_> = results
+            .iter()
+            .map(|result| result.file_path.strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+
+        // Case-insensitive matches are ranked best first; ties are ordered by path
+        assert_eq!(
+            ranked,
+            vec![
+                Path::new("Src/Mine.rs"),
+                Path::new("src/main.rs"),
+                Path::new("scripts/common.rs"),
+            ]
+        );
+        let scores: Vec<_> = results.iter().map(|result| result.fuzzy_score).collect();
+        assert!(scores.iter().all(Option::is_some));
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert!(scores[1] > scores[2]);
+
+        // The same pattern only matches literally in the default mode
+        let options = TraverseOptions {
+            pattern_mode: PatternMode::Auto,
+            ..options
+        };
+        assert!(traverse_directory(root, &options)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_traverse_result_parent_dir() -> Result<()> {
+        let temp_dir = tempfile::TempDir::new()?;
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src/nested"))?;
+        std::fs::write(root.join("src/nested/deep.txt"), "nested")?;
+        std::fs::write(root.join("top.txt"), "top")?;
+
+        let results = traverse_directory(root, &TraverseOptions::default())?;
+        let nested = results
+            .iter()
+            .find(|r| r.file_path.ends_with("deep.txt"))
+            .expect("nested file should be reported");
+        assert_eq!(nested.parent_dir(), Some(root.join("src/nested").as_path()));
+
+        // Parents follow the displayed paths, and files at the root have none
+        let options = TraverseOptions {
+            omit_path_prefix: Some(root.to_path_buf()),
+            ..TraverseOptions::default()
+        };
+        let results = traverse_directory(root, &options)?;
+        let parents: Vec<_> = results
+            .iter()
+            .map(|r| (r.file_path.as_path(), r.parent_dir()))
+            .collect();
+        assert_eq!(
+            parents,
+            vec![
+                (
+                    Path::new("src/nested/deep.txt"),
+                    Some(Path::new("src/nested"))
+                ),
+                (Path::new("top.txt"), None),
+            ]
+        );
+
+        Ok(())
+    }
 }
@@ -26,7 +26,7 @@ mod tree_tests {
         // Check the structure format
         for dir_tree in &result {
             // Dir should be a valid path string
-            assert!(!dir_tree.dir.is_empty());
+            assert!(!dir_tree.dir.as_os_str().is_empty());
 
             // Each directory should have entries
             assert!(!dir_tree.entries.is_empty());
@@ -45,7 +45,11 @@ mod tree_tests {
         }
 
         // Should not find directories in .hidden directory (respects gitignore)
-        assert!(!result.iter().any(|r| r.dir.contains(".hidden")));
+        assert!(
+            !result
+                .iter()
+                .any(|r| r.dir.to_string_lossy().contains(".hidden"))
+        );
 
         Ok(())
     }
@@ -57,14 +61,18 @@ mod tree_tests {
         let _env = TestEnvironment::setup()?;
 
         // Configure to ignore gitignore
-        let mut options = TreeOptions::default();
-        options.respect_gitignore = false;
+        let options = TreeOptions {
+            respect_gitignore: false,
+            ..TreeOptions::default()
+        };
 
         let result = generate_tree(Path::new(TEST_DIR), &options)?;
 
         // Should find .hidden directories
         assert!(
-            result.iter().any(|r| r.dir.contains(".hidden")),
+            result
+                .iter()
+                .any(|r| r.dir.to_string_lossy().contains(".hidden")),
             "Did not find .hidden directories when ignoring gitignore"
         );
 
@@ -100,6 +108,7 @@ mod tree_tests {
         let has_binary_files = result.iter().any(|dir_tree| {
             dir_tree.entries.iter().any(|entry| match entry {
                 Entry::File { name } => {
+                    let name = name.to_string_lossy();
                     name.ends_with(".jpg") || name.ends_with(".png") || name == "binary_executable"
                 }
                 _ => false,
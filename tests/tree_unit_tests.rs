@@ -1,5 +1,9 @@
 use anyhow::Result;
-use lumin::tree::{Entry, TreeOptions, generate_tree};
+use lumin::tree::{
+    DirectoryTree, Entry, EntrySort, TreeOptions, generate_tree, generate_tree_report, list_files,
+    to_dot,
+};
+use std::ffi::OsString;
 use std::path::Path;
 
 #[test]
@@ -26,7 +30,7 @@ fn test_tree_basic() -> Result<()> {
             .iter()
             .filter_map(|e| {
                 if let Entry::Directory { name } = e {
-                    Some(name.as_str())
+                    name.to_str()
                 } else {
                     None
                 }
@@ -42,18 +46,24 @@ fn test_tree_basic() -> Result<()> {
     }
 
     // Should include nested directories
-    let nested_dir = results.iter().find(|d| d.dir.contains("nested"));
+    let nested_dir = results
+        .iter()
+        .find(|d| d.dir.to_string_lossy().contains("nested"));
     assert!(nested_dir.is_some());
 
     // Should include nested/level1 directory
-    let level1_dir = results.iter().find(|d| d.dir.contains("nested/level1"));
+    let level1_dir = results
+        .iter()
+        .find(|d| d.dir.to_string_lossy().contains("nested/level1"));
     assert!(level1_dir.is_some());
 
     // Should include level2 directories with correct parent
-    let level2_dir = results.iter().find(|d| d.dir.contains("level2"));
+    let level2_dir = results
+        .iter()
+        .find(|d| d.dir.to_string_lossy().contains("level2"));
     if let Some(level2) = level2_dir {
         // The level2 directory should be under level1
-        assert!(level2.dir.contains("level1/level2"));
+        assert!(level2.dir.to_string_lossy().contains("level1/level2"));
     }
 
     Ok(())
@@ -70,14 +80,18 @@ fn test_tree_without_gitignore_respect() -> Result<()> {
     let results = generate_tree(directory, &options)?;
 
     // Should include .hidden directory when not respecting gitignore
-    let contains_hidden_dir = results.iter().any(|d| d.dir.contains(".hidden"));
+    let contains_hidden_dir = results
+        .iter()
+        .any(|d| d.dir.to_string_lossy().contains(".hidden"));
     assert!(
         contains_hidden_dir,
         ".hidden directory should be included when not respecting gitignore"
     );
 
     // Should include files from gitignored directories
-    let hidden_dir = results.iter().find(|d| d.dir.contains(".hidden"));
+    let hidden_dir = results
+        .iter()
+        .find(|d| d.dir.to_string_lossy().contains(".hidden"));
     if let Some(dir) = hidden_dir {
         // Should have secret.txt as an entry
         let has_secret_file = dir.entries.iter().any(|e| {
@@ -107,18 +121,20 @@ fn test_tree_structure_integrity() -> Result<()> {
     // Verify the nested directory structure is preserved correctly
 
     // Should have all levels of nesting
-    let has_level1 = results
+    let has_level1 = results.iter().any(|d| {
+        d.dir.to_string_lossy().contains("level1") && !d.dir.to_string_lossy().contains("level2")
+    });
+    let has_level2 = results
         .iter()
-        .any(|d| d.dir.contains("level1") && !d.dir.contains("level2"));
-    let has_level2 = results.iter().any(|d| d.dir.contains("level2"));
+        .any(|d| d.dir.to_string_lossy().contains("level2"));
 
     assert!(has_level1, "Should have level1 directory in results");
     assert!(has_level2, "Should have level2 directory in results");
 
     // Verify level1 directory contains level2 as a subdirectory
-    let level1_dir = results
-        .iter()
-        .find(|d| d.dir.contains("level1") && !d.dir.contains("level2"));
+    let level1_dir = results.iter().find(|d| {
+        d.dir.to_string_lossy().contains("level1") && !d.dir.to_string_lossy().contains("level2")
+    });
     if let Some(level1) = level1_dir {
         let has_level2_entry = level1.entries.iter().any(|e| {
             if let Entry::Directory { name } = e {
@@ -135,7 +151,9 @@ fn test_tree_structure_integrity() -> Result<()> {
     }
 
     // Verify text_files directory contains the correct files
-    let text_files_dir = results.iter().find(|d| d.dir.contains("text_files"));
+    let text_files_dir = results
+        .iter()
+        .find(|d| d.dir.to_string_lossy().contains("text_files"));
     if let Some(dir) = text_files_dir {
         // Should contain sample.txt, markdown.md, and config.toml
         let file_names: Vec<_> = dir
@@ -143,7 +161,7 @@ fn test_tree_structure_integrity() -> Result<()> {
             .iter()
             .filter_map(|e| {
                 if let Entry::File { name } = e {
-                    Some(name.as_str())
+                    name.to_str()
                 } else {
                     None
                 }
@@ -166,3 +184,285 @@ fn test_tree_structure_integrity() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_tree_max_file_size() -> Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let root = temp_dir.path();
+    std::fs::create_dir(root.join("generated"))?;
+    std::fs::write(root.join("small.txt"), "small")?;
+    std::fs::write(root.join("generated/large.json"), "x".repeat(4096))?;
+
+    let options = TreeOptions {
+        respect_gitignore: false, // No gitignore in temp dir
+        max_file_size: Some(1024),
+        ..TreeOptions::default()
+    };
+    let results = generate_tree(root, &options)?;
+
+    let mut file_names = Vec::new();
+    let mut dir_names = Vec::new();
+    for entry in results.iter().flat_map(|tree| tree.entries.iter()) {
+        match entry {
+            Entry::File { name } => file_names.push(name.to_string_lossy()),
+            Entry::Directory { name } => dir_names.push(name.to_string_lossy()),
+        }
+    }
+
+    assert_eq!(file_names, vec!["small.txt"]);
+    // Directories are still listed even if all their files are omitted
+    assert_eq!(dir_names, vec!["generated"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_tree_entry_sort() -> Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let root = temp_dir.path();
+    for name in ["file10.txt", "file2.md", "file1.txt", "File3.rs", "notes"] {
+        std::fs::write(root.join(name), name)?;
+    }
+    std::fs::create_dir(root.join("dir20"))?;
+    std::fs::write(root.join("dir20/inner.txt"), "inner")?;
+
+    let root_entries = |entry_sort: EntrySort| -> Result<Vec<String>> {
+        let options = TreeOptions {
+            respect_gitignore: false, // No gitignore in temp dir
+            entry_sort,
+            ..TreeOptions::default()
+        };
+        let results = generate_tree(root, &options)?;
+        let root_tree = results
+            .iter()
+            .find(|tree| Path::new(&tree.dir) == root)
+            .expect("root directory should be in the tree");
+        Ok(root_tree
+            .entries
+            .iter()
+            .map(|entry| entry.name().to_string_lossy().into_owned())
+            .collect())
+    };
+
+    // Natural order compares numbers by value
+    assert_eq!(
+        root_entries(EntrySort::Natural)?,
+        vec![
+            "File3.rs",
+            "dir20",
+            "file1.txt",
+            "file2.md",
+            "file10.txt",
+            "notes"
+        ]
+    );
+    // Name order compares characters
+    assert_eq!(
+        root_entries(EntrySort::Name)?,
+        vec![
+            "File3.rs",
+            "dir20",
+            "file1.txt",
+            "file10.txt",
+            "file2.md",
+            "notes"
+        ]
+    );
+    // Extension order lists entries without an extension first
+    assert_eq!(
+        root_entries(EntrySort::Extension)?,
+        vec![
+            "dir20",
+            "notes",
+            "file2.md",
+            "File3.rs",
+            "file1.txt",
+            "file10.txt"
+        ]
+    );
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_tree_non_utf8_names() -> Result<()> {
+    use lumin::traverse::{TraverseOptions, TraverseResult, traverse_directory};
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let temp_dir = tempfile::TempDir::new()?;
+    let root = temp_dir.path();
+    let dir_name = OsStr::from_bytes(b"caf\xe9");
+    let file_name = OsStr::from_bytes(b"r\xe9sum\xe9.txt");
+    std::fs::create_dir(root.join(dir_name))?;
+    std::fs::write(root.join(dir_name).join(file_name), "content")?;
+    std::fs::write(root.join("plain.txt"), "content")?;
+
+    let options = TreeOptions {
+        respect_gitignore: false, // No gitignore in temp dir
+        omit_path_prefix: Some(root.to_path_buf()),
+        ..TreeOptions::default()
+    };
+    let results = generate_tree(root, &options)?;
+    let sub_tree = results
+        .iter()
+        .find(|tree| tree.dir.as_os_str() == dir_name)
+        .expect("non-UTF-8 directory should be in the tree");
+    assert_eq!(sub_tree.entries[0].name(), file_name);
+
+    // Non-UTF-8 names are serialized as raw bytes and restored without loss
+    let json = serde_json::to_value(&results)?;
+    let restored: Vec<lumin::tree::DirectoryTree> = serde_json::from_value(json.clone())?;
+    let restored_sub_tree = restored
+        .iter()
+        .find(|tree| tree.dir.as_os_str() == dir_name)
+        .expect("non-UTF-8 directory should survive serialization");
+    assert_eq!(restored_sub_tree.entries[0].name(), file_name);
+    assert!(
+        json.to_string()
+            .contains(r#""name":[114,233,115,117,109,233,46,116,120,116]"#)
+    );
+
+    // Valid UTF-8 names are still serialized as strings
+    assert!(json.to_string().contains(r#""name":"plain.txt""#));
+
+    // Traverse results keep the raw path through serialization too
+    let traverse_options = TraverseOptions {
+        respect_gitignore: false,
+        ..TraverseOptions::default()
+    };
+    let files = traverse_directory(&root.join(dir_name), &traverse_options)?;
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].file_path, root.join(dir_name).join(file_name));
+    let restored: TraverseResult = serde_json::from_value(serde_json::to_value(&files[0])?)?;
+    assert_eq!(restored.file_path, files[0].file_path);
+
+    Ok(())
+}
+
+#[test]
+fn test_tree_max_directories() -> Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let root = temp_dir.path();
+    for i in 0..20 {
+        let dir = root.join(format!("dir{}/nested", i));
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(dir.join("file.txt"), "content")?;
+    }
+
+    let options = TreeOptions {
+        respect_gitignore: false, // No gitignore in temp dir
+        ..TreeOptions::default()
+    };
+    let report = generate_tree_report(root, &options)?;
+    assert!(!report.truncated);
+    // The root and the 20 top-level directories with their nested directories
+    assert_eq!(report.trees.len(), 41);
+
+    let options = TreeOptions {
+        max_directories: Some(5),
+        ..options
+    };
+    let report = generate_tree_report(root, &options)?;
+    assert!(report.truncated);
+    assert!(report.trees.len() <= 5);
+
+    // All top-level directories are still listed as entries of the root
+    let root_tree = report
+        .trees
+        .iter()
+        .find(|tree| tree.dir == root)
+        .expect("root directory should be in the tree");
+    assert_eq!(root_tree.entries.len(), 20);
+
+    // generate_tree applies the same limit
+    assert_eq!(generate_tree(root, &options)?.len(), report.trees.len());
+
+    Ok(())
+}
+
+#[test]
+fn test_tree_list_files() -> Result<()> {
+    let temp_dir = tempfile::TempDir::new()?;
+    let root = temp_dir.path();
+    std::fs::create_dir_all(root.join("src/nested"))?;
+    std::fs::create_dir(root.join("empty"))?;
+    std::fs::write(root.join("README.md"), "readme")?;
+    std::fs::write(root.join("src/lib.rs"), "lib")?;
+    std::fs::write(root.join("src/nested/data.bin"), [0u8, 1, 2, 3])?;
+
+    let options = TreeOptions {
+        respect_gitignore: false, // No gitignore in temp dir
+        omit_path_prefix: Some(root.to_path_buf()),
+        ..TreeOptions::default()
+    };
+
+    // The listed files are exactly the file entries of the tree
+    let mut tree_files: Vec<std::path::PathBuf> = generate_tree(root, &options)?
+        .iter()
+        .flat_map(|tree| {
+            tree.entries.iter().filter_map(|entry| match entry {
+                Entry::File { name } => Some(tree.dir.join(name)),
+                Entry::Directory { .. } => None,
+            })
+        })
+        .collect();
+    tree_files.sort();
+    let mut files = list_files(root, &options)?;
+    assert_eq!(
+        files,
+        vec![
+            Path::new("README.md"),
+            Path::new("src/lib.rs"),
+            Path::new("src/nested/data.bin"),
+        ]
+    );
+    files.sort();
+    assert_eq!(files, tree_files);
+
+    Ok(())
+}
+
+#[test]
+fn test_tree_to_dot() -> Result<()> {
+    let directory = Path::new("tests/fixtures/nested");
+    let trees = generate_tree(directory, &TreeOptions::default())?;
+    let dot = to_dot(&trees);
+
+    assert!(dot.starts_with("digraph tree {"));
+    assert!(dot.trim_end().ends_with('}'));
+    for edge in [
+        r#""tests/fixtures/nested" -> "tests/fixtures/nested/file.rs";"#,
+        r#""tests/fixtures/nested" -> "tests/fixtures/nested/level1";"#,
+        r#""tests/fixtures/nested/level1" -> "tests/fixtures/nested/level1/level1.txt";"#,
+        r#""tests/fixtures/nested/level1" -> "tests/fixtures/nested/level1/level2";"#,
+        r#""tests/fixtures/nested/level1/level2" -> "tests/fixtures/nested/level1/level2/level2.txt";"#,
+    ] {
+        assert!(dot.contains(edge), "missing edge {} in:\n{}", edge, dot);
+    }
+    assert_eq!(dot.matches(" -> ").count(), 5);
+
+    // Each node is declared once, labeled with its name
+    assert_eq!(
+        dot.matches(r#""tests/fixtures/nested/level1" [label="level1", shape=folder];"#)
+            .count(),
+        1
+    );
+
+    // Special characters in names are escaped
+    let trees = vec![DirectoryTree {
+        dir: "root".into(),
+        entries: vec![Entry::File {
+            name: OsString::from("say \"hi\"\\.txt"),
+        }],
+    }];
+    let dot = to_dot(&trees);
+    assert!(
+        dot.contains(r#""root" -> "root/say \"hi\"\\.txt";"#),
+        "{}",
+        dot
+    );
+
+    Ok(())
+}
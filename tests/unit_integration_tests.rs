@@ -1,7 +1,7 @@
 use anyhow::Result;
-use lumin::search::{SearchOptions, search_files};
-use lumin::traverse::{TraverseOptions, traverse_directory};
-use lumin::tree::{TreeOptions, generate_tree};
+use lumin::search::{ContentMode, ReadStrategy, SearchOptions, SearchSort, search_files};
+use lumin::traverse::{PatternMode, TraverseOptions, TraverseSort, traverse_directory};
+use lumin::tree::{EntrySort, TreeOptions, generate_tree};
 use lumin::view::{ViewOptions, view_file};
 use std::path::Path;
 
@@ -19,7 +19,30 @@ fn test_full_workflow() -> Result<()> {
         only_text_files: true,
         pattern: Some("**.txt".to_string()),
         depth: Some(20),
+        min_depth: None,
         omit_path_prefix: None,
+        include_metadata: false,
+        resolve_target: false,
+        sort: TraverseSort::Path,
+        modified_after: None,
+        modified_before: None,
+        cancel: None,
+        include_inode: false,
+        natural_sort: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        one_file_system: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        glob_against_absolute: false,
+        include_dirs: false,
+        tilde_home: false,
+        with_git_status: false,
+        clean_paths: false,
+        pattern_mode: PatternMode::Auto,
+        include_mode: false,
+        include_glob: None,
+        exclude_glob: None,
     };
 
     let traverse_results = traverse_directory(directory, &traverse_options)?;
@@ -35,10 +58,47 @@ fn test_full_workflow() -> Result<()> {
         omit_path_prefix: None,
         match_content_omit_num: None,
         depth: Some(20),
+        min_depth: None,
         before_context: 0,
         after_context: 0,
         skip: None,
         take: None,
+        treat_all_as_text: false,
+        exclude_dir_names: Vec::new(),
+        min_matches_per_line: None,
+        enclosing_symbol: false,
+        sort_by_relevance: false,
+        ignore_root_gitignore: false,
+        highlight_markers: None,
+        read_strategy: ReadStrategy::Buffered,
+        line_from: None,
+        line_to: None,
+        line_terminator: b'\n',
+        auto_repo_relative: false,
+        regex_size_limit: None,
+        dfa_size_limit: None,
+        timeout: None,
+        cancel: None,
+        content: ContentMode::Full,
+        natural_sort: false,
+        with_blame: false,
+        skip_vendored: false,
+        auto_omit_common_prefix: false,
+        snippet_width: None,
+        git_tracked_only: false,
+        search_compressed: false,
+        utf16_offsets: false,
+        tilde_home: false,
+        sort: SearchSort::PathAndLine,
+        clean_paths: false,
+        header_lines: None,
+        context_merge_gap: 0,
+        unique_content: false,
+        extract_pdf_text: false,
+        dedent_context: false,
+        collect_extensions: false,
+        first_match_only: false,
+        threads: None,
     };
 
     let search_results = search_files(search_pattern, directory, &search_options)?;
@@ -50,6 +110,12 @@ fn test_full_workflow() -> Result<()> {
         respect_gitignore: true,
         depth: Some(20),
         omit_path_prefix: None,
+        max_file_size: None,
+        entry_sort: EntrySort::Name,
+        skip_vendored: false,
+        max_directories: None,
+        include_glob: None,
+        exclude_glob: None,
     };
 
     let tree_results = generate_tree(directory, &tree_options)?;
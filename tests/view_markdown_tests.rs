@@ -0,0 +1,77 @@
+#![cfg(feature = "markdown")]
+
+use anyhow::Result;
+use lumin::view::{ViewOptions, render_markdown};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// Test rendering the Markdown fixture as plain text
+#[test]
+fn test_render_markdown_fixture() -> Result<()> {
+    let text = render_markdown(
+        Path::new("tests/fixtures/text_files/markdown.md"),
+        &ViewOptions::default(),
+    )?;
+
+    assert_eq!(
+        text,
+        "Sample Markdown\n\
+         ===============\n\
+         \n\
+         Section 1\n\
+         ---------\n\
+         \n\
+         This is a sample markdown file.\n\
+         \n\
+         Section 2\n\
+         ---------\n\
+         \n\
+         - List item 1\n\
+         - List item 2\n\
+         - List item with a PATTERN\n\
+         \n\
+         Code Sample\n\
+         -----------\n\
+         \n\
+         \x20   fn main() {\n\
+         \x20       println!(\"Hello, world!\");\n\
+         \x20       // This is a pattern in code\n\
+         \x20   }\n"
+    );
+    // The headings survive without their markers
+    assert!(!text.lines().any(|line| line.starts_with('#')));
+
+    Ok(())
+}
+
+/// Test rendering nested lists, block quotes, links and tables
+#[test]
+fn test_render_markdown_structure() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let path = temp_dir.path().join("doc.md");
+    fs::write(
+        &path,
+        "### Steps\n\n\
+         1. Install\n   - with *cargo*\n2. Run `lumin`\n\n\
+         > Read the [guide](https://example.com/guide).\n\n\
+         | Option | Default |\n|---|---|\n| depth | 20 |\n",
+    )?;
+
+    let text = render_markdown(&path, &ViewOptions::default())?;
+    assert_eq!(
+        text,
+        "Steps\n\
+         \n\
+         1. Install\n\
+         \x20  - with cargo\n\
+         2. Run lumin\n\
+         \n\
+         > Read the guide (https://example.com/guide).\n\
+         \n\
+         Option | Default\n\
+         depth | 20\n"
+    );
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use lumin::view::{FileContents, ViewOptions, view_file};
+use lumin::view::{DirMode, FileContents, ViewOptions, view_file};
 use serial_test::serial;
 use std::path::Path;
 
@@ -90,7 +90,9 @@ mod view_tests {
 
         // Check binary content based on enum variant
         match &result.contents {
-            FileContents::Binary { message, metadata } => {
+            FileContents::Binary {
+                message, metadata, ..
+            } => {
                 assert!(message.contains("Binary file detected"));
                 assert!(metadata.binary);
                 assert!(metadata.size_bytes > 0);
@@ -112,6 +114,15 @@ mod view_tests {
             max_size: Some(1024), // 1KB limit
             line_from: None,
             line_to: None,
+            preview: None,
+            lossy: false,
+            text_extensions: Vec::new(),
+            binary_extensions: Vec::new(),
+            byte_offset: None,
+            context_lines: None,
+            on_directory: DirMode::Error,
+            sanitize_control: false,
+            binary_as_base64: false,
         };
 
         // Should return an error due to size limit
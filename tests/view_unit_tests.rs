@@ -1,5 +1,9 @@
 use anyhow::Result;
-use lumin::view::{FileContents, ViewOptions, view_file};
+use lumin::search::SearchOptions;
+use lumin::view::{
+    DirMode, FileContents, LineContent, TokenKind, ViewOptions, detect_language, read_text,
+    tokenize, view_file, view_file_html, view_pages, view_with_highlights, write_file,
+};
 use std::path::Path;
 
 #[test]
@@ -44,7 +48,9 @@ fn test_view_binary_file() -> Result<()> {
 
     // Verify content type is Binary
     match result.contents {
-        FileContents::Binary { message, metadata } => {
+        FileContents::Binary {
+            message, metadata, ..
+        } => {
             // Verify binary message
             assert!(message.contains("Binary file detected"));
 
@@ -74,7 +80,9 @@ fn test_view_image_file() -> Result<()> {
     // Some systems might detect the file differently based on infer library behavior
     // So we'll accept either Image or Binary for this test
     match result.contents {
-        FileContents::Image { message, metadata } => {
+        FileContents::Image {
+            message, metadata, ..
+        } => {
             // Verify image message
             assert!(message.contains("Image file detected"));
 
@@ -83,7 +91,9 @@ fn test_view_image_file() -> Result<()> {
             assert!(metadata.size_bytes > 0);
             assert_eq!(metadata.media_type, "image");
         }
-        FileContents::Binary { message, metadata } => {
+        FileContents::Binary {
+            message, metadata, ..
+        } => {
             // If detected as binary that's also fine
             assert!(message.contains("Binary file detected"));
 
@@ -106,6 +116,15 @@ fn test_view_with_size_limit() -> Result<()> {
         max_size: Some(tiny_limit),
         line_from: None,
         line_to: None,
+        preview: None,
+        lossy: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        byte_offset: None,
+        context_lines: None,
+        on_directory: DirMode::Error,
+        sanitize_control: false,
+        binary_as_base64: false,
     };
 
     // Should fail because file is larger than the limit
@@ -195,6 +214,15 @@ fn test_view_with_line_filtering() -> Result<()> {
         max_size: None,
         line_from: Some(2), // Start from line 2
         line_to: Some(4),   // End at line 4
+        preview: None,
+        lossy: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        byte_offset: None,
+        context_lines: None,
+        on_directory: DirMode::Error,
+        sanitize_control: false,
+        binary_as_base64: false,
     };
 
     // View the file
@@ -227,6 +255,15 @@ fn test_view_with_out_of_range_line_filtering() -> Result<()> {
         max_size: None,
         line_from: Some(100),
         line_to: Some(200),
+        preview: None,
+        lossy: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        byte_offset: None,
+        context_lines: None,
+        on_directory: DirMode::Error,
+        sanitize_control: false,
+        binary_as_base64: false,
     };
 
     // Should not error, just return empty content
@@ -249,6 +286,15 @@ fn test_view_with_out_of_range_line_filtering() -> Result<()> {
         max_size: None,
         line_from: Some(5),
         line_to: Some(10),
+        preview: None,
+        lossy: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        byte_offset: None,
+        context_lines: None,
+        on_directory: DirMode::Error,
+        sanitize_control: false,
+        binary_as_base64: false,
     };
 
     let view_result = view_file(file_path, &options)?;
@@ -271,6 +317,15 @@ fn test_view_with_out_of_range_line_filtering() -> Result<()> {
         max_size: None,
         line_from: Some(4),
         line_to: Some(2),
+        preview: None,
+        lossy: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        byte_offset: None,
+        context_lines: None,
+        on_directory: DirMode::Error,
+        sanitize_control: false,
+        binary_as_base64: false,
     };
 
     let view_result = view_file(file_path, &options)?;
@@ -312,6 +367,15 @@ fn test_total_line_num_field() -> Result<()> {
         max_size: None,
         line_from: Some(2),
         line_to: Some(4),
+        preview: None,
+        lossy: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        byte_offset: None,
+        context_lines: None,
+        on_directory: DirMode::Error,
+        sanitize_control: false,
+        binary_as_base64: false,
     };
 
     let filtered_result = view_file(text_file_path, &filtered_options)?;
@@ -349,6 +413,15 @@ fn test_no_trailing_newlines() -> Result<()> {
         max_size: None,
         line_from: Some(2),
         line_to: Some(4),
+        preview: None,
+        lossy: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        byte_offset: None,
+        context_lines: None,
+        on_directory: DirMode::Error,
+        sanitize_control: false,
+        binary_as_base64: false,
     };
 
     let filtered_result = view_file(text_file_path, &filtered_options)?;
@@ -383,6 +456,15 @@ fn test_size_check_with_line_filters() -> Result<()> {
         max_size: Some(10), // 10 bytes (file is larger)
         line_from: None,
         line_to: None,
+        preview: None,
+        lossy: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        byte_offset: None,
+        context_lines: None,
+        on_directory: DirMode::Error,
+        sanitize_control: false,
+        binary_as_base64: false,
     };
 
     // This should fail - entire file is too large
@@ -395,6 +477,15 @@ fn test_size_check_with_line_filters() -> Result<()> {
         max_size: Some(10), // Same tiny limit
         line_from: Some(1), // Just get the first line
         line_to: Some(1),
+        preview: None,
+        lossy: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        byte_offset: None,
+        context_lines: None,
+        on_directory: DirMode::Error,
+        sanitize_control: false,
+        binary_as_base64: false,
     };
 
     // This should work - we're only loading a small part of the file
@@ -430,6 +521,15 @@ fn test_size_check_with_line_filters() -> Result<()> {
         max_size: Some(6), // "Line1\n" is 6 bytes
         line_from: Some(1),
         line_to: Some(1),
+        preview: None,
+        lossy: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        byte_offset: None,
+        context_lines: None,
+        on_directory: DirMode::Error,
+        sanitize_control: false,
+        binary_as_base64: false,
     };
 
     let tiny_result = view_file(&test_file_path, &tiny_options)?;
@@ -448,6 +548,15 @@ fn test_size_check_with_line_filters() -> Result<()> {
         max_size: Some(6), // Only enough for Line1
         line_from: Some(1),
         line_to: Some(2), // But we want two lines
+        preview: None,
+        lossy: false,
+        text_extensions: Vec::new(),
+        binary_extensions: Vec::new(),
+        byte_offset: None,
+        context_lines: None,
+        on_directory: DirMode::Error,
+        sanitize_control: false,
+        binary_as_base64: false,
     };
 
     let too_small_result = view_file(&test_file_path, &too_small_options);
@@ -458,3 +567,631 @@ fn test_size_check_with_line_filters() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_view_preview_first_lines() -> Result<()> {
+    let file_path = Path::new("tests/fixtures/text_files/sample.txt");
+    let options = ViewOptions {
+        preview: Some(3),
+        ..ViewOptions::default()
+    };
+
+    let result = view_file(file_path, &options)?;
+
+    // The 6-line fixture continues beyond the 3 previewed lines
+    assert!(result.has_more);
+    assert_eq!(result.total_line_num, None);
+
+    match &result.contents {
+        FileContents::Text { content, metadata } => {
+            assert_eq!(content.line_contents.len(), 3);
+            assert_eq!(metadata.line_count, 3);
+            for (i, line) in content.line_contents.iter().enumerate() {
+                assert_eq!(line.line_number, i + 1);
+            }
+            assert!(content.contains("This is a sample text file"));
+        }
+        _ => panic!("Expected text content"),
+    }
+
+    // Previewing more lines than the file has reads the whole file
+    let full_options = ViewOptions {
+        preview: Some(10),
+        ..ViewOptions::default()
+    };
+    let full_result = view_file(file_path, &full_options)?;
+    assert!(!full_result.has_more);
+    assert_eq!(full_result.total_line_num, Some(6));
+
+    Ok(())
+}
+
+#[test]
+fn test_view_lossy_invalid_utf8() -> Result<()> {
+    let test_dir = tempfile::tempdir()?;
+    let test_file_path = test_dir.path().join("mostly_text.txt");
+    std::fs::write(
+        &test_file_path,
+        b"First line\nSecond \xff line\nThird line\n",
+    )?;
+
+    // Without lossy decoding, the file is reported as binary
+    let strict_result = view_file(&test_file_path, &ViewOptions::default())?;
+    assert!(matches!(
+        strict_result.contents,
+        FileContents::Binary { .. }
+    ));
+
+    let options = ViewOptions {
+        lossy: true,
+        ..ViewOptions::default()
+    };
+    let result = view_file(&test_file_path, &options)?;
+
+    match &result.contents {
+        FileContents::Text { content, metadata } => {
+            assert!(metadata.had_invalid_utf8);
+            assert_eq!(metadata.line_count, 3);
+            assert_eq!(content.line_contents[1].line, "Second \u{FFFD} line");
+        }
+        _ => panic!("Expected text content"),
+    }
+
+    // Valid files are not flagged
+    let valid_result = view_file(Path::new("tests/fixtures/text_files/sample.txt"), &options)?;
+    match &valid_result.contents {
+        FileContents::Text { metadata, .. } => assert!(!metadata.had_invalid_utf8),
+        _ => panic!("Expected text content"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_view_byte_offset() -> Result<()> {
+    let test_dir = tempfile::tempdir()?;
+    let file_path = test_dir.path().join("lines.txt");
+    // Each line is 8 bytes including the newline
+    let content = (1..=10)
+        .map(|i| format!("line {:02}\n", i))
+        .collect::<Vec<_>>()
+        .concat();
+    std::fs::write(&file_path, &content)?;
+
+    let view_around =
+        |byte_offset: usize, context_lines: Option<usize>| -> Result<Vec<(usize, bool)>> {
+            let options = ViewOptions {
+                byte_offset: Some(byte_offset),
+                context_lines,
+                line_from: Some(1),
+                ..ViewOptions::default()
+            };
+            match view_file(&file_path, &options)?.contents {
+                FileContents::Text { content, .. } => Ok(content
+                    .line_contents
+                    .iter()
+                    .map(|line| (line.line_number, line.is_target))
+                    .collect()),
+                _ => panic!("Expected text contents"),
+            }
+        };
+
+    // Byte 37 is in the middle of line 5 ("line 05" starts at byte 32)
+    assert_eq!(&content[37..39], "05");
+    assert_eq!(
+        view_around(37, Some(2))?,
+        vec![(3, false), (4, false), (5, true), (6, false), (7, false)]
+    );
+    assert_eq!(view_around(37, None)?, vec![(5, true)]);
+
+    // The newline belongs to the line it ends; the window is clipped at the file bounds
+    assert_eq!(view_around(7, Some(1))?, vec![(1, true), (2, false)]);
+    assert_eq!(view_around(1000, Some(1))?, vec![(9, false), (10, true)]);
+
+    Ok(())
+}
+
+#[test]
+fn test_view_extensionless_script_shebang() -> Result<()> {
+    let test_dir = tempfile::tempdir()?;
+    let script_path = test_dir.path().join("run_checks");
+    std::fs::write(&script_path, "#!/usr/bin/env python3\nprint('checking')\n")?;
+
+    let result = view_file(&script_path, &ViewOptions::default())?;
+    assert_eq!(result.file_type, "text/x-python");
+    assert!(matches!(result.contents, FileContents::Text { .. }));
+
+    Ok(())
+}
+
+#[test]
+fn test_detect_language() -> Result<()> {
+    let test_dir = tempfile::tempdir()?;
+
+    // Extensions
+    let rust_path = test_dir.path().join("lib.RS");
+    std::fs::write(&rust_path, "pub fn lib() {}\n")?;
+    assert_eq!(detect_language(&rust_path, None).as_deref(), Some("rust"));
+    let markdown_path = test_dir.path().join("README.md");
+    std::fs::write(&markdown_path, "# Title\n\nSome text.\n")?;
+    assert_eq!(
+        detect_language(&markdown_path, None).as_deref(),
+        Some("markdown")
+    );
+
+    // Extensionless scripts, with the shebang read from the file or from a given sample
+    let script_path = test_dir.path().join("deploy");
+    std::fs::write(&script_path, "#!/bin/bash\necho deploying\n")?;
+    assert_eq!(detect_language(&script_path, None).as_deref(), Some("bash"));
+    assert_eq!(
+        detect_language(&script_path, Some(b"#!/usr/bin/env -S node --harmony\n")).as_deref(),
+        Some("javascript")
+    );
+
+    // Content heuristics and unknown files
+    let page_path = test_dir.path().join("index");
+    std::fs::write(&page_path, "\n<!DOCTYPE html>\n<html></html>\n")?;
+    assert_eq!(detect_language(&page_path, None).as_deref(), Some("html"));
+    let unknown_path = test_dir.path().join("notes");
+    std::fs::write(&unknown_path, "just some notes\n")?;
+    assert_eq!(detect_language(&unknown_path, None), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_view_pages() -> Result<()> {
+    let test_dir = tempfile::tempdir()?;
+    let test_file_path = test_dir.path().join("ten_lines.txt");
+    let lines: Vec<String> = (1..=10).map(|i| format!("Line {}", i)).collect();
+    std::fs::write(&test_file_path, lines.join("\n") + "\n")?;
+
+    let pages =
+        view_pages(&test_file_path, 4, &ViewOptions::default())?.collect::<Result<Vec<_>>>()?;
+
+    let page_sizes: Vec<usize> = pages.iter().map(|page| page.len()).collect();
+    assert_eq!(page_sizes, vec![4, 4, 2]);
+    assert_eq!(pages[1][0].line_number, 5);
+    assert_eq!(pages[1][0].line, "Line 5");
+    assert_eq!(pages[2][1].line_number, 10);
+
+    // Pages cover only the selected line range
+    let options = ViewOptions {
+        line_from: Some(3),
+        line_to: Some(8),
+        ..ViewOptions::default()
+    };
+    let pages = view_pages(&test_file_path, 4, &options)?.collect::<Result<Vec<_>>>()?;
+    let line_numbers: Vec<Vec<usize>> = pages
+        .iter()
+        .map(|page| page.iter().map(|line| line.line_number).collect())
+        .collect();
+    assert_eq!(line_numbers, vec![vec![3, 4, 5, 6], vec![7, 8]]);
+
+    // A byte offset selects its line and context lines, over the line range
+    let options = ViewOptions {
+        byte_offset: Some(15), // "Line 3"
+        context_lines: Some(1),
+        ..options
+    };
+    let pages = view_pages(&test_file_path, 4, &options)?.collect::<Result<Vec<_>>>()?;
+    let lines: Vec<(usize, bool)> = pages[0]
+        .iter()
+        .map(|line| (line.line_number, line.is_target))
+        .collect();
+    assert_eq!(lines, vec![(2, false), (3, true), (4, false)]);
+
+    // Offsets past the end of the file select the last line, as in view_file
+    let options = ViewOptions {
+        byte_offset: Some(1000),
+        ..options
+    };
+    let pages = view_pages(&test_file_path, 4, &options)?.collect::<Result<Vec<_>>>()?;
+    let FileContents::Text { content, .. } = view_file(&test_file_path, &options)?.contents else {
+        panic!("expected text contents");
+    };
+    let numbers = |lines: &[LineContent]| -> Vec<(usize, bool)> {
+        lines
+            .iter()
+            .map(|line| (line.line_number, line.is_target))
+            .collect()
+    };
+    assert_eq!(numbers(&pages[0]), numbers(&content.line_contents));
+    assert_eq!(numbers(&pages[0]), vec![(9, false), (10, true)]);
+
+    // A page size of 0 is rejected
+    assert!(view_pages(&test_file_path, 0, &ViewOptions::default()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_view_file_html() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let test_file_path = temp_dir.path().join("source.rs");
+    std::fs::write(
+        &test_file_path,
+        "fn lt(a: i32, b: i32) -> bool {\n    a < b && \"x\" != 'y'.to_string()\n}\n",
+    )?;
+
+    let html = view_file_html(&test_file_path, &ViewOptions::default())?;
+    assert!(html.starts_with("<table class=\"lumin-view\">"));
+    assert!(html.contains(
+        "<tr id=\"L2\"><td class=\"lumin-line-number\">2</td><td class=\"lumin-line\">\
+         <pre>    a &lt; b &amp;&amp; &quot;x&quot; != &#39;y&#39;.to_string()</pre></td></tr>"
+    ));
+    assert!(html.contains("-&gt; bool"));
+    assert!(!html.contains("a < b"));
+    assert_eq!(html.matches("<tr ").count(), 3);
+
+    // The gutter shows the original line numbers of the selected range
+    let options = ViewOptions {
+        line_from: Some(3),
+        ..ViewOptions::default()
+    };
+    let html = view_file_html(&test_file_path, &options)?;
+    assert_eq!(html.matches("<tr ").count(), 1);
+    assert!(html.contains("<td class=\"lumin-line-number\">3</td>"));
+
+    Ok(())
+}
+
+#[test]
+fn test_view_with_highlights() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let test_file_path = temp_dir.path().join("notes.txt");
+    std::fs::write(
+        &test_file_path,
+        "first line\nTODO: fix todo list\nlast line\n",
+    )?;
+
+    let view = view_with_highlights(
+        &test_file_path,
+        "todo",
+        &ViewOptions::default(),
+        &SearchOptions::default(),
+    )?;
+    let FileContents::Text { content, .. } = view.contents else {
+        panic!("expected text contents");
+    };
+    let highlights: Vec<&Vec<(usize, usize)>> = content
+        .line_contents
+        .iter()
+        .map(|line| &line.highlights)
+        .collect();
+    assert_eq!(highlights, vec![&vec![], &vec![(0, 4), (10, 14)], &vec![]]);
+
+    // Case sensitive matching follows the search options
+    let options = SearchOptions {
+        case_sensitive: true,
+        ..SearchOptions::default()
+    };
+    let view = view_with_highlights(&test_file_path, "todo", &ViewOptions::default(), &options)?;
+    let FileContents::Text { content, .. } = view.contents else {
+        panic!("expected text contents");
+    };
+    assert_eq!(content.line_contents[1].highlights, vec![(10, 14)]);
+
+    // Plain views have no highlights
+    let view = view_file(&test_file_path, &ViewOptions::default())?;
+    let FileContents::Text { content, .. } = view.contents else {
+        panic!("expected text contents");
+    };
+    assert!(
+        content
+            .line_contents
+            .iter()
+            .all(|line| line.highlights.is_empty())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_view_extension_overrides() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    // A text file that content detection classifies as a PDF document
+    let pdf_path = temp_dir.path().join("notes.pdf");
+    std::fs::write(&pdf_path, "%PDF-1.4 header notes\nsecond line\n")?;
+    let txt_path = temp_dir.path().join("table.txt");
+    std::fs::write(&txt_path, "plain text\n")?;
+
+    let view = view_file(&pdf_path, &ViewOptions::default())?;
+    assert!(matches!(view.contents, FileContents::Binary { .. }));
+
+    let options = ViewOptions {
+        text_extensions: vec!["PDF".to_string()],
+        binary_extensions: vec![".txt".to_string()],
+        ..ViewOptions::default()
+    };
+    let view = view_file(&pdf_path, &options)?;
+    assert_eq!(view.file_type, "text/plain");
+    let FileContents::Text { content, .. } = view.contents else {
+        panic!("expected text contents");
+    };
+    assert_eq!(content.line_contents[1].line, "second line");
+
+    // Binary extensions are not inspected
+    let view = view_file(&txt_path, &options)?;
+    assert!(matches!(view.contents, FileContents::Binary { .. }));
+
+    Ok(())
+}
+
+#[test]
+fn test_write_file() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let test_file_path = temp_dir.path().join("lines.txt");
+    std::fs::write(&test_file_path, "alpha\nbeta\r\ngamma\ndelta\n")?;
+    let path = test_file_path.display();
+
+    let mut output = Vec::new();
+    write_file(&test_file_path, &ViewOptions::default(), &mut output)?;
+    assert_eq!(
+        String::from_utf8(output)?,
+        format!("{path}:1:alpha\n{path}:2:beta\n{path}:3:gamma\n{path}:4:delta\n")
+    );
+
+    // The output matches the lines returned by view_file for the same options
+    let options = ViewOptions {
+        line_from: Some(2),
+        preview: Some(2),
+        ..ViewOptions::default()
+    };
+    let mut output = Vec::new();
+    write_file(&test_file_path, &options, &mut output)?;
+    let FileContents::Text { content, .. } = view_file(&test_file_path, &options)?.contents else {
+        panic!("expected text contents");
+    };
+    let expected: Vec<String> = content
+        .line_contents
+        .iter()
+        .map(|line| format!("{path}:{}:{}\n", line.line_number, line.line))
+        .collect();
+    let expected = expected.concat();
+    assert_eq!(String::from_utf8(output)?, expected);
+    assert_eq!(expected, format!("{path}:2:beta\n{path}:3:gamma\n"));
+
+    // A byte offset selects its line and context lines, ignoring the preview
+    let options = ViewOptions {
+        byte_offset: Some(13), // "gamma"
+        context_lines: Some(1),
+        ..options
+    };
+    let mut output = Vec::new();
+    write_file(&test_file_path, &options, &mut output)?;
+    assert_eq!(
+        String::from_utf8(output)?,
+        format!("{path}:2:beta\n{path}:3:gamma\n{path}:4:delta\n")
+    );
+
+    // Whole files are limited by max_size, unlike selected lines
+    let options = ViewOptions {
+        max_size: Some(8),
+        ..ViewOptions::default()
+    };
+    assert!(write_file(&test_file_path, &options, &mut Vec::new()).is_err());
+    let options = ViewOptions {
+        line_to: Some(4),
+        ..options
+    };
+    assert!(write_file(&test_file_path, &options, &mut Vec::new()).is_ok());
+
+    // Binary files are described in a single line
+    let binary_path = temp_dir.path().join("data.bin");
+    std::fs::write(&binary_path, [0u8, 1, 2, 3, 0xff, 0xfe])?;
+    let mut output = Vec::new();
+    write_file(&binary_path, &ViewOptions::default(), &mut output)?;
+    let output = String::from_utf8(output)?;
+    assert!(output.starts_with(&format!("{}: Binary file detected", binary_path.display())));
+    assert_eq!(output.lines().count(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_view_directory_list_entries() -> Result<()> {
+    let dir_path = Path::new("tests/fixtures");
+
+    // Directories are rejected by default
+    let error = view_file(dir_path, &ViewOptions::default()).unwrap_err();
+    assert!(error.to_string().contains("Not a file"));
+
+    let options = ViewOptions {
+        on_directory: DirMode::ListEntries,
+        ..ViewOptions::default()
+    };
+    let result = view_file(dir_path, &options)?;
+    assert_eq!(result.file_type, "inode/directory");
+    assert_eq!(result.total_line_num, None);
+
+    match result.contents {
+        FileContents::Directory { entries } => {
+            let listed: Vec<_> = entries
+                .iter()
+                .map(|entry| (entry.file_path.clone(), entry.file_type.as_str()))
+                .collect();
+            // Only the immediate entries are listed, with subdirectories marked as such
+            assert_eq!(
+                listed,
+                vec![
+                    (dir_path.join("binary_files"), "dir"),
+                    (dir_path.join("nested"), "dir"),
+                    (dir_path.join("text_files"), "dir"),
+                ]
+            );
+        }
+        _ => panic!("Expected directory content"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_read_text_filtered_range() -> Result<()> {
+    let file_path = Path::new("tests/fixtures/text_files/sample.txt");
+    let options = ViewOptions {
+        line_from: Some(2),
+        line_to: Some(3),
+        ..ViewOptions::default()
+    };
+
+    let text = read_text(file_path, &options)?;
+    assert_eq!(
+        text,
+        "It contains some search patterns.\nFind me if you can!"
+    );
+
+    // The whole file is returned without filters
+    let full = read_text(file_path, &ViewOptions::default())?;
+    assert_eq!(
+        full.trim_end(),
+        std::fs::read_to_string(file_path)?.trim_end()
+    );
+
+    // Binary files are rejected
+    let binary = Path::new("tests/fixtures/binary_files/binary.bin");
+    assert!(read_text(binary, &ViewOptions::default()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_view_sanitize_control() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let file_path = temp_dir.path().join("colored.log");
+    std::fs::write(&file_path, "\x1b[31merror\x1b[0m\tdone\x07\nplain line\n")?;
+
+    let options = ViewOptions {
+        sanitize_control: true,
+        ..ViewOptions::default()
+    };
+    let view = view_file(&file_path, &options)?;
+    match &view.contents {
+        FileContents::Text { content, .. } => {
+            // ESC and BEL are shown in caret notation, and tabs are kept
+            assert_eq!(content.line_contents[0].line, "^[[31merror^[[0m\tdone^G");
+            assert_eq!(content.line_contents[1].line, "plain line");
+        }
+        _ => panic!("Expected text content"),
+    }
+
+    // Pages and previews are sanitized too
+    let page = view_pages(&file_path, 1, &options)?.next().unwrap()?;
+    assert_eq!(page[0].line, "^[[31merror^[[0m\tdone^G");
+    let preview = ViewOptions {
+        preview: Some(1),
+        ..options.clone()
+    };
+    assert_eq!(read_text(&file_path, &preview)?, "^[[31merror^[[0m\tdone^G");
+
+    // Control characters are kept by default
+    let text = read_text(&file_path, &ViewOptions::default())?;
+    assert!(text.starts_with("\x1b[31m"));
+
+    Ok(())
+}
+
+#[test]
+fn test_tokenize_rust_line() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let file_path = temp_dir.path().join("lib.rs");
+    let line = r#"let url = "http://example.com/\"x\""; // fetch<'a>"#;
+    std::fs::write(&file_path, format!("{}\n\nfn main() {{}}\n", line))?;
+
+    let lines = tokenize(&file_path, &ViewOptions::default())?;
+    assert_eq!(lines.len(), 3);
+
+    let spans: Vec<_> = lines[0]
+        .tokens
+        .iter()
+        .map(|token| (token.kind, &line[token.start..token.end]))
+        .collect();
+    assert_eq!(
+        spans,
+        vec![
+            (TokenKind::Keyword, "let"),
+            (TokenKind::Other, " url = "),
+            (TokenKind::String, r#""http://example.com/\"x\"""#),
+            (TokenKind::Other, "; "),
+            (TokenKind::Comment, "// fetch<'a>"),
+        ]
+    );
+
+    // Empty lines have no spans
+    assert!(lines[1].tokens.is_empty());
+    assert_eq!(lines[2].tokens[0].kind, TokenKind::Keyword);
+
+    // Lines of unsupported languages are a single span
+    let text_path = temp_dir.path().join("notes.txt");
+    std::fs::write(&text_path, "let \"this\" be\n")?;
+    let lines = tokenize(&text_path, &ViewOptions::default())?;
+    assert_eq!(lines[0].tokens.len(), 1);
+    assert_eq!(lines[0].tokens[0].kind, TokenKind::Other);
+
+    Ok(())
+}
+
+#[test]
+fn test_file_view_preview() -> Result<()> {
+    let temp_dir = tempfile::tempdir()?;
+    let file_path = temp_dir.path().join("notes.txt");
+    std::fs::write(&file_path, "first\nsecond\nthird\nfourth\n")?;
+
+    let view = view_file(&file_path, &ViewOptions::default())?;
+    let preview = view.preview(2);
+    assert_eq!(preview.file_path, file_path);
+    assert_eq!(preview.file_type, view.file_type);
+    assert_eq!(preview.first_lines, vec!["first", "second"]);
+    assert_eq!(preview.total_line_num, Some(4));
+    assert_eq!(preview.size_bytes, std::fs::metadata(&file_path)?.len());
+
+    // Binary files are summarized by their description
+    let binary = Path::new("tests/fixtures/binary_files/binary.bin");
+    let preview = view_file(binary, &ViewOptions::default())?.preview(2);
+    assert_eq!(preview.first_lines.len(), 1);
+    assert_eq!(preview.total_line_num, None);
+    assert_eq!(preview.size_bytes, std::fs::metadata(binary)?.len());
+
+    Ok(())
+}
+
+#[test]
+fn test_view_binary_as_base64() -> Result<()> {
+    use base64::prelude::{BASE64_STANDARD, Engine as _};
+
+    let binary = Path::new("tests/fixtures/binary_files/binary.bin");
+    let original = std::fs::read(binary)?;
+    let options = ViewOptions {
+        binary_as_base64: true,
+        ..ViewOptions::default()
+    };
+
+    let view = view_file(binary, &options)?;
+    match &view.contents {
+        FileContents::Binary {
+            base64: Some(encoded),
+            ..
+        } => {
+            assert_eq!(BASE64_STANDARD.decode(encoded)?, original);
+        }
+        _ => panic!("Expected binary content with base64"),
+    }
+
+    // The content is omitted by default
+    let view = view_file(binary, &ViewOptions::default())?;
+    assert!(matches!(
+        view.contents,
+        FileContents::Binary { base64: None, .. }
+    ));
+
+    // Files over the size limit are rejected rather than encoded
+    let options = ViewOptions {
+        max_size: Some(original.len() - 1),
+        line_from: Some(1),
+        ..options
+    };
+    assert!(view_file(binary, &options).is_err());
+
+    Ok(())
+}